@@ -1,19 +1,54 @@
 #include "decoder_api.h"
 #include "draco_decoder/src/ffi.rs.h"
 
+#include "draco/attributes/attribute_octahedron_transform.h"
+#include "draco/attributes/attribute_quantization_transform.h"
 #include "draco/attributes/geometry_attribute.h"
 #include "draco/attributes/point_attribute.h"
 #include "draco/compression/decode.h"
+#include "draco/compression/expert_encode.h"
 #include "draco/compression/mesh/mesh_decoder.h"
+#include "draco/compression/mesh/mesh_edgebreaker_decoder.h"
+#include "draco/compression/mesh/mesh_sequential_decoder.h"
 #include "draco/compression/point_cloud/point_cloud_decoder.h"
+#include "draco/compression/point_cloud/point_cloud_kd_tree_decoder.h"
+#include "draco/compression/point_cloud/point_cloud_sequential_decoder.h"
 #include "draco/core/decoder_buffer.h"
+#include "draco/core/draco_version.h"
+#include "draco/core/encoder_buffer.h"
 #include "draco/mesh/mesh.h"
 #include "draco/point_cloud/point_cloud.h"
+#include <algorithm>
+#include <cmath>
+#include <atomic>
+#include <cstdint>
+#include <cstdlib>
+#include <cstring>
+#include <limits>
 #include <memory>
+#include <type_traits>
+#include <vector>
+
+// Number of DracoMesh handles currently alive, used for leak detection
+static std::atomic<int64_t> live_meshes{0};
 
 // DracoMesh implementation
-DracoMesh::DracoMesh(std::unique_ptr<draco::Mesh> m) : mesh(std::move(m)) {}
-DracoMesh::~DracoMesh() = default;
+DracoMesh::DracoMesh(std::unique_ptr<draco::Mesh> m) : mesh(std::move(m)) {
+  live_meshes.fetch_add(1, std::memory_order_relaxed);
+}
+DracoMesh::~DracoMesh() {
+  live_meshes.fetch_sub(1, std::memory_order_relaxed);
+}
+
+int64_t live_mesh_count() {
+  return live_meshes.load(std::memory_order_relaxed);
+}
+
+rust::String draco_version() { return rust::String(draco::Version()); }
+
+// Returned by decode_mesh_to_buffer when the mesh cannot be written, so that
+// an empty mesh (0 bytes written) is distinguishable from a failure
+static constexpr size_t kDecodeFailed = SIZE_MAX;
 
 static size_t sizeof_data_type(draco::DataType type) {
   switch (type) {
@@ -67,143 +102,685 @@ rust::Vec<uint8_t> decode_point_cloud(rust::Slice<const uint8_t> data) {
 }
 
 
-std::unique_ptr<DracoMesh> create_mesh(rust::Slice<const uint8_t> data) {
-  draco::DecoderBuffer buffer;
-  buffer.Init(reinterpret_cast<const char *>(data.data()), data.size());
+// Data type an attribute is written as, draco::DT_INVALID if it is skipped
+static draco::DataType output_data_type(draco::DataType type) {
+  switch (type) {
+  case draco::DT_INT8:
+  case draco::DT_UINT8:
+  case draco::DT_INT16:
+  case draco::DT_UINT16:
+  case draco::DT_INT32:
+  case draco::DT_UINT32:
+  case draco::DT_FLOAT32:
+    return type;
+  case draco::DT_FLOAT64:
+    return draco::DT_FLOAT32;
+  case draco::DT_BOOL:
+    return draco::DT_UINT8;
+  default:
+    return draco::DT_INVALID;
+  }
+}
 
-  draco::Decoder decoder;
-  auto status_or_geometry = decoder.DecodeMeshFromBuffer(&buffer);
-  if (!status_or_geometry.ok()) {
-    return nullptr;
+// AttributeWarning reasons, mirrored in ffi.rs
+static constexpr uint32_t kWarningUnsupported = 0;
+static constexpr uint32_t kWarningConverted = 1;
+static constexpr uint32_t kWarningFiltered = 2;
+
+// All attributes of a point cloud sorted by unique_id
+static std::vector<const draco::PointAttribute *>
+all_attributes(const draco::PointCloud &pc) {
+  std::vector<const draco::PointAttribute *> attrs;
+  attrs.reserve(pc.num_attributes());
+
+  for (int i = 0; i < pc.num_attributes(); ++i) {
+    attrs.push_back(pc.attribute(i));
   }
 
-  std::unique_ptr<draco::Mesh> mesh = std::move(status_or_geometry).value();
-  return std::make_unique<DracoMesh>(std::move(mesh));
+  std::sort(attrs.begin(), attrs.end(),
+            [](const draco::PointAttribute *a, const draco::PointAttribute *b) {
+              return a->unique_id() < b->unique_id();
+            });
+  return attrs;
 }
 
-bool compute_mesh_config(const DracoMesh &draco_mesh, MeshConfig &config) {
-  const draco::Mesh *mesh = draco_mesh.mesh.get();
-  if (!mesh) {
-    return false;
+// Attributes of a point cloud in the order they are laid out in the buffer
+static std::vector<const draco::PointAttribute *>
+sorted_attributes(const draco::PointCloud &pc, bool positions_only) {
+  std::vector<const draco::PointAttribute *> attrs = all_attributes(pc);
+
+  attrs.erase(
+      std::remove_if(attrs.begin(), attrs.end(),
+                     [positions_only](const draco::PointAttribute *attr) {
+                       return (positions_only &&
+                               attr->attribute_type() !=
+                                   draco::GeometryAttribute::POSITION) ||
+                              output_data_type(attr->data_type()) ==
+                                  draco::DT_INVALID;
+                     }),
+      attrs.end());
+  return attrs;
+}
+
+// Records every attribute that is not written as stored in the bitstream
+static void collect_warnings(const draco::PointCloud &pc, bool positions_only,
+                             rust::Vec<AttributeWarning> &out) {
+  for (const draco::PointAttribute *attr : all_attributes(pc)) {
+    AttributeWarning warning;
+    const draco::DataType output = output_data_type(attr->data_type());
+
+    if (positions_only &&
+        attr->attribute_type() != draco::GeometryAttribute::POSITION) {
+      warning.reason = kWarningFiltered;
+    } else if (output == draco::DT_INVALID) {
+      warning.reason = kWarningUnsupported;
+    } else if (output != attr->data_type()) {
+      warning.reason = kWarningConverted;
+    } else {
+      continue;
+    }
+
+    warning.unique_id = attr->unique_id();
+    warning.attribute_type = static_cast<int32_t>(attr->attribute_type());
+    warning.data_type = static_cast<uint32_t>(attr->data_type());
+    out.push_back(warning);
   }
+}
 
-  // Basic info
-  config.vertex_count = mesh->num_points();
-  config.index_count = mesh->num_faces() * 3;
+// Convert Draco DataType to the enum shared with Rust
+static uint32_t data_type_to_enum(draco::DataType type) {
+  switch (type) {
+  case draco::DT_INT8:
+    return 0;
+  case draco::DT_UINT8:
+    return 1;
+  case draco::DT_INT16:
+    return 2;
+  case draco::DT_UINT16:
+    return 3;
+  case draco::DT_INT32:
+    return 4;
+  case draco::DT_UINT32:
+    return 5;
+  case draco::DT_FLOAT32:
+    return 6;
+  default:
+    return 1; // Default to UInt8
+  }
+}
 
-  // Index length
-  if (config.index_count <=
-      static_cast<uint32_t>(std::numeric_limits<uint16_t>::max())) {
-    config.index_length = config.index_count * sizeof(uint16_t);
-  } else {
-    config.index_length = config.index_count * sizeof(uint32_t);
+// Convert the enum shared with Rust to a Draco DataType
+static draco::DataType enum_to_data_type(uint32_t value) {
+  switch (value) {
+  case 0:
+    return draco::DT_INT8;
+  case 1:
+    return draco::DT_UINT8;
+  case 2:
+    return draco::DT_INT16;
+  case 3:
+    return draco::DT_UINT16;
+  case 4:
+    return draco::DT_INT32;
+  case 5:
+    return draco::DT_UINT32;
+  case 6:
+    return draco::DT_FLOAT32;
+  default:
+    return draco::DT_INVALID;
   }
+}
+
+// Data type an attribute is written as in a layout, honouring the output
+// type requested for its unique id, or else for its semantic. Skipped
+// attributes stay skipped
+static draco::DataType layout_data_type(const draco::PointAttribute &attr,
+                                        const LayoutOptions &layout) {
+  const draco::DataType output = output_data_type(attr.data_type());
+  if (output == draco::DT_INVALID)
+    return output;
+
+  int32_t requested = -1;
+  for (const AttributeOutputType &type : layout.attribute_output_types) {
+    if (type.unique_id == attr.unique_id())
+      requested = static_cast<int32_t>(type.data_type);
+  }
+  // Semantics are indexed like AttributeSemantic, other types are generic
+  const size_t semantic = std::min<size_t>(
+      static_cast<size_t>(std::max<int>(attr.attribute_type(), 0)),
+      draco::GeometryAttribute::GENERIC);
+  if (requested < 0 && semantic < layout.output_types.size())
+    requested = layout.output_types[semantic];
+  if (requested < 0)
+    return output;
+  const draco::DataType data_type =
+      enum_to_data_type(static_cast<uint32_t>(requested));
+  return data_type == draco::DT_INVALID ? output : data_type;
+}
+
+// Rounds `offset` up to a multiple of `alignment`, 0 means no alignment
+static uint64_t align_offset(uint64_t offset, uint32_t alignment) {
+  if (alignment <= 1)
+    return offset;
+  return (offset + alignment - 1) / alignment * alignment;
+}
+
+// Axis remapping for the values of an attribute, see LayoutOptions::axis_map.
+// Positions and normals with three components are remapped unless they keep
+// their quantized values, which cannot be negated. Decodes with such
+// attributes are rejected by AxisConvention::check before they are written
+static const int32_t *layout_axis_map(const draco::PointAttribute &attr,
+                                      const LayoutOptions &layout) {
+  if (layout.axis_map.size() != 3 || attr.num_components() != 3 ||
+      attr.GetAttributeTransformData())
+    return nullptr;
+  if (attr.attribute_type() != draco::GeometryAttribute::POSITION &&
+      attr.attribute_type() != draco::GeometryAttribute::NORMAL)
+    return nullptr;
+  return layout.axis_map.data();
+}
+
+// Records the transform parameters of an attribute that was decoded without
+// its attribute transform, see create_mesh
+static void fill_transform(const draco::PointAttribute &attr,
+                           MeshAttribute &out) {
+  out.quantization_bits = 0;
+  out.quantization_range = 0.0f;
+  out.octahedron_bits = 0;
 
-  // Collect and sort attributes by unique_id
-  struct AttrEntry {
-    const draco::PointAttribute *attr = nullptr;
-    int unique_id = 0;
-  };
+  const draco::AttributeTransformData *transform =
+      attr.GetAttributeTransformData();
+  if (!transform)
+    return;
 
-  std::vector<AttrEntry> attrs;
-  attrs.reserve(mesh->num_attributes());
+  switch (transform->transform_type()) {
+  case draco::ATTRIBUTE_QUANTIZATION_TRANSFORM: {
+    draco::AttributeQuantizationTransform quantization;
+    if (!quantization.InitFromAttribute(attr))
+      return;
 
-  for (int i = 0; i < mesh->num_attributes(); ++i) {
-    const draco::PointAttribute *attr = mesh->attribute(i);
-    AttrEntry e;
-    e.attr = attr;
-    e.unique_id = attr->unique_id();
-    attrs.push_back(e);
+    out.quantization_bits = quantization.quantization_bits();
+    out.quantization_range = quantization.range();
+    for (int i = 0; i < attr.num_components(); ++i) {
+      out.quantization_min.push_back(quantization.min_value(i));
+    }
+    break;
+  }
+  case draco::ATTRIBUTE_OCTAHEDRON_TRANSFORM: {
+    draco::AttributeOctahedronTransform octahedron;
+    if (octahedron.InitFromAttribute(attr))
+      out.octahedron_bits = octahedron.quantization_bits();
+    break;
   }
+  default:
+    break;
+  }
+}
 
-  std::sort(attrs.begin(), attrs.end(),
-            [](const AttrEntry &a, const AttrEntry &b) {
-              return a.unique_id < b.unique_id;
-            });
+// Whether integer values written as `output` are normalized. Floats converted
+// to an integer type are scaled to its range, so they become normalized
+static bool output_normalized(const draco::PointAttribute &attr,
+                              draco::DataType output) {
+  if (output == draco::DT_FLOAT32)
+    return false;
+  if (attr.data_type() == draco::DT_FLOAT32)
+    return true;
+  return attr.normalized();
+}
+
+// Largest offset or length of the layout, which is described with uint32_t
+constexpr uint64_t kMaxLayoutSize = std::numeric_limits<uint32_t>::max();
 
-  // Calculate offsets and fill attribute info
-  uint32_t current_offset = config.index_length;
+// Appends the layout of every attribute starting at `offset` and stores the
+// end offset in `end`. Sizes are computed in 64 bits, and false is returned if
+// an offset or length does not fit in uint32_t
+static bool fill_attribute_configs(const draco::PointCloud &pc,
+                                   uint64_t offset,
+                                   const LayoutOptions &layout,
+                                   rust::Vec<MeshAttribute> &out,
+                                   uint64_t &end) {
+  for (const draco::PointAttribute *attr :
+       sorted_attributes(pc, layout.positions_only)) {
+    offset = align_offset(offset, layout.offset_alignment);
 
-  for (auto &entry : attrs) {
-    const draco::PointAttribute *attr = entry.attr;
     MeshAttribute mesh_attr;
+    const draco::DataType output = layout_data_type(*attr, layout);
 
-    mesh_attr.dim = attr->num_components();
+    mesh_attr.padded = layout.pad_vec3 && attr->num_components() == 3;
+    mesh_attr.dim = attr->num_components() + (mesh_attr.padded ? 1 : 0);
+    mesh_attr.data_type = data_type_to_enum(output);
+    mesh_attr.attribute_type = static_cast<int32_t>(attr->attribute_type());
     mesh_attr.unique_id = attr->unique_id();
+    mesh_attr.normalized = output_normalized(*attr, output);
+    const uint64_t length = static_cast<uint64_t>(mesh_attr.dim) *
+                            pc.num_points() * sizeof_data_type(output);
+    if (offset > kMaxLayoutSize || length > kMaxLayoutSize)
+      return false;
+    mesh_attr.offset = static_cast<uint32_t>(offset);
+    mesh_attr.length = static_cast<uint32_t>(length);
+    fill_transform(*attr, mesh_attr);
+
+    out.push_back(mesh_attr);
+    offset += length;
+    if (offset > kMaxLayoutSize)
+      return false;
+  }
+  end = offset;
+  return true;
+}
+
+// Converts one component to T. Conversions between integer and floating
+// point types are normalized, mapping integers to [0, 1] (unsigned) or
+// [-1, 1] (signed), and so are conversions between integer types when the
+// source is `normalized`. Integer results are rounded and clamped to the
+// range of T
+template <typename T>
+static T convert_component(double value, bool from_integer, double from_max,
+                           bool normalized) {
+  constexpr bool to_integer = std::is_integral<T>::value;
+  if (from_integer && (!to_integer || normalized))
+    value = std::max(value / from_max, -1.0);
+  if (to_integer && (!from_integer || normalized))
+    value *= static_cast<double>(std::numeric_limits<T>::max());
+
+  if (to_integer) {
+    value = std::round(value);
+    value = std::min(
+        std::max(value, static_cast<double>(std::numeric_limits<T>::lowest())),
+        static_cast<double>(std::numeric_limits<T>::max()));
+  }
+  return static_cast<T>(value);
+}
+
+// Largest value of an integer data type, 0 for other types
+static double data_type_max(draco::DataType type) {
+  switch (type) {
+  case draco::DT_BOOL:
+    return 1;
+  case draco::DT_INT8:
+    return std::numeric_limits<int8_t>::max();
+  case draco::DT_UINT8:
+    return std::numeric_limits<uint8_t>::max();
+  case draco::DT_INT16:
+    return std::numeric_limits<int16_t>::max();
+  case draco::DT_UINT16:
+    return std::numeric_limits<uint16_t>::max();
+  case draco::DT_INT32:
+    return std::numeric_limits<int32_t>::max();
+  case draco::DT_UINT32:
+    return std::numeric_limits<uint32_t>::max();
+  default:
+    return 0;
+  }
+}
+
+// Negates a signed component, saturating the lowest integer
+template <typename T> static T negate(T value) {
+  if constexpr (std::is_integral<T>::value) {
+    if (value == std::numeric_limits<T>::lowest())
+      return std::numeric_limits<T>::max();
+  }
+  return static_cast<T>(-value);
+}
+
+// Writes the values of one attribute for every point, converted to T. With
+// `convert`, values are converted with convert_component from the type the
+// attribute is written as by default. With `padded`, a zero component is
+// appended to every value. With `axes`, the three components are remapped,
+// see LayoutOptions::axis_map; unsigned types are never remapped. With
+// `extrema`, the component-wise minimum followed by the maximum of the written
+// values is stored in it; NaN is skipped unless the first point has it
+template <typename T>
+static bool write_attribute(const draco::PointAttribute &attr, int num_points,
+                            bool convert, bool padded, const int32_t *axes,
+                            std::vector<uint8_t> *extrema, uint8_t *&out,
+                            const uint8_t *out_end) {
+  const int components = attr.num_components();
+  const size_t size = (components + (padded ? 1 : 0)) * sizeof(T);
+  // The padding component stays zero
+  std::vector<T> values(components + (padded ? 1 : 0));
+
+  const draco::DataType source = output_data_type(attr.data_type());
+  const bool from_integer = source != draco::DT_FLOAT32;
+  const double from_max = data_type_max(attr.data_type());
+  std::vector<int64_t> integers(convert && from_integer ? components : 0);
+  std::vector<double> floats(convert && !from_integer ? components : 0);
+  std::vector<T> min;
+  std::vector<T> max;
+
+  for (draco::PointIndex i(0); i < num_points; ++i) {
+    if (size > static_cast<size_t>(out_end - out))
+      return false;
+    const draco::AttributeValueIndex index = attr.mapped_index(i);
+    if (!convert) {
+      attr.ConvertValue(index, values.data());
+    } else if (from_integer) {
+      attr.ConvertValue(index, integers.data());
+      for (int c = 0; c < components; ++c)
+        values[c] = convert_component<T>(static_cast<double>(integers[c]),
+                                         true, from_max, attr.normalized());
+    } else {
+      attr.ConvertValue(index, floats.data());
+      for (int c = 0; c < components; ++c)
+        values[c] = convert_component<T>(floats[c], false, from_max, false);
+    }
+    if constexpr (std::is_signed<T>::value) {
+      if (axes) {
+        const T source[3] = {values[0], values[1], values[2]};
+        for (int c = 0; c < 3; ++c) {
+          const T value = source[std::abs(axes[c]) - 1];
+          values[c] = axes[c] < 0 ? negate(value) : value;
+        }
+      }
+    }
+    if (extrema) {
+      if (i == 0) {
+        min = values;
+        max = values;
+      }
+      for (size_t c = 0; c < values.size(); ++c) {
+        if (values[c] < min[c])
+          min[c] = values[c];
+        if (values[c] > max[c])
+          max[c] = values[c];
+      }
+    }
+    memcpy(out, values.data(), size);
+    out += size;
+  }
+  if (extrema && num_points > 0) {
+    extrema->resize(2 * size);
+    memcpy(extrema->data(), min.data(), size);
+    memcpy(extrema->data() + size, max.data(), size);
+  }
+  return true;
+}
+
+// Writes all attributes in layout order, zero filling the padding in front of
+// each attribute. Offsets are aligned relative to `out_start`. `control`, which
+// may be null, is told about each written attribute and, with
+// LayoutOptions::min_max, its extrema. Returns false if the attributes do not
+// fit or `control` is cancelled before an attribute is written
+static bool write_attributes(const draco::PointCloud &pc,
+                             const LayoutOptions &layout,
+                             const DecodeControl *control,
+                             const uint8_t *out_start, uint8_t *&out,
+                             const uint8_t *out_end) {
+  const int num_points = pc.num_points();
+  const auto attributes = sorted_attributes(pc, layout.positions_only);
+  const uint32_t count = static_cast<uint32_t>(attributes.size());
 
-    // Convert Draco DataType to enum
-    switch (attr->data_type()) {
+  for (uint32_t index = 0; index < count; ++index) {
+    const draco::PointAttribute *attr = attributes[index];
+    if (control && control->is_cancelled())
+      return false;
+    const size_t position = static_cast<size_t>(out - out_start);
+    const size_t padding =
+        static_cast<size_t>(align_offset(position, layout.offset_alignment) -
+                            position);
+    if (padding > static_cast<size_t>(out_end - out))
+      return false;
+    memset(out, 0, padding);
+    out += padding;
+
+    const draco::DataType output = layout_data_type(*attr, layout);
+    const bool convert = output != output_data_type(attr->data_type());
+    const bool padded = layout.pad_vec3 && attr->num_components() == 3;
+    const int32_t *axes = layout_axis_map(*attr, layout);
+    std::vector<uint8_t> min_max;
+    std::vector<uint8_t> *extrema =
+        control && layout.min_max ? &min_max : nullptr;
+    bool written = false;
+    switch (output) {
     case draco::DT_INT8:
-      mesh_attr.data_type = 0;
+      written = write_attribute<int8_t>(*attr, num_points, convert, padded,
+                                        axes, extrema, out, out_end);
       break;
     case draco::DT_UINT8:
-      mesh_attr.data_type = 1;
+      written = write_attribute<uint8_t>(*attr, num_points, convert, padded,
+                                         axes, extrema, out, out_end);
       break;
     case draco::DT_INT16:
-      mesh_attr.data_type = 2;
+      written = write_attribute<int16_t>(*attr, num_points, convert, padded,
+                                         axes, extrema, out, out_end);
       break;
     case draco::DT_UINT16:
-      mesh_attr.data_type = 3;
+      written = write_attribute<uint16_t>(*attr, num_points, convert, padded,
+                                          axes, extrema, out, out_end);
       break;
     case draco::DT_INT32:
-      mesh_attr.data_type = 4;
+      written = write_attribute<int32_t>(*attr, num_points, convert, padded,
+                                         axes, extrema, out, out_end);
       break;
     case draco::DT_UINT32:
-      mesh_attr.data_type = 5;
+      written = write_attribute<uint32_t>(*attr, num_points, convert, padded,
+                                          axes, extrema, out, out_end);
       break;
     case draco::DT_FLOAT32:
-      mesh_attr.data_type = 6;
+      written = write_attribute<float>(*attr, num_points, convert, padded,
+                                       axes, extrema, out, out_end);
       break;
     default:
-      mesh_attr.data_type = 1; // Default to UInt8
-      break;
+      return false;
     }
+    if (!written)
+      return false;
+    if (!min_max.empty()) {
+      const size_t half = min_max.size() / 2;
+      control->attribute_min_max(
+          index, rust::Slice<const uint8_t>(min_max.data(), half),
+          rust::Slice<const uint8_t>(min_max.data() + half, half));
+    }
+    if (control)
+      control->attribute_written(index, count);
+  }
+  return true;
+}
+
+// Decoder telling `control`, which may be null, when the connectivity of a
+// mesh and the data of each attributes decoder are decompressed. The decode
+// fails once the connectivity is decoded if its counts exceed the limits of
+// `control`, before any attribute values are decompressed
+template <typename Base> class ReportingDecoder : public Base {
+public:
+  explicit ReportingDecoder(const DecodeControl *control) : control_(control) {}
+
+protected:
+  bool DecodeGeometryData() override {
+    if (!Base::DecodeGeometryData())
+      return false;
+    if (!control_)
+      return true;
+
+    uint32_t num_faces = 0;
+    if constexpr (std::is_base_of<draco::MeshDecoder, Base>::value)
+      num_faces = static_cast<uint32_t>(this->mesh()->num_faces());
+    const uint32_t num_points =
+        static_cast<uint32_t>(this->point_cloud()->num_points());
+    if (!control_->counts_fit(num_points, num_faces))
+      return false;
+    if constexpr (std::is_base_of<draco::MeshDecoder, Base>::value)
+      control_->connectivity_decoded();
+    return true;
+  }
+
+  // Same as the base implementation, with each attributes decoder reported
+  bool DecodeAllAttributes() override {
+    const int count = this->num_attributes_decoders();
+    for (int i = 0; i < count; ++i) {
+      if (!this->attributes_decoder(i)->DecodeAttributes(this->buffer()))
+        return false;
+      if (control_)
+        control_->attributes_decoded(static_cast<uint32_t>(i),
+                                     static_cast<uint32_t>(count));
+    }
+    return true;
+  }
 
-    mesh_attr.offset = current_offset;
-    mesh_attr.length = mesh_attr.dim * config.vertex_count *
-                       sizeof_data_type(attr->data_type());
+private:
+  const DecodeControl *control_;
+};
 
-    config.attributes.push_back(mesh_attr);
-    current_offset += mesh_attr.length;
+template <typename Decoder, typename Geometry>
+static std::unique_ptr<draco::PointCloud>
+decode_reporting(const draco::DecoderOptions &options,
+                 draco::DecoderBuffer &buffer, const DecodeControl *control,
+                 rust::String &error) {
+  ReportingDecoder<Decoder> decoder(control);
+  std::unique_ptr<Geometry> geometry = std::make_unique<Geometry>();
+  const draco::Status status = decoder.Decode(options, &buffer, geometry.get());
+  if (!status.ok()) {
+    error = rust::String(status.error_msg_string());
+    return nullptr;
   }
+  return std::unique_ptr<draco::PointCloud>(std::move(geometry));
+}
 
-  config.buffer_size = current_offset;
+// Decodes a bitstream like draco::Decoder, into a draco::Mesh for meshes.
+// Point cloud bitstreams fail if `mesh_only` is set. `control`, which may be
+// null, is told as the sections of the bitstream are decompressed
+static std::unique_ptr<draco::PointCloud>
+decode_geometry(rust::Slice<const uint8_t> data,
+                const draco::DecoderOptions &options,
+                const DecodeControl *control, bool mesh_only,
+                rust::String &error) {
+  draco::DecoderBuffer buffer;
+  buffer.Init(reinterpret_cast<const char *>(data.data()), data.size());
+
+  // The decoders parse the header again, so it is read from a copy
+  draco::DecoderBuffer header_buffer(buffer);
+  draco::DracoHeader header;
+  const draco::Status status =
+      draco::PointCloudDecoder::DecodeHeader(&header_buffer, &header);
+  if (!status.ok()) {
+    error = rust::String(status.error_msg_string());
+    return nullptr;
+  }
+
+  if (header.encoder_type == draco::TRIANGULAR_MESH) {
+    if (header.encoder_method == draco::MESH_EDGEBREAKER_ENCODING)
+      return decode_reporting<draco::MeshEdgebreakerDecoder, draco::Mesh>(
+          options, buffer, control, error);
+    if (header.encoder_method == draco::MESH_SEQUENTIAL_ENCODING)
+      return decode_reporting<draco::MeshSequentialDecoder, draco::Mesh>(
+          options, buffer, control, error);
+  } else if (header.encoder_type == draco::POINT_CLOUD) {
+    if (mesh_only) {
+      error = "Input is not a mesh.";
+      return nullptr;
+    }
+    if (header.encoder_method == draco::POINT_CLOUD_KD_TREE_ENCODING)
+      return decode_reporting<draco::PointCloudKdTreeDecoder,
+                              draco::PointCloud>(options, buffer, control,
+                                                 error);
+    if (header.encoder_method == draco::POINT_CLOUD_SEQUENTIAL_ENCODING)
+      return decode_reporting<draco::PointCloudSequentialDecoder,
+                              draco::PointCloud>(options, buffer, control,
+                                                 error);
+  }
+  error = "Unsupported encoding method.";
+  return nullptr;
+}
+
+std::unique_ptr<DracoMesh>
+create_mesh(rust::Slice<const uint8_t> data,
+            rust::Slice<const int32_t> keep_quantized,
+            const DecodeControl &control, rust::String &error) {
+  // Attributes of these types keep the values stored in the bitstream, their
+  // transform parameters are reported by fill_transform
+  draco::DecoderOptions options;
+  for (int32_t type : keep_quantized) {
+    options.SetAttributeBool(static_cast<draco::GeometryAttribute::Type>(type),
+                             "skip_attribute_transform", true);
+  }
+  std::unique_ptr<draco::PointCloud> geometry =
+      decode_geometry(data, options, &control, true, error);
+  if (!geometry) {
+    return nullptr;
+  }
+
+  std::unique_ptr<draco::Mesh> mesh(
+      static_cast<draco::Mesh *>(geometry.release()));
+  return std::make_unique<DracoMesh>(std::move(mesh));
+}
+
+// Whether indices are written as uint16_t, see IndexFormat. 16-bit indices
+// are used when every vertex index fits unless a width is forced or the
+// legacy rule based on the index count is requested. Forced 16-bit indices
+// are only requested after checking the vertex count, and writing fails
+// rather than truncating an index that does not fit
+static bool use_u16_indices(const draco::Mesh &mesh,
+                            const LayoutOptions &layout) {
+  constexpr uint32_t kMaxU16 = std::numeric_limits<uint16_t>::max();
+  switch (layout.index_format) {
+  case 1:
+    return true;
+  case 2:
+    return false;
+  case 3:
+    return static_cast<uint32_t>(mesh.num_faces()) * 3 <= kMaxU16;
+  default:
+    return mesh.num_points() <= kMaxU16 + 1;
+  }
+}
+
+bool compute_mesh_config(const DracoMesh &draco_mesh,
+                         const LayoutOptions &layout, MeshConfig &config) {
+  const draco::Mesh *mesh = draco_mesh.mesh.get();
+  if (!mesh) {
+    return false;
+  }
+
+  // Basic info, failing for counts whose sizes do not fit in uint32_t
+  const uint64_t index_count =
+      layout.skip_indices ? 0 : static_cast<uint64_t>(mesh->num_faces()) * 3;
+  const uint64_t index_length =
+      index_count *
+      (use_u16_indices(*mesh, layout) ? sizeof(uint16_t) : sizeof(uint32_t));
+  if (index_length > kMaxLayoutSize)
+    return false;
+  config.vertex_count = mesh->num_points();
+  config.index_count = static_cast<uint32_t>(index_count);
+  config.index_length = static_cast<uint32_t>(index_length);
+
+  // Attributes follow the indices, sorted by unique_id
+  uint64_t end = 0;
+  if (!fill_attribute_configs(*mesh, index_length, layout, config.attributes,
+                              end))
+    return false;
+  config.buffer_size = static_cast<size_t>(end);
+  collect_warnings(*mesh, layout.positions_only, config.warnings);
   return true;
 }
 
-size_t decode_mesh_to_buffer(const DracoMesh &draco_mesh, uint8_t *out_ptr,
+size_t decode_mesh_to_buffer(const DracoMesh &draco_mesh,
+                             const LayoutOptions &layout,
+                             const DecodeControl &control, uint8_t *out_ptr,
                              size_t out_len) {
   const draco::Mesh *mesh = draco_mesh.mesh.get();
   if (!mesh) {
-    return 0;
+    return kDecodeFailed;
   }
 
   uint8_t *out = out_ptr;
-  const size_t out_end = reinterpret_cast<size_t>(out_ptr) + out_len;
-
-  // Helper function to write scalar
-  auto write_scalar = [&](const void *src, draco::DataType type) -> bool {
-    size_t size = sizeof_data_type(type);
-    if (reinterpret_cast<size_t>(out) + size > out_end)
-      return false;
-    memcpy(out, src, size);
-    out += size;
-    return true;
-  };
+  const uint8_t *out_end = out_ptr + out_len;
 
-  // Write indices
-  const int num_faces = mesh->num_faces();
-  bool use_u16 =
-      (num_faces * 3 <= static_cast<int>(std::numeric_limits<uint16_t>::max()));
+  // Write indices, swapping the last two corners to flip the winding
+  const int num_faces = layout.skip_indices ? 0 : mesh->num_faces();
+  static const int kCorners[3] = {0, 1, 2};
+  static const int kFlippedCorners[3] = {0, 2, 1};
+  const int *corners = layout.flip_winding ? kFlippedCorners : kCorners;
 
-  if (use_u16) {
+  if (use_u16_indices(*mesh, layout)) {
     for (draco::FaceIndex i(0); i < num_faces; ++i) {
       const auto &face = mesh->face(i);
       for (int j = 0; j < 3; ++j) {
-        uint16_t val = static_cast<uint16_t>(face[j].value());
-        if (reinterpret_cast<size_t>(out) + sizeof(uint16_t) > out_end)
-          return 0;
-        *reinterpret_cast<uint16_t *>(out) = val;
+        const uint32_t index = face[corners[j]].value();
+        if (index > std::numeric_limits<uint16_t>::max() ||
+            sizeof(uint16_t) > static_cast<size_t>(out_end - out))
+          return kDecodeFailed;
+        uint16_t val = static_cast<uint16_t>(index);
+        memcpy(out, &val, sizeof(uint16_t));
         out += sizeof(uint16_t);
       }
     }
@@ -211,115 +788,321 @@ size_t decode_mesh_to_buffer(const DracoMesh &draco_mesh, uint8_t *out_ptr,
     for (draco::FaceIndex i(0); i < num_faces; ++i) {
       const auto &face = mesh->face(i);
       for (int j = 0; j < 3; ++j) {
-        uint32_t val = static_cast<uint32_t>(face[j].value());
-        if (reinterpret_cast<size_t>(out) + sizeof(uint32_t) > out_end)
-          return 0;
-        *reinterpret_cast<uint32_t *>(out) = val;
+        uint32_t val = static_cast<uint32_t>(face[corners[j]].value());
+        if (sizeof(uint32_t) > static_cast<size_t>(out_end - out))
+          return kDecodeFailed;
+        memcpy(out, &val, sizeof(uint32_t));
         out += sizeof(uint32_t);
       }
     }
   }
 
-  // Sort and write attributes
-  struct AttrEntry {
-    const draco::PointAttribute *attr = nullptr;
-    int unique_id = 0;
-  };
+  control.indices_written();
+
+  // Write attributes sorted by unique_id
+  if (!write_attributes(*mesh, layout, &control, out_ptr, out, out_end)) {
+    return kDecodeFailed;
+  }
+
+  return static_cast<size_t>(out - out_ptr);
+}
+
+// DracoPointCloud implementation
+DracoPointCloud::DracoPointCloud(std::unique_ptr<draco::PointCloud> p)
+    : pc(std::move(p)) {}
+DracoPointCloud::~DracoPointCloud() = default;
 
-  std::vector<AttrEntry> attrs;
-  attrs.reserve(mesh->num_attributes());
+std::unique_ptr<DracoPointCloud>
+create_point_cloud(rust::Slice<const uint8_t> data,
+                   const DecodeControl &control, rust::String &error) {
+  std::unique_ptr<draco::PointCloud> pc =
+      decode_geometry(data, draco::DecoderOptions(), &control, false, error);
+  if (!pc) {
+    return nullptr;
+  }
+  return std::make_unique<DracoPointCloud>(std::move(pc));
+}
 
-  for (int i = 0; i < mesh->num_attributes(); ++i) {
-    const draco::PointAttribute *attr = mesh->attribute(i);
-    AttrEntry e;
-    e.attr = attr;
-    e.unique_id = attr->unique_id();
-    attrs.push_back(e);
+bool compute_point_cloud_config(const DracoPointCloud &draco_pc,
+                                PointCloudConfig &config) {
+  const draco::PointCloud *pc = draco_pc.pc.get();
+  if (!pc) {
+    return false;
   }
 
-  std::sort(attrs.begin(), attrs.end(),
-            [](const AttrEntry &a, const AttrEntry &b) {
-              return a.unique_id < b.unique_id;
-            });
+  config.point_count = pc->num_points();
+  // Point clouds always use the default layout
+  const LayoutOptions layout{};
+  uint64_t end = 0;
+  if (!fill_attribute_configs(*pc, 0, layout, config.attributes, end))
+    return false;
+  config.buffer_size = static_cast<size_t>(end);
+  collect_warnings(*pc, false, config.warnings);
+  return true;
+}
 
-  int num_points = mesh->num_points();
-
-  for (auto &entry : attrs) {
-    const draco::PointAttribute *attr = entry.attr;
-    int dim = attr->num_components();
-    draco::DataType type = attr->data_type();
-
-    for (draco::PointIndex j(0); j < num_points; ++j) {
-      switch (type) {
-      case draco::DT_INT8: {
-        int8_t v[4] = {};
-        attr->ConvertValue(attr->mapped_index(j), v);
-        for (int k = 0; k < dim; ++k)
-          if (!write_scalar(&v[k], type))
-            return 0;
-        break;
-      }
-      case draco::DT_UINT8: {
-        uint8_t v[4] = {};
-        attr->ConvertValue(attr->mapped_index(j), v);
-        for (int k = 0; k < dim; ++k)
-          if (!write_scalar(&v[k], type))
-            return 0;
-        break;
-      }
-      case draco::DT_INT16: {
-        int16_t v[4] = {};
-        attr->ConvertValue(attr->mapped_index(j), v);
-        for (int k = 0; k < dim; ++k)
-          if (!write_scalar(&v[k], type))
-            return 0;
-        break;
-      }
-      case draco::DT_UINT16: {
-        uint16_t v[4] = {};
-        attr->ConvertValue(attr->mapped_index(j), v);
-        for (int k = 0; k < dim; ++k)
-          if (!write_scalar(&v[k], type))
-            return 0;
-        break;
-      }
-      case draco::DT_INT32: {
-        int32_t v[4] = {};
-        attr->ConvertValue(attr->mapped_index(j), v);
-        for (int k = 0; k < dim; ++k)
-          if (!write_scalar(&v[k], type))
-            return 0;
-        break;
-      }
-      case draco::DT_UINT32: {
-        uint32_t v[4] = {};
-        attr->ConvertValue(attr->mapped_index(j), v);
-        for (int k = 0; k < dim; ++k)
-          if (!write_scalar(&v[k], type))
-            return 0;
-        break;
-      }
-      case draco::DT_FLOAT32: {
-        float v[4] = {};
-        attr->ConvertValue(attr->mapped_index(j), v);
-        for (int k = 0; k < dim; ++k)
-          if (!write_scalar(&v[k], type))
-            return 0;
-        break;
-      }
-      case draco::DT_FLOAT64: {
-        double v[4] = {};
-        attr->ConvertValue(attr->mapped_index(j), v);
-        for (int k = 0; k < dim; ++k)
-          if (!write_scalar(&v[k], type))
-            return 0;
-        break;
-      }
-      default:
-        return 0;
-      }
-    }
+size_t decode_point_cloud_to_buffer(const DracoPointCloud &draco_pc,
+                                    const DecodeControl &control,
+                                    uint8_t *out_ptr, size_t out_len) {
+  const draco::PointCloud *pc = draco_pc.pc.get();
+  if (!pc) {
+    return kDecodeFailed;
+  }
+
+  uint8_t *out = out_ptr;
+  const LayoutOptions layout{};
+  if (!write_attributes(*pc, layout, &control, out_ptr, out,
+                        out_ptr + out_len)) {
+    return kDecodeFailed;
   }
 
   return static_cast<size_t>(out - out_ptr);
-}
\ No newline at end of file
+}
+
+// DracoMeshBuilder implementation
+DracoMeshBuilder::DracoMeshBuilder(uint32_t num_points)
+    : mesh(std::make_unique<draco::Mesh>()) {
+  mesh->set_num_points(num_points);
+}
+DracoMeshBuilder::~DracoMeshBuilder() = default;
+
+std::unique_ptr<DracoMeshBuilder> create_mesh_builder(uint32_t num_points) {
+  return std::make_unique<DracoMeshBuilder>(num_points);
+}
+
+bool mesh_builder_set_faces(DracoMeshBuilder &builder,
+                            rust::Slice<const uint32_t> indices) {
+  draco::Mesh *mesh = builder.mesh.get();
+  if (indices.size() % 3 != 0) {
+    return false;
+  }
+
+  const uint32_t num_points = mesh->num_points();
+  for (size_t i = 0; i < indices.size(); i += 3) {
+    draco::Mesh::Face face;
+    for (int j = 0; j < 3; ++j) {
+      if (indices[i + j] >= num_points)
+        return false;
+      face[j] = draco::PointIndex(indices[i + j]);
+    }
+    mesh->AddFace(face);
+  }
+  return true;
+}
+
+bool mesh_builder_add_attribute(DracoMeshBuilder &builder,
+                                int32_t attribute_type, uint32_t data_type,
+                                uint32_t dim, int32_t quantization_bits,
+                                rust::Slice<const uint8_t> data) {
+  draco::Mesh *mesh = builder.mesh.get();
+  const draco::DataType type = enum_to_data_type(data_type);
+  if (type == draco::DT_INVALID || dim == 0 || dim > 127) {
+    return false;
+  }
+
+  const size_t num_points = mesh->num_points();
+  const size_t stride = dim * sizeof_data_type(type);
+  if (data.size() != stride * num_points) {
+    return false;
+  }
+
+  draco::GeometryAttribute attr;
+  attr.Init(static_cast<draco::GeometryAttribute::Type>(attribute_type),
+            nullptr, static_cast<uint8_t>(dim), type, false, stride, 0);
+
+  const int id = mesh->AddAttribute(attr, true, num_points);
+  if (id < 0) {
+    return false;
+  }
+
+  draco::PointAttribute *point_attr = mesh->attribute(id);
+  for (size_t i = 0; i < num_points; ++i) {
+    point_attr->SetAttributeValue(draco::AttributeValueIndex(i),
+                                  data.data() + i * stride);
+  }
+
+  builder.quantization_bits.resize(id + 1, 0);
+  builder.quantization_bits[id] = quantization_bits;
+  return true;
+}
+
+// Applies the options to the encoder and encodes into `out`
+static bool encode_to_vec(draco::ExpertEncoder &encoder,
+                          const draco::PointCloud &pc,
+                          const EncodeOptions &options,
+                          const std::vector<int32_t> &quantization_bits,
+                          rust::Vec<uint8_t> &out) {
+  encoder.SetSpeedOptions(options.encoding_speed, options.decoding_speed);
+  if (options.encoding_method >= 0) {
+    encoder.SetEncodingMethod(options.encoding_method);
+  }
+
+  // Quantization only applies to floating point attributes
+  for (int i = 0; i < pc.num_attributes(); ++i) {
+    const int32_t bits = quantization_bits[i];
+    if (bits > 0 && pc.attribute(i)->data_type() == draco::DT_FLOAT32) {
+      encoder.SetAttributeQuantization(i, bits);
+    }
+  }
+
+  draco::EncoderBuffer buffer;
+  if (!encoder.EncodeToBuffer(&buffer).ok()) {
+    return false;
+  }
+
+  out.reserve(buffer.size());
+  for (size_t i = 0; i < buffer.size(); ++i) {
+    out.push_back(static_cast<uint8_t>(buffer.data()[i]));
+  }
+  return true;
+}
+
+bool mesh_builder_encode(const DracoMeshBuilder &builder,
+                         const EncodeOptions &options,
+                         rust::Vec<uint8_t> &out) {
+  const draco::Mesh &mesh = *builder.mesh;
+
+  // The point cloud constructor ignores the faces and selects the point
+  // cloud encoders
+  draco::ExpertEncoder encoder =
+      options.point_cloud
+          ? draco::ExpertEncoder(static_cast<const draco::PointCloud &>(mesh))
+          : draco::ExpertEncoder(mesh);
+  return encode_to_vec(encoder, mesh, options, builder.quantization_bits, out);
+}
+
+bool transcode(rust::Slice<const uint8_t> data, const EncodeOptions &options,
+               rust::Slice<const int32_t> quantization_bits,
+               rust::Vec<uint8_t> &out) {
+  draco::DecoderBuffer buffer;
+  buffer.Init(reinterpret_cast<const char *>(data.data()), data.size());
+
+  // Returns a draco::Mesh for mesh bitstreams
+  draco::Decoder decoder;
+  auto status_or_geometry = decoder.DecodePointCloudFromBuffer(&buffer);
+  if (!status_or_geometry.ok()) {
+    return false;
+  }
+  std::unique_ptr<draco::PointCloud> pc = std::move(status_or_geometry).value();
+
+  // Quantization bits are given per attribute type, with the last entry
+  // applying to generic and any other types
+  if (quantization_bits.empty()) {
+    return false;
+  }
+  std::vector<int32_t> bits(pc->num_attributes());
+  for (int i = 0; i < pc->num_attributes(); ++i) {
+    const size_t type = static_cast<size_t>(pc->attribute(i)->attribute_type());
+    bits[i] = quantization_bits[std::min(type, quantization_bits.size() - 1)];
+  }
+
+  const draco::Mesh *mesh = dynamic_cast<const draco::Mesh *>(pc.get());
+  draco::ExpertEncoder encoder =
+      mesh ? draco::ExpertEncoder(*mesh) : draco::ExpertEncoder(*pc);
+  return encode_to_vec(encoder, *pc, options, bits, out);
+}
+
+// Decoder recording where each section of the bitstream ends
+template <typename Base> class MeasuringDecoder : public Base {
+public:
+  int64_t geometry_start = 0;
+  int64_t geometry_end = 0;
+  int64_t attributes_start = 0;
+  std::vector<int64_t> decoder_ends;
+
+protected:
+  bool DecodeGeometryData() override {
+    geometry_start = this->buffer()->decoded_size();
+    const bool ok = Base::DecodeGeometryData();
+    geometry_end = this->buffer()->decoded_size();
+    return ok;
+  }
+
+  // Same as the base implementation, with the end of each attributes
+  // decoder recorded
+  bool DecodeAllAttributes() override {
+    attributes_start = this->buffer()->decoded_size();
+    for (int i = 0; i < this->num_attributes_decoders(); ++i) {
+      if (!this->attributes_decoder(i)->DecodeAttributes(this->buffer()))
+        return false;
+      decoder_ends.push_back(this->buffer()->decoded_size());
+    }
+    return true;
+  }
+};
+
+template <typename Decoder, typename Geometry>
+static bool measure_stream(const draco::DecoderOptions &options,
+                           draco::DecoderBuffer &buffer, StreamLayout &out) {
+  MeasuringDecoder<Decoder> decoder;
+  Geometry geometry;
+  if (!decoder.Decode(options, &buffer, &geometry).ok())
+    return false;
+
+  out.num_points = geometry.num_points();
+  if constexpr (std::is_same<Geometry, draco::Mesh>::value)
+    out.num_faces = geometry.num_faces();
+  out.geometry_start = static_cast<size_t>(decoder.geometry_start);
+  out.geometry_end = static_cast<size_t>(decoder.geometry_end);
+  out.attributes_start = static_cast<size_t>(decoder.attributes_start);
+  for (int64_t end : decoder.decoder_ends) {
+    out.decoder_ends.push_back(static_cast<size_t>(end));
+  }
+
+  for (int i = 0; i < decoder.num_attributes_decoders(); ++i) {
+    draco::AttributesDecoderInterface *attributes =
+        decoder.attributes_decoder(i);
+    for (int j = 0; j < attributes->GetNumAttributes(); ++j) {
+      const draco::PointAttribute *attr =
+          geometry.attribute(attributes->GetAttributeId(j));
+      if (!attr)
+        continue;
+
+      MeshAttribute transform;
+      fill_transform(*attr, transform);
+
+      StreamAttribute stream_attr;
+      stream_attr.unique_id = attr->unique_id();
+      stream_attr.attribute_type = static_cast<int32_t>(attr->attribute_type());
+      stream_attr.num_components = attr->num_components();
+      stream_attr.decoder = static_cast<uint32_t>(i);
+      stream_attr.quantization_bits = transform.quantization_bits;
+      stream_attr.octahedron_bits = transform.octahedron_bits;
+      out.attributes.push_back(stream_attr);
+    }
+  }
+  return true;
+}
+
+bool analyze_stream(rust::Slice<const uint8_t> data, StreamLayout &out) {
+  // Geometry type and encoding method follow the magic and version
+  constexpr size_t kHeaderLength = 11;
+  if (data.size() < kHeaderLength)
+    return false;
+  const uint8_t geometry_type = data[7];
+  const uint8_t method = data[8];
+
+  draco::DecoderBuffer buffer;
+  buffer.Init(reinterpret_cast<const char *>(data.data()), data.size());
+
+  // Transforms are skipped, their parameters are all that is needed
+  draco::DecoderOptions options;
+  for (int type = draco::GeometryAttribute::POSITION;
+       type < draco::GeometryAttribute::NAMED_ATTRIBUTES_COUNT; ++type) {
+    options.SetAttributeBool(static_cast<draco::GeometryAttribute::Type>(type),
+                             "skip_attribute_transform", true);
+  }
+
+  if (geometry_type == draco::TRIANGULAR_MESH) {
+    if (method == draco::MESH_EDGEBREAKER_ENCODING)
+      return measure_stream<draco::MeshEdgebreakerDecoder, draco::Mesh>(
+          options, buffer, out);
+    return measure_stream<draco::MeshSequentialDecoder, draco::Mesh>(
+        options, buffer, out);
+  }
+  if (method == draco::POINT_CLOUD_KD_TREE_ENCODING)
+    return measure_stream<draco::PointCloudKdTreeDecoder, draco::PointCloud>(
+        options, buffer, out);
+  return measure_stream<draco::PointCloudSequentialDecoder, draco::PointCloud>(
+      options, buffer, out);
+}
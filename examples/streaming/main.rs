@@ -0,0 +1,107 @@
+//! Streams Draco tiles through a `DecodedMeshCache`.
+//!
+//! A camera sweeps back and forth over a row of tiles, keeping a window of
+//! them visible. Tiles are fetched and decoded on a cache miss and evicted
+//! once the decoded meshes exceed the memory budget.
+//!
+//! Native: `cargo run --example streaming -- <tile.drc>...`
+//! WASM: serve the crate root and load the example, tiles are fetched from
+//! `assets/`.
+
+use draco_decoder::DecodedMeshCache;
+
+/// Number of tiles visible at once.
+const VISIBLE_TILES: usize = 3;
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn fetch_tile(url: &str) -> Option<Vec<u8>> {
+    tokio::fs::read(url).await.ok()
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn fetch_tile(url: &str) -> Option<Vec<u8>> {
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::{Request, RequestInit, RequestMode, Response};
+
+    let opts = RequestInit::new();
+    opts.set_method("GET");
+    opts.set_mode(RequestMode::Cors);
+
+    let request = Request::new_with_str_and_init(url, &opts).ok()?;
+    let resp_value = JsFuture::from(web_sys::window()?.fetch_with_request(&request))
+        .await
+        .ok()?;
+    let resp: Response = resp_value.dyn_into().ok()?;
+    if !resp.ok() {
+        return None;
+    }
+    let buf = JsFuture::from(resp.array_buffer().ok()?).await.ok()?;
+    Some(js_sys::Uint8Array::new(&buf).to_vec())
+}
+
+fn log(message: &str) {
+    #[cfg(not(target_arch = "wasm32"))]
+    println!("{message}");
+    #[cfg(target_arch = "wasm32")]
+    web_sys::console::log_1(&message.into());
+}
+
+async fn stream(tiles: Vec<String>, budget: usize) {
+    // Tiles are keyed by their position, so a file listed twice still
+    // behaves like two tiles
+    let mut cache = DecodedMeshCache::<usize>::new(budget);
+
+    // Sweep right and back left over the tiles
+    let last = tiles.len().saturating_sub(VISIBLE_TILES);
+    let positions: Vec<usize> = (0..=last).chain((0..last).rev()).collect();
+    for (frame, start) in positions.into_iter().enumerate() {
+        let mut vertices = 0;
+        for (tile, url) in tiles.iter().enumerate().skip(start).take(VISIBLE_TILES) {
+            match cache.get_or_fetch(tile, || fetch_tile(url)).await {
+                Some(mesh) => vertices += mesh.config.vertex_count(),
+                None => log(&format!("failed to load {url}")),
+            }
+        }
+
+        let stats = cache.stats();
+        log(&format!(
+            "frame {frame}: {vertices} vertices, {} tiles cached in {} / {} bytes, {} hits, {} misses, {} evictions",
+            cache.len(),
+            cache.used_bytes(),
+            cache.budget(),
+            stats.hits,
+            stats.misses,
+            stats.evictions,
+        ));
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[tokio::main]
+async fn main() {
+    let mut tiles: Vec<String> = std::env::args().skip(1).collect();
+    if tiles.is_empty() {
+        tiles = vec!["assets/mesh.drc".to_string(); 8];
+    }
+
+    // Size the budget to hold one tile more than is visible, so the sweep
+    // both hits and evicts
+    let Some(first) = fetch_tile(&tiles[0]).await else {
+        eprintln!("failed to read {}", tiles[0]);
+        return;
+    };
+    let Some(mesh) = draco_decoder::decode_mesh_with_config(&first).await else {
+        eprintln!("failed to decode {}", tiles[0]);
+        return;
+    };
+    stream(tiles, mesh.data.len() * (VISIBLE_TILES + 1)).await;
+}
+
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    wasm_bindgen_futures::spawn_local(async {
+        let tiles = vec!["assets/mesh.drc".to_string(); 8];
+        stream(tiles, 64 * 1024 * 1024).await;
+    });
+}
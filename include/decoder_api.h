@@ -6,11 +6,20 @@
 
 // Forward declarations - defined in ffi.rs.h
 struct MeshAttribute;
+struct AttributeWarning;
 struct MeshConfig;
+struct LayoutOptions;
+struct PointCloudConfig;
+struct EncodeOptions;
+struct StreamLayout;
+
+// Forward declaration - opaque Rust type defined in ffi.rs
+struct DecodeControl;
 
 // Forward declaration for draco::Mesh
 namespace draco {
 class Mesh;
+class PointCloud;
 }
 
 // DracoMesh class - wraps draco::Mesh
@@ -22,14 +31,90 @@ public:
   ~DracoMesh();
 };
 
+// DracoMeshBuilder class - a draco::Mesh being assembled for encoding, also
+// used for point clouds by leaving out the faces
+class DracoMeshBuilder {
+public:
+  std::unique_ptr<draco::Mesh> mesh;
+  // Quantization bits for each attribute, by attribute id
+  std::vector<int32_t> quantization_bits;
+
+  explicit DracoMeshBuilder(uint32_t num_points);
+  ~DracoMeshBuilder();
+};
+
+// DracoPointCloud class - wraps draco::PointCloud
+class DracoPointCloud {
+public:
+  std::unique_ptr<draco::PointCloud> pc;
+
+  explicit DracoPointCloud(std::unique_ptr<draco::PointCloud> p);
+  ~DracoPointCloud();
+};
+
 
 rust::Vec<uint8_t> decode_point_cloud(rust::Slice<const uint8_t> data);
 
-// Cache API - returns opaque type
-std::unique_ptr<DracoMesh> create_mesh(rust::Slice<const uint8_t> data);
+// Cache API - returns opaque type, or null with the draco error in `error`.
+// `control` is told when the connectivity and each attributes decoder are
+// decompressed
+std::unique_ptr<DracoMesh>
+create_mesh(rust::Slice<const uint8_t> data,
+            rust::Slice<const int32_t> keep_quantized,
+            const DecodeControl &control, rust::String &error);
+
+// Mesh Config from DracoMesh in the buffer layout described by `layout`
+bool compute_mesh_config(const DracoMesh &mesh, const LayoutOptions &layout,
+                         MeshConfig &config);
+
+// Decode to pre-allocated buffer, returns SIZE_MAX on failure or once
+// `control` is cancelled, which is checked before each attribute. `control`
+// is told when the indices and each attribute are written
+size_t decode_mesh_to_buffer(const DracoMesh &mesh,
+                             const LayoutOptions &layout,
+                             const DecodeControl &control, uint8_t *out_ptr,
+                             size_t out_len);
+
+// Point cloud API - mirrors the mesh API without indices
+std::unique_ptr<DracoPointCloud>
+create_point_cloud(rust::Slice<const uint8_t> data,
+                   const DecodeControl &control, rust::String &error);
+
+bool compute_point_cloud_config(const DracoPointCloud &pc,
+                                PointCloudConfig &config);
+
+// Decode to pre-allocated buffer, returns SIZE_MAX on failure or once
+// `control` is cancelled, which is checked before each attribute. `control`
+// is told when each attribute is written
+size_t decode_point_cloud_to_buffer(const DracoPointCloud &pc,
+                                    const DecodeControl &control,
+                                    uint8_t *out_ptr, size_t out_len);
+
+// Encoder API - build a mesh, then encode it
+std::unique_ptr<DracoMeshBuilder> create_mesh_builder(uint32_t num_points);
+
+bool mesh_builder_set_faces(DracoMeshBuilder &builder,
+                            rust::Slice<const uint32_t> indices);
+
+bool mesh_builder_add_attribute(DracoMeshBuilder &builder,
+                                int32_t attribute_type, uint32_t data_type,
+                                uint32_t dim, int32_t quantization_bits,
+                                rust::Slice<const uint8_t> data);
+
+bool mesh_builder_encode(const DracoMeshBuilder &builder,
+                         const EncodeOptions &options,
+                         rust::Vec<uint8_t> &out);
+
+// Decode and re-encode without copying the geometry out of draco
+bool transcode(rust::Slice<const uint8_t> data, const EncodeOptions &options,
+               rust::Slice<const int32_t> quantization_bits,
+               rust::Vec<uint8_t> &out);
+
+// Decode while recording the size of each section of the bitstream
+bool analyze_stream(rust::Slice<const uint8_t> data, StreamLayout &out);
 
-// Mesh Config from DracoMesh
-bool compute_mesh_config(const DracoMesh &mesh, MeshConfig &config);
+// Number of DracoMesh handles currently alive
+int64_t live_mesh_count();
 
-// Decode to pre-allocated buffer
-size_t decode_mesh_to_buffer(const DracoMesh &mesh, uint8_t *out_ptr, size_t out_len);
+// Version of the vendored draco library
+rust::String draco_version();
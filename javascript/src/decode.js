@@ -0,0 +1,259 @@
+// Decoding shared by the worker and the time-sliced main thread decoder.
+//
+// tools/build.js appends this file to the Draco module loader of the
+// draco_decoder_js worker, where `loadDraco` resolves to the initialized
+// Draco decoder module.
+//
+// The decode functions are generators that yield between chunks of work, so
+// a caller on the main thread can return to the event loop in between.
+// runSteps runs them to completion without yielding.
+
+// Number of indices or attribute values written between two yields
+const CHUNK_SIZE = 16384;
+
+// Getter and setter for every data type the decoder can extract
+const ATTRIBUTE_ACCESS = draco => ({
+    [draco.DT_FLOAT32]: [draco.DracoFloat32Array, "GetAttributeFloatForAllPoints", "setFloat32"],
+    [draco.DT_INT32]: [draco.DracoInt32Array, "GetAttributeInt32ForAllPoints", "setInt32"],
+    [draco.DT_UINT32]: [draco.DracoUInt32Array, "GetAttributeUInt32ForAllPoints", "setUint32"],
+    [draco.DT_INT16]: [draco.DracoInt16Array, "GetAttributeInt16ForAllPoints", "setInt16"],
+    [draco.DT_UINT16]: [draco.DracoUInt16Array, "GetAttributeUInt16ForAllPoints", "setUint16"],
+    [draco.DT_INT8]: [draco.DracoInt8Array, "GetAttributeInt8ForAllPoints", "setInt8"],
+    [draco.DT_UINT8]: [draco.DracoUInt8Array, "GetAttributeUInt8ForAllPoints", "setUint8"],
+});
+
+// Lowest and largest value of every integer data type
+const INTEGER_RANGE = draco => ({
+    [draco.DT_INT8]: [-128, 127],
+    [draco.DT_UINT8]: [0, 255],
+    [draco.DT_INT16]: [-32768, 32767],
+    [draco.DT_UINT16]: [0, 65535],
+    [draco.DT_INT32]: [-2147483648, 2147483647],
+    [draco.DT_UINT32]: [0, 4294967295],
+});
+
+function dataTypeSize(draco, dataType) {
+    switch (dataType) {
+        case draco.DT_INT8:
+        case draco.DT_UINT8:
+            return 1;
+        case draco.DT_INT16:
+        case draco.DT_UINT16:
+            return 2;
+        case draco.DT_INT32:
+        case draco.DT_UINT32:
+        case draco.DT_FLOAT32:
+            return 4;
+        case draco.DT_FLOAT64:
+            return 8;
+        default:
+            throw new Error("Unknown data type: " + dataType);
+    }
+}
+
+// Decodes `data` into a Draco mesh, or a point cloud if `pointCloud` is set
+// and the data is not a mesh
+function decodeGeometry(draco, decoder, data, pointCloud) {
+    const buffer = new draco.DecoderBuffer();
+    buffer.Init(new Int8Array(data), data.length);
+    const isMesh = decoder.GetEncodedGeometryType(buffer) === draco.TRIANGULAR_MESH;
+    if (!isMesh && !pointCloud) {
+        draco.destroy(buffer);
+        throw new Error("Unsupported geometry type");
+    }
+    const geometry = isMesh ? new draco.Mesh() : new draco.PointCloud();
+    const status = isMesh
+        ? decoder.DecodeBufferToMesh(buffer, geometry)
+        : decoder.DecodeBufferToPointCloud(buffer, geometry);
+    draco.destroy(buffer);
+    if (!status.ok()) {
+        draco.destroy(geometry);
+        throw new Error("Draco decoding failed: " + status.error_msg());
+    }
+    return geometry;
+}
+
+// Data type `attribute` is written as: the type requested for its unique id
+// or attribute type in `outputTypes`, see WasmDecoder::with_worker, or else
+// its decoded type. Attributes that cannot be extracted keep their type
+function outputDataType(draco, attribute, outputTypes) {
+    const dataType = attribute.data_type();
+    if (!outputTypes || !ATTRIBUTE_ACCESS(draco)[dataType]) return dataType;
+    const byId = outputTypes.attributes.find(([uniqueId]) => uniqueId === attribute.unique_id());
+    // Attribute types are indexed like AttributeSemantic, others are generic
+    const semantic = Math.min(Math.max(attribute.attribute_type(), 0), draco.GENERIC);
+    const requested = byId ? byId[1] : outputTypes.semantics[semantic];
+    return ATTRIBUTE_ACCESS(draco)[requested] ? requested : dataType;
+}
+
+// Returns a function converting a value of type `from` to type `to` like the
+// native library: conversions between integer and floating point types, and
+// between integer types when the source is `normalized`, are normalized.
+// Integer results are rounded and clamped to the range of their type
+function converter(draco, from, to, normalized) {
+    const ranges = INTEGER_RANGE(draco);
+    const fromMax = ranges[from] && ranges[from][1];
+    const toRange = ranges[to];
+    const scaleFrom = fromMax !== undefined && (!toRange || normalized);
+    const scaleTo = toRange !== undefined && (fromMax === undefined || normalized);
+    return value => {
+        if (scaleFrom) value = Math.max(value / fromMax, -1);
+        if (scaleTo) value *= toRange[1];
+        if (toRange) value = Math.min(Math.max(Math.round(value), toRange[0]), toRange[1]);
+        return value;
+    };
+}
+
+// Lays out the attributes of `geometry` by unique id, starting at `offset`,
+// in the data types requested by `outputTypes`
+function attributeLayout(draco, decoder, geometry, offset, outputTypes) {
+    const count = geometry.num_points();
+    const attributes = [];
+    for (let index = 0; index < geometry.num_attributes(); index++) {
+        attributes.push(decoder.GetAttribute(geometry, index));
+    }
+    attributes.sort((a, b) => a.unique_id() - b.unique_id());
+
+    const layout = attributes.map(attribute => {
+        const dataType = outputDataType(draco, attribute, outputTypes);
+        const length = attribute.num_components() * count * dataTypeSize(draco, dataType);
+        // Floats converted to integers are scaled to their range
+        const normalized =
+            dataType !== draco.DT_FLOAT32 &&
+            (attribute.normalized() || attribute.data_type() === draco.DT_FLOAT32);
+        const entry = {
+            dim: attribute.num_components(),
+            data_type: dataType,
+            attribute_type: attribute.attribute_type(),
+            offset,
+            length,
+            unique_id: attribute.unique_id(),
+            normalized,
+        };
+        offset += length;
+        return entry;
+    });
+    return { attributes, layout, end: offset };
+}
+
+// Writes the values of every attribute at its offset in `view`, converted to
+// the data type of its layout entry. Attributes with data types that cannot be
+// extracted are left as zeros.
+function* writeAttributes(draco, decoder, geometry, attributes, layout, view) {
+    const access = ATTRIBUTE_ACCESS(draco);
+    for (let index = 0; index < attributes.length; index++) {
+        const entry = layout[index];
+        const source = attributes[index].data_type();
+        if (!access[source] || !access[entry.data_type]) continue;
+        const [ArrayType, getter] = access[source];
+        const setter = access[entry.data_type][2];
+        const convert =
+            source === entry.data_type
+                ? value => value
+                : converter(draco, source, entry.data_type, attributes[index].normalized());
+        const values = new ArrayType();
+        try {
+            decoder[getter](geometry, attributes[index], values);
+            const size = dataTypeSize(draco, entry.data_type);
+            for (let value = 0; value < values.size(); value++) {
+                view[setter](entry.offset + value * size, convert(values.GetValue(value)), true);
+                if ((value + 1) % CHUNK_SIZE === 0) yield;
+            }
+        } finally {
+            draco.destroy(values);
+        }
+        yield;
+    }
+}
+
+// Writes the triangle indices of `mesh` at the start of `view`
+function* writeIndices(draco, decoder, mesh, view, wide) {
+    const face = new draco.DracoInt32Array();
+    try {
+        let offset = 0;
+        for (let index = 0; index < mesh.num_faces(); index++) {
+            decoder.GetFaceFromMesh(mesh, index, face);
+            for (let corner = 0; corner < 3; corner++) {
+                if (wide) {
+                    view.setUint32(offset, face.GetValue(corner), true);
+                    offset += 4;
+                } else {
+                    view.setUint16(offset, face.GetValue(corner), true);
+                    offset += 2;
+                }
+            }
+            if ((3 * (index + 1)) % CHUNK_SIZE === 0) yield;
+        }
+    } finally {
+        draco.destroy(face);
+    }
+    yield;
+}
+
+// Indices are 16-bit when every vertex can be addressed with them
+const hasWideIndices = mesh => mesh.num_points() > 65536;
+
+function* decodeMeshSteps(draco, data, outputTypes) {
+    const decoder = new draco.Decoder();
+    try {
+        const mesh = decodeGeometry(draco, decoder, data, false);
+        try {
+            yield;
+            const vertexCount = mesh.num_points();
+            const indexCount = 3 * mesh.num_faces();
+            const wide = hasWideIndices(mesh);
+            const indexLength = indexCount * (wide ? 4 : 2);
+            const { attributes, layout, end } = attributeLayout(draco, decoder, mesh, indexLength, outputTypes);
+
+            const decoded = new ArrayBuffer(end);
+            const view = new DataView(decoded);
+            yield* writeIndices(draco, decoder, mesh, view, wide);
+            yield* writeAttributes(draco, decoder, mesh, attributes, layout, view);
+
+            return {
+                decoded: new Uint8Array(decoded),
+                config: {
+                    vertex_count: vertexCount,
+                    index_count: indexCount,
+                    index_length: indexLength,
+                    buffer_size: end,
+                    attributes: layout,
+                },
+            };
+        } finally {
+            draco.destroy(mesh);
+        }
+    } finally {
+        draco.destroy(decoder);
+    }
+}
+
+function* decodePointCloudSteps(draco, data, outputTypes) {
+    const decoder = new draco.Decoder();
+    try {
+        const geometry = decodeGeometry(draco, decoder, data, true);
+        try {
+            yield;
+            const { attributes, layout, end } = attributeLayout(draco, decoder, geometry, 0, outputTypes);
+
+            const decoded = new ArrayBuffer(end);
+            yield* writeAttributes(draco, decoder, geometry, attributes, layout, new DataView(decoded));
+
+            return {
+                decoded: new Uint8Array(decoded),
+                config: { point_count: geometry.num_points(), buffer_size: end, attributes: layout },
+            };
+        } finally {
+            draco.destroy(geometry);
+        }
+    } finally {
+        draco.destroy(decoder);
+    }
+}
+
+function runSteps(steps) {
+    for (;;) {
+        const step = steps.next();
+        if (step.done) return step.value;
+    }
+}
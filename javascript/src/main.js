@@ -0,0 +1,191 @@
+// Main thread side of the decoder: starts the worker and resolves decode
+// requests from its replies, or decodes on the main thread in time slices.
+//
+// tools/build.js prepends `dracoSource`, the Draco module loader followed by
+// decode.js, and `workerSource`, the worker built from it and worker.js.
+// Workers created by the page run `workerSource` with serveDecoder.
+
+const workerBlob =
+    typeof self !== "undefined" &&
+    self.Blob &&
+    new Blob(["(self.URL || self.webkitURL).revokeObjectURL(self.location.href);", workerSource], {
+        type: "text/javascript;charset=utf-8",
+    });
+
+// Creates the worker from a Blob URL, or from a data: URL where Blob URLs
+// cannot be used
+function createWorker() {
+    let url;
+    try {
+        url = workerBlob && (self.URL || self.webkitURL).createObjectURL(workerBlob);
+        if (!url) throw "";
+        const worker = new Worker(url);
+        worker.addEventListener("error", () => {
+            (self.URL || self.webkitURL).revokeObjectURL(url);
+        });
+        return worker;
+    } catch {
+        return new Worker("data:text/javascript;charset=utf-8," + encodeURIComponent(workerSource));
+    }
+}
+
+let worker;
+let nextId = 0;
+const pending = new Map();
+
+// Rejects every pending request after the worker failed, and starts a new
+// worker for the next request
+function fail(error) {
+    const requests = [...pending.values()];
+    pending.clear();
+    if (worker) worker.terminate();
+    worker = undefined;
+    for (const request of requests) request.reject(error);
+}
+
+// Starts the worker on the first request, so that the module can be loaded
+// where workers are unavailable
+function getWorker() {
+    if (!worker) {
+        worker = createWorker();
+        worker.addEventListener("error", event => fail(event.message || "Draco worker failed"));
+        worker.addEventListener("messageerror", () => fail("Draco worker reply could not be received"));
+        worker.onmessage = event => {
+            const { id, success, decoded, error, config } = event.data;
+            const request = pending.get(id);
+            if (!request) return;
+            pending.delete(id);
+            if (!success) {
+                request.reject(error);
+            } else if (config) {
+                request.resolve({ decoded, config });
+            } else {
+                request.resolve(decoded);
+            }
+        };
+    }
+    return worker;
+}
+
+const CANCELLED = "Draco decode cancelled";
+
+// Rejects a pending request whose signal was aborted. The worker cannot be
+// interrupted, so it is terminated once no other request is pending and a
+// new one is started by the next request.
+function cancel(id) {
+    const request = pending.get(id);
+    if (!request) return;
+    pending.delete(id);
+    request.reject(CANCELLED);
+    if (pending.size === 0) {
+        worker.terminate();
+        worker = undefined;
+    }
+}
+
+function request(message, view, signal) {
+    return new Promise((resolve, reject) => {
+        if (signal && signal.aborted) return reject(CANCELLED);
+        const id = nextId++;
+        pending.set(id, { resolve, reject });
+        if (signal) signal.addEventListener("abort", () => cancel(id), { once: true });
+        getWorker().postMessage({ id, view, ...message }, [view.buffer]);
+    });
+}
+
+export function decodeDracoMeshInWorker(view, bufferLength) {
+    return request({ bufferLength, withConfig: false }, view);
+}
+
+export function decodeDracoMeshInWorkerWithConfig(view, signal, outputTypes) {
+    return request({ withConfig: true, outputTypes }, view, signal);
+}
+
+export function decodeDracoPointCloudInWorkerWithConfig(view, signal, outputTypes) {
+    return request({ withConfig: true, pointCloud: true, outputTypes }, view, signal);
+}
+
+// Runs the decoder in the calling worker, for workers created by the page
+// instead of the embedded one, see WasmDecoder::with_worker. The worker is
+// loaded from a Blob URL; requests received until it is ready are handled
+// once it is, or fail if it cannot be loaded.
+export function serveDecoder() {
+    const queued = [];
+    const queue = event => queued.push(event);
+    self.addEventListener("message", queue);
+    const url = URL.createObjectURL(new Blob([workerSource], { type: "text/javascript" }));
+    return import(url)
+        .catch(error => {
+            const message = "Draco worker could not be loaded: " + error;
+            self.onmessage = event => self.postMessage({ id: event.data.id, success: false, error: message });
+        })
+        .then(() => {
+            self.removeEventListener("message", queue);
+            for (const event of queued) self.onmessage(event);
+        })
+        .finally(() => URL.revokeObjectURL(url));
+}
+
+let mainThreadDraco;
+
+// Evaluates the Draco module loader and decode.js on the main thread
+function loadMainThreadDraco() {
+    if (!mainThreadDraco) {
+        const source = dracoSource + "\nexport { loadDraco, decodeMeshSteps, decodePointCloudSteps };\n";
+        const url = URL.createObjectURL(new Blob([source], { type: "text/javascript" }));
+        mainThreadDraco = import(url).finally(() => URL.revokeObjectURL(url));
+        mainThreadDraco.catch(() => {
+            mainThreadDraco = undefined;
+        });
+    }
+    return mainThreadDraco;
+}
+
+// Resolves once the browser is idle, or on the next task where
+// requestIdleCallback is unavailable
+function nextSlice() {
+    return new Promise(resolve => {
+        if (typeof requestIdleCallback === "function") {
+            requestIdleCallback(() => resolve(), { timeout: 50 });
+        } else {
+            setTimeout(resolve, 0);
+        }
+    });
+}
+
+// Runs the decode steps, returning to the event loop whenever a slice of
+// `sliceMs` milliseconds is used up. Stops between slices once `signal` is
+// aborted.
+async function runSliced(steps, sliceMs, signal) {
+    let deadline = performance.now() + sliceMs;
+    for (;;) {
+        const step = steps.next();
+        if (step.done) return step.value;
+        if (performance.now() >= deadline) {
+            await nextSlice();
+            if (signal && signal.aborted) {
+                steps.return();
+                throw CANCELLED;
+            }
+            deadline = performance.now() + sliceMs;
+        }
+    }
+}
+
+// Returns functions with the same signatures as the worker functions above
+// that decode on the main thread, in slices of at most `sliceMs`
+// milliseconds between chunks of attribute values. Draco decompresses the
+// whole geometry in one step, which is not sliced.
+export function createTimeSlicedDecoder(sliceMs) {
+    const decode = stepsFunction => async (view, signal, outputTypes) => {
+        const module = await loadMainThreadDraco();
+        const draco = await module.loadDraco();
+        await nextSlice();
+        if (signal && signal.aborted) throw CANCELLED;
+        return runSliced(module[stepsFunction](draco, view, outputTypes), sliceMs, signal);
+    };
+    return {
+        decodeDracoMeshInWorkerWithConfig: decode("decodeMeshSteps"),
+        decodeDracoPointCloudInWorkerWithConfig: decode("decodePointCloudSteps"),
+    };
+}
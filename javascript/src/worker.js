@@ -0,0 +1,38 @@
+// Message handler of the decoder worker.
+//
+// tools/build.js appends this file to the Draco module loader and decode.js,
+// and wraps them in a function that runs in the worker.
+
+async function decodeMeshWithConfig(data, outputTypes) {
+    return runSteps(decodeMeshSteps(await loadDraco(), data, outputTypes));
+}
+
+async function decodePointCloudWithConfig(data, outputTypes) {
+    return runSteps(decodePointCloudSteps(await loadDraco(), data, outputTypes));
+}
+
+// Decodes with the layout of decodeMeshWithConfig, for callers that already
+// know it and only pass the expected buffer length
+async function decodeMesh(data, bufferLength) {
+    const { decoded } = await decodeMeshWithConfig(data);
+    if (decoded.length > bufferLength) {
+        throw new Error("Decoded mesh does not fit in " + bufferLength + " bytes");
+    }
+    return decoded;
+}
+
+self.onmessage = async event => {
+    const { id, view, bufferLength, withConfig, pointCloud, outputTypes } = event.data;
+    try {
+        if (withConfig) {
+            const decode = pointCloud ? decodePointCloudWithConfig : decodeMeshWithConfig;
+            const { decoded, config } = await decode(view, outputTypes);
+            self.postMessage({ id, success: true, decoded, config }, [decoded.buffer]);
+        } else {
+            const decoded = await decodeMesh(view, bufferLength);
+            self.postMessage({ id, success: true, decoded }, [decoded.buffer]);
+        }
+    } catch (error) {
+        self.postMessage({ id, success: false, error: error.message });
+    }
+};
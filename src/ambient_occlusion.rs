@@ -0,0 +1,199 @@
+use crate::bvh::Bvh;
+use crate::math::{Vec3, cross, dot, normalize, sub};
+use crate::{AttributeDataType, AttributeSemantic, MeshAttribute, MeshDecodeResult};
+
+/// Parameters of [`MeshDecodeResult::bake_ambient_occlusion`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AmbientOcclusionParams {
+    samples: u32,
+    max_distance: f32,
+}
+
+impl Default for AmbientOcclusionParams {
+    fn default() -> Self {
+        Self {
+            samples: 64,
+            max_distance: f32::INFINITY,
+        }
+    }
+}
+
+impl AmbientOcclusionParams {
+    /// Creates parameters with 64 rays per vertex and no distance limit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of rays cast from every vertex.
+    pub fn with_samples(mut self, samples: u32) -> Self {
+        self.samples = samples;
+        self
+    }
+
+    /// Sets the distance beyond which geometry no longer occludes a vertex.
+    ///
+    /// Without a limit, interiors of closed meshes are fully occluded.
+    pub fn with_max_distance(mut self, max_distance: f32) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    /// Returns the number of rays per vertex.
+    pub fn samples(&self) -> u32 {
+        self.samples
+    }
+
+    /// Returns the occlusion distance limit.
+    pub fn max_distance(&self) -> f32 {
+        self.max_distance
+    }
+}
+
+/// Cosine weighted directions over the hemisphere around +Z, spread on a
+/// golden angle spiral so the result is deterministic.
+fn hemisphere_directions(samples: u32) -> Vec<Vec3> {
+    let golden_angle = std::f32::consts::PI * (3.0 - 5.0f32.sqrt());
+    (0..samples)
+        .map(|i| {
+            let u = (i as f32 + 0.5) / samples as f32;
+            let radius = u.sqrt();
+            let angle = i as f32 * golden_angle;
+            [radius * angle.cos(), radius * angle.sin(), (1.0 - u).sqrt()]
+        })
+        .collect()
+}
+
+impl MeshDecodeResult {
+    /// Bakes per-vertex ambient occlusion and appends it as an attribute.
+    ///
+    /// From every vertex, `samples` rays are cast over the hemisphere around
+    /// the surface normal, computed from the triangles since decoded normals
+    /// may be quantized or missing. The fraction of rays that escape is
+    /// stored as a normalized single component `UInt8` attribute with a
+    /// [`AttributeSemantic::Generic`] semantic, 255 meaning unoccluded.
+    /// Vertices with identical positions share their normal, so seams do not
+    /// show.
+    ///
+    /// The indices and attributes are rewritten to a tightly packed buffer.
+    /// Meshes without `Float32` positions are returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use draco_decoder::{AmbientOcclusionParams, AttributeSemantic};
+    ///
+    /// let params = AmbientOcclusionParams::new().with_samples(128).with_max_distance(0.5);
+    /// let baked = result.bake_ambient_occlusion(&params);
+    /// let ao = baked.config.attributes().len() - 1;
+    /// let occlusion = baked.view::<u8>(ao).unwrap();
+    /// ```
+    pub fn bake_ambient_occlusion(&self, params: &AmbientOcclusionParams) -> MeshDecodeResult {
+        let position = self.config.find_attribute(AttributeSemantic::Position);
+        let Some(view) = position
+            .and_then(|index| self.view::<f32>(index))
+            .filter(|view| view.dim() >= 3)
+        else {
+            return self.clone();
+        };
+        let vertex_count = self.config.vertex_count();
+        let points: Vec<Vec3> = view.vertices().map(|v| [v[0], v[1], v[2]]).collect();
+        let triangles: Vec<[u32; 3]> = self
+            .triangles()
+            .filter(|triangle| {
+                triangle
+                    .iter()
+                    .all(|&vertex| (vertex as usize) < points.len())
+            })
+            .collect();
+
+        // Area weighted normals, accumulated on the first vertex of each position
+        let welded = self.welded_vertices();
+        let mut normals = vec![[0.0f32; 3]; points.len()];
+        for &[a, b, c] in &triangles {
+            let [pa, pb, pc] = [a, b, c].map(|vertex| points[vertex as usize]);
+            let normal = cross(sub(pb, pa), sub(pc, pa));
+            for vertex in [a, b, c] {
+                let sum = &mut normals[welded[vertex as usize] as usize];
+                for axis in 0..3 {
+                    sum[axis] += normal[axis];
+                }
+            }
+        }
+
+        let (mut min, mut max) = ([f32::INFINITY; 3], [f32::NEG_INFINITY; 3]);
+        for point in &points {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(point[axis]);
+                max[axis] = max[axis].max(point[axis]);
+            }
+        }
+        let diagonal = sub(max, min);
+        let bias = dot(diagonal, diagonal).sqrt() * 1e-4;
+
+        let bvh = Bvh::new(
+            triangles
+                .iter()
+                .map(|triangle| triangle.map(|vertex| points[vertex as usize]))
+                .collect(),
+        );
+        let directions = hemisphere_directions(params.samples);
+
+        let occlusion: Vec<u8> = (0..points.len())
+            .map(|vertex| {
+                let Some(normal) = normalize(normals[welded[vertex] as usize]) else {
+                    return u8::MAX;
+                };
+                if directions.is_empty() {
+                    return u8::MAX;
+                }
+
+                let helper = if normal[0].abs() < 0.9 {
+                    [1.0, 0.0, 0.0]
+                } else {
+                    [0.0, 1.0, 0.0]
+                };
+                let tangent = normalize(cross(helper, normal)).unwrap_or(helper);
+                let bitangent = cross(normal, tangent);
+                let origin: Vec3 =
+                    std::array::from_fn(|axis| points[vertex][axis] + normal[axis] * bias);
+
+                let hits = directions
+                    .iter()
+                    .filter(|local| {
+                        let direction: Vec3 = std::array::from_fn(|axis| {
+                            tangent[axis] * local[0]
+                                + bitangent[axis] * local[1]
+                                + normal[axis] * local[2]
+                        });
+                        bvh.occluded(origin, direction, params.max_distance)
+                    })
+                    .count();
+                let visible = 1.0 - hits as f32 / directions.len() as f32;
+                (visible * u8::MAX as f32).round() as u8
+            })
+            .collect();
+
+        let mut attributes: Vec<(MeshAttribute, Vec<u8>)> = (0..self.config.attributes().len())
+            .filter_map(|index| {
+                let attribute = *self.config.get_attribute(index)?;
+                Some((attribute, self.attribute_bytes(index)?.to_vec()))
+            })
+            .collect();
+        let unique_id = attributes
+            .iter()
+            .map(|(attribute, _)| attribute.unique_id() + 1)
+            .max()
+            .unwrap_or(0);
+        attributes.push((
+            MeshAttribute::new(1, AttributeDataType::UInt8, 0, 0)
+                .with_normalized(true)
+                .with_unique_id(unique_id),
+            occlusion,
+        ));
+
+        let indices: Vec<u32> = triangles.into_iter().flatten().collect();
+        let mut result = MeshDecodeResult::from_parts(vertex_count, &indices, attributes);
+        result.config.inherit(&self.config);
+        result
+    }
+}
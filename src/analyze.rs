@@ -0,0 +1,131 @@
+use crate::AttributeSemantic;
+
+/// Encoded size of one attribute, reported by [`analyze`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AttributeSize {
+    /// Unique id of the attribute in the bitstream.
+    pub unique_id: u32,
+    /// What the attribute represents.
+    pub semantic: AttributeSemantic,
+    /// Number of components per vertex.
+    pub components: u32,
+    /// Number of encoded values.
+    pub value_count: u32,
+    /// Bytes of attribute data, including transform parameters.
+    ///
+    /// Attributes encoded together, listed in `shared_with`, report the size
+    /// of their combined data.
+    pub encoded_size: usize,
+    /// Unique ids of the other attributes encoded in the same block as this
+    /// one.
+    pub shared_with: Vec<u32>,
+    /// Quantization bits, `None` if the attribute is not quantized.
+    pub quantization_bits: Option<u32>,
+    /// Whether the attribute holds octahedron encoded normals, quantized to
+    /// two components of `quantization_bits` each.
+    pub octahedral: bool,
+}
+
+impl AttributeSize {
+    /// Returns an estimate of the bytes saved by quantizing the attribute
+    /// with `fewer_bits` bits less.
+    ///
+    /// Each quantization bit costs about one entropy coded bit per encoded
+    /// component, so the estimate is the bits removed from every value,
+    /// capped at the encoded size. Returns 0 if the attribute is not
+    /// quantized.
+    pub fn estimated_savings(&self, fewer_bits: u32) -> usize {
+        let Some(bits) = self.quantization_bits else {
+            return 0;
+        };
+        let components = if self.octahedral { 2 } else { self.components };
+        let fewer_bits = fewer_bits.min(bits.saturating_sub(1)) as usize;
+        let saved_bits = self.value_count as usize * components as usize * fewer_bits;
+        (saved_bits / 8).min(self.encoded_size)
+    }
+}
+
+/// Where the bytes of a Draco bitstream go, returned by [`analyze`].
+///
+/// The sizes of the header, connectivity and attribute sections add up to
+/// `total_size`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompressionReport {
+    /// Size of the bitstream in bytes.
+    pub total_size: usize,
+    /// Bytes of the header and metadata.
+    pub header_size: usize,
+    /// Bytes of the mesh connectivity. For point clouds this is the point
+    /// count and any data the point cloud encoder writes before the
+    /// attributes.
+    pub connectivity_size: usize,
+    /// Bytes describing the attributes, before their data.
+    pub attribute_header_size: usize,
+    /// Every attribute in order of the bitstream.
+    pub attributes: Vec<AttributeSize>,
+    /// Number of decoded vertices.
+    pub vertex_count: u32,
+    /// Number of triangles, 0 for point clouds.
+    pub face_count: u32,
+}
+
+impl CompressionReport {
+    /// Returns the bytes of all attribute sections, descriptions included.
+    pub fn attribute_size(&self) -> usize {
+        self.total_size - self.header_size - self.connectivity_size
+    }
+
+    /// Returns how many triangle corners share each vertex on average.
+    ///
+    /// Meshes with well deduplicated vertices reuse each vertex about 6
+    /// times, values near 1 mean most triangles have their own vertices and
+    /// the mesh compresses poorly. Returns 0 for point clouds.
+    pub fn vertex_reuse(&self) -> f32 {
+        if self.vertex_count == 0 {
+            return 0.0;
+        }
+        self.face_count as f32 * 3.0 / self.vertex_count as f32
+    }
+
+    /// Returns an estimate of the bytes saved by quantizing every quantized
+    /// attribute with `fewer_bits` bits less, see
+    /// [`AttributeSize::estimated_savings`].
+    pub fn estimated_savings(&self, fewer_bits: u32) -> usize {
+        self.attributes
+            .iter()
+            .map(|attribute| attribute.estimated_savings(fewer_bits))
+            .sum::<usize>()
+            .min(self.attribute_size())
+    }
+}
+
+/// Reports where the bytes of a Draco bitstream go (native only).
+///
+/// The bitstream is decoded while recording where the header, connectivity
+/// and each block of attribute data end, so the sizes are exact rather than
+/// estimated. Attributes the encoder wrote in one block, as the sequential
+/// mesh encoder does, share the size of that block.
+///
+/// Returns `None` if the data cannot be decoded.
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::analyze;
+///
+/// let report = analyze(&data).expect("Failed to analyze");
+/// println!("connectivity: {} bytes", report.connectivity_size);
+/// for attribute in &report.attributes {
+///     println!(
+///         "{:?}: {} bytes, {} with 2 bits less",
+///         attribute.semantic,
+///         attribute.encoded_size,
+///         attribute.estimated_savings(2),
+///     );
+/// }
+/// ```
+pub fn analyze(data: &[u8]) -> Option<CompressionReport> {
+    crate::ffi::analyze(data)
+}
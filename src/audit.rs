@@ -0,0 +1,137 @@
+use std::io::{self, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::content_hash;
+use crate::{DecodeOptions, DecodeWarning, MeshDecodeResult, decode_header};
+
+/// A structured record of one decode, written by [`decode_mesh_audited`].
+///
+/// Hashes are 64-bit FNV-1a hashes prefixed with `fnv1a64:`, so identical
+/// inputs and outputs can be matched across runs and machines.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Version of this crate.
+    pub crate_version: String,
+    /// Backend that decoded the input, `native` or `wasm`.
+    pub backend: String,
+    /// Version of the draco library, if known for the backend.
+    pub draco_version: Option<String>,
+    /// Hash of the encoded input.
+    pub input_hash: String,
+    /// Size of the encoded input in bytes.
+    pub input_size: usize,
+    /// Bitstream version from the Draco header, if the header is valid.
+    pub bitstream_version: Option<String>,
+    /// Options the input was decoded with.
+    pub options: DecodeOptions,
+    /// Wall clock time spent decoding, in milliseconds.
+    pub decode_ms: f64,
+    /// Whether decoding succeeded.
+    pub success: bool,
+    /// Warnings reported while decoding.
+    pub warnings: Vec<DecodeWarning>,
+    /// Hash of the decoded buffer, if decoding succeeded.
+    pub output_hash: Option<String>,
+    /// Size of the decoded buffer in bytes.
+    pub output_size: usize,
+}
+
+impl AuditRecord {
+    fn new(
+        data: &[u8],
+        options: &DecodeOptions,
+        result: Option<&MeshDecodeResult>,
+        decode_ms: f64,
+    ) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        let (backend, draco_version) = ("native", Some(crate::ffi::draco_version()));
+        #[cfg(target_arch = "wasm32")]
+        let (backend, draco_version) = ("wasm", None);
+
+        Self {
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            backend: backend.to_string(),
+            draco_version,
+            input_hash: format_hash(data),
+            input_size: data.len(),
+            bitstream_version: decode_header(data)
+                .ok()
+                .map(|header| format!("{}.{}", header.version_major, header.version_minor)),
+            options: options.clone(),
+            decode_ms,
+            success: result.is_some(),
+            warnings: result.map_or_else(Vec::new, |result| result.config.warnings().to_vec()),
+            output_hash: result.map(|result| format_hash(&result.data)),
+            output_size: result.map_or(0, |result| result.data.len()),
+        }
+    }
+
+    /// Writes the record as a single line of JSON.
+    pub fn write_json_line(&self, writer: &mut impl Write) -> io::Result<()> {
+        serde_json::to_writer(&mut *writer, self)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        writer.write_all(b"\n")
+    }
+}
+
+fn format_hash(data: &[u8]) -> String {
+    format!("fnv1a64:{:016x}", content_hash(data))
+}
+
+/// Measures elapsed wall clock time, `Instant` is unavailable on wasm.
+struct Timer {
+    #[cfg(not(target_arch = "wasm32"))]
+    start: std::time::Instant,
+    #[cfg(target_arch = "wasm32")]
+    start: f64,
+}
+
+impl Timer {
+    fn start() -> Self {
+        Self {
+            #[cfg(not(target_arch = "wasm32"))]
+            start: std::time::Instant::now(),
+            #[cfg(target_arch = "wasm32")]
+            start: js_sys::Date::now(),
+        }
+    }
+
+    fn elapsed_ms(&self) -> f64 {
+        #[cfg(not(target_arch = "wasm32"))]
+        return self.start.elapsed().as_secs_f64() * 1000.0;
+        #[cfg(target_arch = "wasm32")]
+        return js_sys::Date::now() - self.start;
+    }
+}
+
+/// Decodes a Draco compressed mesh like
+/// [`decode_mesh_with_options`](crate::decode_mesh_with_options) and appends
+/// an [`AuditRecord`] to `log` as a JSON line.
+///
+/// A record is written for failed decodes as well. Returns an error only if
+/// the record cannot be written; the decode result is `None` if decoding
+/// fails.
+pub async fn decode_mesh_audited(
+    data: &[u8],
+    options: &DecodeOptions,
+    log: &mut impl Write,
+) -> io::Result<Option<MeshDecodeResult>> {
+    let timer = Timer::start();
+    let result = crate::decode_mesh_with_options(data, options).await;
+    AuditRecord::new(data, options, result.as_ref(), timer.elapsed_ms()).write_json_line(log)?;
+    Ok(result)
+}
+
+/// Synchronous version of [`decode_mesh_audited`] (native only).
+#[cfg(not(target_arch = "wasm32"))]
+pub fn decode_mesh_audited_sync(
+    data: &[u8],
+    options: &DecodeOptions,
+    log: &mut impl Write,
+) -> io::Result<Option<MeshDecodeResult>> {
+    let timer = Timer::start();
+    let result = crate::ffi::decode_mesh_with_options(data, options);
+    AuditRecord::new(data, options, result.as_ref(), timer.elapsed_ms()).write_json_line(log)?;
+    Ok(result)
+}
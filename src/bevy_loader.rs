@@ -0,0 +1,182 @@
+use std::fmt;
+use std::io;
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext, RenderAssetUsages};
+use bevy::render::mesh::{Indices, Mesh, MeshVertexAttribute, PrimitiveTopology};
+
+use crate::{AttributeDataType, AttributeSemantic, DecodeOptions, IndexBuffer, MeshDecodeResult};
+
+/// Errors reported by [`DracoMeshLoader`].
+#[derive(Debug)]
+pub enum DracoLoaderError {
+    /// The asset could not be read.
+    Io(io::Error),
+    /// The data is not a valid Draco mesh.
+    DecodeFailed,
+    /// The mesh has no `Float32` position attribute with three components.
+    MissingPositions,
+}
+
+impl fmt::Display for DracoLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DracoLoaderError::Io(err) => write!(f, "failed to read Draco asset: {err}"),
+            DracoLoaderError::DecodeFailed => write!(f, "Draco mesh failed to decode"),
+            DracoLoaderError::MissingPositions => write!(f, "Draco mesh has no positions"),
+        }
+    }
+}
+
+impl std::error::Error for DracoLoaderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DracoLoaderError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for DracoLoaderError {
+    fn from(err: io::Error) -> Self {
+        DracoLoaderError::Io(err)
+    }
+}
+
+/// Loads `.drc` files as Bevy [`Mesh`] assets.
+///
+/// Every attribute is decoded as `Float32`, normalizing integer colors and
+/// texture coordinates, and mapped by [`MeshDecodeResult::to_bevy_mesh`].
+///
+/// # Example
+///
+/// ```ignore
+/// use bevy::prelude::*;
+/// use draco_decoder::DracoMeshLoader;
+///
+/// App::new()
+///     .add_plugins(DefaultPlugins)
+///     .register_asset_loader(DracoMeshLoader)
+///     .run();
+///
+/// // In a system
+/// let mesh: Handle<Mesh> = asset_server.load("bunny.drc");
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DracoMeshLoader;
+
+impl AssetLoader for DracoMeshLoader {
+    type Asset = Mesh;
+    type Settings = ();
+    type Error = DracoLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Mesh, DracoLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let options = [
+            AttributeSemantic::Position,
+            AttributeSemantic::Normal,
+            AttributeSemantic::Color,
+            AttributeSemantic::TexCoord,
+        ]
+        .into_iter()
+        .fold(DecodeOptions::new(), |options, semantic| {
+            options.with_output_type(semantic, AttributeDataType::Float32)
+        });
+        let result = crate::decode_mesh_with_options(&bytes, &options)
+            .await
+            .ok_or(DracoLoaderError::DecodeFailed)?;
+        result.to_bevy_mesh()
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["drc"]
+    }
+}
+
+impl MeshDecodeResult {
+    /// Converts the decoded mesh to a Bevy [`Mesh`] with a triangle list
+    /// topology.
+    ///
+    /// Positions, normals, the first two texture coordinates and colors map
+    /// to [`Mesh::ATTRIBUTE_POSITION`], [`Mesh::ATTRIBUTE_NORMAL`],
+    /// [`Mesh::ATTRIBUTE_UV_0`], [`Mesh::ATTRIBUTE_UV_1`] and
+    /// [`Mesh::ATTRIBUTE_COLOR`], RGB colors getting an opaque alpha. Only
+    /// `Float32` attributes with the component count Bevy expects are
+    /// converted, others, like generic attributes, are left out. 16-bit
+    /// indices stay 16-bit.
+    ///
+    /// Returns [`DracoLoaderError::MissingPositions`] if the mesh has no
+    /// usable position attribute.
+    pub fn to_bevy_mesh(&self) -> Result<Mesh, DracoLoaderError> {
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        );
+        let mut uv_count = 0;
+
+        for (index, attribute) in self.config.attributes().iter().enumerate() {
+            if attribute.data_type() != AttributeDataType::Float32 {
+                continue;
+            }
+            let Some(view) = self.view::<f32>(index) else {
+                continue;
+            };
+
+            let target: Option<(MeshVertexAttribute, usize)> =
+                match (attribute.semantic(), attribute.dim()) {
+                    (AttributeSemantic::Position, 3) => Some((Mesh::ATTRIBUTE_POSITION, 3)),
+                    (AttributeSemantic::Normal, 3) => Some((Mesh::ATTRIBUTE_NORMAL, 3)),
+                    (AttributeSemantic::Color, 3 | 4) => Some((Mesh::ATTRIBUTE_COLOR, 4)),
+                    (AttributeSemantic::TexCoord, 2) if uv_count < 2 => {
+                        uv_count += 1;
+                        Some(if uv_count == 1 {
+                            (Mesh::ATTRIBUTE_UV_0, 2)
+                        } else {
+                            (Mesh::ATTRIBUTE_UV_1, 2)
+                        })
+                    }
+                    _ => None,
+                };
+            // Attributes of the same semantic after the first are left out
+            let Some((target, dim)) =
+                target.filter(|(target, _)| !mesh.contains_attribute(target.id))
+            else {
+                continue;
+            };
+
+            let vertices = view.vertices();
+            match dim {
+                2 => mesh
+                    .insert_attribute(target, vertices.map(|v| [v[0], v[1]]).collect::<Vec<_>>()),
+                3 => mesh.insert_attribute(
+                    target,
+                    vertices.map(|v| [v[0], v[1], v[2]]).collect::<Vec<_>>(),
+                ),
+                _ => mesh.insert_attribute(
+                    target,
+                    vertices
+                        .map(|v| [v[0], v[1], v[2], v.get(3).copied().unwrap_or(1.0)])
+                        .collect::<Vec<_>>(),
+                ),
+            }
+        }
+
+        if !mesh.contains_attribute(Mesh::ATTRIBUTE_POSITION) {
+            return Err(DracoLoaderError::MissingPositions);
+        }
+
+        match self.indices() {
+            Some(IndexBuffer::U16(indices)) => mesh.insert_indices(Indices::U16(indices)),
+            Some(IndexBuffer::U32(indices)) => mesh.insert_indices(Indices::U32(indices)),
+            None => {}
+        }
+        Ok(mesh)
+    }
+}
@@ -0,0 +1,676 @@
+//! Converts Draco files to OBJ, PLY, STL or GLB, prints what they hold, or
+//! measures how fast they decode.
+//!
+//! `cargo run --features cli --bin draco-decode -- input.drc -o out.glb`
+//! `cargo run --features cli --bin draco-decode -- info input.drc`
+//! `cargo run --release --features cli --bin draco-decode -- bench assets/`
+//!
+//! Exit codes follow `sysexits.h` so asset pipelines can tell bad
+//! invocations from bad input:
+//!
+//! | Code | Meaning                                        |
+//! |------|------------------------------------------------|
+//! | 0    | Success                                        |
+//! | 64   | Invalid arguments                              |
+//! | 65   | The input is not Draco or failed to decode     |
+//! | 66   | The input could not be read                    |
+//! | 73   | The output could not be created                |
+//! | 74   | Writing the output failed                      |
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use draco_decoder::{
+    AttributeSemantic, DracoHeaderInfo, GeometryType, MeshAttribute, MeshHandle, PlyFormat,
+    analyze, decode_header, decode_mesh_with_config_sync, decode_point_cloud_with_config_sync,
+    write_glb, write_obj, write_ply, write_point_cloud_ply, write_stl_binary,
+};
+
+const USAGE: &str = "\
+Usage: draco-decode <INPUT> -o <OUTPUT> [OPTIONS]
+       draco-decode info <INPUT>
+       draco-decode bench <DIR> [--iterations <N>] [--threads <M>]
+
+`info` prints the bitstream version, counts, attributes and decoded size
+of INPUT without writing anything.
+
+`bench` decodes every Draco file in DIR N times on each of M threads and
+reports the throughput per file and in total.
+
+The output format is picked from the extension of OUTPUT:
+.obj, .ply, .stl or .glb. Point clouds can only be written as PLY.
+
+Options:
+  -o, --output <OUTPUT>   File to write
+      --ascii             Write ASCII instead of binary PLY
+      --keep <SEMANTICS>  Only keep attributes with these semantics
+      --drop <SEMANTICS>  Drop attributes with these semantics
+      --iterations <N>    Decodes per file and thread for bench [default: 10]
+      --threads <M>       Decoding threads for bench [default: 1]
+  -h, --help              Print this help
+
+SEMANTICS is a comma separated list of position, normal, color, texcoord
+and generic.";
+
+/// Why the conversion stopped, mapped to an exit code.
+enum Failure {
+    Usage(String),
+    Data(String),
+    NoInput(String),
+    CantCreate(String),
+    Io(String),
+}
+
+impl Failure {
+    fn exit_code(&self) -> u8 {
+        match self {
+            Failure::Usage(_) => 64,
+            Failure::Data(_) => 65,
+            Failure::NoInput(_) => 66,
+            Failure::CantCreate(_) => 73,
+            Failure::Io(_) => 74,
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            Failure::Usage(message)
+            | Failure::Data(message)
+            | Failure::NoInput(message)
+            | Failure::CantCreate(message)
+            | Failure::Io(message) => message,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Obj,
+    Ply,
+    Stl,
+    Glb,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "obj" => Some(Format::Obj),
+            "ply" => Some(Format::Ply),
+            "stl" => Some(Format::Stl),
+            "glb" => Some(Format::Glb),
+            _ => None,
+        }
+    }
+}
+
+struct Convert {
+    input: PathBuf,
+    output: PathBuf,
+    format: Format,
+    ply_format: PlyFormat,
+    keep: Option<Vec<AttributeSemantic>>,
+    drop: Vec<AttributeSemantic>,
+}
+
+impl Convert {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, Failure> {
+        let mut input = None;
+        let mut output = None;
+        let mut ply_format = PlyFormat::BinaryLittleEndian;
+        let mut keep: Option<Vec<AttributeSemantic>> = None;
+        let mut drop = Vec::new();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| Failure::Usage(format!("{name} expects a value")))
+            };
+            match arg.as_str() {
+                "-o" | "--output" => output = Some(PathBuf::from(value(&arg)?)),
+                "--ascii" => ply_format = PlyFormat::Ascii,
+                "--keep" => keep
+                    .get_or_insert_default()
+                    .extend(semantics(&value(&arg)?)?),
+                "--drop" => drop.extend(semantics(&value(&arg)?)?),
+                flag if flag.starts_with('-') && flag.len() > 1 => {
+                    return Err(Failure::Usage(format!("unknown option {flag}")));
+                }
+                path if input.is_none() => input = Some(PathBuf::from(path)),
+                path => return Err(Failure::Usage(format!("unexpected argument {path}"))),
+            }
+        }
+
+        let input = input.ok_or_else(|| Failure::Usage("missing input file".to_string()))?;
+        let output = output.ok_or_else(|| Failure::Usage("missing output file".to_string()))?;
+        let position = AttributeSemantic::Position;
+        if drop.contains(&position) || keep.as_ref().is_some_and(|keep| !keep.contains(&position)) {
+            return Err(Failure::Usage(
+                "every output format needs positions, keep position".to_string(),
+            ));
+        }
+        let format = Format::from_path(&output).ok_or_else(|| {
+            Failure::Usage(format!(
+                "cannot tell the output format of {}, use .obj, .ply, .stl or .glb",
+                output.display()
+            ))
+        })?;
+        Ok(Self {
+            input,
+            output,
+            format,
+            ply_format,
+            keep,
+            drop,
+        })
+    }
+
+    fn keeps(&self, attribute: &MeshAttribute) -> bool {
+        let semantic = attribute.semantic();
+        self.keep
+            .as_ref()
+            .is_none_or(|keep| keep.contains(&semantic))
+            && !self.drop.contains(&semantic)
+    }
+
+    fn run(&self) -> Result<(), Failure> {
+        let (data, header) = read_draco(&self.input)?;
+
+        match header.geometry_type {
+            GeometryType::Mesh => {
+                let result = decode_mesh_with_config_sync(&data)
+                    .ok_or_else(|| decode_failed(&self.input))?
+                    .retain_attributes(|attribute| self.keeps(attribute));
+                self.write(|writer| match self.format {
+                    Format::Obj => write_obj(&result, writer),
+                    Format::Ply => write_ply(&result, self.ply_format, writer),
+                    Format::Stl => write_stl_binary(&result, writer),
+                    Format::Glb => write_glb(std::slice::from_ref(&result), writer),
+                })
+            }
+            GeometryType::PointCloud => {
+                if self.format != Format::Ply {
+                    return Err(Failure::Data(format!(
+                        "{} is a point cloud, which can only be written as PLY",
+                        self.input.display()
+                    )));
+                }
+                let result = decode_point_cloud_with_config_sync(&data)
+                    .ok_or_else(|| decode_failed(&self.input))?
+                    .retain_attributes(|attribute| self.keeps(attribute));
+                self.write(|writer| write_point_cloud_ply(&result, self.ply_format, writer))
+            }
+        }
+    }
+
+    /// Writes the output file, removing it again if writing fails so
+    /// pipelines never pick up a truncated file.
+    fn write(
+        &self,
+        write: impl FnOnce(&mut BufWriter<File>) -> std::io::Result<()>,
+    ) -> Result<(), Failure> {
+        let file = File::create(&self.output).map_err(|error| {
+            Failure::CantCreate(format!("cannot create {}: {error}", self.output.display()))
+        })?;
+        let mut writer = BufWriter::new(file);
+        write(&mut writer)
+            .and_then(|()| writer.flush())
+            .map_err(|error| {
+                let _ = std::fs::remove_file(&self.output);
+                Failure::Io(format!("cannot write {}: {error}", self.output.display()))
+            })
+    }
+}
+
+/// Reads a Draco file and describes it without decoding the attribute
+/// values into a buffer.
+struct Info {
+    input: PathBuf,
+}
+
+impl Info {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, Failure> {
+        let mut input = None;
+        for arg in args {
+            match arg.as_str() {
+                flag if flag.starts_with('-') && flag.len() > 1 => {
+                    return Err(Failure::Usage(format!("unknown option {flag}")));
+                }
+                path if input.is_none() => input = Some(PathBuf::from(path)),
+                path => return Err(Failure::Usage(format!("unexpected argument {path}"))),
+            }
+        }
+        let input = input.ok_or_else(|| Failure::Usage("missing input file".to_string()))?;
+        Ok(Self { input })
+    }
+
+    fn run(&self) -> Result<(), Failure> {
+        let (data, header) = read_draco(&self.input)?;
+
+        // The mesh layout is computed without filling the output buffer,
+        // point clouds have no such stage and are decoded in full
+        let (vertex_count, index_count, buffer_size, attributes, warnings) =
+            match header.geometry_type {
+                GeometryType::Mesh => {
+                    let config = MeshHandle::decode(&data)
+                        .and_then(|handle| handle.config())
+                        .ok_or_else(|| decode_failed(&self.input))?;
+                    (
+                        config.vertex_count(),
+                        Some((config.index_count(), config.index_size_in_bytes())),
+                        config.buffer_size(),
+                        config.attributes(),
+                        config.warnings().to_vec(),
+                    )
+                }
+                GeometryType::PointCloud => {
+                    let config = decode_point_cloud_with_config_sync(&data)
+                        .ok_or_else(|| decode_failed(&self.input))?
+                        .config;
+                    (
+                        config.point_count(),
+                        None,
+                        config.buffer_size(),
+                        config.attributes(),
+                        config.warnings().to_vec(),
+                    )
+                }
+            };
+        // Quantization is undone while decoding, so the bits come from the
+        // attribute descriptions in the bitstream
+        let encoded = analyze(&data).map(|report| report.attributes);
+
+        println!("{}", self.input.display());
+        println!(
+            "  bitstream:    {}.{} {:?} ({:?}){}",
+            header.version_major,
+            header.version_minor,
+            header.geometry_type,
+            header.encoding_method,
+            if header.has_metadata() {
+                ", with metadata"
+            } else {
+                ""
+            }
+        );
+        println!("  vertices:     {vertex_count}");
+        if let Some((index_count, index_size)) = index_count {
+            println!(
+                "  indices:      {index_count} ({} triangles, {}-bit)",
+                index_count / 3,
+                index_size * 8
+            );
+        }
+        println!(
+            "  decoded size: {buffer_size} bytes ({:.1} KiB)",
+            buffer_size as f64 / 1024.0
+        );
+        println!("  attributes:");
+        for attribute in &attributes {
+            let source = encoded.as_ref().and_then(|encoded| {
+                encoded
+                    .iter()
+                    .find(|size| size.unique_id == attribute.unique_id())
+            });
+            let quantization = match source {
+                Some(size) => match (size.quantization_bits, size.octahedral) {
+                    (Some(bits), true) => format!("{bits}-bit octahedral"),
+                    (Some(bits), false) => format!("{bits}-bit quantized"),
+                    (None, _) => "not quantized".to_string(),
+                },
+                None => "quantization unknown".to_string(),
+            };
+            println!(
+                "    #{} {:?}: {} x {:?}, {}",
+                attribute.unique_id(),
+                attribute.semantic(),
+                attribute.dim(),
+                attribute.data_type(),
+                quantization
+            );
+        }
+        for warning in &warnings {
+            println!("  warning: {warning}");
+        }
+        Ok(())
+    }
+}
+
+/// Decodes a corpus repeatedly and reports the throughput.
+struct Bench {
+    dir: PathBuf,
+    iterations: u32,
+    threads: u32,
+    /// Decodes per file over all threads.
+    decodes: u32,
+}
+
+/// Decode timings of one file.
+struct BenchResult {
+    bytes: usize,
+    triangles: usize,
+    decodes: u32,
+    elapsed: Duration,
+}
+
+impl BenchResult {
+    fn print(&self, name: &str) {
+        let seconds = self.elapsed.as_secs_f64().max(f64::MIN_POSITIVE);
+        let decodes = self.decodes as f64;
+        println!(
+            "{name:<32} {:>10} {:>10.1} {:>12.0} {:>10.3}",
+            self.bytes,
+            self.bytes as f64 * decodes / seconds / 1e6,
+            self.triangles as f64 * decodes / seconds,
+            seconds * 1e3 / decodes,
+        );
+    }
+}
+
+impl Bench {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, Failure> {
+        let mut dir = None;
+        let mut iterations = 10;
+        let mut threads = 1;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut count = |name: &str| {
+                let value = args
+                    .next()
+                    .ok_or_else(|| Failure::Usage(format!("{name} expects a value")))?;
+                match value.parse::<u32>() {
+                    Ok(count) if count > 0 => Ok(count),
+                    _ => Err(Failure::Usage(format!(
+                        "{name} expects a positive number, got {value}"
+                    ))),
+                }
+            };
+            match arg.as_str() {
+                "--iterations" => iterations = count(&arg)?,
+                "--threads" => threads = count(&arg)?,
+                flag if flag.starts_with('-') && flag.len() > 1 => {
+                    return Err(Failure::Usage(format!("unknown option {flag}")));
+                }
+                path if dir.is_none() => dir = Some(PathBuf::from(path)),
+                path => return Err(Failure::Usage(format!("unexpected argument {path}"))),
+            }
+        }
+        let dir = dir.ok_or_else(|| Failure::Usage("missing corpus directory".to_string()))?;
+        let decodes = iterations.checked_mul(threads).ok_or_else(|| {
+            Failure::Usage(format!(
+                "{iterations} iterations on {threads} threads are too many decodes"
+            ))
+        })?;
+        Ok(Self {
+            dir,
+            iterations,
+            threads,
+            decodes,
+        })
+    }
+
+    fn run(&self) -> Result<(), Failure> {
+        let cannot_read =
+            |error| Failure::NoInput(format!("cannot read {}: {error}", self.dir.display()));
+        let mut paths = std::fs::read_dir(&self.dir)
+            .map_err(cannot_read)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(cannot_read)?;
+        paths.sort();
+
+        // Files that are not Draco are skipped, so a directory of assets
+        // can be measured as it is
+        let mut corpus = Vec::new();
+        for path in paths.into_iter().filter(|path| path.is_file()) {
+            if let Ok((data, header)) = read_draco(&path) {
+                corpus.push((path, data, header.geometry_type));
+            }
+        }
+        if corpus.is_empty() {
+            return Err(Failure::Data(format!(
+                "no Draco files in {}",
+                self.dir.display()
+            )));
+        }
+
+        println!(
+            "{} files, {} iterations on {} threads",
+            corpus.len(),
+            self.iterations,
+            self.threads
+        );
+        println!(
+            "{:<32} {:>10} {:>10} {:>12} {:>10}",
+            "file", "bytes", "MB/s", "triangles/s", "ms/decode"
+        );
+        // Every file is decoded equally often, so the summed sizes and times
+        // give the throughput of the whole corpus
+        let mut total = BenchResult {
+            bytes: 0,
+            triangles: 0,
+            decodes: self.decodes,
+            elapsed: Duration::ZERO,
+        };
+        for (path, data, geometry_type) in &corpus {
+            let result = self.measure(path, data, *geometry_type)?;
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            result.print(&name);
+            total.bytes += result.bytes;
+            total.triangles += result.triangles;
+            total.elapsed += result.elapsed;
+        }
+        total.print("total");
+        Ok(())
+    }
+
+    /// Decodes a file `iterations` times on each thread after one warm-up
+    /// decode, which also counts the triangles.
+    fn measure(
+        &self,
+        path: &Path,
+        data: &[u8],
+        geometry_type: GeometryType,
+    ) -> Result<BenchResult, Failure> {
+        let decode = || match geometry_type {
+            GeometryType::Mesh => decode_mesh_with_config_sync(data)
+                .map(|result| result.config.index_count() as usize / 3),
+            GeometryType::PointCloud => decode_point_cloud_with_config_sync(data).map(|_| 0),
+        };
+        let triangles = decode().ok_or_else(|| decode_failed(path))?;
+
+        let start = Instant::now();
+        let decoded = std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..self.threads)
+                .map(|_| scope.spawn(|| (0..self.iterations).all(|_| decode().is_some())))
+                .collect();
+            workers
+                .into_iter()
+                .all(|worker| worker.join().unwrap_or(false))
+        });
+        let elapsed = start.elapsed();
+        if !decoded {
+            return Err(decode_failed(path));
+        }
+
+        Ok(BenchResult {
+            bytes: data.len(),
+            triangles,
+            decodes: self.decodes,
+            elapsed,
+        })
+    }
+}
+
+/// Reads a file and parses its Draco header.
+fn read_draco(path: &Path) -> Result<(Vec<u8>, DracoHeaderInfo), Failure> {
+    let data = std::fs::read(path)
+        .map_err(|error| Failure::NoInput(format!("cannot read {}: {error}", path.display())))?;
+    let header = decode_header(&data)
+        .map_err(|error| Failure::Data(format!("{}: {error}", path.display())))?;
+    Ok((data, header))
+}
+
+fn decode_failed(path: &Path) -> Failure {
+    Failure::Data(format!(
+        "{}: Draco failed to decode the data",
+        path.display()
+    ))
+}
+
+fn semantics(list: &str) -> Result<Vec<AttributeSemantic>, Failure> {
+    list.split(',')
+        .map(|name| match name.trim().to_ascii_lowercase().as_str() {
+            "position" => Ok(AttributeSemantic::Position),
+            "normal" => Ok(AttributeSemantic::Normal),
+            "color" => Ok(AttributeSemantic::Color),
+            "texcoord" => Ok(AttributeSemantic::TexCoord),
+            "generic" => Ok(AttributeSemantic::Generic),
+            other => Err(Failure::Usage(format!(
+                "unknown attribute semantic {other}"
+            ))),
+        })
+        .collect()
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() {
+        eprintln!("{USAGE}");
+        return ExitCode::from(64);
+    }
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    let result = match args[0].as_str() {
+        "info" => Info::parse(args.into_iter().skip(1)).and_then(|info| info.run()),
+        "bench" => Bench::parse(args.into_iter().skip(1)).and_then(|bench| bench.run()),
+        _ => Convert::parse(args).and_then(|convert| convert.run()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(failure) => {
+            eprintln!("draco-decode: {}", failure.message());
+            if let Failure::Usage(_) = failure {
+                eprintln!("Run `draco-decode --help` for usage.");
+            }
+            ExitCode::from(failure.exit_code())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn exit_code<T>(result: Result<T, Failure>) -> Option<u8> {
+        result.err().map(|failure| failure.exit_code())
+    }
+
+    #[test]
+    fn test_convert_arguments() {
+        let convert = Convert::parse(args(&[
+            "in.drc",
+            "-o",
+            "out.GLB",
+            "--keep",
+            "position,Normal",
+        ]))
+        .ok()
+        .unwrap();
+        assert!(convert.format == Format::Glb);
+        let normal = MeshAttribute::new(3, draco_decoder::AttributeDataType::Float32, 0, 0)
+            .with_semantic(AttributeSemantic::Normal);
+        assert!(convert.keeps(&normal));
+        assert!(!convert.keeps(&normal.with_semantic(AttributeSemantic::Color)));
+
+        for invalid in [
+            &["in.drc"][..],
+            &["-o", "out.obj"],
+            &["in.drc", "-o", "out.fbx"],
+            &["in.drc", "-o"],
+            &["in.drc", "other.drc", "-o", "out.obj"],
+            &["in.drc", "-o", "out.obj", "--keep", "tangent"],
+            &["in.drc", "-o", "out.obj", "--frobnicate"],
+            // Every format needs positions
+            &["in.drc", "-o", "out.obj", "--keep", "normal"],
+            &["in.drc", "-o", "out.ply", "--drop", "position"],
+        ] {
+            assert_eq!(
+                exit_code(Convert::parse(args(invalid))),
+                Some(64),
+                "{invalid:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_info_and_bench_arguments() {
+        assert!(Info::parse(args(&["in.drc"])).is_ok());
+        assert_eq!(exit_code(Info::parse(args(&[]))), Some(64));
+        assert_eq!(exit_code(Info::parse(args(&["a.drc", "b.drc"]))), Some(64));
+
+        let bench = Bench::parse(args(&["assets", "--iterations", "3", "--threads", "2"]))
+            .ok()
+            .unwrap();
+        assert_eq!((bench.iterations, bench.threads, bench.decodes), (3, 2, 6));
+        for invalid in [
+            &[][..],
+            &["assets", "--iterations", "0"],
+            &["assets", "--threads", "many"],
+            &["assets", "--threads"],
+            &["assets", "--iterations", "65536", "--threads", "65536"],
+        ] {
+            assert_eq!(
+                exit_code(Bench::parse(args(invalid))),
+                Some(64),
+                "{invalid:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_convert_exit_codes() {
+        let dir = std::env::temp_dir().join("draco_decoder_cli");
+        std::fs::create_dir_all(&dir).unwrap();
+        let convert = |input: &Path, output: &Path| {
+            Convert::parse(args(&[
+                input.to_str().unwrap(),
+                "-o",
+                output.to_str().unwrap(),
+            ]))
+            .and_then(|convert| convert.run())
+        };
+
+        let output = dir.join("mesh.obj");
+        assert_eq!(
+            exit_code(convert(&dir.join("missing.drc"), &output)),
+            Some(66)
+        );
+        let not_draco = dir.join("not_draco.drc");
+        std::fs::write(&not_draco, b"not a draco file").unwrap();
+        assert_eq!(exit_code(convert(&not_draco, &output)), Some(65));
+        let missing_dir = dir.join("missing").join("mesh.obj");
+        assert_eq!(
+            exit_code(convert(Path::new("assets/mesh.drc"), &missing_dir)),
+            Some(73)
+        );
+
+        assert!(convert(Path::new("assets/mesh.drc"), &output).is_ok());
+        let obj = std::fs::read_to_string(&output).unwrap();
+        assert!(obj.lines().any(|line| line.starts_with("v ")));
+        assert!(obj.lines().any(|line| line.starts_with("f ")));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
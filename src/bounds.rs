@@ -0,0 +1,240 @@
+use crate::{
+    AttributeComponent, AttributeDataType, AttributeValues, MeshAttribute, MeshDecodeResult,
+};
+
+impl MeshDecodeResult {
+    /// Computes the axis-aligned bounding box of the vertex positions as
+    /// `(min, max)`.
+    ///
+    /// The position attribute is found by its semantic and converted like
+    /// [`MeshDecodeResult::positions`], so quantized positions are
+    /// dequantized first. The corners can be used for frustum culling or as
+    /// the `min` and `max` of a glTF `POSITION` accessor.
+    ///
+    /// Returns `None` if the mesh has no three component position attribute
+    /// or no vertices.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let (min, max) = result.compute_aabb().unwrap();
+    /// let size = [max[0] - min[0], max[1] - min[1], max[2] - min[2]];
+    /// ```
+    pub fn compute_aabb(&self) -> Option<([f32; 3], [f32; 3])> {
+        let mut positions = self.positions::<[f32; 3]>()?;
+        let first = positions.next()?;
+        Some(positions.fold((first, first), |(min, max), position| {
+            (
+                std::array::from_fn(|axis| min[axis].min(position[axis])),
+                std::array::from_fn(|axis| max[axis].max(position[axis])),
+            )
+        }))
+    }
+
+    /// Computes a bounding sphere of the vertex positions as
+    /// `(center, radius)`.
+    ///
+    /// The sphere is found with Ritter's algorithm: an initial sphere through
+    /// two distant positions is grown until it contains every position. The
+    /// result is at most a few percent larger than the minimal sphere, which
+    /// is tight enough for tile culling and LOD selection. The radius is the
+    /// distance to the farthest position from the center, rounded up.
+    ///
+    /// Positions are read like for [`MeshDecodeResult::compute_aabb`], and
+    /// `None` is returned in the same cases.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let (center, radius) = result.compute_bounding_sphere().unwrap();
+    /// ```
+    pub fn compute_bounding_sphere(&self) -> Option<([f32; 3], f32)> {
+        let positions = || {
+            self.positions::<[f32; 3]>()
+                .into_iter()
+                .flatten()
+                .map(|position| position.map(f64::from))
+        };
+        let farthest = |from: [f64; 3]| {
+            positions()
+                .map(|position| (distance(from, position), position))
+                .max_by(|a, b| a.0.total_cmp(&b.0))
+        };
+
+        let first = positions().next()?;
+        let (_, a) = farthest(first)?;
+        let (diameter, b) = farthest(a)?;
+        let mut center: [f64; 3] = std::array::from_fn(|axis| (a[axis] + b[axis]) / 2.0);
+        let mut radius = diameter / 2.0;
+        for position in positions() {
+            let distance = distance(center, position);
+            if distance > radius {
+                // Grow the sphere to touch the position on its far side
+                let grown = (radius + distance) / 2.0;
+                let shift = (grown - radius) / distance;
+                center = std::array::from_fn(|axis| {
+                    center[axis] + (position[axis] - center[axis]) * shift
+                });
+                radius = grown;
+            }
+        }
+
+        let center = center.map(|value| value as f32);
+        let radius = farthest(center.map(f64::from)).map_or(0.0, |(radius, _)| radius);
+        Some((center, (radius as f32).next_up()))
+    }
+}
+
+impl MeshAttribute {
+    /// Computes the component-wise minimum and maximum of this attribute in
+    /// `result` as `(min, max)`.
+    ///
+    /// Both hold one value per component, in the data type the attribute is
+    /// stored as, so they can be written as the `min` and `max` of the glTF
+    /// accessor of any attribute. Quantized or normalized values are not
+    /// converted; padded attributes include their zero fourth component. The
+    /// attribute is read in a single pass without copying the buffer when it
+    /// is aligned. `NaN` components are skipped unless the first vertex has
+    /// them.
+    ///
+    /// If `result` was decoded with
+    /// [`DecodeOptions::compute_min_max`](crate::DecodeOptions::compute_min_max),
+    /// the extrema recorded while decoding are returned without reading the
+    /// buffer.
+    ///
+    /// Returns `None` if the attribute lies outside of the decoded buffer or
+    /// has no vertices.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use draco_decoder::AttributeValues;
+    ///
+    /// let color = result.config.get_attribute(2).unwrap();
+    /// if let Some((AttributeValues::UInt8(min), AttributeValues::UInt8(max))) =
+    ///     color.compute_min_max(&result)
+    /// {
+    ///     println!("red between {} and {}", min[0], max[0]);
+    /// }
+    /// ```
+    pub fn compute_min_max(
+        &self,
+        result: &MeshDecodeResult,
+    ) -> Option<(AttributeValues, AttributeValues)> {
+        let recorded = result
+            .config
+            .attributes()
+            .iter()
+            .position(|attribute| attribute == self)
+            .and_then(|index| result.config.min_max(index));
+        if recorded.is_some() {
+            return recorded;
+        }
+
+        let data = &result.data;
+        Some(match self.data_type() {
+            AttributeDataType::Int8 => {
+                let (min, max) = min_max::<i8>(self, data)?;
+                (AttributeValues::Int8(min), AttributeValues::Int8(max))
+            }
+            AttributeDataType::UInt8 => {
+                let (min, max) = min_max::<u8>(self, data)?;
+                (AttributeValues::UInt8(min), AttributeValues::UInt8(max))
+            }
+            AttributeDataType::Int16 => {
+                let (min, max) = min_max::<i16>(self, data)?;
+                (AttributeValues::Int16(min), AttributeValues::Int16(max))
+            }
+            AttributeDataType::UInt16 => {
+                let (min, max) = min_max::<u16>(self, data)?;
+                (AttributeValues::UInt16(min), AttributeValues::UInt16(max))
+            }
+            AttributeDataType::Int32 => {
+                let (min, max) = min_max::<i32>(self, data)?;
+                (AttributeValues::Int32(min), AttributeValues::Int32(max))
+            }
+            AttributeDataType::UInt32 => {
+                let (min, max) = min_max::<u32>(self, data)?;
+                (AttributeValues::UInt32(min), AttributeValues::UInt32(max))
+            }
+            AttributeDataType::Float32 => {
+                let (min, max) = min_max::<f32>(self, data)?;
+                (AttributeValues::Float32(min), AttributeValues::Float32(max))
+            }
+        })
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl MeshDecodeResult {
+    /// Records the extrema of every attribute, for the worker, which cannot
+    /// compute them while writing the buffer.
+    pub(crate) fn record_min_max(&mut self) {
+        fn bytes(values: &AttributeValues) -> Vec<u8> {
+            match values {
+                AttributeValues::Int8(v) => bytemuck::cast_slice(v).to_vec(),
+                AttributeValues::UInt8(v) => v.clone(),
+                AttributeValues::Int16(v) => bytemuck::cast_slice(v).to_vec(),
+                AttributeValues::UInt16(v) => bytemuck::cast_slice(v).to_vec(),
+                AttributeValues::Int32(v) => bytemuck::cast_slice(v).to_vec(),
+                AttributeValues::UInt32(v) => bytemuck::cast_slice(v).to_vec(),
+                AttributeValues::Float32(v) => bytemuck::cast_slice(v).to_vec(),
+            }
+        }
+
+        for (index, attribute) in self.config.attributes().iter().enumerate() {
+            if let Some((min, max)) = attribute.compute_min_max(self) {
+                self.config.set_min_max(index, bytes(&min), bytes(&max));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "mint")]
+impl MeshDecodeResult {
+    /// Computes the axis-aligned bounding box like
+    /// [`MeshDecodeResult::compute_aabb`], as `mint` points (requires the
+    /// `mint` feature).
+    pub fn compute_aabb_mint(&self) -> Option<(mint::Point3<f32>, mint::Point3<f32>)> {
+        let (min, max) = self.compute_aabb()?;
+        Some((min.into(), max.into()))
+    }
+
+    /// Computes a bounding sphere like
+    /// [`MeshDecodeResult::compute_bounding_sphere`], with the center as a
+    /// `mint` point (requires the `mint` feature).
+    pub fn compute_bounding_sphere_mint(&self) -> Option<(mint::Point3<f32>, f32)> {
+        let (center, radius) = self.compute_bounding_sphere()?;
+        Some((center.into(), radius))
+    }
+}
+
+/// Component-wise extrema of an attribute stored as `T`.
+fn min_max<T: AttributeComponent + PartialOrd>(
+    attribute: &MeshAttribute,
+    data: &[u8],
+) -> Option<(Vec<T>, Vec<T>)> {
+    let view = attribute.view_in::<T>(data)?;
+    let mut vertices = view.vertices();
+    let first = vertices.next()?;
+    let (mut min, mut max) = (first.to_vec(), first.to_vec());
+    for vertex in vertices {
+        for (axis, &value) in vertex.iter().enumerate() {
+            if value < min[axis] {
+                min[axis] = value;
+            }
+            if value > max[axis] {
+                max[axis] = value;
+            }
+        }
+    }
+    Some((min, max))
+}
+
+/// Distance between two points.
+fn distance(a: [f64; 3], b: [f64; 3]) -> f64 {
+    (0..3)
+        .map(|axis| (a[axis] - b[axis]).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
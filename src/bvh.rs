@@ -0,0 +1,262 @@
+use crate::math::{Vec3, cross, dot, sub};
+
+/// Triangles per leaf of the bounding volume hierarchy.
+const LEAF_SIZE: usize = 4;
+
+/// A node of [`Bvh`]. Leaves hold `count` triangles from `start`, inner
+/// nodes have their first child right after them and the second at `start`.
+struct Node {
+    min: Vec3,
+    max: Vec3,
+    start: u32,
+    count: u32,
+}
+
+/// A bounding volume hierarchy over triangles for ray and distance queries.
+pub(crate) struct Bvh {
+    nodes: Vec<Node>,
+    triangles: Vec<[Vec3; 3]>,
+}
+
+impl Bvh {
+    pub(crate) fn new(triangles: Vec<[Vec3; 3]>) -> Self {
+        fn bounds(triangles: &[[Vec3; 3]]) -> (Vec3, Vec3) {
+            let mut min = [f32::INFINITY; 3];
+            let mut max = [f32::NEG_INFINITY; 3];
+            for point in triangles.iter().flatten() {
+                for axis in 0..3 {
+                    min[axis] = min[axis].min(point[axis]);
+                    max[axis] = max[axis].max(point[axis]);
+                }
+            }
+            (min, max)
+        }
+
+        fn build(nodes: &mut Vec<Node>, triangles: &mut [[Vec3; 3]], start: usize) {
+            let (min, max) = bounds(triangles);
+            let node = nodes.len();
+            nodes.push(Node {
+                min,
+                max,
+                start: start as u32,
+                count: triangles.len() as u32,
+            });
+            if triangles.len() <= LEAF_SIZE {
+                return;
+            }
+
+            // Median split along the longest axis of the bounds
+            let extent = sub(max, min);
+            let axis = (0..3)
+                .max_by(|&a, &b| extent[a].total_cmp(&extent[b]))
+                .unwrap_or(0);
+            let centroid = |triangle: &[Vec3; 3]| triangle.iter().map(|p| p[axis]).sum::<f32>();
+            let middle = triangles.len() / 2;
+            triangles.select_nth_unstable_by(middle, |a, b| centroid(a).total_cmp(&centroid(b)));
+
+            let (left, right) = triangles.split_at_mut(middle);
+            build(nodes, left, start);
+            nodes[node].start = nodes.len() as u32;
+            nodes[node].count = 0;
+            build(nodes, right, start + middle);
+        }
+
+        let mut triangles = triangles;
+        let mut nodes = Vec::with_capacity(triangles.len() / LEAF_SIZE * 2 + 1);
+        if !triangles.is_empty() {
+            build(&mut nodes, &mut triangles, 0);
+        }
+        Self { nodes, triangles }
+    }
+
+    /// Passes the triangles of every leaf whose nodes all pass `enter` to
+    /// `leaf`, stopping once `leaf` returns `false`.
+    fn visit(
+        &self,
+        mut enter: impl FnMut(&Node) -> bool,
+        mut leaf: impl FnMut(&[[Vec3; 3]]) -> bool,
+    ) {
+        let mut stack = Vec::with_capacity(64);
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !enter(node) {
+                continue;
+            }
+            if node.count == 0 {
+                stack.push(node.start as usize);
+                stack.push(index + 1);
+                continue;
+            }
+            let start = node.start as usize;
+            if !leaf(&self.triangles[start..start + node.count as usize]) {
+                return;
+            }
+        }
+    }
+
+    /// Returns `true` if the ray hits a triangle closer than `max_distance`.
+    #[cfg(feature = "bake-ao")]
+    pub(crate) fn occluded(&self, origin: Vec3, direction: Vec3, max_distance: f32) -> bool {
+        let inverse = direction.map(|c| 1.0 / c);
+        let mut hit = false;
+        self.visit(
+            |node| hits_box(node, origin, inverse, max_distance),
+            |triangles| {
+                hit = triangles.iter().any(|triangle| {
+                    intersect(triangle, origin, direction).is_some_and(|t| t < max_distance)
+                });
+                !hit
+            },
+        );
+        hit
+    }
+
+    /// Returns the number of triangles the ray crosses.
+    #[cfg(feature = "bake-sdf")]
+    pub(crate) fn crossings(&self, origin: Vec3, direction: Vec3) -> usize {
+        let inverse = direction.map(|c| 1.0 / c);
+        let mut crossings = 0;
+        self.visit(
+            |node| hits_box(node, origin, inverse, f32::INFINITY),
+            |triangles| {
+                crossings += triangles
+                    .iter()
+                    .filter(|triangle| intersect(triangle, origin, direction).is_some())
+                    .count();
+                true
+            },
+        );
+        crossings
+    }
+
+    /// Returns the distance from `point` to the closest triangle, or
+    /// infinity without triangles.
+    #[cfg(feature = "bake-sdf")]
+    pub(crate) fn distance(&self, point: Vec3) -> f32 {
+        // Squared distance to the closest triangle so far, shared by both
+        // closures to prune nodes further away
+        let closest = std::cell::Cell::new(f32::INFINITY);
+        self.visit(
+            |node| box_distance_squared(node, point) < closest.get(),
+            |triangles| {
+                for triangle in triangles {
+                    let offset = sub(point, closest_point(triangle, point));
+                    closest.set(closest.get().min(dot(offset, offset)));
+                }
+                true
+            },
+        );
+        closest.get().sqrt()
+    }
+}
+
+/// Slab test of a ray against the bounds of a node.
+fn hits_box(node: &Node, origin: Vec3, inverse: Vec3, max_distance: f32) -> bool {
+    let (mut near, mut far) = (0.0f32, max_distance);
+    for axis in 0..3 {
+        let a = (node.min[axis] - origin[axis]) * inverse[axis];
+        let b = (node.max[axis] - origin[axis]) * inverse[axis];
+        // NaN from 0 * inf leaves the bounds unchanged
+        near = near.max(a.min(b));
+        far = far.min(a.max(b));
+    }
+    near <= far
+}
+
+/// Squared distance from a point to the bounds of a node, zero inside.
+#[cfg(feature = "bake-sdf")]
+fn box_distance_squared(node: &Node, point: Vec3) -> f32 {
+    (0..3)
+        .map(|axis| {
+            let outside = (node.min[axis] - point[axis]).max(point[axis] - node.max[axis]);
+            outside.max(0.0).powi(2)
+        })
+        .sum()
+}
+
+/// Möller-Trumbore ray triangle intersection, returning the ray parameter of
+/// a hit in front of the origin.
+fn intersect(triangle: &[Vec3; 3], origin: Vec3, direction: Vec3) -> Option<f32> {
+    let edge1 = sub(triangle[1], triangle[0]);
+    let edge2 = sub(triangle[2], triangle[0]);
+    let p = cross(direction, edge2);
+    let determinant = dot(edge1, p);
+    if determinant == 0.0 {
+        return None;
+    }
+
+    let inverse = 1.0 / determinant;
+    let s = sub(origin, triangle[0]);
+    let u = dot(s, p) * inverse;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = cross(s, edge1);
+    let v = dot(direction, q) * inverse;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let t = dot(edge2, q) * inverse;
+    (t > 0.0).then_some(t)
+}
+
+/// Closest point on a triangle, from the Voronoi regions of its vertices and
+/// edges (Ericson, Real-Time Collision Detection 5.1.5).
+#[cfg(feature = "bake-sdf")]
+fn closest_point(triangle: &[Vec3; 3], point: Vec3) -> Vec3 {
+    let [a, b, c] = *triangle;
+    let along = |origin: Vec3, direction: Vec3, t: f32| -> Vec3 {
+        std::array::from_fn(|axis| origin[axis] + direction[axis] * t)
+    };
+    let ab = sub(b, a);
+    let ac = sub(c, a);
+    let ap = sub(point, a);
+    let d1 = dot(ab, ap);
+    let d2 = dot(ac, ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+
+    let bp = sub(point, b);
+    let d3 = dot(ab, bp);
+    let d4 = dot(ac, bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return along(a, ab, d1 / (d1 - d3));
+    }
+
+    let cp = sub(point, c);
+    let d5 = dot(ab, cp);
+    let d6 = dot(ac, cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return along(a, ac, d2 / (d2 - d6));
+    }
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        return along(b, sub(c, b), (d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    let denominator = va + vb + vc;
+    if denominator == 0.0 {
+        // Degenerate triangle, fall back to the closest vertex
+        let distance = |vertex: Vec3| dot(sub(point, vertex), sub(point, vertex));
+        return [a, b, c]
+            .into_iter()
+            .min_by(|&x, &y| distance(x).total_cmp(&distance(y)))
+            .unwrap_or(a);
+    }
+    let v = vb / denominator;
+    let w = vc / denominator;
+    std::array::from_fn(|axis| a[axis] + ab[axis] * v + ac[axis] * w)
+}
@@ -0,0 +1,209 @@
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use crate::{MeshDecodeResult, MeshDecoder};
+
+/// Hit and eviction counts of a [`DecodedMeshCache`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Lookups answered from the cache.
+    pub hits: u64,
+    /// Lookups that had to decode.
+    pub misses: u64,
+    /// Meshes evicted to stay within the budget.
+    pub evictions: u64,
+}
+
+struct CacheEntry {
+    mesh: Arc<MeshDecodeResult>,
+    size: usize,
+    last_used: u64,
+}
+
+/// A least recently used cache of decoded meshes bounded by memory.
+///
+/// Streaming viewers, for example of map tiles, decode the same `.drc` files
+/// again as the view moves back and forth. The cache keeps decoded meshes by
+/// key, such as the tile URL, and evicts the least recently used ones once
+/// the decoded buffers exceed the byte budget. Meshes are shared as `Arc`s,
+/// so evicting a mesh that is still rendered does not free it early.
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::DecodedMeshCache;
+///
+/// let mut cache = DecodedMeshCache::new(256 * 1024 * 1024);
+/// let mesh = cache
+///     .get_or_fetch(url.clone(), || fetch_bytes(&url))
+///     .await;
+/// ```
+pub struct DecodedMeshCache<K = String> {
+    budget: usize,
+    used: usize,
+    clock: u64,
+    entries: HashMap<K, CacheEntry>,
+    // Keys ordered by last use, oldest first
+    order: BTreeMap<u64, K>,
+    stats: CacheStats,
+}
+
+impl<K: Eq + Hash + Clone> DecodedMeshCache<K> {
+    /// Creates an empty cache holding at most `budget` bytes of decoded
+    /// buffers.
+    pub fn new(budget: usize) -> Self {
+        Self {
+            budget,
+            used: 0,
+            clock: 0,
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// Returns the byte budget.
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Returns the bytes of decoded buffers currently cached.
+    pub fn used_bytes(&self) -> usize {
+        self.used
+    }
+
+    /// Returns the number of cached meshes.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no mesh is cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the hit and eviction counts.
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Changes the byte budget, evicting meshes if it shrinks.
+    pub fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+        self.evict_to(budget);
+    }
+
+    /// Returns `true` if a mesh is cached for `key`, without marking it used.
+    pub fn contains(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Returns the mesh cached for `key` and marks it most recently used.
+    pub fn get(&mut self, key: &K) -> Option<Arc<MeshDecodeResult>> {
+        self.clock += 1;
+        let entry = self.entries.get_mut(key)?;
+        self.order.remove(&entry.last_used);
+        entry.last_used = self.clock;
+        self.order.insert(self.clock, key.clone());
+        Some(entry.mesh.clone())
+    }
+
+    /// Caches a decoded mesh, evicting the least recently used meshes until
+    /// it fits.
+    ///
+    /// A mesh larger than the whole budget is returned without being cached.
+    pub fn insert(&mut self, key: K, mesh: MeshDecodeResult) -> Arc<MeshDecodeResult> {
+        let mesh = Arc::new(mesh);
+        let size = mesh.data.len();
+        self.remove(&key);
+        if size > self.budget {
+            return mesh;
+        }
+
+        self.evict_to(self.budget - size);
+        self.clock += 1;
+        self.used += size;
+        self.order.insert(self.clock, key.clone());
+        self.entries.insert(
+            key,
+            CacheEntry {
+                mesh: mesh.clone(),
+                size,
+                last_used: self.clock,
+            },
+        );
+        mesh
+    }
+
+    /// Removes the mesh cached for `key`.
+    pub fn remove(&mut self, key: &K) -> Option<Arc<MeshDecodeResult>> {
+        let entry = self.entries.remove(key)?;
+        self.order.remove(&entry.last_used);
+        self.used -= entry.size;
+        Some(entry.mesh)
+    }
+
+    /// Removes every cached mesh.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+        self.used = 0;
+    }
+
+    /// Returns the cached mesh for `key`, or decodes `data` with `decoder`
+    /// and caches the result.
+    ///
+    /// Returns `None` if decoding fails; failures are not cached.
+    pub fn get_or_decode(
+        &mut self,
+        key: K,
+        data: &[u8],
+        decoder: &impl MeshDecoder,
+    ) -> Option<Arc<MeshDecodeResult>> {
+        if let Some(mesh) = self.get(&key) {
+            self.stats.hits += 1;
+            return Some(mesh);
+        }
+
+        self.stats.misses += 1;
+        let mesh = decoder.decode_mesh(data)?;
+        Some(self.insert(key, mesh))
+    }
+
+    /// Returns the cached mesh for `key`, or fetches the encoded data with
+    /// `fetch`, decodes it with
+    /// [`decode_mesh_with_config`](crate::decode_mesh_with_config) and caches
+    /// the result.
+    ///
+    /// `fetch` is only called on a miss, so cached tiles are not downloaded
+    /// again. Returns `None` if fetching or decoding fails.
+    pub async fn get_or_fetch<F, Fut>(&mut self, key: K, fetch: F) -> Option<Arc<MeshDecodeResult>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Option<Vec<u8>>>,
+    {
+        if let Some(mesh) = self.get(&key) {
+            self.stats.hits += 1;
+            return Some(mesh);
+        }
+
+        self.stats.misses += 1;
+        let data = fetch().await?;
+        let mesh = crate::decode_mesh_with_config(&data).await?;
+        Some(self.insert(key, mesh))
+    }
+
+    fn evict_to(&mut self, target: usize) {
+        while self.used > target {
+            let Some((_, key)) = self.order.pop_first() else {
+                break;
+            };
+            if let Some(entry) = self.entries.remove(&key) {
+                self.used -= entry.size;
+                self.stats.evictions += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use std::future::poll_fn;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
+
+/// Aborts decodes it is attached to with
+/// [`DecodeOptions::with_cancellation`](crate::DecodeOptions::with_cancellation).
+///
+/// Clones share their state, so a loader can keep one clone per tile and
+/// cancel it once the tile scrolls out of view. A cancelled decode returns
+/// `None`. On native targets the decode checks the token between its stages:
+/// before and after Draco decompresses the mesh, before the decoded buffer
+/// is allocated and between attributes while the buffer is written.
+/// Decompression itself cannot be interrupted. On WASM the pending worker
+/// job is abandoned at once, and the embedded worker is restarted if the job
+/// was the only one in flight, which stops the decode.
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::{CancellationToken, DecodeOptions, decode_mesh_with_options};
+///
+/// let token = CancellationToken::new();
+/// tile.cancel = token.clone();
+/// let options = DecodeOptions::new().with_cancellation(token);
+/// // Returns `None` if `tile.cancel.cancel()` is called in the meantime
+/// let mesh = decode_mesh_with_options(&data, &options).await;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    /// Tasks waiting in [`CancellationToken::cancelled`]
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl CancellationToken {
+    /// Creates a token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels every decode using this token or one of its clones.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Release);
+        let wakers = std::mem::take(
+            &mut *self
+                .inner
+                .wakers
+                .lock()
+                .unwrap_or_else(|err| err.into_inner()),
+        );
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    /// Returns `true` once [`cancel`](Self::cancel) has been called.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Completes once the token is cancelled.
+    pub async fn cancelled(&self) {
+        poll_fn(|cx| {
+            if self.is_cancelled() {
+                return Poll::Ready(());
+            }
+            let mut wakers = self
+                .inner
+                .wakers
+                .lock()
+                .unwrap_or_else(|err| err.into_inner());
+            // Checked again under the lock, `cancel` may have run meanwhile
+            if self.is_cancelled() {
+                return Poll::Ready(());
+            }
+            if !wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                wakers.push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+/// Tokens are equal if they are clones of each other.
+impl PartialEq for CancellationToken {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for CancellationToken {}
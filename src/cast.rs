@@ -0,0 +1,188 @@
+use std::fmt;
+
+use crate::{AttributeDataType, MeshAttribute, MeshDecodeResult, PointCloudDecodeResult};
+
+/// Types an attribute can be reinterpreted as by [`attribute_as_slice`].
+///
+/// Implemented for the component types of every [`AttributeDataType`], which
+/// view the attribute component by component, and for arrays of them, which
+/// view it vertex by vertex. Implement it for `#[repr(C)]` vector types to
+/// view vertices as those.
+pub trait CastElement: bytemuck::Pod {
+    /// Data type of the components.
+    const DATA_TYPE: AttributeDataType;
+    /// Number of components, 1 for a single component or the dimension of
+    /// the attribute for a whole vertex.
+    const COMPONENTS: u32;
+}
+
+macro_rules! impl_cast_element {
+    ($($ty:ty => $data_type:ident),* $(,)?) => {
+        $(
+            impl CastElement for $ty {
+                const DATA_TYPE: AttributeDataType = AttributeDataType::$data_type;
+                const COMPONENTS: u32 = 1;
+            }
+
+            impl<const N: usize> CastElement for [$ty; N]
+            where
+                [$ty; N]: bytemuck::Pod,
+            {
+                const DATA_TYPE: AttributeDataType = AttributeDataType::$data_type;
+                const COMPONENTS: u32 = N as u32;
+            }
+        )*
+    };
+}
+
+impl_cast_element!(
+    i8 => Int8,
+    u8 => UInt8,
+    i16 => Int16,
+    u16 => UInt16,
+    i32 => Int32,
+    u32 => UInt32,
+    f32 => Float32,
+);
+
+/// Decode results whose attributes [`attribute_as_slice`] can view.
+pub trait DecodedAttributes {
+    /// Returns the attribute at the given index.
+    fn attribute(&self, index: usize) -> Option<&MeshAttribute>;
+
+    /// Returns the decoded buffer the attributes point into.
+    fn buffer(&self) -> &[u8];
+}
+
+impl DecodedAttributes for MeshDecodeResult {
+    fn attribute(&self, index: usize) -> Option<&MeshAttribute> {
+        self.config.get_attribute(index)
+    }
+
+    fn buffer(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl DecodedAttributes for PointCloudDecodeResult {
+    fn attribute(&self, index: usize) -> Option<&MeshAttribute> {
+        self.config.get_attribute(index)
+    }
+
+    fn buffer(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// Reasons [`attribute_as_slice`] cannot view an attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CastError {
+    /// No attribute has the given index.
+    MissingAttribute(usize),
+    /// The byte range of the attribute lies outside of the decoded buffer.
+    OutOfBounds(usize),
+    /// The attribute holds components of another data type.
+    DataType {
+        /// Data type of the attribute.
+        attribute: AttributeDataType,
+        /// Data type of the requested components.
+        requested: AttributeDataType,
+    },
+    /// The requested type is neither a single component nor a whole vertex.
+    ElementSize {
+        /// Size in bytes of one vertex of the attribute.
+        element_size: usize,
+        /// Size in bytes of the requested type.
+        requested: usize,
+    },
+    /// The attribute does not start at an address aligned for the requested
+    /// type, for example because of the alignment of the decoded buffer or
+    /// [`DecodeOptions::with_offset_alignment`](crate::DecodeOptions::with_offset_alignment).
+    Misaligned {
+        /// Byte offset of the attribute in the decoded buffer.
+        offset: u32,
+        /// Alignment of the requested type.
+        align: usize,
+    },
+}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CastError::MissingAttribute(index) => write!(f, "no attribute at index {index}"),
+            CastError::OutOfBounds(index) => {
+                write!(f, "attribute {index} lies outside of the decoded buffer")
+            }
+            CastError::DataType {
+                attribute,
+                requested,
+            } => write!(f, "cannot view {attribute:?} components as {requested:?}"),
+            CastError::ElementSize {
+                element_size,
+                requested,
+            } => write!(
+                f,
+                "cannot view {element_size} byte vertices as {requested} byte elements"
+            ),
+            CastError::Misaligned { offset, align } => write!(
+                f,
+                "attribute at offset {offset} is not aligned to {align} bytes"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CastError {}
+
+/// Reinterprets an attribute of a decoded mesh or point cloud as a slice of
+/// `T` without copying.
+///
+/// Unlike [`MeshDecodeResult::view`], which silently copies attributes it
+/// cannot borrow, this fails with a [`CastError`] explaining why the bytes
+/// cannot be viewed as `T`: a data type other than [`CastElement::DATA_TYPE`],
+/// a `T` that is neither one component nor one vertex, or an attribute not
+/// aligned for `T`.
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::attribute_as_slice;
+///
+/// let index = result.config.find_attribute(AttributeSemantic::Position).unwrap();
+/// let positions: &[[f32; 3]] = attribute_as_slice(&result, index)?;
+/// let components = attribute_as_slice::<f32>(&result, index)?;
+/// ```
+pub fn attribute_as_slice<T: CastElement>(
+    result: &impl DecodedAttributes,
+    index: usize,
+) -> Result<&[T], CastError> {
+    let attribute = result
+        .attribute(index)
+        .ok_or(CastError::MissingAttribute(index))?;
+    if attribute.data_type() != T::DATA_TYPE {
+        return Err(CastError::DataType {
+            attribute: attribute.data_type(),
+            requested: T::DATA_TYPE,
+        });
+    }
+    if T::COMPONENTS != 1 && T::COMPONENTS != attribute.dim() {
+        return Err(CastError::ElementSize {
+            element_size: attribute.element_size(),
+            requested: std::mem::size_of::<T>(),
+        });
+    }
+
+    let bytes = attribute
+        .bytes_in(result.buffer())
+        .ok_or(CastError::OutOfBounds(index))?;
+    bytemuck::try_cast_slice(bytes).map_err(|error| match error {
+        bytemuck::PodCastError::TargetAlignmentGreaterAndInputNotAligned => CastError::Misaligned {
+            offset: attribute.offset(),
+            align: std::mem::align_of::<T>(),
+        },
+        _ => CastError::ElementSize {
+            element_size: attribute.element_size(),
+            requested: std::mem::size_of::<T>(),
+        },
+    })
+}
@@ -0,0 +1,209 @@
+use std::ops::Range;
+
+use crate::{MeshAttribute, MeshDecodeResult};
+
+const UNMAPPED: u32 = u32::MAX;
+
+/// A piece of a mesh split by [`MeshDecodeResult::split_by_vertex_cap`].
+#[derive(Debug, Clone)]
+pub struct MeshChunk {
+    /// The chunk as a standalone mesh with its own indices and attributes.
+    pub result: MeshDecodeResult,
+    /// Range of triangles of the source mesh contained in this chunk.
+    ///
+    /// Empty for meshes without faces, which are split by vertex ranges.
+    pub triangles: Range<usize>,
+    /// Source mesh vertex index for each vertex of the chunk.
+    pub source_vertices: Vec<u32>,
+}
+
+/// Iterator over the chunks of a mesh, created by
+/// [`MeshDecodeResult::into_chunks`].
+///
+/// Each chunk is built when it is requested, so only the chunk being built
+/// is held in addition to the source mesh.
+#[derive(Debug)]
+pub struct MeshChunks {
+    mesh: MeshDecodeResult,
+    max_vertices: u32,
+    /// Chunk vertex index of each source vertex, `UNMAPPED` outside the
+    /// chunk being built.
+    remap: Vec<u32>,
+    /// First triangle, or for meshes without faces first vertex, of the next
+    /// chunk.
+    next: usize,
+    started: bool,
+    finished: bool,
+}
+
+impl MeshChunks {
+    /// Reads the triangle at `index` from the index buffer.
+    fn triangle(&self, index: usize) -> Option<[u32; 3]> {
+        let index_size = self.mesh.config.index_size_in_bytes();
+        let bytes = self
+            .mesh
+            .index_bytes()?
+            .get(index * index_size * 3..(index + 1) * index_size * 3)?;
+        let mut triangle = [0u32; 3];
+        for (value, bytes) in triangle.iter_mut().zip(bytes.chunks_exact(index_size)) {
+            *value = match *bytes {
+                [a, b] => u16::from_ne_bytes([a, b]) as u32,
+                [a, b, c, d] => u32::from_ne_bytes([a, b, c, d]),
+                _ => 0,
+            };
+        }
+        Some(triangle)
+    }
+}
+
+impl Iterator for MeshChunks {
+    type Item = MeshChunk;
+
+    fn next(&mut self) -> Option<MeshChunk> {
+        if self.finished {
+            return None;
+        }
+
+        let vertex_count = self.mesh.config.vertex_count();
+        let triangle_count = self.mesh.config.index_count() as usize / 3;
+        if !self.started {
+            self.started = true;
+            if vertex_count <= self.max_vertices {
+                self.finished = true;
+                // Nothing else is built, so the mesh moves into the chunk
+                let empty = MeshDecodeResult::from_parts(0, &[], Vec::new());
+                return Some(MeshChunk {
+                    source_vertices: (0..vertex_count).collect(),
+                    triangles: 0..triangle_count,
+                    result: std::mem::replace(&mut self.mesh, empty),
+                });
+            }
+            self.remap = vec![UNMAPPED; vertex_count as usize];
+        }
+
+        if triangle_count == 0 {
+            let start = self.next as u32;
+            if start >= vertex_count {
+                self.finished = true;
+                return None;
+            }
+            let end = start.saturating_add(self.max_vertices).min(vertex_count);
+            self.next = end as usize;
+            let source_vertices: Vec<u32> = (start..end).collect();
+            return Some(MeshChunk {
+                result: self.mesh.gather(&source_vertices, &[]),
+                triangles: 0..0,
+                source_vertices,
+            });
+        }
+
+        let first_triangle = self.next;
+        let mut source_vertices: Vec<u32> = Vec::new();
+        let mut indices: Vec<u32> = Vec::new();
+        let mut triangle_index = first_triangle;
+        while triangle_index < triangle_count {
+            let triangle = self.triangle(triangle_index).unwrap_or([u32::MAX; 3]);
+            // Triangles referencing vertices outside the mesh are dropped.
+            if triangle.iter().any(|&vertex| vertex >= vertex_count) {
+                triangle_index += 1;
+                continue;
+            }
+
+            let new_vertices = triangle
+                .iter()
+                .enumerate()
+                .filter(|&(i, v)| self.remap[*v as usize] == UNMAPPED && !triangle[..i].contains(v))
+                .count();
+            if source_vertices.len() + new_vertices > self.max_vertices as usize {
+                break;
+            }
+
+            for vertex in triangle {
+                let slot = &mut self.remap[vertex as usize];
+                if *slot == UNMAPPED {
+                    *slot = source_vertices.len() as u32;
+                    source_vertices.push(vertex);
+                }
+                indices.push(*slot);
+            }
+            triangle_index += 1;
+        }
+
+        for &vertex in &source_vertices {
+            self.remap[vertex as usize] = UNMAPPED;
+        }
+        self.next = triangle_index;
+        if indices.is_empty() {
+            self.finished = true;
+            return None;
+        }
+        Some(MeshChunk {
+            result: self.mesh.gather(&source_vertices, &indices),
+            triangles: first_triangle..triangle_index,
+            source_vertices,
+        })
+    }
+}
+
+impl MeshDecodeResult {
+    /// Splits the mesh into chunks with at most `max_vertices` vertices each.
+    ///
+    /// Triangles are assigned to chunks in their original order, and the
+    /// vertices they reference are copied into the chunk, so vertices shared by
+    /// triangles of different chunks are duplicated. Meshes without faces are
+    /// split into contiguous vertex ranges.
+    ///
+    /// A mesh that already fits the cap is returned as a single chunk.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_vertices` is less than 3.
+    pub fn split_by_vertex_cap(self, max_vertices: u32) -> Vec<MeshChunk> {
+        self.into_chunks(max_vertices).collect()
+    }
+
+    /// Returns an iterator building the chunks of
+    /// [`MeshDecodeResult::split_by_vertex_cap`] one at a time, so they can
+    /// be processed and dropped without holding all of them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_vertices` is less than 3.
+    pub fn into_chunks(self, max_vertices: u32) -> MeshChunks {
+        assert!(
+            max_vertices >= 3,
+            "vertex cap must allow at least one triangle"
+        );
+        MeshChunks {
+            mesh: self,
+            max_vertices,
+            remap: Vec::new(),
+            next: 0,
+            started: false,
+            finished: false,
+        }
+    }
+
+    /// Builds a mesh from a subset of vertices and indices into that subset.
+    pub(crate) fn gather(&self, source_vertices: &[u32], indices: &[u32]) -> MeshDecodeResult {
+        let attributes: Vec<(MeshAttribute, Vec<u8>)> = (0..self.config.attributes().len())
+            .filter_map(|index| {
+                let attribute = *self.config.get_attribute(index)?;
+                let bytes = self.attribute_bytes(index)?;
+                let element_size = attribute.element_size();
+
+                let mut gathered = Vec::with_capacity(source_vertices.len() * element_size);
+                for &vertex in source_vertices {
+                    let start = vertex as usize * element_size;
+                    gathered.extend_from_slice(bytes.get(start..start + element_size)?);
+                }
+                Some((attribute, gathered))
+            })
+            .collect();
+
+        let mut result =
+            MeshDecodeResult::from_parts(source_vertices.len() as u32, indices, attributes);
+        result.config.inherit(&self.config);
+        result
+    }
+}
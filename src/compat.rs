@@ -0,0 +1,219 @@
+use crate::header::{HEADER_LENGTH, METADATA_FLAG_MASK};
+use crate::{DracoHeaderInfo, GeometryType, MeshDecodeResult, decode_header};
+
+/// First bitstream version carrying a metadata section.
+pub(crate) const METADATA_VERSION: (u8, u8) = (1, 3);
+
+/// Nesting depth after which metadata is treated as malformed.
+const MAX_METADATA_DEPTH: usize = 32;
+
+/// A workaround applied by [`decode_mesh_compat`] to make a bitstream
+/// decodable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CompatShim {
+    /// The metadata section was rejected and removed, the geometry is
+    /// decoded without it.
+    MetadataStripped,
+}
+
+/// Result of [`decode_mesh_compat`].
+#[derive(Debug, Clone)]
+pub struct CompatDecodeResult {
+    /// The decoded mesh.
+    pub result: MeshDecodeResult,
+    /// Header of the original bitstream.
+    pub header: DracoHeaderInfo,
+    /// Workarounds needed to decode the bitstream, empty if it decoded as is.
+    pub shims: Vec<CompatShim>,
+}
+
+/// Decodes a Draco compressed mesh, retrying with known workarounds if the
+/// bundled decoder rejects the bitstream.
+///
+/// Bitstreams from other Draco 1.x encoders are occasionally rejected even
+/// though their geometry is intact. When a plain decode fails, the bitstream
+/// version is read from the header and the applicable workarounds are
+/// tried: a metadata section the decoder rejects is removed. Bitstreams
+/// declaring a version newer than the bundled decoder supports are not
+/// rewritten, since they may use features it cannot decode.
+///
+/// The shims used are reported on the result. Only the bitstream is
+/// rewritten; the same bundled decoder is used for every attempt.
+///
+/// Returns `None` if the data is not a Draco mesh or no workaround helps.
+pub async fn decode_mesh_compat(data: &[u8]) -> Option<CompatDecodeResult> {
+    let header = decode_header(data).ok()?;
+    if let Some(result) = crate::decode_mesh_with_config(data).await {
+        return Some(CompatDecodeResult {
+            result,
+            header,
+            shims: Vec::new(),
+        });
+    }
+
+    for (patched, shims) in candidates(data, &header) {
+        if let Some(result) = crate::decode_mesh_with_config(&patched).await {
+            return Some(CompatDecodeResult {
+                result,
+                header,
+                shims,
+            });
+        }
+    }
+    None
+}
+
+/// Synchronous version of [`decode_mesh_compat`] (native only).
+#[cfg(not(target_arch = "wasm32"))]
+pub fn decode_mesh_compat_sync(data: &[u8]) -> Option<CompatDecodeResult> {
+    let header = decode_header(data).ok()?;
+    if let Some(result) = crate::ffi::decode_mesh_with_config(data) {
+        return Some(CompatDecodeResult {
+            result,
+            header,
+            shims: Vec::new(),
+        });
+    }
+
+    candidates(data, &header)
+        .into_iter()
+        .find_map(|(patched, shims)| {
+            crate::ffi::decode_mesh_with_config(&patched).map(|result| CompatDecodeResult {
+                result,
+                header,
+                shims,
+            })
+        })
+}
+
+/// Rewritten bitstreams to try, every applicable shim alone first and then
+/// all of them together.
+fn candidates(data: &[u8], header: &DracoHeaderInfo) -> Vec<(Vec<u8>, Vec<CompatShim>)> {
+    if header.geometry_type != GeometryType::Mesh {
+        return Vec::new();
+    }
+
+    let version = (header.version_major, header.version_minor);
+    let mut shims = Vec::new();
+    if header.has_metadata() && version >= METADATA_VERSION && metadata_length(data).is_some() {
+        shims.push(CompatShim::MetadataStripped);
+    }
+
+    let mut candidates = Vec::new();
+    for mask in 1..1usize << shims.len() {
+        let selected: Vec<CompatShim> = shims
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| mask & (1 << index) != 0)
+            .map(|(_, shim)| *shim)
+            .collect();
+        if let Some(patched) = apply(data, &selected) {
+            candidates.push((patched, selected));
+        }
+    }
+    candidates
+}
+
+/// Applies shims to a copy of the bitstream.
+fn apply(data: &[u8], shims: &[CompatShim]) -> Option<Vec<u8>> {
+    let mut patched = data.to_vec();
+    for shim in shims {
+        match *shim {
+            CompatShim::MetadataStripped => {
+                let length = metadata_length(&patched)?;
+                patched.drain(HEADER_LENGTH..HEADER_LENGTH + length);
+                let flags = u16::from_le_bytes([patched[9], patched[10]]) & !METADATA_FLAG_MASK;
+                patched[9..HEADER_LENGTH].copy_from_slice(&flags.to_le_bytes());
+            }
+        }
+    }
+    Some(patched)
+}
+
+/// Returns the length of the metadata section following the header.
+///
+/// The section is parsed more leniently than by the decoder, so sections it
+/// rejects can still be skipped. Returns `None` if the section is truncated.
+fn metadata_length(data: &[u8]) -> Option<usize> {
+    let mut reader = Reader::new(data);
+
+    let attribute_metadata = reader.varint()?;
+    for _ in 0..attribute_metadata {
+        reader.varint()?; // attribute unique id
+        reader.metadata(0)?;
+    }
+    reader.metadata(0)?;
+    Some(reader.position - HEADER_LENGTH)
+}
+
+/// Cursor over the metadata section of a bitstream.
+pub(crate) struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// Starts reading at the metadata section following the header.
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            position: HEADER_LENGTH,
+        }
+    }
+
+    fn bytes(&mut self, length: usize) -> Option<&'a [u8]> {
+        let end = self.position.checked_add(length)?;
+        let bytes = self.data.get(self.position..end)?;
+        self.position = end;
+        Some(bytes)
+    }
+
+    fn byte(&mut self) -> Option<u8> {
+        let byte = *self.data.get(self.position)?;
+        self.position += 1;
+        Some(byte)
+    }
+
+    /// Reads a LEB128 encoded `u32` as written by Draco's `EncodeVarint`.
+    pub(crate) fn varint(&mut self) -> Option<u32> {
+        let mut value = 0u32;
+        for shift in (0..35).step_by(7) {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7f) as u32).checked_shl(shift)?;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    /// Reads a name prefixed by its length in one byte.
+    pub(crate) fn name(&mut self) -> Option<&'a [u8]> {
+        let length = self.byte()? as usize;
+        self.bytes(length)
+    }
+
+    /// Reads a value prefixed by its varint encoded length.
+    pub(crate) fn value(&mut self) -> Option<&'a [u8]> {
+        let length = self.varint()? as usize;
+        self.bytes(length)
+    }
+
+    /// Skips a metadata entry list and its sub-metadata.
+    pub(crate) fn metadata(&mut self, depth: usize) -> Option<()> {
+        if depth > MAX_METADATA_DEPTH {
+            return None;
+        }
+
+        for _ in 0..self.varint()? {
+            self.name()?;
+            self.value()?;
+        }
+        for _ in 0..self.varint()? {
+            self.name()?;
+            self.metadata(depth + 1)?;
+        }
+        Some(())
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use crate::{AttributeSemantic, MeshDecodeResult};
+
+const UNMAPPED: u32 = u32::MAX;
+
+impl MeshDecodeResult {
+    /// Returns the connected component of every triangle, in the order of
+    /// [`MeshDecodeResult::triangles`].
+    ///
+    /// Triangles sharing a vertex belong to the same component. Vertices
+    /// with identical positions count as shared, so the seams Draco creates
+    /// by splitting vertices with different normals or texture coordinates
+    /// do not separate components. Components are numbered from 0 in the
+    /// order of their first triangle.
+    pub fn connected_components(&self) -> Vec<u32> {
+        let vertex_count = self.config.vertex_count();
+        let welded = self.welded_vertices();
+        let mut parents: Vec<u32> = (0..vertex_count).collect();
+
+        fn root(parents: &mut [u32], mut vertex: u32) -> u32 {
+            while parents[vertex as usize] != vertex {
+                let parent = parents[vertex as usize];
+                parents[vertex as usize] = parents[parent as usize];
+                vertex = parent;
+            }
+            vertex
+        }
+
+        let triangles: Vec<[u32; 3]> = self.triangles().collect();
+        for triangle in &triangles {
+            let mut vertices = triangle
+                .iter()
+                .filter(|&&vertex| vertex < vertex_count)
+                .map(|&vertex| welded[vertex as usize]);
+            let Some(first) = vertices.next() else {
+                continue;
+            };
+            for vertex in vertices {
+                let (a, b) = (root(&mut parents, first), root(&mut parents, vertex));
+                parents[a.max(b) as usize] = a.min(b);
+            }
+        }
+
+        // Triangles without a vertex inside the mesh each form a component
+        let mut components: HashMap<u32, u32> = HashMap::new();
+        let mut next = 0;
+        triangles
+            .iter()
+            .map(|triangle| {
+                let vertex = triangle.iter().find(|&&vertex| vertex < vertex_count);
+                let key = match vertex {
+                    Some(&vertex) => root(&mut parents, welded[vertex as usize]),
+                    None => vertex_count + next,
+                };
+                *components.entry(key).or_insert_with(|| {
+                    next += 1;
+                    next - 1
+                })
+            })
+            .collect()
+    }
+
+    /// Splits the mesh into one mesh per connected component, such as the
+    /// floaters and debris around a scanned object.
+    ///
+    /// Components are ordered as in
+    /// [`MeshDecodeResult::connected_components`]. Each mesh holds the
+    /// vertices its triangles reference, in the order they are first used.
+    /// Triangles referencing vertices outside the mesh are dropped.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut components = result.split_components();
+    /// // Keep the largest component, drop the debris
+    /// components.sort_by_key(|mesh| std::cmp::Reverse(mesh.config.index_count()));
+    /// let main = components.swap_remove(0);
+    /// ```
+    pub fn split_components(&self) -> Vec<MeshDecodeResult> {
+        let vertex_count = self.config.vertex_count();
+        let components = self.connected_components();
+        let component_count = components.iter().max().map_or(0, |&max| max as usize + 1);
+
+        let mut remap = vec![UNMAPPED; vertex_count as usize];
+        let mut parts: Vec<(Vec<u32>, Vec<u32>)> = vec![Default::default(); component_count];
+        for (triangle, component) in self.triangles().zip(components) {
+            if triangle.iter().any(|&vertex| vertex >= vertex_count) {
+                continue;
+            }
+
+            let (source_vertices, indices) = &mut parts[component as usize];
+            for vertex in triangle {
+                // Vertices belong to a single component, so one map serves all
+                let slot = &mut remap[vertex as usize];
+                if *slot == UNMAPPED {
+                    *slot = source_vertices.len() as u32;
+                    source_vertices.push(vertex);
+                }
+                indices.push(*slot);
+            }
+        }
+
+        parts
+            .into_iter()
+            .filter(|(_, indices)| !indices.is_empty())
+            .map(|(source_vertices, indices)| self.gather(&source_vertices, &indices))
+            .collect()
+    }
+
+    /// Maps every vertex to the first vertex with the same position.
+    pub(crate) fn welded_vertices(&self) -> Vec<u32> {
+        let vertex_count = self.config.vertex_count();
+        let positions = self
+            .config
+            .find_attribute(AttributeSemantic::Position)
+            .and_then(|index| {
+                let attribute = self.config.get_attribute(index)?;
+                Some((attribute.element_size(), self.attribute_bytes(index)?))
+            });
+        let Some((element_size, bytes)) = positions else {
+            return (0..vertex_count).collect();
+        };
+
+        let mut first: HashMap<&[u8], u32> = HashMap::new();
+        (0..vertex_count)
+            .map(|vertex| {
+                let start = vertex as usize * element_size;
+                match bytes.get(start..start + element_size) {
+                    Some(position) => *first.entry(position).or_insert(vertex),
+                    None => vertex,
+                }
+            })
+            .collect()
+    }
+}
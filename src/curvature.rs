@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+use std::f32::consts::{PI, TAU};
+
+use crate::math::{cross, dot, float_positions, length, sub};
+use crate::predicates::is_degenerate;
+use crate::{AttributeSemantic, MeshDecodeResult};
+
+/// Discrete curvature at a vertex, see [`MeshDecodeResult::compute_curvature`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct VertexCurvature {
+    /// Mean curvature, positive where the surface bends away from its
+    /// normal, like the outside of a sphere.
+    pub mean: f32,
+    /// Gaussian curvature, positive at bumps and dents and negative at
+    /// saddles.
+    pub gaussian: f32,
+}
+
+impl MeshDecodeResult {
+    /// Returns the first three components of the `Float32` positions, `None`
+    /// without such an attribute.
+    pub(crate) fn float_positions(&self) -> Option<Vec<[f32; 3]>> {
+        let index = self.config.find_attribute(AttributeSemantic::Position)?;
+        float_positions(self.config.get_attribute(index), &self.data)
+    }
+
+    /// Computes the mean and Gaussian curvature at every vertex, for example
+    /// to find the crumpled regions over-quantized positions produce.
+    ///
+    /// The Gaussian curvature is the angle defect and the mean curvature the
+    /// length of the cotangent Laplacian, both divided by a third of the area
+    /// of the adjacent triangles. Vertices with identical positions are
+    /// treated as one, so seams do not show. At boundary vertices the
+    /// Gaussian curvature uses the defect against a straight angle and the
+    /// mean curvature is unreliable. Vertices without triangles get zero.
+    ///
+    /// Returns `None` if the mesh has no `Float32` positions.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let curvature = result.compute_curvature().unwrap();
+    /// let crumpled = curvature.iter().filter(|c| c.mean.abs() > 50.0).count();
+    /// ```
+    pub fn compute_curvature(&self) -> Option<Vec<VertexCurvature>> {
+        let points = self.float_positions()?;
+        let welded = self.welded_vertices();
+        let triangles: Vec<[usize; 3]> = self
+            .triangles()
+            .filter(|triangle| {
+                triangle
+                    .iter()
+                    .all(|&vertex| (vertex as usize) < points.len())
+            })
+            .map(|triangle| triangle.map(|vertex| welded[vertex as usize] as usize))
+            .collect();
+
+        let mut areas = vec![0.0f32; points.len()];
+        let mut angles = vec![0.0f32; points.len()];
+        let mut laplacians = vec![[0.0f32; 3]; points.len()];
+        let mut normals = vec![[0.0f32; 3]; points.len()];
+        // Edges of a single triangle lie on the boundary
+        let mut edges: HashMap<(usize, usize), u32> = HashMap::new();
+
+        for &[a, b, c] in &triangles {
+            let normal = cross(sub(points[b], points[a]), sub(points[c], points[a]));
+            let area = length(normal) / 2.0;
+            if area == 0.0 || !area.is_finite() {
+                continue;
+            }
+
+            for (corner, next, previous) in [(a, b, c), (b, c, a), (c, a, b)] {
+                let to_next = sub(points[next], points[corner]);
+                let to_previous = sub(points[previous], points[corner]);
+                let sine = length(cross(to_next, to_previous));
+                let cosine = dot(to_next, to_previous);
+                angles[corner] += sine.atan2(cosine);
+                areas[corner] += area / 3.0;
+                for axis in 0..3 {
+                    normals[corner][axis] += normal[axis];
+                }
+
+                // The cotangent of this corner weighs the opposite edge
+                let cotangent = cosine / sine;
+                let edge = sub(points[next], points[previous]);
+                for axis in 0..3 {
+                    laplacians[next][axis] += cotangent * edge[axis];
+                    laplacians[previous][axis] -= cotangent * edge[axis];
+                }
+                *edges
+                    .entry((next.min(previous), next.max(previous)))
+                    .or_default() += 1;
+            }
+        }
+
+        let mut boundary = vec![false; points.len()];
+        for (&(a, b), &count) in &edges {
+            if count == 1 {
+                boundary[a] = true;
+                boundary[b] = true;
+            }
+        }
+
+        Some(
+            (0..points.len())
+                .map(|vertex| {
+                    let source = welded[vertex] as usize;
+                    let area = areas[source];
+                    if area == 0.0 {
+                        return VertexCurvature::default();
+                    }
+
+                    let full_angle = if boundary[source] { PI } else { TAU };
+                    let laplacian = laplacians[source].map(|c| c / (2.0 * area));
+                    let sign = if dot(laplacian, normals[source]) < 0.0 {
+                        -1.0
+                    } else {
+                        1.0
+                    };
+                    VertexCurvature {
+                        mean: sign * length(laplacian) / 2.0,
+                        gaussian: (full_angle - angles[source]) / area,
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns the quality of every triangle, in the order of
+    /// [`MeshDecodeResult::triangles`].
+    ///
+    /// The quality is `4√3 · area / (a² + b² + c²)` for edge lengths `a`, `b`
+    /// and `c`: 1 for equilateral triangles and 0 for degenerate ones, such
+    /// as triangles collapsed by quantization. Whether a triangle is
+    /// degenerate is decided exactly, so the quality is 0 for precisely the
+    /// triangles [`MeshDecodeResult::degenerate_triangles`] reports, and
+    /// positive otherwise, however thin the triangle. Triangles referencing
+    /// vertices outside the mesh have quality 0. Returns `None` if the mesh
+    /// has no `Float32` positions.
+    pub fn triangle_qualities(&self) -> Option<Vec<f32>> {
+        let points = self.float_positions()?;
+        Some(
+            self.triangles()
+                .map(|triangle| {
+                    let corners = triangle.map(|vertex| points.get(vertex as usize).copied());
+                    let [Some(a), Some(b), Some(c)] = corners else {
+                        return 0.0;
+                    };
+                    if is_degenerate(a, b, c) {
+                        return 0.0;
+                    }
+                    let (ab, bc, ca) = (sub(b, a), sub(c, b), sub(a, c));
+                    let squared_lengths = dot(ab, ab) + dot(bc, bc) + dot(ca, ca);
+                    let area = length(cross(ab, sub(c, a))) / 2.0;
+                    let quality = 4.0 * 3.0f32.sqrt() * area / squared_lengths;
+                    // Rounding can flush the area of a thin triangle to zero
+                    if quality.is_nan() {
+                        f32::MIN_POSITIVE
+                    } else {
+                        quality.clamp(f32::MIN_POSITIVE, 1.0)
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns the indices of the degenerate triangles, in the order of
+    /// [`MeshDecodeResult::triangles`].
+    ///
+    /// A triangle is degenerate if its corners lie on a line, including
+    /// triangles with repeated corners, or if it references vertices outside
+    /// the mesh. Collinearity is decided with exact predicates on the decoded
+    /// floats, so near-degenerate triangles from scans or coarse quantization
+    /// get the same verdict every time. Returns `None` if the mesh has no
+    /// `Float32` positions.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let degenerate = result.degenerate_triangles().unwrap();
+    /// assert!(degenerate.is_empty(), "{} degenerate triangles", degenerate.len());
+    /// ```
+    pub fn degenerate_triangles(&self) -> Option<Vec<usize>> {
+        let points = self.float_positions()?;
+        Some(
+            self.triangles()
+                .enumerate()
+                .filter(|(_, triangle)| {
+                    let corners = triangle.map(|vertex| points.get(vertex as usize).copied());
+                    let [Some(a), Some(b), Some(c)] = corners else {
+                        return true;
+                    };
+                    is_degenerate(a, b, c)
+                })
+                .map(|(index, _)| index)
+                .collect(),
+        )
+    }
+
+    /// Counts the triangles per quality range, `bins` equal ranges between 0
+    /// and 1 of [`MeshDecodeResult::triangle_qualities`].
+    ///
+    /// A mesh whose lowest bins fill up after a change of quantization
+    /// settings has slivers the compression introduced. Returns `None` if the
+    /// mesh has no `Float32` positions.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let histogram = result.triangle_quality_histogram(10).unwrap();
+    /// let slivers = histogram[0] as f32 / result.triangles().count() as f32;
+    /// ```
+    pub fn triangle_quality_histogram(&self, bins: usize) -> Option<Vec<u32>> {
+        let mut histogram = vec![0u32; bins];
+        if bins == 0 {
+            return Some(histogram);
+        }
+        for quality in self.triangle_qualities()? {
+            let bin = ((quality * bins as f32) as usize).min(bins - 1);
+            histogram[bin] += 1;
+        }
+        Some(histogram)
+    }
+}
@@ -0,0 +1,31 @@
+use crate::MeshDecodeResult;
+
+/// A synchronous source of decoded meshes.
+///
+/// Code that handles decoded meshes can be written against this trait so that
+/// tests can substitute a [`ReplayDecoder`](crate::ReplayDecoder) for the native
+/// Draco decoder.
+pub trait MeshDecoder {
+    /// Decodes a Draco compressed mesh.
+    ///
+    /// Returns `None` if decoding fails.
+    fn decode_mesh(&self, data: &[u8]) -> Option<MeshDecodeResult>;
+}
+
+/// The native Draco decoder backed by the bundled C++ library.
+///
+/// The decoder is stateless and can be shared freely between threads; every
+/// call creates its own [`MeshHandle`](crate::MeshHandle).
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NativeDecoder;
+
+#[cfg(not(target_arch = "wasm32"))]
+static_assertions::assert_impl_all!(NativeDecoder: Send, Sync);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl MeshDecoder for NativeDecoder {
+    fn decode_mesh(&self, data: &[u8]) -> Option<MeshDecodeResult> {
+        crate::ffi::decode_mesh_with_config(data)
+    }
+}
@@ -0,0 +1,231 @@
+use std::cell::{Cell, RefCell};
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::header::HEADER_LENGTH;
+use crate::utils::content_hash;
+use crate::{DecodeError, DecodeOptions, GeometryType, decode_header};
+
+/// Directory diagnostics bundles are written to, `None` while disabled.
+static DIAGNOSTICS_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+thread_local! {
+    /// Whether failures on this thread are captured instead of written as
+    /// bundles, while [`export_reproducer`] tries candidate inputs.
+    static CAPTURING: Cell<bool> = const { Cell::new(false) };
+    /// Stage and message of the last captured failure.
+    static CAPTURED: RefCell<Option<(String, String)>> = const { RefCell::new(None) };
+}
+
+/// Writes a diagnostics bundle to `dir` whenever a decode fails (native
+/// only).
+///
+/// Each bundle is a text file named after the hash of the input, so the same
+/// failing input is written once. It holds what is needed to file an
+/// actionable issue without sharing the geometry:
+///
+/// - the first 11 bytes of the input, which make up the Draco header, and
+///   the parsed header
+/// - the size and hash of the input
+/// - the decode options
+/// - the crate and draco versions, the backend and the target
+/// - the failing stage and the error chain, including the message of the
+///   draco library
+///
+/// The rest of the input is never written. Failing to write a bundle does
+/// not affect the decode.
+///
+/// # Example
+///
+/// ```ignore
+/// draco_decoder::enable_crash_diagnostics("draco-diagnostics")?;
+/// if decode_mesh_with_config(&data).await.is_none() {
+///     eprintln!("attach the bundle in draco-diagnostics/ to your report");
+/// }
+/// ```
+pub fn enable_crash_diagnostics(dir: impl Into<PathBuf>) -> io::Result<()> {
+    let dir = dir.into();
+    fs::create_dir_all(&dir)?;
+    *DIAGNOSTICS_DIR
+        .lock()
+        .unwrap_or_else(|err| err.into_inner()) = Some(dir);
+    Ok(())
+}
+
+/// Stops writing diagnostics bundles enabled by [`enable_crash_diagnostics`].
+pub fn disable_crash_diagnostics() {
+    *DIAGNOSTICS_DIR
+        .lock()
+        .unwrap_or_else(|err| err.into_inner()) = None;
+}
+
+/// Writes a bundle for a failed decode if diagnostics are enabled.
+///
+/// `stage` names the step that failed and `error` is the message it
+/// reported, which may be empty.
+pub(crate) fn report_failure(data: &[u8], options: &DecodeOptions, stage: &str, error: &str) {
+    if CAPTURING.get() {
+        CAPTURED.replace(Some((stage.to_string(), error.to_string())));
+        return;
+    }
+    let dir = DIAGNOSTICS_DIR
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .clone();
+    if let Some(dir) = dir {
+        // Diagnostics must never turn into a second failure
+        write_bundle(&dir, data, options, stage, error).ok();
+    }
+}
+
+fn write_bundle(
+    dir: &Path,
+    data: &[u8],
+    options: &DecodeOptions,
+    stage: &str,
+    error: &str,
+) -> io::Result<PathBuf> {
+    let hash = content_hash(data);
+    let header = decode_header(data);
+
+    let mut bundle = String::new();
+    let mut line = |key: &str, value: &dyn std::fmt::Display| {
+        writeln!(bundle, "{key}: {value}").ok();
+    };
+    line("crate_version", &env!("CARGO_PKG_VERSION"));
+    line("backend", &"native");
+    line("draco_version", &crate::ffi::draco_version());
+    line(
+        "target",
+        &format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS),
+    );
+    line("input_size", &data.len());
+    line("input_hash", &format!("fnv1a64:{hash:016x}"));
+    line(
+        "header_bytes",
+        &data[..data.len().min(HEADER_LENGTH)]
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<Vec<_>>()
+            .join(" "),
+    );
+    line("header", &format!("{header:?}"));
+    line("options", &format!("{options:?}"));
+    line("stage", &stage);
+
+    bundle.push_str("errors:\n");
+    if let Err(err) = header {
+        writeln!(bundle, "  - header: {err}").ok();
+    }
+    if error.is_empty() {
+        writeln!(bundle, "  - {stage}: failed without a message").ok();
+    } else {
+        writeln!(bundle, "  - {stage}: {error}").ok();
+    }
+
+    let path = dir.join(format!("draco-diagnostics-{hash:016x}.txt"));
+    fs::write(&path, bundle)?;
+    Ok(path)
+}
+
+/// Why an input fails to decode. Two inputs fail the same way if the error,
+/// the failing stage and the message of the draco library all match.
+#[derive(Debug, PartialEq, Eq)]
+struct FailureClass {
+    error: DecodeError,
+    stage: String,
+    message: String,
+}
+
+/// Decodes `data` with the default options and classifies the failure,
+/// returning `None` if it decodes.
+fn classify(data: &[u8]) -> Option<FailureClass> {
+    let header = match decode_header(data) {
+        Ok(header) => header,
+        Err(error) => {
+            return Some(FailureClass {
+                error,
+                stage: "header".to_string(),
+                message: String::new(),
+            });
+        }
+    };
+
+    CAPTURING.set(true);
+    CAPTURED.take();
+    let decoded = match header.geometry_type {
+        GeometryType::Mesh => crate::ffi::decode_mesh_with_config(data).is_some(),
+        GeometryType::PointCloud => crate::ffi::decode_point_cloud_with_config(data).is_some(),
+    };
+    CAPTURING.set(false);
+    let (stage, message) = CAPTURED.take().unwrap_or_default();
+
+    (!decoded).then_some(FailureClass {
+        error: DecodeError::DecodeFailed,
+        stage,
+        message,
+    })
+}
+
+/// Writes the shortest prefix of `data` that still fails to decode with
+/// `error` to `dir`, for attaching to an upstream bug report (native only).
+///
+/// Failures are compared by their [`DecodeError`] and, for bitstreams with a
+/// valid header, by the failing stage and the message of the draco library,
+/// so the reproducer fails for the same reason as the original input. The
+/// prefix is found by bisecting its length, assuming that once a prefix
+/// fails this way all longer ones do. Each candidate is decoded with the
+/// default options; no diagnostics bundles are written for them.
+///
+/// The reproducer is named after its hash, `draco-reproducer-<hash>.drc`,
+/// and its path is returned. Fails with [`io::ErrorKind::InvalidInput`] if
+/// `data` does not fail with `error`.
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::{DecodeError, export_reproducer};
+///
+/// if let Err(err @ DecodeError::DecodeFailed) = decode(&data) {
+///     let path = export_reproducer(&data, &err, "draco-reproducers")?;
+///     eprintln!("attach {} to the upstream issue", path.display());
+/// }
+/// ```
+pub fn export_reproducer(
+    data: &[u8],
+    error: &DecodeError,
+    dir: impl AsRef<Path>,
+) -> io::Result<PathBuf> {
+    let class = classify(data)
+        .filter(|class| class.error == *error)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("the data does not fail to decode with \"{error}\""),
+            )
+        })?;
+
+    // A prefix of length `high` fails the same way, shorter than `low` do not
+    let (mut low, mut high) = (0, data.len());
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if classify(&data[..middle]).is_some_and(|candidate| candidate == class) {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+
+    let reproducer = &data[..high];
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!(
+        "draco-reproducer-{:016x}.drc",
+        content_hash(reproducer)
+    ));
+    fs::write(&path, reproducer)?;
+    Ok(path)
+}
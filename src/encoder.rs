@@ -0,0 +1,298 @@
+use crate::{
+    AttributeComponent, AttributeDataType, AttributeSemantic, EncodingMethod, MeshAttribute,
+    MeshDecodeResult, PointCloudDecodeResult,
+};
+
+/// Per-vertex data to encode alongside the positions.
+#[derive(Debug, Clone, Copy)]
+pub struct EncodeAttribute<'a> {
+    semantic: AttributeSemantic,
+    dim: u32,
+    data_type: AttributeDataType,
+    data: &'a [u8],
+}
+
+impl<'a> EncodeAttribute<'a> {
+    /// Creates an attribute with `dim` components per vertex.
+    ///
+    /// `values` holds the components of every vertex, tightly packed.
+    pub fn new<T: AttributeComponent>(
+        semantic: AttributeSemantic,
+        dim: u32,
+        values: &'a [T],
+    ) -> Self {
+        Self {
+            semantic,
+            dim,
+            data_type: T::DATA_TYPE,
+            data: bytemuck::cast_slice(values),
+        }
+    }
+
+    /// Creates an attribute from raw bytes in the layout described by
+    /// `data_type` and `dim`.
+    pub fn from_bytes(
+        semantic: AttributeSemantic,
+        dim: u32,
+        data_type: AttributeDataType,
+        data: &'a [u8],
+    ) -> Self {
+        Self {
+            semantic,
+            dim,
+            data_type,
+            data,
+        }
+    }
+
+    /// Returns what the attribute represents.
+    pub fn semantic(&self) -> AttributeSemantic {
+        self.semantic
+    }
+
+    /// Returns the number of components per vertex.
+    pub fn dim(&self) -> u32 {
+        self.dim
+    }
+
+    /// Returns the component data type.
+    pub fn data_type(&self) -> AttributeDataType {
+        self.data_type
+    }
+
+    /// Returns the raw attribute bytes.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+/// Settings for [`encode_mesh`] and [`encode_point_cloud`].
+///
+/// The defaults match the `draco_encoder` command line tool: speed 3
+/// (compression level 7) and 11, 8, 8, 10 and 8 quantization bits for positions, normals, colors,
+/// texture coordinates and generic attributes. The encoding method is chosen
+/// by the encoder unless set explicitly.
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::{AttributeSemantic, EncodeConfig};
+///
+/// let config = EncodeConfig::default()
+///     .with_encoding_speed(0)
+///     .with_quantization_bits(AttributeSemantic::Position, 14);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodeConfig {
+    encoding_speed: u8,
+    decoding_speed: u8,
+    quantization_bits: [u32; 5],
+    encoding_method: Option<EncodingMethod>,
+}
+
+impl Default for EncodeConfig {
+    fn default() -> Self {
+        Self {
+            encoding_speed: 3,
+            decoding_speed: 3,
+            quantization_bits: [11, 8, 8, 10, 8],
+            encoding_method: None,
+        }
+    }
+}
+
+impl EncodeConfig {
+    /// Sets the encoding speed from 0 (best compression) to 10 (fastest).
+    pub fn with_encoding_speed(mut self, speed: u8) -> Self {
+        self.encoding_speed = speed.min(10);
+        self
+    }
+
+    /// Sets the decoding speed from 0 (best compression) to 10 (fastest).
+    pub fn with_decoding_speed(mut self, speed: u8) -> Self {
+        self.decoding_speed = speed.min(10);
+        self
+    }
+
+    /// Sets the quantization bits for floating point attributes with the
+    /// given semantic.
+    ///
+    /// 0 disables quantization, which encodes the values losslessly. Integer
+    /// attributes are never quantized.
+    pub fn with_quantization_bits(mut self, semantic: AttributeSemantic, bits: u32) -> Self {
+        self.quantization_bits[semantic as usize] = bits.min(30);
+        self
+    }
+
+    /// Selects the encoding method instead of letting the encoder choose.
+    ///
+    /// Meshes support [`EncodingMethod::Sequential`] and
+    /// [`EncodingMethod::Edgebreaker`], point clouds
+    /// [`EncodingMethod::Sequential`] and [`EncodingMethod::KdTree`]. Encoding
+    /// fails with an unsupported method. The KD-tree method requires all
+    /// floating point attributes to be quantized.
+    pub fn with_encoding_method(mut self, method: EncodingMethod) -> Self {
+        self.encoding_method = Some(method);
+        self
+    }
+
+    /// Returns the selected encoding method, if any.
+    pub fn encoding_method(&self) -> Option<EncodingMethod> {
+        self.encoding_method
+    }
+
+    /// Returns the encoding speed.
+    pub fn encoding_speed(&self) -> u8 {
+        self.encoding_speed
+    }
+
+    /// Returns the decoding speed.
+    pub fn decoding_speed(&self) -> u8 {
+        self.decoding_speed
+    }
+
+    /// Returns the quantization bits for attributes with the given semantic.
+    pub fn quantization_bits(&self, semantic: AttributeSemantic) -> u32 {
+        self.quantization_bits[semantic as usize]
+    }
+}
+
+/// Encodes a triangle mesh into a Draco bitstream (native only).
+///
+/// `positions` holds three floats per vertex and `indices` three vertex
+/// indices per triangle. Every attribute in `attributes` must have one
+/// element per vertex.
+///
+/// Returns `None` if the inputs are inconsistent, for example when an index
+/// is out of range, or if encoding fails.
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::{EncodeConfig, encode_mesh};
+///
+/// let positions = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+/// let encoded = encode_mesh(&positions, &[0, 1, 2], &[], &EncodeConfig::default());
+/// ```
+pub fn encode_mesh(
+    positions: &[f32],
+    indices: &[u32],
+    attributes: &[EncodeAttribute<'_>],
+    config: &EncodeConfig,
+) -> Option<Vec<u8>> {
+    if !positions.len().is_multiple_of(3) {
+        return None;
+    }
+    let vertex_count = u32::try_from(positions.len() / 3).ok()?;
+
+    let mut all_attributes = Vec::with_capacity(attributes.len() + 1);
+    all_attributes.push(EncodeAttribute::new(
+        AttributeSemantic::Position,
+        3,
+        positions,
+    ));
+    all_attributes.extend_from_slice(attributes);
+
+    crate::ffi::encode(vertex_count, Some(indices), &all_attributes, config)
+}
+
+/// Re-encodes a decoded mesh into a Draco bitstream (native only).
+///
+/// All attributes of `mesh` are encoded with their semantics, so decoding
+/// the result yields the same layout up to quantization.
+///
+/// Returns `None` if the mesh is inconsistent or encoding fails.
+pub fn encode_mesh_with_config(mesh: &MeshDecodeResult, config: &EncodeConfig) -> Option<Vec<u8>> {
+    let indices = mesh.indices()?.into_u32();
+    let attributes = encode_attributes(&mesh.config.attributes(), |index| {
+        mesh.attribute_bytes(index)
+    })?;
+
+    crate::ffi::encode(
+        mesh.config.vertex_count(),
+        Some(&indices),
+        &attributes,
+        config,
+    )
+}
+
+/// Encodes a point cloud into a Draco bitstream (native only).
+///
+/// `positions` holds three floats per point. Every attribute in
+/// `attributes` must have one element per point. Use
+/// [`EncodeConfig::with_encoding_method`] to choose between KD-tree and
+/// sequential encoding.
+///
+/// Returns `None` if the inputs are inconsistent or encoding fails.
+pub fn encode_point_cloud(
+    positions: &[f32],
+    attributes: &[EncodeAttribute<'_>],
+    config: &EncodeConfig,
+) -> Option<Vec<u8>> {
+    if !positions.len().is_multiple_of(3) {
+        return None;
+    }
+    let point_count = u32::try_from(positions.len() / 3).ok()?;
+
+    let mut all_attributes = Vec::with_capacity(attributes.len() + 1);
+    all_attributes.push(EncodeAttribute::new(
+        AttributeSemantic::Position,
+        3,
+        positions,
+    ));
+    all_attributes.extend_from_slice(attributes);
+
+    crate::ffi::encode(point_count, None, &all_attributes, config)
+}
+
+/// Re-encodes a decoded point cloud into a Draco bitstream (native only).
+///
+/// All attributes of `point_cloud` are encoded with their semantics.
+///
+/// Returns `None` if the point cloud is inconsistent or encoding fails.
+pub fn encode_point_cloud_with_config(
+    point_cloud: &PointCloudDecodeResult,
+    config: &EncodeConfig,
+) -> Option<Vec<u8>> {
+    let attributes = encode_attributes(&point_cloud.config.attributes(), |index| {
+        point_cloud.attribute_bytes(index)
+    })?;
+
+    crate::ffi::encode(point_cloud.config.point_count(), None, &attributes, config)
+}
+
+/// Decodes a Draco bitstream and re-encodes it with new settings (native
+/// only).
+///
+/// This normalizes third-party assets to a target quality, for example by
+/// changing quantization bits. The geometry stays inside the native library
+/// between decoding and encoding, no [`MeshDecodeResult`] is built. Meshes
+/// stay meshes and point clouds stay point clouds; metadata is preserved.
+///
+/// Already quantized attributes are decoded to floating point first, so
+/// increasing the quantization bits cannot recover precision that was lost.
+///
+/// Returns `None` if decoding or encoding fails.
+pub fn transcode(data: &[u8], config: &EncodeConfig) -> Option<Vec<u8>> {
+    crate::ffi::transcode(data, config)
+}
+
+/// Describes decoded attributes for encoding, reading their bytes with
+/// `bytes`.
+fn encode_attributes<'a>(
+    attributes: &[MeshAttribute],
+    bytes: impl Fn(usize) -> Option<&'a [u8]>,
+) -> Option<Vec<EncodeAttribute<'a>>> {
+    attributes
+        .iter()
+        .enumerate()
+        .map(|(index, attribute)| {
+            Some(EncodeAttribute::from_bytes(
+                attribute.semantic(),
+                attribute.dim(),
+                attribute.data_type(),
+                bytes(index)?,
+            ))
+        })
+        .collect()
+}
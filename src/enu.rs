@@ -0,0 +1,142 @@
+use crate::PntsTile;
+use crate::math::{self, dot};
+
+/// Semi-major axis of the WGS84 ellipsoid, in meters.
+const SEMI_MAJOR_AXIS: f64 = 6378137.0;
+/// Semi-minor axis of the WGS84 ellipsoid, in meters.
+const SEMI_MINOR_AXIS: f64 = 6356752.314245179;
+
+fn normalize(v: [f64; 3]) -> [f64; 3] {
+    math::normalize(v).unwrap_or(v)
+}
+
+/// A local east, north, up frame tangent to the WGS84 ellipsoid, to convert
+/// between the earth centered, earth fixed (ECEF) coordinates of 3D Tiles
+/// and coordinates in meters around a site.
+///
+/// The axes follow the ellipsoid normal at the origin, as Cesium's
+/// `eastNorthUpToFixedFrame` does. At the poles east is the ECEF y axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnuFrame {
+    origin: [f64; 3],
+    axes: [[f64; 3]; 3],
+}
+
+impl EnuFrame {
+    /// Creates the frame at a geodetic position given as `[latitude,
+    /// longitude, height]`, in degrees and meters above the ellipsoid.
+    pub fn new(origin_llh: [f64; 3]) -> Self {
+        let [latitude, longitude, height] = origin_llh;
+        let (sin_latitude, cos_latitude) = latitude.to_radians().sin_cos();
+        let (sin_longitude, cos_longitude) = longitude.to_radians().sin_cos();
+        let eccentricity_squared =
+            1.0 - (SEMI_MINOR_AXIS * SEMI_MINOR_AXIS) / (SEMI_MAJOR_AXIS * SEMI_MAJOR_AXIS);
+        // Radius of curvature in the prime vertical
+        let radius =
+            SEMI_MAJOR_AXIS / (1.0 - eccentricity_squared * sin_latitude * sin_latitude).sqrt();
+        Self::from_ecef([
+            (radius + height) * cos_latitude * cos_longitude,
+            (radius + height) * cos_latitude * sin_longitude,
+            (radius * (1.0 - eccentricity_squared) + height) * sin_latitude,
+        ])
+    }
+
+    /// Creates the frame at an ECEF position, such as the `rtc_center` of a
+    /// tile.
+    pub fn from_ecef(origin: [f64; 3]) -> Self {
+        let radii_squared = [
+            SEMI_MAJOR_AXIS * SEMI_MAJOR_AXIS,
+            SEMI_MAJOR_AXIS * SEMI_MAJOR_AXIS,
+            SEMI_MINOR_AXIS * SEMI_MINOR_AXIS,
+        ];
+        let up = normalize(std::array::from_fn(|i| origin[i] / radii_squared[i]));
+        let east = if origin[0] == 0.0 && origin[1] == 0.0 {
+            [0.0, 1.0, 0.0]
+        } else {
+            normalize([-origin[1], origin[0], 0.0])
+        };
+        let north = [
+            up[1] * east[2] - up[2] * east[1],
+            up[2] * east[0] - up[0] * east[2],
+            up[0] * east[1] - up[1] * east[0],
+        ];
+        Self {
+            origin,
+            axes: [east, north, up],
+        }
+    }
+
+    /// Returns the ECEF position of the origin.
+    pub fn origin(&self) -> [f64; 3] {
+        self.origin
+    }
+
+    /// Returns the east, north and up axes in ECEF coordinates.
+    pub fn axes(&self) -> [[f64; 3]; 3] {
+        self.axes
+    }
+
+    /// Converts an ECEF position to east, north and up meters from the
+    /// origin.
+    pub fn to_local_enu(&self, ecef: [f64; 3]) -> [f64; 3] {
+        let offset = std::array::from_fn(|i| ecef[i] - self.origin[i]);
+        self.axes.map(|axis| dot(axis, offset))
+    }
+
+    /// Converts east, north and up meters from the origin to an ECEF
+    /// position.
+    pub fn to_ecef(&self, enu: [f64; 3]) -> [f64; 3] {
+        let [east, north, up] = self.axes;
+        std::array::from_fn(|i| {
+            self.origin[i] + east[i] * enu[0] + north[i] * enu[1] + up[i] * enu[2]
+        })
+    }
+
+    /// Returns the column-major 4x4 matrix taking local east, north, up
+    /// coordinates to ECEF, as a model matrix for content placed at the
+    /// origin.
+    pub fn matrix(&self) -> [f64; 16] {
+        std::array::from_fn(|i| match (i / 4, i % 4) {
+            (3, 3) => 1.0,
+            (_, 3) => 0.0,
+            (3, row) => self.origin[row],
+            (col, row) => self.axes[col][row],
+        })
+    }
+}
+
+/// Converts the frame to its [`EnuFrame::matrix`] (requires the `mint`
+/// feature).
+#[cfg(feature = "mint")]
+impl From<EnuFrame> for mint::ColumnMatrix4<f64> {
+    fn from(frame: EnuFrame) -> Self {
+        frame.matrix().into()
+    }
+}
+
+impl PntsTile {
+    /// Returns the positions of the points in east, north and up meters
+    /// around `origin_llh`, a `[latitude, longitude, height]` in degrees and
+    /// meters.
+    ///
+    /// The positions are offset by the `rtc_center` in `f64` before the
+    /// conversion, so points far from the earth center keep their
+    /// precision.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let tile = decode_pnts(&bytes).await?;
+    /// let local = tile.to_local_enu([48.8584, 2.2945, 35.0]);
+    /// ```
+    pub fn to_local_enu(&self, origin_llh: [f64; 3]) -> Vec<[f64; 3]> {
+        let frame = EnuFrame::new(origin_llh);
+        let center = self.rtc_center.unwrap_or_default();
+        self.positions
+            .iter()
+            .map(|position| {
+                frame.to_local_enu(std::array::from_fn(|i| center[i] + position[i] as f64))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,93 @@
+use std::fmt;
+
+use crate::LimitKind;
+
+/// Errors reported by the decoding APIs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The data does not start with the `DRACO` magic bytes.
+    NotDraco,
+    /// The data ends before the Draco header is complete.
+    TruncatedHeader,
+    /// The header names an encoder type that is not known.
+    UnknownEncoderType(u8),
+    /// The header names an encoding method that is not known for its encoder type.
+    UnknownEncodingMethod(u8),
+    /// The header is valid but Draco could not decode the body.
+    DecodeFailed,
+    /// The decoded mesh exceeds a limit set with
+    /// [`DecodeOptions::with_limits`](crate::DecodeOptions::with_limits).
+    LimitExceeded {
+        /// The exceeded limit.
+        kind: LimitKind,
+        /// The size of the decoded mesh.
+        actual: usize,
+        /// The limit.
+        max: usize,
+    },
+    /// The decoded index buffer references a vertex that does not exist,
+    /// found by
+    /// [`DecodeOptions::validate_indices`](crate::DecodeOptions::validate_indices).
+    IndexOutOfBounds {
+        /// The first out of range index.
+        index: u32,
+        /// The number of vertices of the mesh.
+        vertex_count: u32,
+    },
+    /// [`IndexFormat::U16`](crate::IndexFormat::U16) was requested for a mesh
+    /// whose vertex indices do not fit in `u16`.
+    IndicesDoNotFit {
+        /// The number of vertices of the mesh.
+        vertex_count: u32,
+    },
+    /// The quota set with
+    /// [`DecodeOptions::with_quota`](crate::DecodeOptions::with_quota) denied
+    /// the decode.
+    QuotaDenied,
+    /// A position or normal attribute cannot be written in the convention set
+    /// with
+    /// [`DecodeOptions::with_axis_convention`](crate::DecodeOptions::with_axis_convention),
+    /// because its values are quantized, octahedron encoded, unsigned or not
+    /// three component vectors.
+    AxesNotRemappable {
+        /// The Draco unique id of the attribute.
+        unique_id: u32,
+    },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::NotDraco => write!(f, "data is not Draco encoded"),
+            DecodeError::TruncatedHeader => write!(f, "Draco header is truncated"),
+            DecodeError::UnknownEncoderType(value) => {
+                write!(f, "unknown Draco encoder type {value}")
+            }
+            DecodeError::UnknownEncodingMethod(value) => {
+                write!(f, "unknown Draco encoding method {value}")
+            }
+            DecodeError::DecodeFailed => write!(f, "Draco failed to decode the data"),
+            DecodeError::LimitExceeded { kind, actual, max } => {
+                write!(f, "decoded mesh has {actual} {kind}, the limit is {max}")
+            }
+            DecodeError::IndexOutOfBounds {
+                index,
+                vertex_count,
+            } => write!(
+                f,
+                "index {index} is out of bounds for a mesh with {vertex_count} vertices"
+            ),
+            DecodeError::IndicesDoNotFit { vertex_count } => write!(
+                f,
+                "a mesh with {vertex_count} vertices cannot be written with 16-bit indices"
+            ),
+            DecodeError::QuotaDenied => write!(f, "the quota denied the decode"),
+            DecodeError::AxesNotRemappable { unique_id } => write!(
+                f,
+                "attribute {unique_id} cannot be written in the requested axis convention"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
@@ -0,0 +1,198 @@
+use std::fmt;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+/// Runs the blocking part of the async decode functions off the async
+/// reactor (native only).
+///
+/// Draco decodes are CPU-bound and can take hundreds of milliseconds for
+/// large meshes. Once an executor is set with
+/// [`DecodeOptions::with_executor`](crate::DecodeOptions::with_executor),
+/// [`decode_mesh_with_options`](crate::decode_mesh_with_options) and the
+/// other native async entry points taking these options hand the decode to
+/// it and only await the result, which works with any runtime polling the
+/// future. Implement it to run decodes on the blocking pool of the host
+/// application's runtime:
+///
+/// ```ignore
+/// use draco_decoder::{AsyncExecutor, DecodeOptions};
+///
+/// struct AsyncStd;
+///
+/// impl AsyncExecutor for AsyncStd {
+///     fn spawn_blocking(&self, task: Box<dyn FnOnce() + Send>) {
+///         async_std::task::spawn_blocking(task);
+///     }
+/// }
+///
+/// let options = DecodeOptions::new().with_executor(AsyncStd);
+/// ```
+///
+/// The `tokio` and `blocking` features provide [`TokioExecutor`] and
+/// [`BlockingExecutor`].
+pub trait AsyncExecutor: Send + Sync {
+    /// Runs `task` on a thread that may block.
+    ///
+    /// The task must eventually run or be dropped. A dropped task makes the
+    /// waiting decode return `None`.
+    fn spawn_blocking(&self, task: Box<dyn FnOnce() + Send>);
+}
+
+/// Runs decodes on the blocking thread pool of a Tokio runtime (requires
+/// the `tokio` feature).
+///
+/// With the `tokio` feature this is used by default whenever an async decode
+/// is polled within a Tokio runtime and its options set no executor.
+#[cfg(feature = "tokio")]
+#[derive(Debug, Clone)]
+pub struct TokioExecutor {
+    handle: tokio::runtime::Handle,
+}
+
+#[cfg(feature = "tokio")]
+impl TokioExecutor {
+    /// Runs decodes on the runtime of `handle`.
+    pub fn new(handle: tokio::runtime::Handle) -> Self {
+        Self { handle }
+    }
+
+    /// Runs decodes on the current Tokio runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub fn current() -> Self {
+        Self::new(tokio::runtime::Handle::current())
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncExecutor for TokioExecutor {
+    fn spawn_blocking(&self, task: Box<dyn FnOnce() + Send>) {
+        self.handle.spawn_blocking(task);
+    }
+}
+
+/// Runs decodes on the thread pool of the `blocking` crate, which async-std
+/// and smol use for their own blocking tasks (requires the `blocking`
+/// feature).
+///
+/// The pool does not depend on a runtime, so the decode futures can be
+/// polled by any executor.
+#[cfg(feature = "blocking")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BlockingExecutor;
+
+#[cfg(feature = "blocking")]
+impl AsyncExecutor for BlockingExecutor {
+    fn spawn_blocking(&self, task: Box<dyn FnOnce() + Send>) {
+        blocking::unblock(task).detach();
+    }
+}
+
+/// An executor set with
+/// [`DecodeOptions::with_executor`](crate::DecodeOptions::with_executor).
+#[derive(Clone)]
+pub(crate) struct Executor(pub(crate) Arc<dyn AsyncExecutor>);
+
+impl fmt::Debug for Executor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Executor").finish_non_exhaustive()
+    }
+}
+
+/// Executors are equal if they are clones of each other.
+impl PartialEq for Executor {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Executor {}
+
+/// Returns the executor async decodes with `executor` set in their options
+/// run on, `None` to decode inline.
+pub(crate) fn current_executor(executor: Option<&Executor>) -> Option<Arc<dyn AsyncExecutor>> {
+    if let Some(executor) = executor {
+        return Some(executor.0.clone());
+    }
+    #[cfg(feature = "tokio")]
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        return Some(Arc::new(TokioExecutor::new(handle)));
+    }
+    None
+}
+
+/// Runs `task` on `executor` and waits for its result.
+///
+/// Returns `None` if the executor drops the task. A panic in the task is
+/// resumed in the awaiting task.
+pub(crate) async fn run_blocking<T: Send + 'static>(
+    executor: &dyn AsyncExecutor,
+    task: impl FnOnce() -> T + Send + 'static,
+) -> Option<T> {
+    let slot = Arc::new(Mutex::new(Slot {
+        result: None,
+        done: false,
+        waker: None,
+    }));
+    let sender = Sender(slot.clone());
+    executor.spawn_blocking(Box::new(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(task));
+        sender
+            .0
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .result = Some(result);
+    }));
+
+    match Completion(slot).await? {
+        Ok(value) => Some(value),
+        Err(payload) => panic::resume_unwind(payload),
+    }
+}
+
+/// Result of a task shared between the task and the awaiting future.
+struct Slot<T> {
+    result: Option<thread::Result<T>>,
+    /// Set once the task ran or was dropped
+    done: bool,
+    waker: Option<Waker>,
+}
+
+/// Handle of the task to its slot. Dropping it, whether the task ran or not,
+/// completes the slot.
+struct Sender<T>(Arc<Mutex<Slot<T>>>);
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let waker = {
+            let mut slot = self.0.lock().unwrap_or_else(|err| err.into_inner());
+            slot.done = true;
+            slot.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// Resolves to the result of a task, `None` if it was dropped unrun.
+struct Completion<T>(Arc<Mutex<Slot<T>>>);
+
+impl<T> Future for Completion<T> {
+    type Output = Option<thread::Result<T>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.0.lock().unwrap_or_else(|err| err.into_inner());
+        if slot.done {
+            return Poll::Ready(slot.result.take());
+        }
+        slot.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
@@ -0,0 +1,722 @@
+//! Writers turning decoded meshes into interchange and engine formats.
+//!
+//! The writers are also exported at the crate root. [`engine_pack`] packs
+//! vertices into the interleaved formats of legacy engines.
+
+use std::io::{self, BufWriter, Write};
+
+use crate::vector::converted_vectors_in;
+
+pub mod engine_pack;
+
+use crate::{
+    AttributeDataType, AttributeSemantic, AttributeValues, MeshAttribute, MeshDecodeResult,
+    PointCloudDecodeResult,
+};
+pub use engine_pack::{ByteOrder, EnginePackError, EngineVertexFormat, PackedFormat, VertexPacker};
+
+/// Writes a mesh as a Wavefront OBJ file, for inspecting decoded assets in
+/// any 3D tool.
+///
+/// Positions are written as `v` records, normals as `vn` and the first set
+/// of texture coordinates as `vt` records, all converted to floats like
+/// [`MeshDecodeResult::positions`]. Every vertex has the same index in each
+/// list, so faces are written as `f a/a/a` with 1-based indices, leaving out
+/// what the mesh does not have. Triangles referencing missing vertices are
+/// skipped. Texture coordinates are written as stored, without flipping `v`.
+///
+/// The output is buffered, so `writer` can be a plain `File`. Fails with
+/// [`io::ErrorKind::InvalidInput`] if the mesh has no three component
+/// positions.
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::write_obj;
+///
+/// write_obj(&result, std::fs::File::create("mesh.obj")?)?;
+/// ```
+pub fn write_obj(result: &MeshDecodeResult, writer: impl Write) -> io::Result<()> {
+    let positions: Vec<[f32; 3]> = result
+        .positions()
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "mesh has no three component positions",
+            )
+        })?
+        .collect();
+    let normals: Option<Vec<[f32; 3]>> = result.normals().map(|normals| normals.collect());
+    let uvs: Option<Vec<[f32; 2]>> = result.uvs(0).map(|uvs| uvs.collect());
+
+    let mut writer = BufWriter::new(writer);
+    writeln!(writer, "# draco_decoder {}", env!("CARGO_PKG_VERSION"))?;
+    for [x, y, z] in &positions {
+        writeln!(writer, "v {x} {y} {z}")?;
+    }
+    for [u, v] in uvs.iter().flatten() {
+        writeln!(writer, "vt {u} {v}")?;
+    }
+    for [x, y, z] in normals.iter().flatten() {
+        writeln!(writer, "vn {x} {y} {z}")?;
+    }
+
+    let has = |list: Option<usize>, vertex: u32| list.is_some_and(|len| (vertex as usize) < len);
+    let (normal_count, uv_count) = (normals.as_ref().map(Vec::len), uvs.as_ref().map(Vec::len));
+    for triangle in result.triangles() {
+        if triangle
+            .iter()
+            .any(|&vertex| vertex as usize >= positions.len())
+        {
+            continue;
+        }
+        let with_uvs = triangle.iter().all(|&vertex| has(uv_count, vertex));
+        let with_normals = triangle.iter().all(|&vertex| has(normal_count, vertex));
+
+        write!(writer, "f")?;
+        for vertex in triangle.map(|vertex| vertex + 1) {
+            match (with_uvs, with_normals) {
+                (true, true) => write!(writer, " {vertex}/{vertex}/{vertex}")?,
+                (true, false) => write!(writer, " {vertex}/{vertex}")?,
+                (false, true) => write!(writer, " {vertex}//{vertex}")?,
+                (false, false) => write!(writer, " {vertex}")?,
+            }
+        }
+        writeln!(writer)?;
+    }
+    writer.flush()
+}
+
+/// Encoding of a PLY file written by [`write_ply`] or
+/// [`write_point_cloud_ply`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PlyFormat {
+    /// Human-readable text, one element per line.
+    Ascii,
+    /// Little-endian binary, several times smaller and faster to load.
+    #[default]
+    BinaryLittleEndian,
+}
+
+/// Vertex and face data of a PLY file.
+struct PlyElements {
+    positions: Vec<[f32; 3]>,
+    normals: Option<Vec<[f32; 3]>>,
+    /// Colors and whether they have an alpha channel.
+    colors: Option<(Vec<[u8; 4]>, bool)>,
+    faces: Vec<[u32; 3]>,
+}
+
+impl PlyElements {
+    /// Collects the positions, normals and first colors stored in `data`.
+    ///
+    /// Normals and colors with a different count than the positions are left
+    /// out.
+    fn new(attributes: &[MeshAttribute], data: &[u8]) -> io::Result<Self> {
+        let find = |semantic| {
+            attributes
+                .iter()
+                .find(|attribute| attribute.semantic() == semantic)
+        };
+        let positions: Vec<[f32; 3]> = find(AttributeSemantic::Position)
+            .and_then(|position| converted_vectors_in(position, data))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "geometry has no three component positions",
+                )
+            })?
+            .collect();
+        let normals = find(AttributeSemantic::Normal)
+            .and_then(|normal| converted_vectors_in(normal, data))
+            .map(|normals| normals.collect::<Vec<_>>())
+            .filter(|normals| normals.len() == positions.len());
+        let colors = find(AttributeSemantic::Color)
+            .and_then(|color| Some((colors_in(color, data)?, color.dim() == 4)))
+            .filter(|(colors, _)| colors.len() == positions.len());
+        Ok(Self {
+            positions,
+            normals,
+            colors,
+            faces: Vec::new(),
+        })
+    }
+
+    fn write(&self, format: PlyFormat, writer: impl Write) -> io::Result<()> {
+        let mut writer = BufWriter::new(writer);
+        let format_name = match format {
+            PlyFormat::Ascii => "ascii",
+            PlyFormat::BinaryLittleEndian => "binary_little_endian",
+        };
+        writeln!(writer, "ply")?;
+        writeln!(writer, "format {format_name} 1.0")?;
+        writeln!(
+            writer,
+            "comment draco_decoder {}",
+            env!("CARGO_PKG_VERSION")
+        )?;
+        writeln!(writer, "element vertex {}", self.positions.len())?;
+        for axis in ["x", "y", "z"] {
+            writeln!(writer, "property float {axis}")?;
+        }
+        if self.normals.is_some() {
+            for axis in ["nx", "ny", "nz"] {
+                writeln!(writer, "property float {axis}")?;
+            }
+        }
+        let channels = match &self.colors {
+            Some((_, true)) => &["red", "green", "blue", "alpha"][..],
+            Some((_, false)) => &["red", "green", "blue"][..],
+            None => &[][..],
+        };
+        for channel in channels {
+            writeln!(writer, "property uchar {channel}")?;
+        }
+        if !self.faces.is_empty() {
+            writeln!(writer, "element face {}", self.faces.len())?;
+            writeln!(writer, "property list uchar int vertex_indices")?;
+        }
+        writeln!(writer, "end_header")?;
+
+        for (vertex, position) in self.positions.iter().enumerate() {
+            let normal = self.normals.as_ref().map(|normals| normals[vertex]);
+            let color = self.colors.as_ref().map(|(colors, _)| colors[vertex]);
+            let color = color
+                .as_ref()
+                .map_or(&[][..], |color| &color[..channels.len()]);
+            match format {
+                PlyFormat::Ascii => {
+                    let floats = position.iter().chain(normal.iter().flatten());
+                    let line = floats
+                        .map(f32::to_string)
+                        .chain(color.iter().map(u8::to_string))
+                        .collect::<Vec<_>>();
+                    writeln!(writer, "{}", line.join(" "))?;
+                }
+                PlyFormat::BinaryLittleEndian => {
+                    for value in position.iter().chain(normal.iter().flatten()) {
+                        writer.write_all(&value.to_le_bytes())?;
+                    }
+                    writer.write_all(color)?;
+                }
+            }
+        }
+        for &[a, b, c] in &self.faces {
+            match format {
+                PlyFormat::Ascii => writeln!(writer, "3 {a} {b} {c}")?,
+                PlyFormat::BinaryLittleEndian => {
+                    writer.write_all(&[3])?;
+                    for vertex in [a, b, c] {
+                        writer.write_all(&(vertex as i32).to_le_bytes())?;
+                    }
+                }
+            }
+        }
+        writer.flush()
+    }
+}
+
+/// Reads a color attribute with three or four components as 8-bit RGBA,
+/// with an opaque alpha for three components.
+///
+/// `UInt8` colors are copied as stored, other data types are converted to
+/// floats like [`MeshDecodeResult::positions`] and scaled from `0.0..=1.0`.
+fn colors_in(attribute: &MeshAttribute, data: &[u8]) -> Option<Vec<[u8; 4]>> {
+    let to_u8 = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    match (attribute.data_type(), attribute.dim()) {
+        (AttributeDataType::UInt8, dim @ (3 | 4)) => {
+            let bytes = attribute.bytes_in(data)?;
+            Some(
+                bytes
+                    .chunks_exact(dim as usize)
+                    .map(|color| [color[0], color[1], color[2], *color.get(3).unwrap_or(&255)])
+                    .collect(),
+            )
+        }
+        (_, 3) => Some(
+            converted_vectors_in::<[f32; 3], 3>(attribute, data)?
+                .map(|[r, g, b]| [to_u8(r), to_u8(g), to_u8(b), 255])
+                .collect(),
+        ),
+        (_, 4) => Some(
+            converted_vectors_in::<[f32; 4], 4>(attribute, data)?
+                .map(|color| color.map(to_u8))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// Writes a mesh as a PLY file, for inspecting decoded assets in tools like
+/// MeshLab or CloudCompare.
+///
+/// Vertices get `x`, `y` and `z` float properties, `nx`, `ny` and `nz` if the
+/// mesh has normals, and `red`, `green`, `blue` and, for four component
+/// colors, `alpha` uchar properties from the first color attribute. Values
+/// are converted like [`MeshDecodeResult::positions`]; `UInt8` colors are
+/// written as stored and other colors are scaled from `0.0..=1.0`. Triangles
+/// are written as `vertex_indices` faces, skipping those that reference
+/// missing vertices.
+///
+/// The output is buffered, so `writer` can be a plain `File`. Fails with
+/// [`io::ErrorKind::InvalidInput`] if the mesh has no three component
+/// positions.
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::{write_ply, PlyFormat};
+///
+/// write_ply(&result, PlyFormat::BinaryLittleEndian, std::fs::File::create("mesh.ply")?)?;
+/// ```
+pub fn write_ply(
+    result: &MeshDecodeResult,
+    format: PlyFormat,
+    writer: impl Write,
+) -> io::Result<()> {
+    let mut elements = PlyElements::new(&result.config.attributes(), &result.data)?;
+    let vertex_count = elements.positions.len();
+    elements.faces = result
+        .triangles()
+        .filter(|triangle| {
+            triangle
+                .iter()
+                .all(|&vertex| (vertex as usize) < vertex_count)
+        })
+        .collect();
+    elements.write(format, writer)
+}
+
+/// Writes a point cloud as a PLY file with a vertex per point.
+///
+/// Points get the same properties as the vertices written by [`write_ply`].
+pub fn write_point_cloud_ply(
+    result: &PointCloudDecodeResult,
+    format: PlyFormat,
+    writer: impl Write,
+) -> io::Result<()> {
+    PlyElements::new(&result.config.attributes(), &result.data)?.write(format, writer)
+}
+
+/// Writes a mesh as a binary STL file, for 3D printing and CAD tools.
+///
+/// Every triangle is written with its face normal, computed from the
+/// winding of its positions since STL has no per-vertex normals, or zero for
+/// degenerate triangles. Positions are converted like
+/// [`MeshDecodeResult::positions`]. The 80 byte header names this crate and
+/// never starts with `solid`, so readers do not mistake the file for ASCII
+/// STL. Triangles referencing missing vertices are skipped.
+///
+/// The output is buffered, so `writer` can be a plain `File`. Fails with
+/// [`io::ErrorKind::InvalidInput`] if the mesh has no three component
+/// positions.
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::write_stl_binary;
+///
+/// write_stl_binary(&result, std::fs::File::create("mesh.stl")?)?;
+/// ```
+pub fn write_stl_binary(result: &MeshDecodeResult, writer: impl Write) -> io::Result<()> {
+    let positions: Vec<[f32; 3]> = result
+        .positions()
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "mesh has no three component positions",
+            )
+        })?
+        .collect();
+    let triangles: Vec<[[f32; 3]; 3]> = result
+        .triangles()
+        .filter_map(|triangle| {
+            let [a, b, c] = triangle.map(|vertex| positions.get(vertex as usize).copied());
+            Some([a?, b?, c?])
+        })
+        .collect();
+    let count = u32::try_from(triangles.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "too many triangles for STL"))?;
+
+    let mut writer = BufWriter::new(writer);
+    let mut header = [b' '; 80];
+    let name = format!("draco_decoder {}", env!("CARGO_PKG_VERSION"));
+    let name = &name.as_bytes()[..name.len().min(header.len())];
+    header[..name.len()].copy_from_slice(name);
+    writer.write_all(&header)?;
+    writer.write_all(&count.to_le_bytes())?;
+    for triangle in &triangles {
+        for value in face_normal(triangle)
+            .iter()
+            .chain(triangle.iter().flatten())
+        {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        // Attribute byte count, unused
+        writer.write_all(&[0, 0])?;
+    }
+    writer.flush()
+}
+
+/// Unit normal of a counter-clockwise triangle, zero if it is degenerate.
+fn face_normal([a, b, c]: &[[f32; 3]; 3]) -> [f32; 3] {
+    let ab = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+    let ac = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+    let normal = [
+        ab[1] * ac[2] - ab[2] * ac[1],
+        ab[2] * ac[0] - ab[0] * ac[2],
+        ab[0] * ac[1] - ab[1] * ac[0],
+    ];
+    let length = normal.iter().map(|c| c * c).sum::<f32>().sqrt();
+    if length > 0.0 && length.is_finite() {
+        normal.map(|c| c / length)
+    } else {
+        [0.0; 3]
+    }
+}
+
+/// glTF `componentType` of 32-bit floats.
+const GLTF_FLOAT: u32 = 5126;
+/// glTF `target` of vertex attribute buffer views.
+const GLTF_ARRAY_BUFFER: u32 = 34962;
+/// glTF `target` of index buffer views.
+const GLTF_ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+/// Accessors, buffer views and the binary chunk of a GLB file being built.
+#[derive(Default)]
+struct GlbBuilder {
+    accessors: Vec<String>,
+    buffer_views: Vec<String>,
+    binary: Vec<u8>,
+}
+
+impl GlbBuilder {
+    /// Appends little-endian `bytes` as a buffer view with an accessor of
+    /// `count` elements of the given glTF type, returning the accessor index.
+    ///
+    /// Vertex buffer views record the stride of their elements, which must
+    /// be a multiple of four bytes.
+    fn push(
+        &mut self,
+        bytes: &[u8],
+        target: u32,
+        component_type: u32,
+        normalized: bool,
+        (count, element_type): (usize, &str),
+        bounds: Option<([f32; 3], [f32; 3])>,
+    ) -> usize {
+        self.binary.resize(self.binary.len().next_multiple_of(4), 0);
+        let mut view = format!(
+            "{{\"buffer\":0,\"byteOffset\":{},\"byteLength\":{},\"target\":{target}",
+            self.binary.len(),
+            bytes.len(),
+        );
+        if target == GLTF_ARRAY_BUFFER && count > 0 {
+            view.push_str(&format!(",\"byteStride\":{}", bytes.len() / count));
+        }
+        view.push('}');
+        self.buffer_views.push(view);
+        self.binary.extend_from_slice(bytes);
+
+        let mut accessor = format!(
+            "{{\"bufferView\":{},\"componentType\":{component_type},\"count\":{count},\
+             \"type\":\"{element_type}\"",
+            self.buffer_views.len() - 1,
+        );
+        if normalized {
+            accessor.push_str(",\"normalized\":true");
+        }
+        if let Some((min, max)) = bounds {
+            let list = |values: [f32; 3]| values.map(|v| v.to_string()).join(",");
+            accessor.push_str(&format!(",\"min\":[{}],\"max\":[{}]", list(min), list(max)));
+        }
+        accessor.push('}');
+        self.accessors.push(accessor);
+        self.accessors.len() - 1
+    }
+
+    /// Appends float vectors as a vertex attribute accessor.
+    fn push_floats<const N: usize>(
+        &mut self,
+        values: &[[f32; N]],
+        bounds: Option<([f32; 3], [f32; 3])>,
+    ) -> usize {
+        let bytes: Vec<u8> = values
+            .iter()
+            .flatten()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        let element_type = ["SCALAR", "VEC2", "VEC3", "VEC4"][N - 1];
+        self.push(
+            &bytes,
+            GLTF_ARRAY_BUFFER,
+            GLTF_FLOAT,
+            false,
+            (values.len(), element_type),
+            bounds,
+        )
+    }
+
+    /// Adds the primitive of a mesh, returning its JSON, or `None` for a
+    /// mesh without vertices, which glTF cannot describe.
+    fn push_mesh(&mut self, result: &MeshDecodeResult) -> io::Result<Option<String>> {
+        if result.config.vertex_count() == 0 {
+            return Ok(None);
+        }
+        let positions: Vec<[f32; 3]> = result
+            .positions()
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "mesh has no three component positions",
+                )
+            })?
+            .collect();
+        if positions.is_empty() {
+            return Ok(None);
+        }
+        // Float positions are read as stored, so their extrema are those of
+        // the attribute, which may have been recorded while decoding
+        let position = result
+            .config
+            .find_attribute(AttributeSemantic::Position)
+            .and_then(|index| result.config.get_attribute(index));
+        let float_bounds = position
+            .filter(|attribute| attribute.quantization().is_none())
+            .and_then(|attribute| attribute.compute_min_max(result))
+            .and_then(|bounds| match bounds {
+                (AttributeValues::Float32(min), AttributeValues::Float32(max)) => Some((
+                    <[f32; 3]>::try_from(min.get(..3)?).ok()?,
+                    <[f32; 3]>::try_from(max.get(..3)?).ok()?,
+                )),
+                _ => None,
+            });
+        let finite = |values: [f32; 3]| values.map(|v| if v.is_finite() { v } else { 0.0 });
+        let bounds = float_bounds
+            .or_else(|| result.compute_aabb())
+            .map(|(min, max)| (finite(min), finite(max)));
+
+        let mut attributes = vec![format!(
+            "\"POSITION\":{}",
+            self.push_floats(&positions, bounds)
+        )];
+        let (mut has_normals, mut has_tangents, mut uv_sets, mut color_sets) = (false, false, 0, 0);
+        for (index, attribute) in result.config.attributes().into_iter().enumerate() {
+            let data = &result.data;
+            let (name, accessor) = match attribute.semantic() {
+                AttributeSemantic::Position => continue,
+                AttributeSemantic::Generic
+                    if !has_tangents
+                        && result.config.attribute_name(index) == Some("TANGENT")
+                        && attribute.dim() == 4 =>
+                {
+                    let Some(tangents) = converted_vectors_in::<[f32; 4], 4>(&attribute, data)
+                    else {
+                        continue;
+                    };
+                    has_tangents = true;
+                    let tangents: Vec<[f32; 4]> = tangents.collect();
+                    ("TANGENT".to_string(), self.push_floats(&tangents, None))
+                }
+                AttributeSemantic::Normal if !has_normals => {
+                    let Some(normals) = converted_vectors_in::<[f32; 3], 3>(&attribute, data)
+                    else {
+                        continue;
+                    };
+                    has_normals = true;
+                    let normals: Vec<[f32; 3]> = normals.collect();
+                    ("NORMAL".to_string(), self.push_floats(&normals, None))
+                }
+                AttributeSemantic::TexCoord => {
+                    let Some(uvs) = converted_vectors_in::<[f32; 2], 2>(&attribute, data) else {
+                        continue;
+                    };
+                    let uvs: Vec<[f32; 2]> = uvs.collect();
+                    uv_sets += 1;
+                    (
+                        format!("TEXCOORD_{}", uv_sets - 1),
+                        self.push_floats(&uvs, None),
+                    )
+                }
+                AttributeSemantic::Color => {
+                    let accessor = match attribute.dim() {
+                        3 => converted_vectors_in::<[f32; 3], 3>(&attribute, data)
+                            .map(|colors| self.push_floats(&colors.collect::<Vec<_>>(), None)),
+                        4 => converted_vectors_in::<[f32; 4], 4>(&attribute, data)
+                            .map(|colors| self.push_floats(&colors.collect::<Vec<_>>(), None)),
+                        _ => None,
+                    };
+                    let Some(accessor) = accessor else {
+                        continue;
+                    };
+                    color_sets += 1;
+                    (format!("COLOR_{}", color_sets - 1), accessor)
+                }
+                AttributeSemantic::Normal | AttributeSemantic::Generic => {
+                    let Some(accessor) = attribute
+                        .bytes_in(data)
+                        .and_then(|bytes| self.push_raw(&attribute, bytes))
+                    else {
+                        continue;
+                    };
+                    (format!("_ATTRIBUTE_{}", attribute.unique_id()), accessor)
+                }
+            };
+            attributes.push(format!("\"{name}\":{accessor}"));
+        }
+
+        let mut primitive = format!("{{\"attributes\":{{{}}}", attributes.join(","));
+        let triangles: Vec<[u32; 3]> = result
+            .triangles()
+            .filter(|triangle| triangle.iter().all(|&v| (v as usize) < positions.len()))
+            .collect();
+        if !triangles.is_empty() {
+            // Unsigned short indices where they fit, unsigned int otherwise.
+            // glTF reserves the largest value of either for primitive restart.
+            let (bytes, component_type): (Vec<u8>, u32) = if positions.len() < 1 << 16 {
+                let bytes = triangles.iter().flatten();
+                (
+                    bytes.flat_map(|&v| (v as u16).to_le_bytes()).collect(),
+                    5123,
+                )
+            } else {
+                let bytes = triangles.iter().flatten();
+                (bytes.flat_map(|&v| v.to_le_bytes()).collect(), 5125)
+            };
+            let accessor = self.push(
+                &bytes,
+                GLTF_ELEMENT_ARRAY_BUFFER,
+                component_type,
+                false,
+                (triangles.len() * 3, "SCALAR"),
+                None,
+            );
+            primitive.push_str(&format!(",\"indices\":{accessor},\"mode\":4}}"));
+        } else {
+            primitive.push_str(",\"mode\":0}");
+        }
+        Ok(Some(format!("{{\"primitives\":[{primitive}]}}")))
+    }
+
+    /// Appends an attribute as stored, byte swapped to little endian, if glTF
+    /// allows its data type and component count for vertex attributes.
+    /// Elements are padded with zeros to a multiple of four bytes.
+    fn push_raw(&mut self, attribute: &MeshAttribute, bytes: &[u8]) -> Option<usize> {
+        let component_type = match attribute.data_type() {
+            AttributeDataType::Int8 => 5120,
+            AttributeDataType::UInt8 => 5121,
+            AttributeDataType::Int16 => 5122,
+            AttributeDataType::UInt16 => 5123,
+            AttributeDataType::Float32 => GLTF_FLOAT,
+            AttributeDataType::Int32 | AttributeDataType::UInt32 => return None,
+        };
+        let element_type =
+            ["SCALAR", "VEC2", "VEC3", "VEC4"].get(attribute.dim().checked_sub(1)? as usize)?;
+        let element_size = attribute.element_size();
+        let count = bytes.len() / element_size;
+        let stride = element_size.next_multiple_of(4);
+        let mut padded = Vec::with_capacity(count * stride);
+        for element in bytes.chunks_exact(element_size) {
+            let start = padded.len();
+            padded.extend_from_slice(element);
+            if cfg!(target_endian = "big") {
+                for component in
+                    padded[start..].chunks_exact_mut(attribute.data_type().size_in_bytes())
+                {
+                    component.reverse();
+                }
+            }
+            padded.resize(start + stride, 0);
+        }
+        Some(self.push(
+            &padded,
+            GLTF_ARRAY_BUFFER,
+            component_type,
+            attribute.normalized() && attribute.data_type() != AttributeDataType::Float32,
+            (count, element_type),
+            None,
+        ))
+    }
+}
+
+/// Writes meshes as a binary glTF (GLB) file without compression, a mesh
+/// and node per decoded mesh in a single scene.
+///
+/// Positions, normals, texture coordinates and colors are written as float
+/// accessors, converted like [`MeshDecodeResult::positions`], with the
+/// `min` and `max` glTF requires for `POSITION`. The tangents of
+/// [`generate_tangents`](crate::postprocess::generate_tangents) are written
+/// as `TANGENT`. Other attributes are written as stored under
+/// `_ATTRIBUTE_<unique id>`, unless glTF does not allow their data type or
+/// component count for vertex attributes.
+/// Triangles referencing missing vertices are skipped, and meshes without
+/// triangles become point primitives. Meshes without vertices, which glTF
+/// cannot describe, are left out with their nodes. All data goes into the
+/// binary chunk, each accessor with its own buffer view.
+///
+/// The output is buffered, so `writer` can be a plain `File`. Fails with
+/// [`io::ErrorKind::InvalidInput`] if a mesh has no three component
+/// positions, if no mesh has vertices, or if the file would exceed the
+/// 4 GiB GLB limit.
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::write_glb;
+///
+/// write_glb(&[result], std::fs::File::create("mesh.glb")?)?;
+/// ```
+pub fn write_glb(meshes: &[MeshDecodeResult], writer: impl Write) -> io::Result<()> {
+    let mut builder = GlbBuilder::default();
+    let meshes: Vec<String> = meshes
+        .iter()
+        .filter_map(|mesh| builder.push_mesh(mesh).transpose())
+        .collect::<io::Result<_>>()?;
+    if meshes.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "no mesh has vertices",
+        ));
+    }
+
+    let nodes: Vec<String> = (0..meshes.len())
+        .map(|mesh| format!("{{\"mesh\":{mesh}}}"))
+        .collect();
+    let node_indices: Vec<String> = (0..meshes.len()).map(|node| node.to_string()).collect();
+    let mut json = format!(
+        "{{\"asset\":{{\"version\":\"2.0\",\"generator\":\"draco_decoder {}\"}},\
+         \"scene\":0,\"scenes\":[{{\"nodes\":[{}]}}],\"nodes\":[{}],\"meshes\":[{}]",
+        env!("CARGO_PKG_VERSION"),
+        node_indices.join(","),
+        nodes.join(","),
+        meshes.join(","),
+    );
+    // Every written mesh has positions, so the binary chunk is never empty
+    json.push_str(&format!(
+        ",\"accessors\":[{}],\"bufferViews\":[{}],\"buffers\":[{{\"byteLength\":{}}}]",
+        builder.accessors.join(","),
+        builder.buffer_views.join(","),
+        builder.binary.len(),
+    ));
+    json.push('}');
+
+    // Chunks are padded to four bytes, JSON with spaces and binary with zeros
+    let mut json = json.into_bytes();
+    json.resize(json.len().next_multiple_of(4), b' ');
+    let mut binary = builder.binary;
+    binary.resize(binary.len().next_multiple_of(4), 0);
+    let length = u32::try_from(12 + 8 + json.len() + 8 + binary.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "meshes too large for GLB"))?;
+
+    let mut writer = BufWriter::new(writer);
+    writer.write_all(b"glTF")?;
+    writer.write_all(&2u32.to_le_bytes())?;
+    writer.write_all(&length.to_le_bytes())?;
+    writer.write_all(&(json.len() as u32).to_le_bytes())?;
+    writer.write_all(b"JSON")?;
+    writer.write_all(&json)?;
+    writer.write_all(&(binary.len() as u32).to_le_bytes())?;
+    writer.write_all(b"BIN\0")?;
+    writer.write_all(&binary)?;
+    writer.flush()
+}
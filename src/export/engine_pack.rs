@@ -0,0 +1,429 @@
+//! Packing of decoded vertices into the vertex formats of legacy engines.
+//!
+//! An [`EngineVertexFormat`] lists the elements of a vertex, each written by
+//! a [`VertexPacker`] such as the built-in [`PackedFormat`]s.
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::vector::converted_vectors_in;
+use crate::{AttributeDataType, AttributeSemantic, MeshAttribute, MeshDecodeResult};
+
+/// Byte order of the values written by a [`VertexPacker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ByteOrder {
+    /// Least significant byte first, as on x86 and ARM.
+    #[default]
+    LittleEndian,
+    /// Most significant byte first, as on PowerPC based consoles.
+    BigEndian,
+}
+
+impl ByteOrder {
+    /// Writes a 16-bit word to the start of `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out` is shorter than 2 bytes.
+    pub fn write_u16(self, value: u16, out: &mut [u8]) {
+        out[..2].copy_from_slice(&match self {
+            ByteOrder::LittleEndian => value.to_le_bytes(),
+            ByteOrder::BigEndian => value.to_be_bytes(),
+        });
+    }
+
+    /// Writes a 32-bit word to the start of `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out` is shorter than 4 bytes.
+    pub fn write_u32(self, value: u32, out: &mut [u8]) {
+        out[..4].copy_from_slice(&match self {
+            ByteOrder::LittleEndian => value.to_le_bytes(),
+            ByteOrder::BigEndian => value.to_be_bytes(),
+        });
+    }
+}
+
+/// Writes one vertex element of an [`EngineVertexFormat`].
+///
+/// Implement this for encodings the built-in [`PackedFormat`]s do not cover.
+pub trait VertexPacker: Send + Sync {
+    /// Returns the number of bytes written per vertex.
+    fn size(&self) -> usize;
+
+    /// Writes the components of one vertex into `out`, which is exactly
+    /// [`size`](Self::size) bytes long.
+    ///
+    /// `value` holds the components of the source attribute converted to
+    /// floats like [`MeshDecodeResult::positions`], so it may be shorter or
+    /// longer than the element. Multi-byte words are written in `order`.
+    fn pack(&self, value: &[f32], order: ByteOrder, out: &mut [u8]);
+}
+
+/// Vertex encodings commonly found in legacy engine formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackedFormat {
+    /// The given number of 32-bit floats.
+    ///
+    /// Sizes that overflow `usize` saturate, which makes
+    /// [`EngineVertexFormat::pack`] fail with [`EnginePackError::TooLarge`].
+    Float32(usize),
+    /// The given number of signed 16-bit integers mapping `-1.0..=1.0`, such
+    /// as D3D `SHORT2N` texture coordinates.
+    Snorm16(usize),
+    /// The given number of unsigned bytes mapping `0.0..=1.0`.
+    Unorm8(usize),
+    /// A D3D9 `D3DCOLOR`: one 32-bit word holding 8-bit alpha, red, green
+    /// and blue from the most significant byte down. Three component colors
+    /// are opaque.
+    DwordColor,
+    /// Three signed 10-bit and one signed 2-bit component in one 32-bit word,
+    /// `x` in the least significant bits, such as D3D `DEC3N` or OpenGL
+    /// `INT_2_10_10_10_REV` normals. A missing fourth component is written
+    /// as 0.
+    Snorm1010102,
+}
+
+/// Missing components are written as 0.
+fn component(value: &[f32], index: usize) -> f32 {
+    value.get(index).copied().unwrap_or(0.0)
+}
+
+/// Maps `-1.0..=1.0` to a signed integer with the largest value `max`.
+fn snorm(value: f32, max: f32) -> i32 {
+    (value.clamp(-1.0, 1.0) * max).round() as i32
+}
+
+/// Maps `0.0..=1.0` to `0..=255`.
+fn unorm8(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+impl VertexPacker for PackedFormat {
+    fn size(&self) -> usize {
+        match *self {
+            PackedFormat::Float32(components) => components.saturating_mul(4),
+            PackedFormat::Snorm16(components) => components.saturating_mul(2),
+            PackedFormat::Unorm8(components) => components,
+            PackedFormat::DwordColor | PackedFormat::Snorm1010102 => 4,
+        }
+    }
+
+    fn pack(&self, value: &[f32], order: ByteOrder, out: &mut [u8]) {
+        match *self {
+            PackedFormat::Float32(_) => {
+                for (i, word) in out.chunks_exact_mut(4).enumerate() {
+                    order.write_u32(component(value, i).to_bits(), word);
+                }
+            }
+            PackedFormat::Snorm16(_) => {
+                for (i, word) in out.chunks_exact_mut(2).enumerate() {
+                    order.write_u16(snorm(component(value, i), 32767.0) as i16 as u16, word);
+                }
+            }
+            PackedFormat::Unorm8(_) => {
+                for (i, byte) in out.iter_mut().enumerate() {
+                    *byte = unorm8(component(value, i));
+                }
+            }
+            PackedFormat::DwordColor => {
+                let alpha = value.get(3).copied().unwrap_or(1.0);
+                let [r, g, b, a] = [
+                    component(value, 0),
+                    component(value, 1),
+                    component(value, 2),
+                    alpha,
+                ]
+                .map(|value| unorm8(value) as u32);
+                order.write_u32(a << 24 | r << 16 | g << 8 | b, out);
+            }
+            PackedFormat::Snorm1010102 => {
+                let bits = |value: f32, max: f32, width: u32| {
+                    snorm(value, max) as u32 & ((1 << width) - 1)
+                };
+                let word = bits(component(value, 0), 511.0, 10)
+                    | bits(component(value, 1), 511.0, 10) << 10
+                    | bits(component(value, 2), 511.0, 10) << 20
+                    | bits(component(value, 3), 1.0, 2) << 30;
+                order.write_u32(word, out);
+            }
+        }
+    }
+}
+
+/// One element of an [`EngineVertexFormat`].
+#[derive(Clone)]
+struct Element {
+    semantic: AttributeSemantic,
+    set: usize,
+    packer: Arc<dyn VertexPacker>,
+}
+
+/// Declarative description of an interleaved vertex format of a target
+/// engine.
+///
+/// Elements are laid out in the order they are added, each taking the
+/// [`VertexPacker::size`] of its packer, and are read from the attribute
+/// with the given semantic and set, set 0 being the first attribute with
+/// that semantic. [`EngineVertexFormat::pack`] then writes a decoded mesh
+/// straight into that format, without an intermediate float buffer.
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::{AttributeSemantic, EngineVertexFormat, PackedFormat};
+///
+/// // Position, DEC3N normal, D3DCOLOR and SHORT2N texture coordinates
+/// let format = EngineVertexFormat::new()
+///     .with_element(AttributeSemantic::Position, 0, PackedFormat::Float32(3))
+///     .with_element(AttributeSemantic::Normal, 0, PackedFormat::Snorm1010102)
+///     .with_element(AttributeSemantic::Color, 0, PackedFormat::DwordColor)
+///     .with_element(AttributeSemantic::TexCoord, 0, PackedFormat::Snorm16(2));
+/// let vertices = format.pack(&result)?;
+/// assert_eq!(vertices.len(), format.stride() * result.config.vertex_count() as usize);
+/// ```
+#[derive(Clone, Default)]
+pub struct EngineVertexFormat {
+    elements: Vec<Element>,
+    stride: Option<usize>,
+    byte_order: ByteOrder,
+}
+
+impl fmt::Debug for EngineVertexFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EngineVertexFormat")
+            .field(
+                "elements",
+                &self
+                    .elements
+                    .iter()
+                    .map(|element| (element.semantic, element.set, element.packer.size()))
+                    .collect::<Vec<_>>(),
+            )
+            .field("stride", &self.stride())
+            .field("byte_order", &self.byte_order)
+            .finish()
+    }
+}
+
+impl EngineVertexFormat {
+    /// Creates a format without elements.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends an element written with a built-in encoding.
+    pub fn with_element(
+        self,
+        semantic: AttributeSemantic,
+        set: usize,
+        format: PackedFormat,
+    ) -> Self {
+        self.with_packer(semantic, set, Arc::new(format))
+    }
+
+    /// Appends an element written by a custom packer.
+    pub fn with_packer(
+        mut self,
+        semantic: AttributeSemantic,
+        set: usize,
+        packer: Arc<dyn VertexPacker>,
+    ) -> Self {
+        self.elements.push(Element {
+            semantic,
+            set,
+            packer,
+        });
+        self
+    }
+
+    /// Sets the distance between vertices in bytes, for formats that pad
+    /// vertices to a fixed size. The padding is zero filled.
+    ///
+    /// Defaults to the sum of the element sizes.
+    pub fn with_stride(mut self, stride: usize) -> Self {
+        self.stride = Some(stride);
+        self
+    }
+
+    /// Sets the byte order multi-byte words are written in. Defaults to
+    /// little endian.
+    pub fn with_byte_order(mut self, byte_order: ByteOrder) -> Self {
+        self.byte_order = byte_order;
+        self
+    }
+
+    /// Returns the distance between vertices in bytes.
+    pub fn stride(&self) -> usize {
+        self.stride.unwrap_or_else(|| self.packed_size())
+    }
+
+    /// Returns the byte offset of every element within a vertex.
+    pub fn offsets(&self) -> Vec<usize> {
+        self.elements
+            .iter()
+            .scan(0usize, |offset, element| {
+                let start = *offset;
+                *offset = start.saturating_add(element.packer.size());
+                Some(start)
+            })
+            .collect()
+    }
+
+    /// Returns the size of the elements without padding, saturating on
+    /// overflow.
+    fn packed_size(&self) -> usize {
+        self.elements.iter().fold(0, |size, element| {
+            size.saturating_add(element.packer.size())
+        })
+    }
+
+    /// Writes the vertices of a decoded mesh in this format.
+    ///
+    /// Returns `stride() * vertex_count` bytes. Fails if an element's
+    /// attribute is missing or cannot be read as floats, if the stride is
+    /// smaller than the elements or if the output size overflows.
+    pub fn pack(&self, result: &MeshDecodeResult) -> Result<Vec<u8>, EnginePackError> {
+        let stride = self.stride();
+        let required = self.packed_size();
+        if stride < required {
+            return Err(EnginePackError::StrideTooSmall { stride, required });
+        }
+
+        let vertex_count = result.config.vertex_count() as usize;
+        let len = stride
+            .checked_mul(vertex_count)
+            .filter(|&len| len <= isize::MAX as usize)
+            .ok_or(EnginePackError::TooLarge {
+                stride,
+                vertex_count,
+            })?;
+        let attributes = result.config.attributes();
+        let mut sources = Vec::with_capacity(self.elements.len());
+        for element in &self.elements {
+            let missing = EnginePackError::MissingAttribute {
+                semantic: element.semantic,
+                set: element.set,
+            };
+            let attribute = attributes
+                .iter()
+                .filter(|attribute| attribute.semantic() == element.semantic)
+                .nth(element.set)
+                .ok_or(missing.clone())?;
+            let (dim, values) =
+                float_components_in(attribute, &result.data).ok_or(missing.clone())?;
+            if values.len() < vertex_count * dim {
+                return Err(missing);
+            }
+            sources.push((dim, values));
+        }
+
+        let mut out = vec![0u8; len];
+        for (vertex, bytes) in out.chunks_exact_mut(stride.max(1)).enumerate() {
+            let mut offset = 0;
+            for (element, (dim, values)) in self.elements.iter().zip(&sources) {
+                let size = element.packer.size();
+                element.packer.pack(
+                    &values[vertex * dim..(vertex + 1) * dim],
+                    self.byte_order,
+                    &mut bytes[offset..offset + size],
+                );
+                offset += size;
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// Reads every component of an attribute with up to four components as
+/// floats, converted like [`MeshDecodeResult::positions`], returning the
+/// number of components per vertex.
+///
+/// `UInt8` colors that are not flagged normalized are scaled from `0..=255`,
+/// as by [`write_ply`](crate::write_ply).
+fn float_components_in(attribute: &MeshAttribute, data: &[u8]) -> Option<(usize, Vec<f32>)> {
+    let dim = if attribute.octahedron().is_some() {
+        3
+    } else {
+        (attribute.dim() as usize).saturating_sub(usize::from(attribute.padded()))
+    };
+    let mut values: Vec<f32> = match dim {
+        1 => converted_vectors_in::<[f32; 1], 1>(attribute, data)?
+            .flatten()
+            .collect(),
+        2 => converted_vectors_in::<[f32; 2], 2>(attribute, data)?
+            .flatten()
+            .collect(),
+        3 => converted_vectors_in::<[f32; 3], 3>(attribute, data)?
+            .flatten()
+            .collect(),
+        4 => converted_vectors_in::<[f32; 4], 4>(attribute, data)?
+            .flatten()
+            .collect(),
+        _ => return None,
+    };
+    if attribute.semantic() == AttributeSemantic::Color
+        && attribute.data_type() == AttributeDataType::UInt8
+        && !attribute.normalized()
+    {
+        values.iter_mut().for_each(|value| *value /= 255.0);
+    }
+    Some((dim, values))
+}
+
+/// Errors reported by [`EngineVertexFormat::pack`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnginePackError {
+    /// The mesh has no readable attribute for an element.
+    MissingAttribute {
+        /// Semantic of the element.
+        semantic: AttributeSemantic,
+        /// Set of the element.
+        set: usize,
+    },
+    /// The stride is smaller than the elements of a vertex.
+    StrideTooSmall {
+        /// The requested stride.
+        stride: usize,
+        /// The size of the elements.
+        required: usize,
+    },
+    /// The packed vertices do not fit into memory.
+    TooLarge {
+        /// The stride of the format.
+        stride: usize,
+        /// The number of vertices.
+        vertex_count: usize,
+    },
+}
+
+impl fmt::Display for EnginePackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnginePackError::MissingAttribute { semantic, set } => {
+                write!(
+                    f,
+                    "mesh has no readable {semantic:?} attribute in set {set}"
+                )
+            }
+            EnginePackError::StrideTooSmall { stride, required } => {
+                write!(
+                    f,
+                    "stride {stride} is smaller than the {required} byte vertex"
+                )
+            }
+            EnginePackError::TooLarge {
+                stride,
+                vertex_count,
+            } => {
+                write!(
+                    f,
+                    "{vertex_count} vertices of {stride} bytes do not fit into memory"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for EnginePackError {}
@@ -1,11 +1,34 @@
+use std::cell::{Cell, RefCell};
+
+use cxx::UniquePtr;
+
 #[cxx::bridge]
 mod cpp {
     struct MeshAttribute {
         dim: u32,
         data_type: u32,
+        attribute_type: i32,
         offset: u32,
         length: u32,
         unique_id: u32,
+        normalized: bool,
+        /// 0 unless the attribute holds quantized values
+        quantization_bits: u32,
+        quantization_range: f32,
+        quantization_min: Vec<f32>,
+        /// 0 unless the attribute holds octahedron encoded normals
+        octahedron_bits: u32,
+        /// The last component is zero padding
+        padded: bool,
+    }
+
+    struct AttributeWarning {
+        unique_id: u32,
+        attribute_type: i32,
+        /// Raw draco::DataType of the attribute in the bitstream
+        data_type: u32,
+        /// 0: unsupported and skipped, 1: converted, 2: filtered out
+        reason: u32,
     }
 
     struct MeshConfig {
@@ -14,6 +37,95 @@ mod cpp {
         index_length: u32,
         buffer_size: usize,
         attributes: Vec<MeshAttribute>,
+        warnings: Vec<AttributeWarning>,
+    }
+
+    /// Data type requested for the attribute with a unique id
+    struct AttributeOutputType {
+        unique_id: u32,
+        data_type: u32,
+    }
+
+    /// Layout of a decoded mesh buffer
+    struct LayoutOptions {
+        positions_only: bool,
+        /// Attribute offsets are rounded up to a multiple of this
+        offset_alignment: u32,
+        /// 0: by vertex count, 1: prefer 16-bit, 2: 32-bit, 3: by index count
+        index_format: u32,
+        /// Requested data type per semantic, -1 keeps the decoded type
+        output_types: Vec<i32>,
+        /// Requested data types of single attributes, taking precedence over
+        /// `output_types`
+        attribute_output_types: Vec<AttributeOutputType>,
+        /// Three component attributes are padded to four
+        pad_vec3: bool,
+        /// Source axis of each written position and normal component, 1-based
+        /// and negative to negate it, or empty to keep the axes
+        axis_map: Vec<i32>,
+        /// The last two indices of every triangle are swapped
+        flip_winding: bool,
+        /// No indices are written and the index count is reported as zero,
+        /// for previews that only read attributes
+        skip_indices: bool,
+        /// The component-wise extrema of each written attribute are reported
+        min_max: bool,
+    }
+
+    struct PointCloudConfig {
+        point_count: u32,
+        buffer_size: usize,
+        attributes: Vec<MeshAttribute>,
+        warnings: Vec<AttributeWarning>,
+    }
+
+    struct EncodeOptions {
+        point_cloud: bool,
+        /// Draco encoding method, or -1 to let the encoder choose
+        encoding_method: i32,
+        encoding_speed: i32,
+        decoding_speed: i32,
+    }
+
+    /// An attribute of an analyzed bitstream
+    struct StreamAttribute {
+        unique_id: u32,
+        attribute_type: i32,
+        num_components: u32,
+        /// Index of the attributes decoder the attribute is encoded with
+        decoder: u32,
+        quantization_bits: u32,
+        octahedron_bits: u32,
+    }
+
+    /// Byte offsets of the sections of a bitstream
+    struct StreamLayout {
+        num_points: u32,
+        num_faces: u32,
+        geometry_start: usize,
+        geometry_end: usize,
+        attributes_start: usize,
+        /// End of each attributes decoder's data
+        decoder_ends: Vec<usize>,
+        attributes: Vec<StreamAttribute>,
+    }
+
+    extern "Rust" {
+        type DecodeControl;
+
+        fn is_cancelled(self: &DecodeControl) -> bool;
+
+        fn counts_fit(self: &DecodeControl, num_points: u32, num_faces: u32) -> bool;
+
+        fn connectivity_decoded(self: &DecodeControl);
+
+        fn attributes_decoded(self: &DecodeControl, index: u32, count: u32);
+
+        fn indices_written(self: &DecodeControl);
+
+        fn attribute_min_max(self: &DecodeControl, index: u32, min: &[u8], max: &[u8]);
+
+        fn attribute_written(self: &DecodeControl, index: u32, count: u32);
     }
 
     unsafe extern "C++" {
@@ -23,15 +135,177 @@ mod cpp {
 
         pub fn decode_point_cloud(data: &[u8]) -> Vec<u8>;
 
-        pub fn create_mesh(data: &[u8]) -> UniquePtr<DracoMesh>;
+        pub fn create_mesh(
+            data: &[u8],
+            keep_quantized: &[i32],
+            control: &DecodeControl,
+            error: &mut String,
+        ) -> UniquePtr<DracoMesh>;
 
-        pub fn compute_mesh_config(mesh: &DracoMesh, config: &mut MeshConfig) -> bool;
+        pub fn compute_mesh_config(
+            mesh: &DracoMesh,
+            layout: &LayoutOptions,
+            config: &mut MeshConfig,
+        ) -> bool;
 
         pub unsafe fn decode_mesh_to_buffer(
             mesh: &DracoMesh,
+            layout: &LayoutOptions,
+            control: &DecodeControl,
+            out_ptr: *mut u8,
+            out_len: usize,
+        ) -> usize;
+
+        type DracoPointCloud;
+
+        pub fn create_point_cloud(
+            data: &[u8],
+            control: &DecodeControl,
+            error: &mut String,
+        ) -> UniquePtr<DracoPointCloud>;
+
+        pub fn compute_point_cloud_config(
+            pc: &DracoPointCloud,
+            config: &mut PointCloudConfig,
+        ) -> bool;
+
+        pub unsafe fn decode_point_cloud_to_buffer(
+            pc: &DracoPointCloud,
+            control: &DecodeControl,
             out_ptr: *mut u8,
             out_len: usize,
         ) -> usize;
+
+        type DracoMeshBuilder;
+
+        pub fn create_mesh_builder(num_points: u32) -> UniquePtr<DracoMeshBuilder>;
+
+        pub fn mesh_builder_set_faces(builder: Pin<&mut DracoMeshBuilder>, indices: &[u32])
+        -> bool;
+
+        pub fn mesh_builder_add_attribute(
+            builder: Pin<&mut DracoMeshBuilder>,
+            attribute_type: i32,
+            data_type: u32,
+            dim: u32,
+            quantization_bits: i32,
+            data: &[u8],
+        ) -> bool;
+
+        pub fn mesh_builder_encode(
+            builder: &DracoMeshBuilder,
+            options: &EncodeOptions,
+            out: &mut Vec<u8>,
+        ) -> bool;
+
+        pub fn transcode(
+            data: &[u8],
+            options: &EncodeOptions,
+            quantization_bits: &[i32],
+            out: &mut Vec<u8>,
+        ) -> bool;
+
+        pub fn analyze_stream(data: &[u8], out: &mut StreamLayout) -> bool;
+
+        pub fn live_mesh_count() -> i64;
+
+        pub fn draco_version() -> String;
+    }
+}
+
+/// Cancellation token polled, limits checked and progress callback called by
+/// the native library while decompressing and writing a mesh or point cloud.
+#[derive(Default)]
+pub struct DecodeControl {
+    cancellation: Option<crate::CancellationToken>,
+    progress: Option<crate::progress::Progress>,
+    limits: crate::Limits,
+    /// Set once the counts read by Draco exceed `limits`
+    exceeded: Cell<Option<crate::DecodeError>>,
+    /// Attribute index and the bytes of the extrema of each written attribute
+    min_max: RefCell<Vec<crate::utils::RecordedMinMax>>,
+}
+
+impl DecodeControl {
+    fn new(options: &crate::DecodeOptions) -> Self {
+        Self {
+            cancellation: options.cancellation().cloned(),
+            progress: options.progress().cloned(),
+            limits: options.limits(),
+            exceeded: Cell::new(None),
+            min_max: RefCell::default(),
+        }
+    }
+
+    /// Moves the extrema reported with [`DecodeControl::attribute_min_max`]
+    /// to `config`.
+    fn take_min_max(&self, config: &mut crate::DracoDecodeConfig) {
+        for (index, min, max) in self.min_max.take() {
+            config.set_min_max(index, min, max);
+        }
+    }
+
+    /// Returns the limit error of a decode aborted by
+    /// [`DecodeControl::counts_fit`].
+    fn take_exceeded(&self) -> Option<crate::DecodeError> {
+        self.exceeded.take()
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(crate::CancellationToken::is_cancelled)
+    }
+
+    /// Checks the counts Draco read with the connectivity against the vertex
+    /// and index limits, before any attribute is decompressed.
+    fn counts_fit(&self, num_points: u32, num_faces: u32) -> bool {
+        match self
+            .limits
+            .check(num_points, num_faces.saturating_mul(3), 0)
+        {
+            Ok(()) => true,
+            Err(err) => {
+                self.exceeded.set(Some(err));
+                false
+            }
+        }
+    }
+
+    fn connectivity_decoded(&self) {
+        if let Some(progress) = &self.progress {
+            progress.report(crate::DecodeStage::ConnectivityDecoded, 0);
+        }
+    }
+
+    fn attributes_decoded(&self, index: u32, count: u32) {
+        if let Some(progress) = &self.progress {
+            progress.report(
+                crate::DecodeStage::AttributesDecoded(index as usize),
+                count as usize,
+            );
+        }
+    }
+
+    fn indices_written(&self) {
+        if let Some(progress) = &self.progress {
+            progress.report(crate::DecodeStage::IndicesWritten, 0);
+        }
+    }
+
+    fn attribute_min_max(&self, index: u32, min: &[u8], max: &[u8]) {
+        self.min_max
+            .borrow_mut()
+            .push((index as usize, min.to_vec(), max.to_vec()));
+    }
+
+    fn attribute_written(&self, index: u32, count: u32) {
+        if let Some(progress) = &self.progress {
+            progress.report(
+                crate::DecodeStage::AttributeExtracted(index as usize),
+                count as usize,
+            );
+        }
     }
 }
 
@@ -40,62 +314,745 @@ pub fn decode_point_cloud_native(data: &[u8]) -> Vec<u8> {
     cpp::decode_point_cloud(data)
 }
 
-fn convert_config(cpp_config: cpp::MeshConfig) -> crate::DracoDecodeConfig {
+#[allow(dead_code)]
+pub fn live_mesh_count() -> i64 {
+    cpp::live_mesh_count()
+}
+
+/// Version of the vendored draco library.
+pub fn draco_version() -> String {
+    cpp::draco_version()
+}
+
+fn convert_attribute(attr: &cpp::MeshAttribute) -> crate::MeshAttribute {
+    let data_type = match attr.data_type {
+        0 => crate::AttributeDataType::Int8,
+        1 => crate::AttributeDataType::UInt8,
+        2 => crate::AttributeDataType::Int16,
+        3 => crate::AttributeDataType::UInt16,
+        4 => crate::AttributeDataType::Int32,
+        5 => crate::AttributeDataType::UInt32,
+        6 => crate::AttributeDataType::Float32,
+        _ => crate::AttributeDataType::UInt8,
+    };
+
+    let attribute = crate::MeshAttribute::new(attr.dim, data_type, attr.offset, attr.length)
+        .with_semantic(crate::AttributeSemantic::from_draco(attr.attribute_type))
+        .with_unique_id(attr.unique_id)
+        .with_normalized(attr.normalized)
+        .with_padded(attr.padded);
+
+    if attr.octahedron_bits > 0 {
+        return attribute.with_octahedron(crate::OctahedronInfo::new(attr.octahedron_bits));
+    }
+    match crate::QuantizationInfo::new(
+        attr.quantization_bits,
+        attr.quantization_range,
+        &attr.quantization_min,
+    ) {
+        Some(quantization) if attr.quantization_bits > 0 => {
+            attribute.with_quantization(quantization)
+        }
+        _ => attribute,
+    }
+}
+
+/// Converts the layout computed by the native library, returns `None` if an
+/// attribute lies outside the buffer or overlaps the indices or another
+/// attribute.
+fn convert_config(cpp_config: cpp::MeshConfig) -> Option<crate::DracoDecodeConfig> {
     let mut config = crate::DracoDecodeConfig::new(
         cpp_config.vertex_count,
         cpp_config.index_count,
         cpp_config.buffer_size,
-    );
-
-    for attr in cpp_config.attributes {
-        let data_type = match attr.data_type {
-            0 => crate::AttributeDataType::Int8,
-            1 => crate::AttributeDataType::UInt8,
-            2 => crate::AttributeDataType::Int16,
-            3 => crate::AttributeDataType::UInt16,
-            4 => crate::AttributeDataType::Int32,
-            5 => crate::AttributeDataType::UInt32,
-            6 => crate::AttributeDataType::Float32,
-            _ => crate::AttributeDataType::UInt8,
+    )
+    .with_index_length(cpp_config.index_length);
+
+    for attr in &cpp_config.attributes {
+        config.add_attribute(convert_attribute(attr));
+    }
+
+    for warning in &cpp_config.warnings {
+        config.add_warning(convert_warning(warning, &cpp_config.attributes));
+    }
+
+    config.has_valid_layout().then_some(config)
+}
+
+fn convert_warning(
+    warning: &cpp::AttributeWarning,
+    attributes: &[cpp::MeshAttribute],
+) -> crate::DecodeWarning {
+    use crate::{WarningAction, WarningReason};
+
+    let attribute_index = attributes
+        .iter()
+        .position(|attr| attr.unique_id == warning.unique_id);
+
+    let (reason, action) = match (warning.reason, attribute_index) {
+        (2, _) => (WarningReason::Filtered, WarningAction::Skipped),
+        (1, Some(index)) => (
+            WarningReason::UnsupportedDataType(warning.data_type as u8),
+            WarningAction::Converted(convert_attribute(&attributes[index]).data_type()),
+        ),
+        _ => (
+            WarningReason::UnsupportedDataType(warning.data_type as u8),
+            WarningAction::Skipped,
+        ),
+    };
+
+    crate::DecodeWarning {
+        unique_id: warning.unique_id,
+        semantic: crate::AttributeSemantic::from_draco(warning.attribute_type),
+        attribute_index,
+        reason,
+        action,
+    }
+}
+
+/// A decoded Draco mesh held by the native library.
+///
+/// The handle owns the `draco::Mesh` produced by the first decode stage. Its
+/// layout can be inspected with [`MeshHandle::config`] before allocating and
+/// filling the output buffer with [`MeshHandle::decode_into`].
+///
+/// `MeshHandle` is `Send` but not `Sync`: it can be moved to another thread,
+/// but not shared between threads.
+pub struct MeshHandle {
+    mesh: UniquePtr<cpp::DracoMesh>,
+}
+
+// SAFETY: `DracoMesh` exclusively owns its `draco::Mesh` and holds no
+// thread-local or thread-affine state, so it may be dropped or used from a
+// thread other than the one that created it. The only shared state touched by
+// its constructor and destructor is an atomic counter.
+//
+// `Sync` is deliberately not implemented: draco does not document its const
+// accessors as safe for concurrent use, so shared access across threads is not
+// guaranteed to be sound.
+unsafe impl Send for cpp::DracoMesh {}
+
+static_assertions::assert_impl_all!(MeshHandle: Send);
+static_assertions::assert_not_impl_any!(MeshHandle: Sync);
+
+impl MeshHandle {
+    /// Decodes a Draco compressed mesh into a native handle.
+    ///
+    /// Returns `None` if the data is not a valid Draco mesh.
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        Self::decode_with_options(data, &crate::DecodeOptions::default())
+    }
+
+    /// Decodes a Draco compressed mesh into a native handle, keeping the
+    /// quantized values requested by `options` and reporting the
+    /// decompression to its progress callback.
+    ///
+    /// The layout options are applied later by
+    /// [`MeshHandle::config_with_options`] and
+    /// [`MeshHandle::decode_into_with_options`].
+    ///
+    /// Returns `None` if the data is not a valid Draco mesh or its counts
+    /// exceed the limits of `options`.
+    pub fn decode_with_options(data: &[u8], options: &crate::DecodeOptions) -> Option<Self> {
+        Self::try_decode(data, options).ok()
+    }
+
+    /// Decodes like [`MeshHandle::decode_with_options`], failing with
+    /// [`DecodeError::LimitExceeded`](crate::DecodeError::LimitExceeded) as
+    /// soon as Draco has read vertex or face counts exceeding the limits of
+    /// `options` and with `DecodeFailed` if the data is not a valid Draco
+    /// mesh.
+    fn try_decode(data: &[u8], options: &crate::DecodeOptions) -> Result<Self, crate::DecodeError> {
+        let keep_quantized: Vec<i32> = SEMANTICS
+            .into_iter()
+            .filter(|&semantic| options.keeps_quantized(semantic))
+            .map(crate::AttributeSemantic::to_draco)
+            .collect();
+
+        let control = DecodeControl::new(options);
+        let mut error = String::new();
+        let mesh = cpp::create_mesh(data, &keep_quantized, &control, &mut error);
+        if mesh.is_null() {
+            // Exceeded limits are not failures, so they are not reported
+            if let Some(err) = control.take_exceeded() {
+                return Err(err);
+            }
+            crate::diagnostics::report_failure(data, options, "decode", &error);
+            return Err(crate::DecodeError::DecodeFailed);
+        }
+        Ok(Self { mesh })
+    }
+
+    /// Computes the layout of the decoded buffer.
+    ///
+    /// Returns `None` if the layout cannot be computed.
+    pub fn config(&self) -> Option<crate::DracoDecodeConfig> {
+        self.layout(&layout_options(false, &crate::DecodeOptions::default()))
+    }
+
+    /// Computes the layout of the decoded buffer with the given options.
+    ///
+    /// The layout is checked like by
+    /// [`try_decode_mesh_with_options`](crate::try_decode_mesh_with_options):
+    /// fails with [`DecodeError::LimitExceeded`](crate::DecodeError::LimitExceeded)
+    /// if it exceeds the limits of `options`, with
+    /// [`DecodeError::IndicesDoNotFit`](crate::DecodeError::IndicesDoNotFit)
+    /// if [`IndexFormat::U16`](crate::IndexFormat::U16) is requested for more
+    /// than 65536 vertices, with
+    /// [`DecodeError::AxesNotRemappable`](crate::DecodeError::AxesNotRemappable)
+    /// if the positions or normals cannot be written in its axis convention,
+    /// and with `DecodeFailed` if the layout cannot be computed, including
+    /// when an offset or length of the buffer does not fit in `u32`. The
+    /// quota is consulted when the buffer is written.
+    pub fn config_with_options(
+        &self,
+        options: &crate::DecodeOptions,
+    ) -> Result<crate::DracoDecodeConfig, crate::DecodeError> {
+        self.checked_layout(&layout_options(false, options), options)
+    }
+
+    /// Computes the layout and checks it against the limits, index format and
+    /// axis convention of `options`.
+    fn checked_layout(
+        &self,
+        layout: &cpp::LayoutOptions,
+        options: &crate::DecodeOptions,
+    ) -> Result<crate::DracoDecodeConfig, crate::DecodeError> {
+        let config = self
+            .layout(layout)
+            .ok_or(crate::DecodeError::DecodeFailed)?;
+        options.limits().check(
+            config.vertex_count(),
+            config.index_count(),
+            config.buffer_size(),
+        )?;
+        options.index_format().check(config.vertex_count())?;
+        options.axis_convention().check(&config.attributes())?;
+        Ok(config)
+    }
+
+    fn layout(&self, layout: &cpp::LayoutOptions) -> Option<crate::DracoDecodeConfig> {
+        let mut cpp_config = cpp::MeshConfig {
+            vertex_count: 0,
+            index_count: 0,
+            index_length: 0,
+            buffer_size: 0,
+            attributes: Vec::new(),
+            warnings: Vec::new(),
+        };
+
+        if !cpp::compute_mesh_config(&self.mesh, layout, &mut cpp_config) {
+            return None;
+        }
+
+        convert_config(cpp_config)
+    }
+
+    /// Writes the indices and attributes into `buffer`.
+    ///
+    /// The buffer should be at least [`DracoDecodeConfig::buffer_size`] bytes
+    /// long. Returns the number of bytes written, which is 0 for an empty mesh,
+    /// or `None` if the mesh does not fit into the buffer.
+    ///
+    /// [`DracoDecodeConfig::buffer_size`]: crate::DracoDecodeConfig::buffer_size
+    pub fn decode_into(&self, buffer: &mut [u8]) -> Option<usize> {
+        self.write_into(
+            &layout_options(false, &crate::DecodeOptions::default()),
+            &DecodeControl::default(),
+            buffer,
+        )
+    }
+
+    /// Writes the indices and attributes into `buffer` using the layout of
+    /// [`MeshHandle::config_with_options`] for the same options.
+    ///
+    /// See [`MeshHandle::decode_into`]. Fails like
+    /// [`MeshHandle::config_with_options`] before anything is written, with
+    /// [`DecodeError::QuotaDenied`](crate::DecodeError::QuotaDenied) if the
+    /// quota of `options` denies the buffer, and with `DecodeFailed` if the
+    /// mesh does not fit into `buffer` or the cancellation token of `options`
+    /// is cancelled before every attribute is written. With
+    /// [`DecodeOptions::validate_indices`](crate::DecodeOptions::validate_indices)
+    /// the written indices are checked, failing with
+    /// [`DecodeError::IndexOutOfBounds`](crate::DecodeError::IndexOutOfBounds);
+    /// `buffer` then holds the invalid mesh. The progress callback of
+    /// `options` is called as the indices and each attribute are written.
+    pub fn decode_into_with_options(
+        &self,
+        options: &crate::DecodeOptions,
+        buffer: &mut [u8],
+    ) -> Result<usize, crate::DecodeError> {
+        self.write_with_options(options, buffer)
+            .map(|(written, _)| written)
+    }
+
+    /// Writes like [`MeshHandle::decode_into_with_options`], returning the
+    /// number of bytes written and the layout, with the extrema recorded for
+    /// [`DecodeOptions::compute_min_max`](crate::DecodeOptions::compute_min_max).
+    pub(crate) fn write_with_options(
+        &self,
+        options: &crate::DecodeOptions,
+        buffer: &mut [u8],
+    ) -> Result<(usize, crate::DracoDecodeConfig), crate::DecodeError> {
+        let layout = layout_options(false, options);
+        let mut config = self.checked_layout(&layout, options)?;
+        options.check_quota(config.buffer_size())?;
+        let control = DecodeControl::new(options);
+        let written = self
+            .write_into(&layout, &control, buffer)
+            .ok_or(crate::DecodeError::DecodeFailed)?;
+        if options.validates_indices() {
+            config.check_indices(&buffer[..written])?;
+        }
+        control.take_min_max(&mut config);
+        Ok((written, config))
+    }
+
+    fn write_into(
+        &self,
+        layout: &cpp::LayoutOptions,
+        control: &DecodeControl,
+        buffer: &mut [u8],
+    ) -> Option<usize> {
+        // SAFETY: the pointer and length describe the mutable slice, which
+        // stays borrowed for the duration of the call.
+        let written = unsafe {
+            cpp::decode_mesh_to_buffer(
+                &self.mesh,
+                layout,
+                control,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+            )
         };
-        config.add_attribute(attr.dim, data_type, attr.offset, attr.length);
+
+        if written == usize::MAX {
+            None
+        } else {
+            Some(written)
+        }
+    }
+
+    fn to_result(&self, layout: &cpp::LayoutOptions) -> Option<crate::MeshDecodeResult> {
+        self.fill(layout, &DecodeControl::default(), self.layout(layout)?)
+    }
+
+    /// Allocates a buffer for the layout computed with [`MeshHandle::layout`]
+    /// and writes the mesh into it, with the extrema reported to `control`.
+    fn fill(
+        &self,
+        layout: &cpp::LayoutOptions,
+        control: &DecodeControl,
+        mut config: crate::DracoDecodeConfig,
+    ) -> Option<crate::MeshDecodeResult> {
+        let mut buffer = vec![0u8; config.buffer_size()];
+        let written = self.write_into(layout, control, &mut buffer)?;
+
+        buffer.truncate(written);
+        control.take_min_max(&mut config);
+
+        Some(crate::MeshDecodeResult {
+            data: buffer,
+            config,
+        })
     }
+}
 
-    config
+/// Every attribute semantic, in the order used to index per-semantic options.
+const SEMANTICS: [crate::AttributeSemantic; 5] = [
+    crate::AttributeSemantic::Position,
+    crate::AttributeSemantic::Normal,
+    crate::AttributeSemantic::Color,
+    crate::AttributeSemantic::TexCoord,
+    crate::AttributeSemantic::Generic,
+];
+
+/// Describes the buffer layout requested by `options` for the native library.
+fn layout_options(positions_only: bool, options: &crate::DecodeOptions) -> cpp::LayoutOptions {
+    cpp::LayoutOptions {
+        positions_only,
+        offset_alignment: options.offset_alignment(),
+        index_format: match options.index_format() {
+            crate::IndexFormat::Auto => 0,
+            crate::IndexFormat::U16 => 1,
+            crate::IndexFormat::U32 => 2,
+            crate::IndexFormat::ByIndexCount => 3,
+        },
+        output_types: SEMANTICS
+            .into_iter()
+            .map(|semantic| {
+                options
+                    .output_type(semantic)
+                    .map_or(-1, |data_type| data_type as i32)
+            })
+            .collect(),
+        attribute_output_types: options
+            .attribute_output_types()
+            .iter()
+            .map(|&(unique_id, data_type)| cpp::AttributeOutputType {
+                unique_id,
+                data_type: data_type as u32,
+            })
+            .collect(),
+        pad_vec3: options.pads_vec3(),
+        axis_map: options
+            .axis_convention()
+            .axis_map()
+            .map_or_else(Vec::new, Vec::from),
+        flip_winding: options.writes_flipped_winding(),
+        skip_indices: false,
+        min_max: options.computes_min_max(),
+    }
 }
 
 pub fn decode_mesh_with_config(data: &[u8]) -> Option<crate::MeshDecodeResult> {
-    let mesh = cpp::create_mesh(data);
-    if mesh.is_null() {
-        panic!("Failed to create mesh from data");
+    decode_mesh(data, false, &crate::DecodeOptions::default())
+}
+
+/// Decodes the mesh with the layout requested by `options`.
+pub fn decode_mesh_with_options(
+    data: &[u8],
+    options: &crate::DecodeOptions,
+) -> Option<crate::MeshDecodeResult> {
+    decode_mesh(data, false, options)
+}
+
+/// Decodes the indices and the position attribute only.
+pub fn decode_mesh_positions(data: &[u8]) -> Option<crate::MeshDecodeResult> {
+    decode_mesh(data, true, &crate::DecodeOptions::default())
+}
+
+fn decode_mesh(
+    data: &[u8],
+    positions_only: bool,
+    options: &crate::DecodeOptions,
+) -> Option<crate::MeshDecodeResult> {
+    try_decode_mesh(data, positions_only, options).ok()
+}
+
+/// Decodes the mesh with the layout requested by `options`, reporting why
+/// decoding failed.
+pub fn try_decode_mesh_with_options(
+    data: &[u8],
+    options: &crate::DecodeOptions,
+) -> Result<crate::MeshDecodeResult, crate::DecodeError> {
+    try_decode_mesh(data, false, options)
+}
+
+fn try_decode_mesh(
+    data: &[u8],
+    positions_only: bool,
+    options: &crate::DecodeOptions,
+) -> Result<crate::MeshDecodeResult, crate::DecodeError> {
+    use crate::DecodeError;
+
+    // Cancelled decodes are not failures, so they are not reported
+    if options.is_cancelled() {
+        return Err(DecodeError::DecodeFailed);
+    }
+    let header = crate::decode_header(data);
+    if header.is_ok() {
+        options.report_progress(crate::DecodeStage::HeaderParsed, 0);
+    }
+    options.check_quota_decode(data.len())?;
+    let handle = match MeshHandle::try_decode(data, options) {
+        Ok(handle) => handle,
+        Err(DecodeError::DecodeFailed) => {
+            return Err(header.err().unwrap_or(DecodeError::DecodeFailed));
+        }
+        Err(err) => return Err(err),
+    };
+    if options.is_cancelled() {
+        return Err(DecodeError::DecodeFailed);
+    }
+    let layout = layout_options(positions_only, options);
+    let result = match handle.checked_layout(&layout, options) {
+        Ok(config) => {
+            options.check_quota(config.buffer_size())?;
+            handle.fill(&layout, &DecodeControl::new(options), config)
+        }
+        // Neither are exceeded limits or a denied decode
+        Err(DecodeError::DecodeFailed) => None,
+        Err(err) => return Err(err),
+    };
+    match result {
+        Some(mut result) => {
+            if options.validates_indices() {
+                result.check_indices()?;
+            }
+            result.config.set_crs(crate::crs_from_metadata(data));
+            Ok(result)
+        }
+        None if options.is_cancelled() => Err(DecodeError::DecodeFailed),
+        None => {
+            crate::diagnostics::report_failure(
+                data,
+                options,
+                "layout",
+                "the decoded mesh could not be written in the requested layout",
+            );
+            Err(DecodeError::DecodeFailed)
+        }
+    }
+}
+
+/// Reports a coarse position sample before converting the full mesh.
+pub fn decode_progressive(data: &[u8], on_partial: &mut dyn FnMut(crate::PartialGeometry)) -> bool {
+    let Some(handle) = MeshHandle::decode(data) else {
+        return false;
+    };
+
+    // The coarse level only samples positions, the indices are written once
+    // for the full mesh
+    let defaults = crate::DecodeOptions::default();
+    let positions = cpp::LayoutOptions {
+        skip_indices: true,
+        ..layout_options(true, &defaults)
+    };
+    if let Some(coarse) = handle
+        .to_result(&positions)
+        .and_then(|positions| crate::PartialGeometry::coarse(&positions))
+    {
+        on_partial(coarse);
+    }
+
+    match handle.to_result(&layout_options(false, &defaults)) {
+        Some(mut result) => {
+            result.config.set_crs(crate::crs_from_metadata(data));
+            on_partial(crate::PartialGeometry::Full(result));
+            true
+        }
+        None => false,
+    }
+}
+
+pub fn decode_point_cloud_with_config(data: &[u8]) -> Option<crate::PointCloudDecodeResult> {
+    try_decode_point_cloud(data, &crate::DecodeOptions::default()).ok()
+}
+
+/// Decodes a point cloud, honoring the limits, quota, cancellation and
+/// progress callback of `options`.
+pub fn try_decode_point_cloud(
+    data: &[u8],
+    options: &crate::DecodeOptions,
+) -> Result<crate::PointCloudDecodeResult, crate::DecodeError> {
+    use crate::DecodeError;
+
+    if options.is_cancelled() {
+        return Err(DecodeError::DecodeFailed);
+    }
+    let header = crate::decode_header(data);
+    if header.is_ok() {
+        options.report_progress(crate::DecodeStage::HeaderParsed, 0);
+    }
+    options.check_quota_decode(data.len())?;
+    let control = DecodeControl::new(options);
+    let mut error = String::new();
+    let pc = cpp::create_point_cloud(data, &control, &mut error);
+    if pc.is_null() {
+        if let Some(err) = control.take_exceeded() {
+            return Err(err);
+        }
+        crate::diagnostics::report_failure(data, options, "decode", &error);
+        return Err(header.err().unwrap_or(DecodeError::DecodeFailed));
+    }
+    if options.is_cancelled() {
+        return Err(DecodeError::DecodeFailed);
     }
 
-    let mut cpp_config = cpp::MeshConfig {
-        vertex_count: 0,
-        index_count: 0,
-        index_length: 0,
+    let mut cpp_config = cpp::PointCloudConfig {
+        point_count: 0,
         buffer_size: 0,
         attributes: Vec::new(),
+        warnings: Vec::new(),
     };
 
-    if !cpp::compute_mesh_config(&mesh, &mut cpp_config) {
-        panic!("Failed to compute mesh config");
+    if !cpp::compute_point_cloud_config(&pc, &mut cpp_config) {
+        return Err(DecodeError::DecodeFailed);
     }
 
-    let buffer_size = cpp_config.buffer_size;
-    let config = convert_config(cpp_config);
-    let mut buffer = vec![0u8; buffer_size];
+    let mut config =
+        crate::PointCloudDecodeConfig::new(cpp_config.point_count, cpp_config.buffer_size);
+    for attr in &cpp_config.attributes {
+        config.add_attribute(convert_attribute(attr));
+    }
+    for warning in &cpp_config.warnings {
+        config.add_warning(convert_warning(warning, &cpp_config.attributes));
+    }
+    if !config.has_valid_layout() {
+        return Err(DecodeError::DecodeFailed);
+    }
+    options
+        .limits()
+        .check(config.point_count(), 0, config.buffer_size())?;
+    options.check_quota(config.buffer_size())?;
+    config.set_crs(crate::crs_from_metadata(data));
 
-    let written = unsafe { cpp::decode_mesh_to_buffer(&mesh, buffer.as_mut_ptr(), buffer.len()) };
+    let mut buffer = vec![0u8; config.buffer_size()];
 
-    if written == 0 {
-        panic!("Failed to decode mesh to buffer");
+    // SAFETY: the pointer and length describe `buffer`, which outlives the call.
+    let written = unsafe {
+        cpp::decode_point_cloud_to_buffer(&pc, &control, buffer.as_mut_ptr(), buffer.len())
+    };
+
+    if written == usize::MAX {
+        return Err(DecodeError::DecodeFailed);
     }
 
     buffer.truncate(written);
 
-    Some(crate::MeshDecodeResult {
+    Ok(crate::PointCloudDecodeResult {
         data: buffer,
         config,
     })
 }
+
+/// Converts the encoder settings, returns `None` for an encoding method the
+/// geometry type does not support.
+fn encode_options(config: &crate::EncodeConfig, point_cloud: bool) -> Option<cpp::EncodeOptions> {
+    use crate::EncodingMethod;
+
+    // Values of draco's MeshEncoderMethod and PointCloudEncodingMethod
+    let encoding_method = match (config.encoding_method(), point_cloud) {
+        (None, _) => -1,
+        (Some(EncodingMethod::Sequential), _) => 0,
+        (Some(EncodingMethod::Edgebreaker), false) | (Some(EncodingMethod::KdTree), true) => 1,
+        _ => return None,
+    };
+
+    Some(cpp::EncodeOptions {
+        point_cloud,
+        encoding_method,
+        encoding_speed: config.encoding_speed() as i32,
+        decoding_speed: config.decoding_speed() as i32,
+    })
+}
+
+/// Encodes a mesh, or a point cloud if `indices` is `None`.
+pub fn encode(
+    vertex_count: u32,
+    indices: Option<&[u32]>,
+    attributes: &[crate::EncodeAttribute<'_>],
+    config: &crate::EncodeConfig,
+) -> Option<Vec<u8>> {
+    let options = encode_options(config, indices.is_none())?;
+    let mut builder = cpp::create_mesh_builder(vertex_count);
+
+    if let Some(indices) = indices
+        && !cpp::mesh_builder_set_faces(builder.pin_mut(), indices)
+    {
+        return None;
+    }
+
+    for attribute in attributes {
+        let added = cpp::mesh_builder_add_attribute(
+            builder.pin_mut(),
+            attribute.semantic().to_draco(),
+            attribute.data_type() as u32,
+            attribute.dim(),
+            config.quantization_bits(attribute.semantic()) as i32,
+            attribute.data(),
+        );
+        if !added {
+            return None;
+        }
+    }
+
+    let mut out = Vec::new();
+    if !cpp::mesh_builder_encode(&builder, &options, &mut out) {
+        return None;
+    }
+
+    Some(out)
+}
+
+pub fn transcode(data: &[u8], config: &crate::EncodeConfig) -> Option<Vec<u8>> {
+    use crate::AttributeSemantic;
+
+    let header = crate::decode_header(data).ok()?;
+    let point_cloud = header.geometry_type == crate::GeometryType::PointCloud;
+    let options = encode_options(config, point_cloud)?;
+
+    // Indexed by draco::GeometryAttribute::Type, see `AttributeSemantic::to_draco`
+    let quantization_bits = [
+        AttributeSemantic::Position,
+        AttributeSemantic::Normal,
+        AttributeSemantic::Color,
+        AttributeSemantic::TexCoord,
+        AttributeSemantic::Generic,
+    ]
+    .map(|semantic| config.quantization_bits(semantic) as i32);
+
+    let mut out = Vec::new();
+    if !cpp::transcode(data, &options, &quantization_bits, &mut out) {
+        return None;
+    }
+
+    Some(out)
+}
+
+pub fn analyze(data: &[u8]) -> Option<crate::CompressionReport> {
+    crate::decode_header(data).ok()?;
+
+    let mut layout = cpp::StreamLayout {
+        num_points: 0,
+        num_faces: 0,
+        geometry_start: 0,
+        geometry_end: 0,
+        attributes_start: 0,
+        decoder_ends: Vec::new(),
+        attributes: Vec::new(),
+    };
+    if !cpp::analyze_stream(data, &mut layout) {
+        return None;
+    }
+
+    // Each attributes decoder's data starts where the previous one ends
+    let decoder_size = |decoder: usize| {
+        let start = match decoder {
+            0 => layout.attributes_start,
+            _ => *layout.decoder_ends.get(decoder - 1)?,
+        };
+        Some(layout.decoder_ends.get(decoder)? - start)
+    };
+
+    let attributes = layout
+        .attributes
+        .iter()
+        .map(|attr| {
+            let octahedral = attr.octahedron_bits > 0;
+            let bits = if octahedral {
+                attr.octahedron_bits
+            } else {
+                attr.quantization_bits
+            };
+            crate::AttributeSize {
+                unique_id: attr.unique_id,
+                semantic: crate::AttributeSemantic::from_draco(attr.attribute_type),
+                components: attr.num_components,
+                value_count: layout.num_points,
+                encoded_size: decoder_size(attr.decoder as usize).unwrap_or(0),
+                shared_with: layout
+                    .attributes
+                    .iter()
+                    .filter(|other| {
+                        other.decoder == attr.decoder && other.unique_id != attr.unique_id
+                    })
+                    .map(|other| other.unique_id)
+                    .collect(),
+                quantization_bits: (bits > 0).then_some(bits),
+                octahedral,
+            }
+        })
+        .collect();
+
+    Some(crate::CompressionReport {
+        total_size: data.len(),
+        header_size: layout.geometry_start,
+        connectivity_size: layout.geometry_end - layout.geometry_start,
+        attribute_header_size: layout.attributes_start - layout.geometry_end,
+        attributes,
+        vertex_count: layout.num_points,
+        face_count: layout.num_faces,
+    })
+}
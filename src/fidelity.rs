@@ -0,0 +1,298 @@
+use crate::{
+    AttributeDataType, AttributeSemantic, AttributeValues, EncodeConfig, EncodingMethod,
+    MeshDecodeResult, encode_mesh_with_config,
+};
+
+/// Encoding speeds tried by [`auto_tune_encode`].
+const TUNE_SPEEDS: [u8; 5] = [0, 3, 5, 7, 10];
+
+/// Fewest quantization bits tried by [`auto_tune_encode`], normals need at
+/// least 2.
+const MIN_TUNE_BITS: u32 = 2;
+
+/// Every attribute semantic, in the order used to index per-semantic settings.
+const SEMANTICS: [AttributeSemantic; 5] = [
+    AttributeSemantic::Position,
+    AttributeSemantic::Normal,
+    AttributeSemantic::Color,
+    AttributeSemantic::TexCoord,
+    AttributeSemantic::Generic,
+];
+
+/// Maximum acceptable errors for [`roundtrip_check`].
+///
+/// Errors are Euclidean distances between the original and the decoded value
+/// of an attribute element, in the units of the attribute. Semantics without
+/// a tolerance always pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Tolerances {
+    max_error: [Option<f32>; 5],
+}
+
+impl Tolerances {
+    /// Sets the maximum error for attributes with the given semantic.
+    pub fn with_max_error(mut self, semantic: AttributeSemantic, max_error: f32) -> Self {
+        self.max_error[semantic as usize] = Some(max_error);
+        self
+    }
+
+    /// Returns the maximum error for attributes with the given semantic, if
+    /// any.
+    pub fn max_error(&self, semantic: AttributeSemantic) -> Option<f32> {
+        self.max_error[semantic as usize]
+    }
+}
+
+/// Error measured for one attribute by [`roundtrip_check`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AttributeFidelity {
+    /// What the attribute represents.
+    pub semantic: AttributeSemantic,
+    /// Unique id of the attribute in the original mesh.
+    pub unique_id: u32,
+    /// Largest error of any element.
+    pub max_error: f32,
+    /// Average error over all elements.
+    pub mean_error: f32,
+    /// Whether `max_error` is within the tolerance for the semantic.
+    pub passed: bool,
+}
+
+/// Result of [`roundtrip_check`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FidelityReport {
+    /// Size of the bitstream produced with the checked settings.
+    pub encoded_size: usize,
+    /// Measured error of every attribute, in the order of the mesh
+    /// attributes.
+    pub attributes: Vec<AttributeFidelity>,
+}
+
+impl FidelityReport {
+    /// Returns `true` if every attribute is within its tolerance.
+    pub fn passed(&self) -> bool {
+        self.attributes.iter().all(|attribute| attribute.passed)
+    }
+}
+
+/// Encodes `mesh` with `config`, decodes it again and measures the error of
+/// every attribute (native only).
+///
+/// The edgebreaker method reorders vertices, so errors are measured on a
+/// sequentially encoded copy with the same quantization settings, which
+/// keeps the vertex order and loses the same precision. The reported size is
+/// the one of the bitstream encoded with `config`.
+///
+/// Returns `None` if encoding or decoding fails.
+pub fn roundtrip_check(
+    mesh: &MeshDecodeResult,
+    config: &EncodeConfig,
+    tolerances: &Tolerances,
+) -> Option<FidelityReport> {
+    let encoded = encode_mesh_with_config(mesh, config)?;
+    let decoded = if config.encoding_method() == Some(EncodingMethod::Sequential) {
+        crate::ffi::decode_mesh_with_config(&encoded)?
+    } else {
+        let sequential = config
+            .clone()
+            .with_encoding_method(EncodingMethod::Sequential);
+        crate::ffi::decode_mesh_with_config(&encode_mesh_with_config(mesh, &sequential)?)?
+    };
+
+    let original_attributes = mesh.config.attributes();
+    if decoded.config.vertex_count() != mesh.config.vertex_count()
+        || decoded.config.attributes().len() != original_attributes.len()
+    {
+        return None;
+    }
+
+    let attributes = original_attributes
+        .iter()
+        .enumerate()
+        .map(|(index, attribute)| {
+            let original = components(mesh.attribute_values(index)?);
+            let roundtrip = components(decoded.attribute_values(index)?);
+            if original.len() != roundtrip.len() {
+                return None;
+            }
+
+            let dim = attribute.dim().max(1) as usize;
+            let (max_error, total_error) = original
+                .chunks_exact(dim)
+                .zip(roundtrip.chunks_exact(dim))
+                .map(|(a, b)| {
+                    a.iter()
+                        .zip(b)
+                        .map(|(a, b)| (a - b) * (a - b))
+                        .sum::<f64>()
+                        .sqrt()
+                })
+                .fold((0.0f64, 0.0f64), |(max, total), error| {
+                    (max.max(error), total + error)
+                });
+            let elements = original.len() / dim;
+            let mean_error = if elements == 0 {
+                0.0
+            } else {
+                total_error / elements as f64
+            };
+
+            let max_error = max_error as f32;
+            Some(AttributeFidelity {
+                semantic: attribute.semantic(),
+                unique_id: attribute.unique_id(),
+                max_error,
+                mean_error: mean_error as f32,
+                passed: tolerances
+                    .max_error(attribute.semantic())
+                    .is_none_or(|tolerance| max_error <= tolerance),
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(FidelityReport {
+        encoded_size: encoded.len(),
+        attributes,
+    })
+}
+
+/// Goal of [`auto_tune_encode`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QualityTarget {
+    /// The smallest output whose errors stay within the tolerances.
+    MaxError(Tolerances),
+    /// The most precise output that fits in the given number of bytes.
+    SizeBudget(usize),
+}
+
+/// Searches encoder settings for `mesh` that meet `target` (native only).
+///
+/// For [`QualityTarget::MaxError`], the lowest quantization bits within the
+/// tolerance are searched for every floating point attribute semantic with a
+/// tolerance; semantics that need more than 30 bits are encoded losslessly.
+/// For [`QualityTarget::SizeBudget`], the quantization bits of all semantics
+/// are raised or lowered together from the defaults. The encoding speed
+/// producing the smallest output is picked last.
+///
+/// Every step encodes the mesh, so tuning takes a few dozen encodes.
+///
+/// Returns `None` if encoding fails or no settings fit the size budget.
+pub fn auto_tune_encode(mesh: &MeshDecodeResult, target: &QualityTarget) -> Option<EncodeConfig> {
+    let config = match target {
+        QualityTarget::MaxError(tolerances) => tune_for_error(mesh, tolerances),
+        QualityTarget::SizeBudget(budget) => tune_for_size(mesh, *budget)?,
+    };
+
+    let mut best: Option<(usize, EncodeConfig)> = None;
+    for speed in TUNE_SPEEDS {
+        let candidate = config.clone().with_encoding_speed(speed);
+        let size = encode_mesh_with_config(mesh, &candidate)?.len();
+        if best
+            .as_ref()
+            .is_none_or(|(best_size, _)| size <= *best_size)
+        {
+            best = Some((size, candidate));
+        }
+    }
+
+    let (size, config) = best?;
+    match target {
+        QualityTarget::SizeBudget(budget) if size > *budget => None,
+        _ => Some(config),
+    }
+}
+
+/// Lowers the quantization bits of each semantic with a tolerance as far as
+/// the tolerance allows.
+fn tune_for_error(mesh: &MeshDecodeResult, tolerances: &Tolerances) -> EncodeConfig {
+    let mut config = EncodeConfig::default();
+    let attributes = mesh.config.attributes();
+    let semantics = SEMANTICS.into_iter().filter(|&semantic| {
+        tolerances.max_error(semantic).is_some()
+            && attributes.iter().any(|attribute| {
+                attribute.semantic() == semantic
+                    && attribute.data_type() == AttributeDataType::Float32
+            })
+    });
+
+    for semantic in semantics {
+        // Settings the encoder rejects count as failing
+        let passes = |config: &EncodeConfig, bits: u32| {
+            // Sequential encoding lets the check reuse its bitstream
+            let candidate = config
+                .clone()
+                .with_encoding_method(EncodingMethod::Sequential)
+                .with_quantization_bits(semantic, bits);
+            roundtrip_check(mesh, &candidate, tolerances).is_some_and(|report| {
+                report
+                    .attributes
+                    .iter()
+                    .filter(|attribute| attribute.semantic == semantic)
+                    .all(|attribute| attribute.passed)
+            })
+        };
+
+        // Lowest passing bit count in 2..=30, 0 (lossless) if none passes
+        let (mut low, mut high) = (MIN_TUNE_BITS, 31);
+        while low < high {
+            let mid = (low + high) / 2;
+            if passes(&config, mid) {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        config = config.with_quantization_bits(semantic, if low > 30 { 0 } else { low });
+    }
+
+    config
+}
+
+/// Finds the largest shift of the default quantization bits whose output fits
+/// in `budget` bytes at the best compression.
+fn tune_for_size(mesh: &MeshDecodeResult, budget: usize) -> Option<EncodeConfig> {
+    let defaults = EncodeConfig::default();
+    let shifted = |shift: i32| {
+        SEMANTICS.iter().fold(
+            defaults.clone().with_encoding_speed(0),
+            |config, &semantic| {
+                let bits = defaults.quantization_bits(semantic) as i32 + shift;
+                config.with_quantization_bits(semantic, bits.clamp(MIN_TUNE_BITS as i32, 30) as u32)
+            },
+        )
+    };
+    let fits = |shift: i32| {
+        encode_mesh_with_config(mesh, &shifted(shift))
+            .is_some_and(|encoded| encoded.len() <= budget)
+    };
+
+    // Largest fitting shift, the lowest shift reduces every semantic to the
+    // minimum bit count
+    let (mut low, mut high) = (-30, 30);
+    if !fits(low) {
+        return None;
+    }
+    while low < high {
+        let mid = (low + high + 1).div_euclid(2);
+        if fits(mid) {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+
+    Some(shifted(low))
+}
+
+/// Widens attribute values to `f64` for comparison.
+fn components(values: AttributeValues) -> Vec<f64> {
+    match values {
+        AttributeValues::Int8(v) => v.into_iter().map(f64::from).collect(),
+        AttributeValues::UInt8(v) => v.into_iter().map(f64::from).collect(),
+        AttributeValues::Int16(v) => v.into_iter().map(f64::from).collect(),
+        AttributeValues::UInt16(v) => v.into_iter().map(f64::from).collect(),
+        AttributeValues::Int32(v) => v.into_iter().map(f64::from).collect(),
+        AttributeValues::UInt32(v) => v.into_iter().map(f64::from).collect(),
+        AttributeValues::Float32(v) => v.into_iter().map(f64::from).collect(),
+    }
+}
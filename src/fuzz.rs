@@ -0,0 +1,52 @@
+use crate::{
+    Geometry, GeometryType, MeshDecodeResult, PointCloudDecodeResult, decode_mesh_with_config_sync,
+    decode_point_cloud_with_config_sync,
+};
+
+/// Decodes arbitrary, untrusted bytes as a Draco mesh or point cloud and
+/// reads back every index and attribute value.
+///
+/// This is the entry point for fuzzing the FFI boundary, used by the
+/// `decode` target in the `fuzz` directory:
+///
+/// ```ignore
+/// fuzz_target!(|data: &[u8]| {
+///     let _ = draco_decoder::decode_unchecked_input(data);
+/// });
+/// ```
+///
+/// It never panics: malformed or truncated input, and decoded layouts whose
+/// sections overlap or exceed the decoded buffer, return `None`. Panics are
+/// not caught, so any panic a fuzzer finds is a bug.
+pub fn decode_unchecked_input(data: &[u8]) -> Option<Geometry> {
+    match crate::sniff(data)? {
+        GeometryType::Mesh => {
+            let mesh = decode_mesh_with_config_sync(data)?;
+            read_mesh(&mesh)?;
+            Some(Geometry::Mesh(mesh))
+        }
+        GeometryType::PointCloud => {
+            let points = decode_point_cloud_with_config_sync(data)?;
+            read_point_cloud(&points)?;
+            Some(Geometry::PointCloud(points))
+        }
+    }
+}
+
+/// Reads the indices, triangles and attribute values of a decoded mesh.
+fn read_mesh(mesh: &MeshDecodeResult) -> Option<()> {
+    mesh.indices()?;
+    mesh.triangles().for_each(drop);
+    for index in 0..mesh.config.attributes().len() {
+        mesh.attribute_values(index)?;
+    }
+    Some(())
+}
+
+/// Reads the attribute values of a decoded point cloud.
+fn read_point_cloud(points: &PointCloudDecodeResult) -> Option<()> {
+    for index in 0..points.config.attributes().len() {
+        points.attribute_values(index)?;
+    }
+    Some(())
+}
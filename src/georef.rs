@@ -0,0 +1,202 @@
+use std::fmt;
+
+use crate::compat::{METADATA_VERSION, Reader};
+use crate::{
+    AttributeDataType, AttributeSemantic, MeshAttribute, MeshDecodeResult, PointCloudDecodeResult,
+    decode_header,
+};
+
+/// Geometry metadata keys holding the coordinate reference system.
+const CRS_KEYS: [&[u8]; 2] = [b"crs", b"epsg"];
+
+/// A coordinate reference system, identified by its EPSG code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Crs {
+    epsg: u32,
+}
+
+impl Crs {
+    /// WGS 84 longitude and latitude in degrees.
+    pub const WGS84: Crs = Crs::from_epsg(4326);
+    /// WGS 84 Earth-centered, Earth-fixed coordinates in meters, the frame of
+    /// 3D Tiles.
+    pub const ECEF: Crs = Crs::from_epsg(4978);
+
+    /// Creates the CRS with the given EPSG code.
+    pub const fn from_epsg(epsg: u32) -> Self {
+        Self { epsg }
+    }
+
+    /// Returns the EPSG code.
+    pub fn epsg(&self) -> u32 {
+        self.epsg
+    }
+
+    /// Parses a metadata value, text such as `EPSG:4978` or `4978`.
+    fn from_metadata_value(value: &[u8]) -> Option<Self> {
+        let text = std::str::from_utf8(value).ok()?.trim();
+        let code = text
+            .get(..5)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("EPSG:"))
+            .map_or(text, |_| &text[5..]);
+        code.parse().ok().map(Self::from_epsg)
+    }
+}
+
+impl fmt::Display for Crs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EPSG:{}", self.epsg)
+    }
+}
+
+/// Returns the CRS recorded in the geometry metadata of a Draco bitstream.
+///
+/// The CRS is read from a `crs` or `epsg` entry holding text such as
+/// `EPSG:4978` or `4978`. Returns `None` if the bitstream has no metadata
+/// or no such entry. Decoding attaches it to the result automatically, see
+/// [`DracoDecodeConfig::crs`](crate::DracoDecodeConfig::crs).
+pub fn crs_from_metadata(data: &[u8]) -> Option<Crs> {
+    let header = decode_header(data).ok()?;
+    if !header.has_metadata() || (header.version_major, header.version_minor) < METADATA_VERSION {
+        return None;
+    }
+
+    let mut reader = Reader::new(data);
+    for _ in 0..reader.varint()? {
+        reader.varint()?; // attribute unique id
+        reader.metadata(0)?;
+    }
+    for _ in 0..reader.varint()? {
+        let name = reader.name()?;
+        let value = reader.value()?;
+        if CRS_KEYS.contains(&name) {
+            return Crs::from_metadata_value(value);
+        }
+    }
+    None
+}
+
+/// Transforms positions from one coordinate reference system to another,
+/// for [`MeshDecodeResult::reproject`] and
+/// [`PointCloudDecodeResult::reproject`].
+///
+/// Closures taking and returning `[f64; 3]` implement this trait. Implement
+/// it directly to wrap a projection library and report the target CRS.
+pub trait CoordinateTransformer {
+    /// Transforms one position.
+    fn transform(&self, position: [f64; 3]) -> [f64; 3];
+
+    /// Returns the CRS of the transformed positions, `None` if unknown.
+    fn target_crs(&self) -> Option<Crs> {
+        None
+    }
+}
+
+impl<F: Fn([f64; 3]) -> [f64; 3]> CoordinateTransformer for F {
+    fn transform(&self, position: [f64; 3]) -> [f64; 3] {
+        self(position)
+    }
+}
+
+/// Transforms the first three components of `Float32` positions in place,
+/// returns `false` for other positions.
+fn reproject_positions(
+    data: &mut [u8],
+    attribute: Option<&MeshAttribute>,
+    transformer: &impl CoordinateTransformer,
+) -> bool {
+    let Some(attribute) =
+        attribute.filter(|a| a.data_type() == AttributeDataType::Float32 && a.dim() >= 3)
+    else {
+        return false;
+    };
+    let start = attribute.offset() as usize;
+    let Some(bytes) = start
+        .checked_add(attribute.lenght() as usize)
+        .and_then(|end| data.get_mut(start..end))
+    else {
+        return false;
+    };
+
+    for vertex in bytes.chunks_exact_mut(attribute.element_size()) {
+        let position: [f64; 3] = std::array::from_fn(|axis| {
+            let component = &vertex[axis * 4..axis * 4 + 4];
+            f32::from_ne_bytes(component.try_into().unwrap()) as f64
+        });
+        for (axis, value) in transformer.transform(position).into_iter().enumerate() {
+            vertex[axis * 4..axis * 4 + 4].copy_from_slice(&(value as f32).to_ne_bytes());
+        }
+    }
+    true
+}
+
+impl MeshDecodeResult {
+    /// Attaches a coordinate reference system to the result, for example one
+    /// known from the tileset the mesh came from.
+    pub fn with_crs(mut self, crs: Crs) -> Self {
+        self.config.set_crs(Some(crs));
+        self
+    }
+
+    /// Transforms the positions in place, for example from a projected CRS
+    /// to ECEF, computing in `f64` and storing the result as `f32`.
+    ///
+    /// Afterwards [`DracoDecodeConfig::crs`](crate::DracoDecodeConfig::crs)
+    /// is the [`CoordinateTransformer::target_crs`] of the transformer, so a
+    /// closure leaves the result without a CRS. Returns `false`, leaving the
+    /// result unchanged, if the positions are not `Float32` with at least
+    /// three components; decode them with
+    /// [`DecodeOptions::with_output_type`](crate::DecodeOptions::with_output_type)
+    /// to reproject quantized positions.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use draco_decoder::{CoordinateTransformer, Crs};
+    ///
+    /// struct UtmToEcef(proj::Proj);
+    ///
+    /// impl CoordinateTransformer for UtmToEcef {
+    ///     fn transform(&self, [x, y, z]: [f64; 3]) -> [f64; 3] {
+    ///         self.0.convert((x, y, z)).map_or([f64::NAN; 3], |(x, y, z)| [x, y, z])
+    ///     }
+    ///
+    ///     fn target_crs(&self) -> Option<Crs> {
+    ///         Some(Crs::ECEF)
+    ///     }
+    /// }
+    ///
+    /// if result.config.crs() == Some(Crs::from_epsg(32632)) {
+    ///     result.reproject(UtmToEcef(proj));
+    /// }
+    /// ```
+    pub fn reproject(&mut self, transformer: impl CoordinateTransformer) -> bool {
+        let index = self.config.find_attribute(AttributeSemantic::Position);
+        let attribute = index.and_then(|index| self.config.get_attribute(index));
+        if !reproject_positions(&mut self.data, attribute, &transformer) {
+            return false;
+        }
+        self.config.set_crs(transformer.target_crs());
+        true
+    }
+}
+
+impl PointCloudDecodeResult {
+    /// Attaches a coordinate reference system to the result.
+    pub fn with_crs(mut self, crs: Crs) -> Self {
+        self.config.set_crs(Some(crs));
+        self
+    }
+
+    /// Transforms the positions in place. See [`MeshDecodeResult::reproject`].
+    pub fn reproject(&mut self, transformer: impl CoordinateTransformer) -> bool {
+        let index = self.config.find_attribute(AttributeSemantic::Position);
+        let attribute = index.and_then(|index| self.config.get_attribute(index));
+        if !reproject_positions(&mut self.data, attribute, &transformer) {
+            return false;
+        }
+        self.config.set_crs(transformer.target_crs());
+        true
+    }
+}
@@ -0,0 +1,369 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fmt;
+
+use base64::Engine;
+use serde_json::Value;
+
+use crate::{
+    DecodeOptions, GltfAttributeError, MeshAttribute, MeshDecodeResult, map_gltf_attribute_indices,
+};
+
+const GLB_MAGIC: &[u8; 4] = b"glTF";
+const GLB_HEADER_LENGTH: usize = 12;
+const CHUNK_JSON: u32 = 0x4E4F_534A;
+const CHUNK_BIN: u32 = 0x004E_4942;
+const DRACO_EXTENSION: &str = "KHR_draco_mesh_compression";
+
+/// Errors reported by [`decode_gltf`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GltfError {
+    /// The GLB container is truncated or malformed.
+    InvalidGlb,
+    /// The glTF JSON could not be parsed.
+    InvalidJson(String),
+    /// A buffer or buffer view referenced by a Draco primitive is missing or
+    /// out of range.
+    InvalidBufferView(usize),
+    /// A buffer refers to an external file, only GLB and `data:` URIs are
+    /// embedded.
+    ExternalBuffer(usize),
+    /// The Draco data of a primitive could not be decoded.
+    DecodeFailed {
+        /// Index of the mesh in the glTF document.
+        mesh: usize,
+        /// Index of the primitive within the mesh.
+        primitive: usize,
+    },
+    /// The extension's attributes do not match the decoded mesh.
+    Attributes {
+        /// Index of the mesh in the glTF document.
+        mesh: usize,
+        /// Index of the primitive within the mesh.
+        primitive: usize,
+        /// The mismatch.
+        error: GltfAttributeError,
+    },
+}
+
+impl fmt::Display for GltfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GltfError::InvalidGlb => write!(f, "GLB container is malformed"),
+            GltfError::InvalidJson(err) => write!(f, "glTF JSON is invalid: {err}"),
+            GltfError::InvalidBufferView(index) => write!(f, "buffer view {index} is invalid"),
+            GltfError::ExternalBuffer(index) => {
+                write!(f, "buffer {index} refers to an external file")
+            }
+            GltfError::DecodeFailed { mesh, primitive } => {
+                write!(f, "failed to decode primitive {primitive} of mesh {mesh}")
+            }
+            GltfError::Attributes {
+                mesh,
+                primitive,
+                error,
+            } => write!(f, "primitive {primitive} of mesh {mesh}: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for GltfError {}
+
+/// A glTF accessor filled from a decoded Draco primitive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GltfAccessor {
+    /// Index of the accessor in the glTF document.
+    pub accessor: usize,
+    /// glTF attribute semantic, such as `POSITION`.
+    pub semantic: String,
+    /// Layout of the accessor data in the decoded buffer.
+    pub attribute: MeshAttribute,
+}
+
+/// A Draco compressed glTF primitive, decoded.
+#[derive(Debug, Clone)]
+pub struct DecodedPrimitive {
+    /// Index of the mesh in the glTF document.
+    pub mesh: usize,
+    /// Index of the primitive within the mesh.
+    pub primitive: usize,
+    /// The decoded mesh.
+    pub result: MeshDecodeResult,
+    /// Accessors of the primitive's attributes, in order of their semantic.
+    pub attributes: Vec<GltfAccessor>,
+    /// Index of the primitive's indices accessor, if it has one.
+    pub indices: Option<usize>,
+}
+
+impl DecodedPrimitive {
+    /// Returns the accessor for the given glTF semantic.
+    pub fn attribute(&self, semantic: &str) -> Option<&GltfAccessor> {
+        self.attributes
+            .iter()
+            .find(|accessor| accessor.semantic == semantic)
+    }
+
+    /// Returns the decoded data of a glTF accessor of this primitive.
+    ///
+    /// The indices accessor holds `u16` or `u32` values, see
+    /// [`DracoDecodeConfig::index_size_in_bytes`](crate::DracoDecodeConfig::index_size_in_bytes).
+    /// Returns `None` if the primitive does not use the accessor.
+    pub fn accessor_bytes(&self, accessor: usize) -> Option<&[u8]> {
+        if self.indices == Some(accessor) {
+            return self.result.index_bytes();
+        }
+        self.attributes
+            .iter()
+            .find(|filled| filled.accessor == accessor)
+            .and_then(|filled| filled.attribute.bytes_in(&self.result.data))
+    }
+}
+
+/// The Draco compressed primitives of a glTF document, decoded.
+#[derive(Debug, Clone)]
+pub struct DecodedGltf {
+    /// The parsed glTF JSON.
+    pub json: Value,
+    /// Every primitive using `KHR_draco_mesh_compression`, in document order.
+    pub primitives: Vec<DecodedPrimitive>,
+}
+
+/// A Draco primitive found in the document, before decoding.
+struct DracoPrimitive {
+    mesh: usize,
+    primitive: usize,
+    data: Vec<u8>,
+    /// glTF semantic to Draco unique id
+    ids: HashMap<String, u32>,
+    /// glTF semantic to accessor index
+    accessors: HashMap<String, usize>,
+    indices: Option<usize>,
+}
+
+/// Decodes every Draco compressed primitive of a glTF or GLB document.
+///
+/// The document is parsed, primitives using `KHR_draco_mesh_compression` are
+/// decoded with [`decode_mesh_with_config`](crate::decode_mesh_with_config),
+/// and each primitive's accessors are resolved to the decoded data, so the
+/// accessors without buffer views can be filled. Primitives without the
+/// extension are left to the caller.
+///
+/// Buffers must be embedded, either as the GLB binary chunk or as base64
+/// `data:` URIs.
+pub async fn decode_gltf(bytes: &[u8]) -> Result<DecodedGltf, GltfError> {
+    decode_gltf_with_options(bytes, &DecodeOptions::default()).await
+}
+
+/// Decodes the Draco primitives of a glTF document in the buffer layout
+/// requested by `options`.
+///
+/// The accessors in the glTF JSON are not rewritten, the layout of each
+/// accessor is described by [`GltfAccessor::attribute`].
+pub(crate) async fn decode_gltf_with_options(
+    bytes: &[u8],
+    options: &DecodeOptions,
+) -> Result<DecodedGltf, GltfError> {
+    let (json, primitives) = parse(bytes)?;
+    let mut decoded = Vec::with_capacity(primitives.len());
+    for primitive in primitives {
+        let result = crate::decode_mesh_with_options(&primitive.data, options).await;
+        decoded.push(resolve(primitive, result)?);
+    }
+    Ok(DecodedGltf {
+        json,
+        primitives: decoded,
+    })
+}
+
+/// Synchronous version of [`decode_gltf`] (native only).
+#[cfg(not(target_arch = "wasm32"))]
+pub fn decode_gltf_sync(bytes: &[u8]) -> Result<DecodedGltf, GltfError> {
+    decode_gltf_with_options_sync(bytes, &DecodeOptions::default())
+}
+
+/// Synchronous version of [`decode_gltf_with_options`] (native only).
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn decode_gltf_with_options_sync(
+    bytes: &[u8],
+    options: &DecodeOptions,
+) -> Result<DecodedGltf, GltfError> {
+    let (json, primitives) = parse(bytes)?;
+    let primitives = primitives
+        .into_iter()
+        .map(|primitive| {
+            let result = crate::ffi::decode_mesh_with_options(&primitive.data, options);
+            resolve(primitive, result)
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(DecodedGltf { json, primitives })
+}
+
+fn resolve(
+    primitive: DracoPrimitive,
+    result: Option<MeshDecodeResult>,
+) -> Result<DecodedPrimitive, GltfError> {
+    let (mesh, index) = (primitive.mesh, primitive.primitive);
+    let result = result.ok_or(GltfError::DecodeFailed {
+        mesh,
+        primitive: index,
+    })?;
+    let indices = map_gltf_attribute_indices(&primitive.ids, &result.config).map_err(|error| {
+        GltfError::Attributes {
+            mesh,
+            primitive: index,
+            error,
+        }
+    })?;
+
+    let mut attributes: Vec<GltfAccessor> = indices
+        .into_iter()
+        .filter_map(|(semantic, attribute)| {
+            Some(GltfAccessor {
+                accessor: *primitive.accessors.get(&semantic)?,
+                attribute: *result.config.get_attribute(attribute)?,
+                semantic,
+            })
+        })
+        .collect();
+    attributes.sort_by(|a, b| a.semantic.cmp(&b.semantic));
+
+    Ok(DecodedPrimitive {
+        mesh,
+        primitive: index,
+        result,
+        attributes,
+        indices: primitive.indices,
+    })
+}
+
+/// Splits a GLB container into its JSON and binary chunks, or returns the
+/// bytes as JSON if they are not a GLB.
+fn split_glb(bytes: &[u8]) -> Result<(&[u8], Option<&[u8]>), GltfError> {
+    if !bytes.starts_with(GLB_MAGIC) {
+        return Ok((bytes, None));
+    }
+
+    let read_u32 = |offset: usize| -> Result<u32, GltfError> {
+        bytes
+            .get(offset..offset + 4)
+            .map(|word| u32::from_le_bytes([word[0], word[1], word[2], word[3]]))
+            .ok_or(GltfError::InvalidGlb)
+    };
+    let length = (read_u32(8)? as usize).min(bytes.len());
+
+    let mut json = None;
+    let mut bin = None;
+    let mut offset = GLB_HEADER_LENGTH;
+    while offset + 8 <= length {
+        let chunk_length = read_u32(offset)? as usize;
+        let chunk_type = read_u32(offset + 4)?;
+        let start = offset + 8;
+        let chunk = bytes
+            .get(
+                start
+                    ..start
+                        .checked_add(chunk_length)
+                        .ok_or(GltfError::InvalidGlb)?,
+            )
+            .ok_or(GltfError::InvalidGlb)?;
+        match chunk_type {
+            CHUNK_JSON if json.is_none() => json = Some(chunk),
+            CHUNK_BIN if bin.is_none() => bin = Some(chunk),
+            _ => {}
+        }
+        offset = start + chunk_length;
+    }
+
+    Ok((json.ok_or(GltfError::InvalidGlb)?, bin))
+}
+
+/// Returns the contents of a buffer, which must be embedded.
+fn load_buffer(json: &Value, bin: Option<&[u8]>, index: usize) -> Result<Vec<u8>, GltfError> {
+    let buffer = json["buffers"]
+        .get(index)
+        .ok_or(GltfError::InvalidBufferView(index))?;
+    match buffer["uri"].as_str() {
+        None => bin
+            .map(<[u8]>::to_vec)
+            .ok_or(GltfError::InvalidBufferView(index)),
+        Some(uri) if uri.starts_with("data:") => {
+            let (_, encoded) = uri
+                .split_once(";base64,")
+                .ok_or(GltfError::ExternalBuffer(index))?;
+            base64::engine::general_purpose::STANDARD
+                .decode(encoded)
+                .map_err(|_| GltfError::InvalidBufferView(index))
+        }
+        Some(_) => Err(GltfError::ExternalBuffer(index)),
+    }
+}
+
+fn as_index(value: &Value) -> Option<usize> {
+    value.as_u64().map(|value| value as usize)
+}
+
+/// Parses the document and extracts the Draco data of every primitive.
+fn parse(bytes: &[u8]) -> Result<(Value, Vec<DracoPrimitive>), GltfError> {
+    let (json_bytes, bin) = split_glb(bytes)?;
+    let json: Value = serde_json::from_slice(json_bytes)
+        .map_err(|err| GltfError::InvalidJson(err.to_string()))?;
+
+    let mut buffers: HashMap<usize, Vec<u8>> = HashMap::new();
+    let mut primitives = Vec::new();
+    let meshes = json["meshes"].as_array().map_or(&[][..], Vec::as_slice);
+    for (mesh, mesh_json) in meshes.iter().enumerate() {
+        let mesh_primitives = mesh_json["primitives"]
+            .as_array()
+            .map_or(&[][..], Vec::as_slice);
+        for (primitive, primitive_json) in mesh_primitives.iter().enumerate() {
+            let extension = &primitive_json["extensions"][DRACO_EXTENSION];
+            if !extension.is_object() {
+                continue;
+            }
+
+            let view_index = as_index(&extension["bufferView"]).ok_or(GltfError::InvalidJson(
+                format!("primitive {primitive} of mesh {mesh} has no Draco buffer view"),
+            ))?;
+            let view = json["bufferViews"]
+                .get(view_index)
+                .ok_or(GltfError::InvalidBufferView(view_index))?;
+            let buffer_index =
+                as_index(&view["buffer"]).ok_or(GltfError::InvalidBufferView(view_index))?;
+            if let Entry::Vacant(entry) = buffers.entry(buffer_index) {
+                entry.insert(load_buffer(&json, bin, buffer_index)?);
+            }
+
+            let offset = as_index(&view["byteOffset"]).unwrap_or(0);
+            let length =
+                as_index(&view["byteLength"]).ok_or(GltfError::InvalidBufferView(view_index))?;
+            let data = buffers[&buffer_index]
+                .get(offset..offset.saturating_add(length))
+                .ok_or(GltfError::InvalidBufferView(view_index))?
+                .to_vec();
+
+            let ids = extension["attributes"]
+                .as_object()
+                .into_iter()
+                .flatten()
+                .filter_map(|(semantic, id)| Some((semantic.clone(), id.as_u64()? as u32)))
+                .collect();
+            let accessors = primitive_json["attributes"]
+                .as_object()
+                .into_iter()
+                .flatten()
+                .filter_map(|(semantic, accessor)| Some((semantic.clone(), as_index(accessor)?)))
+                .collect();
+
+            primitives.push(DracoPrimitive {
+                mesh,
+                primitive,
+                data,
+                ids,
+                accessors,
+                indices: as_index(&primitive_json["indices"]),
+            });
+        }
+    }
+
+    Ok((json, primitives))
+}
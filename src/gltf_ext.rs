@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{DracoDecodeConfig, MeshAttribute};
+
+/// Errors reported by [`map_gltf_attribute_indices`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GltfAttributeError {
+    /// The extension refers to a Draco unique id that no decoded attribute
+    /// has.
+    MissingAttribute {
+        /// glTF attribute semantic, such as `TEXCOORD_0`.
+        semantic: String,
+        /// Draco unique id the extension refers to.
+        unique_id: u32,
+    },
+    /// The decoded attribute has a component count the glTF semantic does
+    /// not allow.
+    ComponentCount {
+        /// glTF attribute semantic, such as `TEXCOORD_0`.
+        semantic: String,
+        /// Component count of the decoded attribute.
+        dim: u32,
+    },
+}
+
+impl fmt::Display for GltfAttributeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GltfAttributeError::MissingAttribute {
+                semantic,
+                unique_id,
+            } => write!(
+                f,
+                "glTF attribute {semantic} refers to missing Draco attribute {unique_id}"
+            ),
+            GltfAttributeError::ComponentCount { semantic, dim } => {
+                write!(f, "glTF attribute {semantic} cannot have {dim} components")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GltfAttributeError {}
+
+/// Component counts allowed for a glTF attribute semantic, `None` for
+/// application specific semantics.
+fn allowed_dims(semantic: &str) -> Option<&'static [u32]> {
+    let base = semantic.split_once('_').map_or(semantic, |(base, set)| {
+        if set.chars().all(|c| c.is_ascii_digit()) {
+            base
+        } else {
+            semantic
+        }
+    });
+    match base {
+        "POSITION" | "NORMAL" => Some(&[3]),
+        "TANGENT" | "JOINTS" | "WEIGHTS" => Some(&[4]),
+        "TEXCOORD" => Some(&[2]),
+        "COLOR" => Some(&[3, 4]),
+        _ => None,
+    }
+}
+
+/// Resolves the `attributes` map of a glTF `KHR_draco_mesh_compression`
+/// extension against the unique ids of a decoded mesh.
+///
+/// The extension maps each glTF semantic, such as `POSITION` or
+/// `TEXCOORD_0`, to the unique id of a Draco attribute. Each semantic is
+/// returned with the decoded attribute carrying that id, in order of the
+/// semantic names, so a loader can wire up its accessors from the attribute
+/// layouts. Semantics whose id is missing from the decoded mesh are left out;
+/// use [`map_gltf_attribute_indices`] to treat them as errors.
+pub fn map_gltf_attributes(
+    config: &DracoDecodeConfig,
+    ext_map: &HashMap<String, u32>,
+) -> Vec<(String, MeshAttribute)> {
+    let attributes = config.attributes();
+    let mut resolved: Vec<(String, MeshAttribute)> = ext_map
+        .iter()
+        .filter_map(|(semantic, &unique_id)| {
+            attributes
+                .iter()
+                .find(|attribute| attribute.unique_id() == unique_id)
+                .map(|attribute| (semantic.clone(), *attribute))
+        })
+        .collect();
+    resolved.sort_by(|a, b| a.0.cmp(&b.0));
+    resolved
+}
+
+/// Resolves the `attributes` map of a glTF `KHR_draco_mesh_compression`
+/// extension to attribute indices of a decoded mesh, validating every entry.
+///
+/// The extension maps each glTF semantic, such as `POSITION` or
+/// `TEXCOORD_0`, to the unique id of a Draco attribute, while the decoded
+/// config lists attributes by index. The returned map takes each semantic to
+/// the index to use with
+/// [`DracoDecodeConfig::get_attribute`](crate::DracoDecodeConfig::get_attribute)
+/// or [`MeshDecodeResult::view`](crate::MeshDecodeResult::view).
+///
+/// Returns an error if a unique id is missing from the decoded mesh, for
+/// example because the attribute was skipped, or if a decoded attribute has
+/// a component count its glTF semantic does not allow. Application specific
+/// semantics starting with `_` accept any component count.
+///
+/// # Example
+///
+/// ```ignore
+/// use std::collections::HashMap;
+/// use draco_decoder::map_gltf_attribute_indices;
+///
+/// let extension = HashMap::from([("POSITION".to_string(), 0), ("NORMAL".to_string(), 1)]);
+/// let indices = map_gltf_attribute_indices(&extension, &result.config)?;
+/// let normals = result.view::<f32>(indices["NORMAL"]);
+/// ```
+pub fn map_gltf_attribute_indices(
+    ext_map: &HashMap<String, u32>,
+    config: &DracoDecodeConfig,
+) -> Result<HashMap<String, usize>, GltfAttributeError> {
+    let attributes = config.attributes();
+    let mut indices = HashMap::with_capacity(ext_map.len());
+
+    // Sorted, so the same error is reported for every iteration order
+    let mut semantics: Vec<(&String, u32)> = ext_map
+        .iter()
+        .map(|(semantic, &unique_id)| (semantic, unique_id))
+        .collect();
+    semantics.sort();
+
+    for (semantic, unique_id) in semantics {
+        let Some(index) = attributes
+            .iter()
+            .position(|attribute| attribute.unique_id() == unique_id)
+        else {
+            return Err(GltfAttributeError::MissingAttribute {
+                semantic: semantic.clone(),
+                unique_id,
+            });
+        };
+
+        let dim = attributes[index].dim();
+        if allowed_dims(semantic).is_some_and(|dims| !dims.contains(&dim)) {
+            return Err(GltfAttributeError::ComponentCount {
+                semantic: semantic.clone(),
+                dim,
+            });
+        }
+        indices.insert(semantic.clone(), index);
+    }
+
+    Ok(indices)
+}
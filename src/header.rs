@@ -0,0 +1,117 @@
+use crate::DecodeError;
+
+const DRACO_MAGIC: &[u8; 5] = b"DRACO";
+pub(crate) const HEADER_LENGTH: usize = 11;
+pub(crate) const METADATA_FLAG_MASK: u16 = 0x8000;
+
+/// Kind of geometry stored in a Draco bitstream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GeometryType {
+    /// A point cloud without connectivity.
+    PointCloud,
+    /// A triangular mesh.
+    Mesh,
+}
+
+/// Method used to encode the geometry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EncodingMethod {
+    /// Sequential encoding of meshes or point clouds.
+    Sequential,
+    /// Edgebreaker connectivity encoding of meshes.
+    Edgebreaker,
+    /// KD-tree encoding of point clouds.
+    KdTree,
+}
+
+/// Information parsed from the header of a Draco bitstream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DracoHeaderInfo {
+    /// Major bitstream version.
+    pub version_major: u8,
+    /// Minor bitstream version.
+    pub version_minor: u8,
+    /// Kind of geometry stored in the bitstream.
+    pub geometry_type: GeometryType,
+    /// Method used to encode the geometry.
+    pub encoding_method: EncodingMethod,
+    /// Raw header flags.
+    pub flags: u16,
+}
+
+impl DracoHeaderInfo {
+    /// Returns `true` if the bitstream carries geometry metadata.
+    pub fn has_metadata(&self) -> bool {
+        self.flags & METADATA_FLAG_MASK != 0
+    }
+}
+
+/// Parses the header of a Draco bitstream without decoding the geometry.
+///
+/// Only the first 11 bytes are inspected, which makes this cheap enough to
+/// validate and route payloads before committing to a full decode. A valid
+/// header does not guarantee that the rest of the bitstream decodes.
+///
+/// # Example
+///
+/// ```
+/// use draco_decoder::{GeometryType, decode_header};
+///
+/// let data = [b'D', b'R', b'A', b'C', b'O', 2, 2, 1, 1, 0, 0];
+/// let header = decode_header(&data).unwrap();
+/// assert_eq!(header.geometry_type, GeometryType::Mesh);
+/// ```
+pub fn decode_header(data: &[u8]) -> Result<DracoHeaderInfo, DecodeError> {
+    if !data.starts_with(&DRACO_MAGIC[..data.len().min(DRACO_MAGIC.len())]) {
+        return Err(DecodeError::NotDraco);
+    }
+    if data.len() < HEADER_LENGTH {
+        return Err(DecodeError::TruncatedHeader);
+    }
+
+    let geometry_type = match data[7] {
+        0 => GeometryType::PointCloud,
+        1 => GeometryType::Mesh,
+        other => return Err(DecodeError::UnknownEncoderType(other)),
+    };
+
+    let encoding_method = match (geometry_type, data[8]) {
+        (_, 0) => EncodingMethod::Sequential,
+        (GeometryType::Mesh, 1) => EncodingMethod::Edgebreaker,
+        (GeometryType::PointCloud, 1) => EncodingMethod::KdTree,
+        (_, other) => return Err(DecodeError::UnknownEncodingMethod(other)),
+    };
+
+    Ok(DracoHeaderInfo {
+        version_major: data[5],
+        version_minor: data[6],
+        geometry_type,
+        encoding_method,
+        flags: u16::from_le_bytes([data[9], data[10]]),
+    })
+}
+
+/// Returns `true` if the data starts with a complete Draco header.
+///
+/// This is a cheap check for dispatching mixed payloads and does not validate
+/// the rest of the bitstream.
+pub fn is_draco(data: &[u8]) -> bool {
+    decode_header(data).is_ok()
+}
+
+/// Detects the kind of geometry stored in Draco encoded data.
+///
+/// Returns `None` if the data does not start with a valid Draco header.
+///
+/// # Example
+///
+/// ```
+/// use draco_decoder::{GeometryType, sniff};
+///
+/// let point_cloud = [b'D', b'R', b'A', b'C', b'O', 2, 3, 0, 1, 0, 0];
+/// assert_eq!(sniff(&point_cloud), Some(GeometryType::PointCloud));
+/// assert_eq!(sniff(b"glTF"), None);
+/// ```
+pub fn sniff(data: &[u8]) -> Option<GeometryType> {
+    decode_header(data).ok().map(|header| header.geometry_type)
+}
@@ -0,0 +1,107 @@
+use crate::{IndexBuffer, MeshDecodeResult};
+
+/// Compact encodings of an index buffer, see
+/// [`MeshDecodeResult::indices_compressed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IndexEncoding {
+    /// The index count followed by the difference of each index to the
+    /// previous one, zigzag encoded so small negative steps stay small, as
+    /// LEB128 varints.
+    ///
+    /// Indices decoded by Draco mostly refer to recently used vertices, so
+    /// most differences fit into one byte.
+    DeltaVarint,
+}
+
+impl MeshDecodeResult {
+    /// Returns the indices in a compact encoding for storing the decoded mesh.
+    ///
+    /// The encoding is lossless and much cheaper to reverse with
+    /// [`decode_indices`] than decoding the Draco bitstream again.
+    ///
+    /// Returns `None` if the index data lies outside of the decoded buffer.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use draco_decoder::{IndexEncoding, decode_indices};
+    ///
+    /// let compressed = result.indices_compressed(IndexEncoding::DeltaVarint).unwrap();
+    /// let indices = decode_indices(&compressed, IndexEncoding::DeltaVarint).unwrap();
+    /// ```
+    pub fn indices_compressed(&self, encoding: IndexEncoding) -> Option<Vec<u8>> {
+        let indices = self.indices().map(IndexBuffer::into_u32)?;
+        Some(encode_indices(&indices, encoding))
+    }
+}
+
+/// Encodes indices with the given encoding.
+pub fn encode_indices(indices: &[u32], encoding: IndexEncoding) -> Vec<u8> {
+    match encoding {
+        IndexEncoding::DeltaVarint => {
+            let mut out = Vec::with_capacity(indices.len() + 5);
+            write_varint(&mut out, indices.len() as u32);
+            let mut previous = 0u32;
+            for &index in indices {
+                let delta = index.wrapping_sub(previous) as i32;
+                write_varint(&mut out, ((delta << 1) ^ (delta >> 31)) as u32);
+                previous = index;
+            }
+            out
+        }
+    }
+}
+
+/// Decodes indices encoded by [`MeshDecodeResult::indices_compressed`] or
+/// [`encode_indices`].
+///
+/// Returns `None` if the data is truncated, has trailing bytes or holds a
+/// malformed varint.
+pub fn decode_indices(data: &[u8], encoding: IndexEncoding) -> Option<Vec<u32>> {
+    match encoding {
+        IndexEncoding::DeltaVarint => {
+            let mut data = data;
+            let count = read_varint(&mut data)? as usize;
+            // Every index takes at least one byte
+            if count > data.len() {
+                return None;
+            }
+
+            let mut indices = Vec::with_capacity(count);
+            let mut previous = 0u32;
+            for _ in 0..count {
+                let zigzag = read_varint(&mut data)?;
+                let delta = ((zigzag >> 1) as i32) ^ -((zigzag & 1) as i32);
+                previous = previous.wrapping_add(delta as u32);
+                indices.push(previous);
+            }
+            data.is_empty().then_some(indices)
+        }
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u32) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn read_varint(data: &mut &[u8]) -> Option<u32> {
+    let mut value = 0u32;
+    for shift in (0..35).step_by(7) {
+        let (&byte, rest) = data.split_first()?;
+        *data = rest;
+        let bits = (byte & 0x7f) as u32;
+        if shift == 28 && bits > 0x0f {
+            return None;
+        }
+        value |= bits << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
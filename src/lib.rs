@@ -13,16 +13,190 @@
 //!     println!("Indices: {}", result.config.index_count());
 //! }
 //! ```
+//!
+//! [`load`] decodes `.drc`, GLB and pnts inputs in one call, and
+//! [`prelude`] imports the commonly used items.
 
+#[cfg(feature = "bake-ao")]
+mod ambient_occlusion;
+#[cfg(not(target_arch = "wasm32"))]
+mod analyze;
+#[cfg(feature = "audit")]
+mod audit;
+#[cfg(feature = "bevy")]
+mod bevy_loader;
+mod bounds;
+#[cfg(any(feature = "bake-ao", feature = "bake-sdf"))]
+mod bvh;
+mod cache;
+mod cancel;
+mod cast;
+mod chunk;
+mod compat;
+mod components;
+mod curvature;
+mod decoder;
+#[cfg(not(target_arch = "wasm32"))]
+mod diagnostics;
+#[cfg(not(target_arch = "wasm32"))]
+mod encoder;
+#[cfg(feature = "tiles3d")]
+mod enu;
+mod error;
+#[cfg(not(target_arch = "wasm32"))]
+mod executor;
+pub mod export;
 #[cfg(not(target_arch = "wasm32"))]
 mod ffi;
+#[cfg(not(target_arch = "wasm32"))]
+mod fidelity;
+#[cfg(not(target_arch = "wasm32"))]
+mod fuzz;
+mod georef;
+#[cfg(feature = "gltf")]
+mod gltf;
+mod gltf_ext;
+mod header;
+mod index_codec;
+mod load;
+mod math;
+mod options;
+mod outliers;
+mod planes;
+mod point_cloud;
+pub mod postprocess;
+mod predicates;
+#[cfg(not(target_arch = "wasm32"))]
+mod prefetch;
+pub mod prelude;
+mod preview;
+mod progress;
+mod progressive;
+mod quota;
+#[cfg(feature = "repair")]
+mod repair;
+#[cfg(feature = "replay")]
+mod replay;
+#[cfg(feature = "bake-sdf")]
+mod sdf;
+#[cfg(feature = "serde")]
+pub mod serde_base64;
+mod split;
+#[cfg(all(feature = "stress", not(target_arch = "wasm32")))]
+pub mod stress;
+mod summary;
+#[cfg(feature = "thumbnail")]
+mod thumbnail;
+#[cfg(feature = "tiles3d")]
+mod tiles3d;
 pub mod utils;
+mod vector;
+mod vertex_layout;
+mod voxel;
+mod warning;
 #[cfg(target_arch = "wasm32")]
 mod wasm;
+#[cfg(all(feature = "wgpu", not(target_arch = "wasm32")))]
+mod wgpu_upload;
 
+#[cfg(feature = "bake-ao")]
+pub use ambient_occlusion::AmbientOcclusionParams;
+#[cfg(not(target_arch = "wasm32"))]
+pub use analyze::{AttributeSize, CompressionReport, analyze};
+#[cfg(all(feature = "audit", not(target_arch = "wasm32")))]
+pub use audit::decode_mesh_audited_sync;
+#[cfg(feature = "audit")]
+pub use audit::{AuditRecord, decode_mesh_audited};
+#[cfg(feature = "bevy")]
+pub use bevy_loader::{DracoLoaderError, DracoMeshLoader};
+pub use cache::{CacheStats, DecodedMeshCache};
+pub use cancel::CancellationToken;
+pub use cast::{CastElement, CastError, DecodedAttributes, attribute_as_slice};
+pub use chunk::{MeshChunk, MeshChunks};
+#[cfg(not(target_arch = "wasm32"))]
+pub use compat::decode_mesh_compat_sync;
+pub use compat::{CompatDecodeResult, CompatShim, decode_mesh_compat};
+pub use curvature::VertexCurvature;
+pub use decoder::MeshDecoder;
+#[cfg(not(target_arch = "wasm32"))]
+pub use decoder::NativeDecoder;
+#[cfg(not(target_arch = "wasm32"))]
+pub use diagnostics::{disable_crash_diagnostics, enable_crash_diagnostics, export_reproducer};
+#[cfg(not(target_arch = "wasm32"))]
+pub use encoder::{
+    EncodeAttribute, EncodeConfig, encode_mesh, encode_mesh_with_config, encode_point_cloud,
+    encode_point_cloud_with_config, transcode,
+};
+#[cfg(feature = "tiles3d")]
+pub use enu::EnuFrame;
+pub use error::DecodeError;
+#[cfg(not(target_arch = "wasm32"))]
+pub use executor::AsyncExecutor;
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+pub use executor::BlockingExecutor;
+#[cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
+pub use executor::TokioExecutor;
+pub use export::{
+    ByteOrder, EnginePackError, EngineVertexFormat, PackedFormat, PlyFormat, VertexPacker,
+    write_glb, write_obj, write_ply, write_point_cloud_ply, write_stl_binary,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use ffi::MeshHandle;
+#[cfg(not(target_arch = "wasm32"))]
+pub use fidelity::{
+    AttributeFidelity, FidelityReport, QualityTarget, Tolerances, auto_tune_encode, roundtrip_check,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use fuzz::decode_unchecked_input;
+pub use georef::{CoordinateTransformer, Crs, crs_from_metadata};
+#[cfg(all(feature = "gltf", not(target_arch = "wasm32")))]
+pub use gltf::decode_gltf_sync;
+#[cfg(feature = "gltf")]
+pub use gltf::{DecodedGltf, DecodedPrimitive, GltfAccessor, GltfError, decode_gltf};
+pub use gltf_ext::{GltfAttributeError, map_gltf_attribute_indices, map_gltf_attributes};
+pub use header::{DracoHeaderInfo, EncodingMethod, GeometryType, decode_header, is_draco, sniff};
+pub use index_codec::{IndexEncoding, decode_indices, encode_indices};
+#[cfg(not(target_arch = "wasm32"))]
+pub use load::load_sync;
+pub use load::{
+    Geometry, LoadError, LoadOptions, LoadPreset, LoadSource, SourceFormat, load, sniff_format,
+};
+pub use options::{AxisConvention, DecodeOptions, IndexFormat, LimitKind, Limits};
+pub use outliers::StatisticalOutlierParams;
+pub use planes::{DetectedPlane, PlaneDetectionParams};
+pub use point_cloud::{PointCloudDecodeConfig, PointCloudDecodeResult};
+#[cfg(not(target_arch = "wasm32"))]
+pub use prefetch::{PrefetchedMesh, prefetch_config};
+pub use progress::DecodeStage;
+pub use progressive::{COARSE_SAMPLE_POINTS, PartialGeometry};
+pub use quota::{QuotaDecision, QuotaProvider};
+#[cfg(feature = "replay")]
+pub use replay::ReplayDecoder;
+#[cfg(feature = "bake-sdf")]
+pub use sdf::SignedDistanceField;
+pub use split::SplitMesh;
+#[cfg(feature = "thumbnail")]
+pub use thumbnail::{GBuffer, PreviewCamera, render_gbuffer, render_preview};
+#[cfg(feature = "tiles3d")]
+pub use tiles3d::{
+    B3dmTile, I3dmTile, InstanceTransform, PntsTile, Tiles3dError, decode_b3dm, decode_i3dm,
+    decode_pnts,
+};
+#[cfg(all(feature = "tiles3d", not(target_arch = "wasm32")))]
+pub use tiles3d::{decode_b3dm_sync, decode_i3dm_sync, decode_pnts_sync};
 pub use utils::{
-    AttributeDataType, AttributeValues, DracoDecodeConfig, MeshAttribute, MeshDecodeResult,
+    AttributeComponent, AttributeDataType, AttributeSemantic, AttributeValues, AttributeView,
+    DracoDecodeConfig, IndexBuffer, LayoutDescriptor, MAX_QUANTIZED_COMPONENTS, MeshAttribute,
+    MeshDecodeResult, OctahedronInfo, QuantizationInfo,
 };
+pub use vector::VectorLike;
+pub use vertex_layout::{
+    VertexAttributeLayout, VertexBufferLayout, VertexFormat, VertexLayoutMode,
+};
+pub use voxel::{VoxelGrid, VoxelMode};
+pub use warning::{DecodeWarning, WarningAction, WarningReason};
+#[cfg(target_arch = "wasm32")]
+pub use wasm::{DecodeStrategy, EnvironmentReport, ReadyDecoder, WasmDecoder, environment_report};
 
 /// Decodes a Draco compressed mesh asynchronously.
 ///
@@ -41,6 +215,17 @@ pub use utils::{
 ///
 /// Returns `None` if decoding fails.
 ///
+/// Meshes without faces or vertices decode successfully: the result has an
+/// empty index section, zero-length attributes and a `buffer_size` of 0 where
+/// applicable, so no special casing is needed.
+///
+/// The Draco decode itself is blocking and runs on the polling thread. With
+/// the `tokio` feature, decodes polled within a Tokio runtime run on its
+/// blocking thread pool instead, which keeps the reactor responsive while
+/// large meshes decode. Other runtimes can pass an [`AsyncExecutor`] with
+/// [`DecodeOptions::with_executor`] to [`decode_mesh_with_options`] and the
+/// other native async entry points taking options.
+///
 /// # Example
 ///
 /// ```ignore
@@ -56,7 +241,30 @@ pub use utils::{
 /// ```
 #[cfg(not(target_arch = "wasm32"))]
 pub async fn decode_mesh_with_config(data: &[u8]) -> Option<MeshDecodeResult> {
-    ffi::decode_mesh_with_config(data)
+    offload(data, None, ffi::decode_mesh_with_config).await
+}
+
+/// Runs a native decode for an async entry point.
+///
+/// The decode runs on a copy of `data` on the executor returned by
+/// [`executor::current_executor`] for the `executor` of the options, so
+/// large meshes do not stall the reactor, or inline on the polling thread if
+/// there is none.
+#[cfg(not(target_arch = "wasm32"))]
+async fn offload<T: Send + 'static>(
+    data: &[u8],
+    executor: Option<&executor::Executor>,
+    decode: impl FnOnce(&[u8]) -> Option<T> + Send + 'static,
+) -> Option<T> {
+    match executor::current_executor(executor) {
+        Some(executor) => {
+            let data = data.to_vec();
+            executor::run_blocking(&*executor, move || decode(&data))
+                .await
+                .flatten()
+        }
+        None => decode(data),
+    }
 }
 
 /// Decodes a Draco compressed mesh synchronously (native only).
@@ -74,7 +282,8 @@ pub async fn decode_mesh_with_config(data: &[u8]) -> Option<MeshDecodeResult> {
 /// - `data` - The decoded mesh buffer
 /// - `config` - Metadata about the decoded mesh
 ///
-/// Returns `None` if decoding fails.
+/// Returns `None` if decoding fails. Empty meshes decode to an empty but
+/// consistent result, as described for [`decode_mesh_with_config`].
 #[cfg(not(target_arch = "wasm32"))]
 pub fn decode_mesh_with_config_sync(data: &[u8]) -> Option<MeshDecodeResult> {
     ffi::decode_mesh_with_config(data)
@@ -92,11 +301,468 @@ pub fn decode_mesh_with_config_sync(data: &[u8]) -> Option<MeshDecodeResult> {
 /// # Returns
 ///
 /// Returns `Some(MeshDecodeResult)` on success, `None` if decoding fails.
+/// Empty meshes decode to an empty but consistent result.
 #[cfg(target_arch = "wasm32")]
 pub async fn decode_mesh_with_config(data: &[u8]) -> Option<MeshDecodeResult> {
     wasm::decode_mesh_wasm_worker_with_config(data).await
 }
 
+/// Decodes a Draco compressed mesh asynchronously with the buffer layout
+/// requested by `options`.
+///
+/// With an offset alignment, every attribute starts at a multiple of the
+/// alignment and the reported offsets, lengths and `buffer_size` account for
+/// the zeroed padding. The index width can be fixed with an [`IndexFormat`],
+/// and attributes can be converted to other data types with
+/// [`DecodeOptions::with_output_type`]. On WASM the worker writes the
+/// converted attributes, the rest of its output is rewritten in the requested
+/// layout after decoding.
+///
+/// Returns `None` if decoding fails, the mesh exceeds the [`Limits`] set with
+/// [`DecodeOptions::with_limits`], a quota set with
+/// [`DecodeOptions::with_quota`] denies the decode or the
+/// [`CancellationToken`] set with [`DecodeOptions::with_cancellation`] is
+/// cancelled. [`try_decode_mesh_with_options`] reports why.
+pub async fn decode_mesh_with_options(
+    data: &[u8],
+    options: &DecodeOptions,
+) -> Option<MeshDecodeResult> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let executor = options.executor().cloned();
+        let options = options.clone();
+        return offload(data, executor.as_ref(), move |data| {
+            ffi::decode_mesh_with_options(data, &options)
+        })
+        .await;
+    }
+    #[cfg(target_arch = "wasm32")]
+    return WasmDecoder::init()
+        .await?
+        .decode_mesh_with_options(data, options)
+        .await;
+}
+
+/// Decodes a Draco compressed mesh synchronously with the buffer layout
+/// requested by `options` (native only).
+///
+/// This is the decode the native async functions run, without any async
+/// machinery around it. The call is purely CPU-bound: it neither performs
+/// I/O nor waits on other decodes or an async runtime, so it is safe to call
+/// from rayon workers, for example inside `par_iter` or a `rayon::scope`, as
+/// well as from any other thread pool. Large meshes take tens to hundreds of
+/// milliseconds, so avoid calling it on an async reactor thread and use
+/// [`decode_mesh_with_options`] there. See [`decode_mesh_with_options`] for
+/// the layout options and when `None` is returned.
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::{DecodeOptions, decode_mesh_with_options_sync};
+/// use rayon::prelude::*;
+///
+/// let options = DecodeOptions::new().with_offset_alignment(4);
+/// let meshes: Vec<_> = tiles
+///     .par_iter()
+///     .filter_map(|tile| decode_mesh_with_options_sync(tile, &options))
+///     .collect();
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+pub fn decode_mesh_with_options_sync(
+    data: &[u8],
+    options: &DecodeOptions,
+) -> Option<MeshDecodeResult> {
+    ffi::decode_mesh_with_options(data, options)
+}
+
+/// Decodes a Draco compressed mesh asynchronously like
+/// [`decode_mesh_with_options`], reporting why decoding failed.
+///
+/// Data without a valid Draco header fails with the header error of
+/// [`decode_header`], and a mesh exceeding the [`Limits`] set with
+/// [`DecodeOptions::with_limits`] with [`DecodeError::LimitExceeded`]. With
+/// [`DecodeOptions::validate_indices`], an index past the last vertex fails
+/// with [`DecodeError::IndexOutOfBounds`], and [`IndexFormat::U16`] for a mesh
+/// with more than 65536 vertices fails with [`DecodeError::IndicesDoNotFit`].
+/// A quota set with [`DecodeOptions::with_quota`] that denies the decode
+/// fails with [`DecodeError::QuotaDenied`]. Any other failure, including a
+/// cancelled decode, is [`DecodeError::DecodeFailed`].
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::{DecodeError, DecodeOptions, Limits, try_decode_mesh_with_options};
+///
+/// let limits = Limits {
+///     max_vertices: 5_000_000,
+///     max_buffer_bytes: 256 << 20,
+///     ..Limits::default()
+/// };
+/// let options = DecodeOptions::new().with_limits(limits);
+/// match try_decode_mesh_with_options(&upload, &options).await {
+///     Ok(mesh) => store(mesh),
+///     Err(err @ DecodeError::LimitExceeded { .. }) => reject(err),
+///     Err(err) => fail(err),
+/// }
+/// ```
+pub async fn try_decode_mesh_with_options(
+    data: &[u8],
+    options: &DecodeOptions,
+) -> Result<MeshDecodeResult, DecodeError> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let executor = options.executor().cloned();
+        let options = options.clone();
+        return offload(data, executor.as_ref(), move |data| {
+            Some(try_decode_mesh_with_options_sync(data, &options))
+        })
+        .await
+        .unwrap_or(Err(DecodeError::DecodeFailed));
+    }
+    #[cfg(target_arch = "wasm32")]
+    return WasmDecoder::init()
+        .await
+        .ok_or(DecodeError::DecodeFailed)?
+        .try_decode_mesh_with_options(data, options)
+        .await;
+}
+
+/// Synchronous version of [`try_decode_mesh_with_options`] (native only).
+#[cfg(not(target_arch = "wasm32"))]
+pub fn try_decode_mesh_with_options_sync(
+    data: &[u8],
+    options: &DecodeOptions,
+) -> Result<MeshDecodeResult, DecodeError> {
+    ffi::try_decode_mesh_with_options(data, options)
+}
+
+/// Decodes a stream of Draco compressed meshes, with at most `concurrency`
+/// decodes in flight (requires the `stream` feature).
+///
+/// Results are yielded in the order of the inputs. The next input is only
+/// pulled once a decode slot is free, so a stream of tile fetches is
+/// throttled by decoding. A `concurrency` of 0 is treated as 1. Each input
+/// fails on its own, like [`decode_mesh_blocking_chunks`]: an invalid header
+/// reports the header error and a body Draco cannot decode reports
+/// [`DecodeError::DecodeFailed`].
+///
+/// On WASM each decode runs in a worker, so several decode at once. On
+/// native targets the decodes run concurrently on the Tokio blocking thread
+/// pool with the `tokio` feature, see [`decode_mesh_with_config`], and one
+/// after another on the polling thread otherwise.
+///
+/// # Example
+///
+/// ```ignore
+/// use futures_util::StreamExt;
+///
+/// let fetches = futures_util::stream::iter(urls).then(fetch_bytes);
+/// let mut meshes = std::pin::pin!(draco_decoder::decode_stream(fetches, 4));
+/// while let Some(result) = meshes.next().await {
+///     upload(result?);
+/// }
+/// ```
+#[cfg(feature = "stream")]
+pub fn decode_stream(
+    inputs: impl futures_util::Stream<Item = Vec<u8>>,
+    concurrency: usize,
+) -> impl futures_util::Stream<Item = Result<MeshDecodeResult, DecodeError>> {
+    use futures_util::StreamExt;
+
+    inputs
+        .map(|data| async move {
+            decode_header(&data)?;
+            decode_mesh_with_config(&data)
+                .await
+                .ok_or(DecodeError::DecodeFailed)
+        })
+        .buffered(concurrency.max(1))
+}
+
+/// Decodes a Draco compressed point cloud asynchronously.
+///
+/// All attributes (positions, colors, normals, intensity and other generic
+/// attributes) are decoded into a single buffer, tightly packed in order of
+/// their unique id. Mesh bitstreams are accepted as well, in which case the
+/// connectivity is ignored.
+///
+/// # Returns
+///
+/// Returns `Some(PointCloudDecodeResult)` on success, containing:
+/// - `data` - The decoded attribute buffer
+/// - `config` - The point count and attribute layouts
+///
+/// Returns `None` if decoding fails.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn decode_point_cloud_with_config(data: &[u8]) -> Option<PointCloudDecodeResult> {
+    offload(data, None, ffi::decode_point_cloud_with_config).await
+}
+
+/// Decodes a Draco compressed point cloud asynchronously (WASM).
+///
+/// Like [`decode_mesh_with_config`], the point cloud is decoded in a
+/// JavaScript Worker. The result has the same layout as on native targets.
+///
+/// Returns `Some(PointCloudDecodeResult)` on success, `None` if decoding fails.
+#[cfg(target_arch = "wasm32")]
+pub async fn decode_point_cloud_with_config(data: &[u8]) -> Option<PointCloudDecodeResult> {
+    wasm::decode_point_cloud_wasm_worker_with_config(data).await
+}
+
+/// Decodes a Draco compressed point cloud synchronously (native only).
+///
+/// See [`decode_point_cloud_with_config`] for details.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn decode_point_cloud_with_config_sync(data: &[u8]) -> Option<PointCloudDecodeResult> {
+    ffi::decode_point_cloud_with_config(data)
+}
+
+/// Decodes a Draco compressed point cloud asynchronously, honoring the
+/// [`Limits`], quota, [`CancellationToken`] and progress callback set on
+/// `options` and reporting why decoding failed.
+///
+/// The layout options of [`DecodeOptions`] only apply to meshes, the point
+/// cloud is decoded like [`decode_point_cloud_with_config`]. The limit on
+/// vertices applies to the points. Errors are reported like
+/// [`try_decode_mesh_with_options`].
+pub async fn try_decode_point_cloud_with_options(
+    data: &[u8],
+    options: &DecodeOptions,
+) -> Result<PointCloudDecodeResult, DecodeError> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let executor = options.executor().cloned();
+        let options = options.clone();
+        return offload(data, executor.as_ref(), move |data| {
+            Some(ffi::try_decode_point_cloud(data, &options))
+        })
+        .await
+        .unwrap_or(Err(DecodeError::DecodeFailed));
+    }
+    #[cfg(target_arch = "wasm32")]
+    return WasmDecoder::init()
+        .await
+        .ok_or(DecodeError::DecodeFailed)?
+        .try_decode_point_cloud_with_options(data, options)
+        .await;
+}
+
+/// Synchronous version of [`try_decode_point_cloud_with_options`] (native
+/// only).
+#[cfg(not(target_arch = "wasm32"))]
+pub fn try_decode_point_cloud_with_options_sync(
+    data: &[u8],
+    options: &DecodeOptions,
+) -> Result<PointCloudDecodeResult, DecodeError> {
+    ffi::try_decode_point_cloud(data, options)
+}
+
+/// Decodes a Draco compressed mesh for a quick, low fidelity preview.
+///
+/// Only the indices and positions are kept: normals, texture coordinates and
+/// other attributes are skipped. If `max_vertices` is set, the mesh is
+/// additionally decimated by vertex clustering until it has at most that many
+/// vertices, which keeps thumbnails tiny regardless of the source size.
+///
+/// On native targets the skipped attributes are never converted or copied,
+/// which makes this considerably faster than a full decode. On WASM the mesh
+/// is decoded in full and reduced afterwards.
+///
+/// Returns `None` if decoding fails.
+pub async fn decode_mesh_fast_preview(
+    data: &[u8],
+    max_vertices: Option<u32>,
+) -> Option<MeshDecodeResult> {
+    #[cfg(not(target_arch = "wasm32"))]
+    let result = offload(data, None, ffi::decode_mesh_positions).await?;
+    #[cfg(target_arch = "wasm32")]
+    let result = wasm::decode_mesh_wasm_worker_with_config(data)
+        .await?
+        .into_positions_only();
+
+    Some(match max_vertices {
+        Some(max_vertices) => result.decimate(max_vertices),
+        None => result,
+    })
+}
+
+/// Decodes a Draco compressed mesh for a quick preview synchronously (native
+/// only).
+///
+/// See [`decode_mesh_fast_preview`] for details.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn decode_mesh_fast_preview_sync(
+    data: &[u8],
+    max_vertices: Option<u32>,
+) -> Option<MeshDecodeResult> {
+    let result = ffi::decode_mesh_positions(data)?;
+    Some(match max_vertices {
+        Some(max_vertices) => result.decimate(max_vertices),
+        None => result,
+    })
+}
+
+/// Decodes a Draco compressed mesh in stages, from coarse to fine.
+///
+/// `on_partial` is first called with a [`PartialGeometry::Coarse`] sample of
+/// at most [`COARSE_SAMPLE_POINTS`] positions and then with the
+/// [`PartialGeometry::Full`] mesh, so viewers can show a point preview early.
+///
+/// Draco bitstreams, including sequentially encoded ones, can only be
+/// decompressed as a whole. On native targets the sample is taken right after
+/// decompression, before the remaining attributes are converted and copied.
+/// On WASM the worker returns the full mesh at once, and the sample is
+/// derived from it just before it is reported.
+///
+/// Returns `false` if decoding fails, in which case the full mesh is never
+/// reported.
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::{PartialGeometry, decode_progressive};
+///
+/// decode_progressive(&data, |partial| match partial {
+///     PartialGeometry::Coarse { positions, .. } => draw_points(&positions),
+///     PartialGeometry::Full(mesh) => draw_mesh(&mesh),
+/// })
+/// .await;
+/// ```
+pub async fn decode_progressive(data: &[u8], mut on_partial: impl FnMut(PartialGeometry)) -> bool {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        ffi::decode_progressive(data, &mut on_partial)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        let Some(result) = wasm::decode_mesh_wasm_worker_with_config(data).await else {
+            return false;
+        };
+        if let Some(coarse) = PartialGeometry::coarse(&result) {
+            on_partial(coarse);
+        }
+        on_partial(PartialGeometry::Full(result));
+        true
+    }
+}
+
+/// Decodes a Draco compressed mesh in stages synchronously (native only).
+///
+/// See [`decode_progressive`] for details.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn decode_progressive_sync(data: &[u8], mut on_partial: impl FnMut(PartialGeometry)) -> bool {
+    ffi::decode_progressive(data, &mut on_partial)
+}
+
+/// Decodes a Draco compressed mesh and splits it into chunks of at most
+/// `max_vertices` vertices.
+///
+/// This is useful when the consumer cannot handle large meshes in one piece,
+/// such as WebGL contexts limited to 16-bit indices. See
+/// [`MeshDecodeResult::split_by_vertex_cap`] for how the mesh is split.
+///
+/// # Returns
+///
+/// Returns `Some(Vec<MeshChunk>)` on success, `None` if decoding fails.
+///
+/// # Panics
+///
+/// Panics if `max_vertices` is less than 3.
+pub async fn decode_mesh_chunked(data: &[u8], max_vertices: u32) -> Option<Vec<MeshChunk>> {
+    decode_mesh_with_config(data)
+        .await
+        .map(|result| result.split_by_vertex_cap(max_vertices))
+}
+
+/// Decodes a Draco compressed mesh and splits it into chunks synchronously
+/// (native only).
+///
+/// See [`decode_mesh_chunked`] for details.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn decode_mesh_chunked_sync(data: &[u8], max_vertices: u32) -> Option<Vec<MeshChunk>> {
+    decode_mesh_with_config_sync(data).map(|result| result.split_by_vertex_cap(max_vertices))
+}
+
+/// Decodes a Draco compressed mesh on the calling thread and yields it in
+/// chunks of at most `chunk_size` vertices (native only).
+///
+/// No async runtime is involved, and nothing is decoded until the first
+/// chunk is requested. Draco decodes the whole mesh in one step, so the
+/// decoded mesh is held while iterating, but each chunk is only built when
+/// it is requested and copies just its own vertices: memory stays at the
+/// decoded mesh plus one chunk, however many chunks the mesh splits into.
+/// Chunks are split like [`MeshDecodeResult::split_by_vertex_cap`].
+///
+/// If the header is invalid or decoding fails, the only item is the error.
+///
+/// # Panics
+///
+/// Panics if `chunk_size` is less than 3.
+///
+/// # Example
+///
+/// ```ignore
+/// for chunk in draco_decoder::decode_mesh_blocking_chunks(&data, 65_536) {
+///     upload(chunk?.result);
+/// }
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+pub fn decode_mesh_blocking_chunks(
+    data: &[u8],
+    chunk_size: u32,
+) -> impl Iterator<Item = Result<MeshChunk, DecodeError>> + '_ {
+    assert!(
+        chunk_size >= 3,
+        "vertex cap must allow at least one triangle"
+    );
+    std::iter::once_with(move || {
+        decode_header(data)?;
+        decode_mesh_with_config_sync(data).ok_or(DecodeError::DecodeFailed)
+    })
+    .flat_map(move |result| {
+        let (chunks, error) = match result {
+            Ok(mesh) => (Some(mesh.into_chunks(chunk_size)), None),
+            Err(error) => (None, Some(Err(error))),
+        };
+        error
+            .into_iter()
+            .chain(chunks.into_iter().flatten().map(Ok))
+    })
+}
+
+/// Decodes many Draco compressed meshes in parallel on the rayon thread pool
+/// (native only, requires the `rayon` feature).
+///
+/// Results are returned in the order of `inputs`. Each input fails on its
+/// own: an invalid header reports the header error, and a body Draco cannot
+/// decode reports [`DecodeError::DecodeFailed`]. To limit the threads used,
+/// call it from within [`rayon::ThreadPool::install`].
+///
+/// # Example
+///
+/// ```ignore
+/// let inputs: Vec<&[u8]> = tiles.iter().map(Vec::as_slice).collect();
+/// for (tile, result) in tiles.iter().zip(draco_decoder::decode_many(&inputs)) {
+///     match result {
+///         Ok(mesh) => upload(mesh),
+///         Err(err) => eprintln!("skipping tile: {err}"),
+///     }
+/// }
+/// ```
+#[cfg(all(feature = "rayon", not(target_arch = "wasm32")))]
+pub fn decode_many(inputs: &[&[u8]]) -> Vec<Result<MeshDecodeResult, DecodeError>> {
+    use rayon::prelude::*;
+
+    inputs
+        .par_iter()
+        .map(|data| {
+            decode_header(data)?;
+            ffi::decode_mesh_with_config(data).ok_or(DecodeError::DecodeFailed)
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -105,6 +771,18 @@ mod tests {
     use std::collections::HashSet;
     use std::fs::{self};
 
+    /// Builds a mesh from `Float32` positions, the fixture of most geometry
+    /// tests.
+    fn position_mesh(positions: &[f32], indices: &[u32]) -> crate::MeshDecodeResult {
+        let position = crate::MeshAttribute::new(3, crate::AttributeDataType::Float32, 0, 0)
+            .with_semantic(crate::AttributeSemantic::Position);
+        crate::MeshDecodeResult::from_parts(
+            (positions.len() / 3) as u32,
+            indices,
+            vec![(position, bytemuck::cast_slice(positions).to_vec())],
+        )
+    }
+
     fn quantize(v: &[f32]) -> [i32; 3] {
         [
             (v[0] * 1000.0).round() as i32,
@@ -181,43 +859,3853 @@ mod tests {
         }
     }
 
-    #[cfg(target_arch = "wasm32")]
-    #[wasm_bindgen_test]
-    async fn test_decode_mesh_with_config_wasm() {
-        use crate::decode_mesh_with_config;
-        use wasm_bindgen::JsCast;
-        use wasm_bindgen_futures::JsFuture;
-        use web_sys::{Request, RequestInit, RequestMode, Response};
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_header() {
+        use crate::{DecodeError, EncodingMethod, GeometryType, decode_header, is_draco, sniff};
 
-        // Fetch test data
-        let mut opts = RequestInit::new();
-        opts.set_method("GET");
-        opts.set_mode(RequestMode::Cors);
+        let mesh = fs::read("assets/mesh.drc").expect("Failed to read mesh.drc");
+        let header = decode_header(&mesh).expect("Failed to parse mesh header");
+        assert_eq!(header.geometry_type, GeometryType::Mesh);
+        assert_eq!(header.encoding_method, EncodingMethod::Edgebreaker);
+        assert_eq!(header.version_major, 2);
 
-        let request = Request::new_with_str_and_init("assets/20/20_data.bin", &opts).unwrap();
-        let resp_value = JsFuture::from(web_sys::window().unwrap().fetch_with_request(&request))
-            .await
+        let point_cloud = fs::read("assets/pointcloud.drc").expect("Failed to read pointcloud.drc");
+        let header = decode_header(&point_cloud).expect("Failed to parse point cloud header");
+        assert_eq!(header.geometry_type, GeometryType::PointCloud);
+
+        assert_eq!(sniff(&mesh), Some(GeometryType::Mesh));
+        assert_eq!(sniff(&point_cloud), Some(GeometryType::PointCloud));
+        assert!(!is_draco(b"glTF"));
+
+        assert_eq!(decode_header(&mesh[..8]), Err(DecodeError::TruncatedHeader));
+        assert_eq!(decode_header(b"glTF\x02\0\0\0"), Err(DecodeError::NotDraco));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_point_cloud_with_config() {
+        use crate::{AttributeSemantic, decode_point_cloud_with_config_sync};
+
+        let input = fs::read("assets/pointcloud.drc").expect("Failed to read pointcloud.drc");
+        let result = decode_point_cloud_with_config_sync(&input).expect("Failed to decode");
+
+        assert_eq!(result.config.point_count(), 3);
+        assert_eq!(result.data.len(), result.config.buffer_size());
+
+        let position = result
+            .config
+            .find_attribute(AttributeSemantic::Position)
+            .expect("Expected a position attribute");
+        let positions = result
+            .view::<f32>(position)
+            .expect("Expected float positions");
+
+        let actual: HashSet<[i32; 3]> = positions.vertices().map(quantize).collect();
+        let expected: HashSet<[i32; 3]> = [[0.0, 0.0, 0.0], [1.0, 1.0, 1.0], [2.0, 2.0, 2.0]]
+            .iter()
+            .map(|v| quantize(v))
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_encode_mesh_roundtrip() {
+        use crate::{
+            AttributeSemantic, EncodeAttribute, EncodeConfig, decode_mesh_with_config_sync,
+            encode_mesh, encode_mesh_with_config,
+        };
+
+        let positions = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0];
+        let uvs = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0];
+        let indices = [0, 1, 2, 0, 2, 3];
+        let config =
+            EncodeConfig::default().with_quantization_bits(AttributeSemantic::Position, 14);
+
+        let encoded = encode_mesh(
+            &positions,
+            &indices,
+            &[EncodeAttribute::new(AttributeSemantic::TexCoord, 2, &uvs)],
+            &config,
+        )
+        .expect("Failed to encode");
+        let decoded = decode_mesh_with_config_sync(&encoded).expect("Failed to decode");
+
+        assert_eq!(decoded.config.vertex_count(), 4);
+        assert_eq!(decoded.config.index_count(), 6);
+        let position = decoded
+            .config
+            .find_attribute(AttributeSemantic::Position)
             .unwrap();
-        let resp: Response = resp_value.dyn_into().unwrap();
-        let buf = JsFuture::from(resp.array_buffer().unwrap()).await.unwrap();
-        let u8_array = js_sys::Uint8Array::new(&buf);
-        let mut input = vec![0; u8_array.length() as usize];
-        u8_array.copy_to(&mut input[..]);
+        let actual: HashSet<[i32; 3]> = decoded
+            .view::<f32>(position)
+            .unwrap()
+            .vertices()
+            .map(quantize)
+            .collect();
+        let expected: HashSet<[i32; 3]> = positions.chunks(3).map(quantize).collect();
+        assert_eq!(actual, expected);
+        assert!(
+            decoded
+                .config
+                .find_attribute(AttributeSemantic::TexCoord)
+                .is_some()
+        );
 
-        // Decode
-        let result = decode_mesh_with_config(&input).await;
+        // Re-encoding a decoded mesh keeps its size and attributes
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let mesh = decode_mesh_with_config_sync(&input).expect("Failed to decode");
+        let reencoded = encode_mesh_with_config(&mesh, &EncodeConfig::default()).unwrap();
+        let redecoded = decode_mesh_with_config_sync(&reencoded).expect("Failed to decode");
+        assert_eq!(redecoded.config.index_count(), mesh.config.index_count());
+        assert_eq!(
+            redecoded.config.attributes().len(),
+            mesh.config.attributes().len()
+        );
 
-        assert!(result.is_some(), "Decoding should succeed");
-        let mesh = result.unwrap();
+        assert!(encode_mesh(&positions, &[0, 1, 4], &[], &config).is_none());
+    }
 
-        web_sys::console::log_1(&format!("vertex_count: {}", mesh.config.vertex_count()).into());
-        web_sys::console::log_1(&format!("index_count: {}", mesh.config.index_count()).into());
-        web_sys::console::log_1(&format!("buffer_size: {}", mesh.config.buffer_size()).into());
-        web_sys::console::log_1(&format!("attributes: {}", mesh.config.attributes().len()).into());
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_mesh_fast_preview() {
+        use crate::{
+            AttributeSemantic, WarningAction, WarningReason, decode_mesh_fast_preview_sync,
+        };
 
-        assert_eq!(mesh.config.vertex_count(), 3254);
-        assert_eq!(mesh.config.index_count(), 4368);
-        assert_eq!(mesh.config.attributes().len(), 3);
-        assert_eq!(mesh.data.len(), mesh.config.buffer_size());
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+
+        let preview = decode_mesh_fast_preview_sync(&input, None).expect("Failed to decode");
+        assert_eq!(preview.config.vertex_count(), 3254);
+        assert_eq!(preview.config.index_count(), 4368);
+        assert_eq!(preview.config.attributes().len(), 1);
+        assert_eq!(
+            preview.config.find_attribute(AttributeSemantic::Position),
+            Some(0)
+        );
+        assert_eq!(preview.data.len(), preview.config.buffer_size());
+        assert_eq!(preview.config.warnings().len(), 2);
+        assert!(
+            preview
+                .config
+                .warnings()
+                .iter()
+                .all(|warning| warning.reason == WarningReason::Filtered
+                    && warning.action == WarningAction::Skipped)
+        );
+
+        let decimated = decode_mesh_fast_preview_sync(&input, Some(500)).expect("Failed to decode");
+        assert!(decimated.config.vertex_count() <= 500);
+        assert!(decimated.config.index_count() > 0);
+        assert!(
+            decimated
+                .triangles()
+                .flatten()
+                .all(|v| v < decimated.config.vertex_count())
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_positions_as_vectors() {
+        use crate::{AttributeSemantic, decode_mesh_with_config_sync};
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let result = decode_mesh_with_config_sync(&input).expect("Failed to decode");
+
+        let position = result
+            .config
+            .find_attribute(AttributeSemantic::Position)
+            .expect("Missing positions");
+        let view = result.view::<f32>(position).expect("Invalid positions");
+        let positions: Vec<[f32; 3]> = result.positions().expect("Invalid positions").collect();
+        assert_eq!(positions.len(), result.config.vertex_count() as usize);
+        assert!(positions.iter().zip(view.vertices()).all(|(a, b)| a == b));
+
+        assert!(result.vectors::<[f32; 2], 2>(position).is_none());
+
+        #[cfg(feature = "mint")]
+        {
+            let points: Vec<mint::Point3<f32>> =
+                result.positions().expect("Invalid positions").collect();
+            assert!(
+                points
+                    .iter()
+                    .zip(&positions)
+                    .all(|(p, a)| [p.x, p.y, p.z] == *a)
+            );
+        }
+    }
+
+    #[test]
+    fn test_converted_vectors() {
+        use crate::{
+            AttributeDataType, AttributeSemantic, MeshAttribute, MeshDecodeResult, OctahedronInfo,
+            QuantizationInfo,
+        };
+
+        let quantization = QuantizationInfo::new(8, 2.0, &[-1.0, -1.0, -1.0]).unwrap();
+        let nan = QuantizationInfo::new(8, f32::NAN, &[-1.0]).unwrap();
+        assert_eq!(nan, nan);
+        assert_ne!(nan, QuantizationInfo::new(8, -f32::NAN, &[-1.0]).unwrap());
+        let position = MeshAttribute::new(3, AttributeDataType::UInt16, 0, 0)
+            .with_semantic(AttributeSemantic::Position)
+            .with_quantization(quantization);
+        let normal = MeshAttribute::new(2, AttributeDataType::UInt8, 0, 0)
+            .with_semantic(AttributeSemantic::Normal)
+            .with_octahedron(OctahedronInfo::new(8));
+        let uv = |set: u8| {
+            let attribute = MeshAttribute::new(2, AttributeDataType::UInt8, 0, 0)
+                .with_semantic(AttributeSemantic::TexCoord)
+                .with_normalized(true);
+            (attribute, vec![0, 255, 51 * set, 0])
+        };
+        let positions: [u16; 6] = [0, 255, 0, 255, 0, 255];
+        let mesh = MeshDecodeResult::from_parts(
+            2,
+            &[],
+            vec![
+                (position, bytemuck::cast_slice(&positions).to_vec()),
+                (normal, vec![127, 127, 127, 127]),
+                uv(0),
+                uv(1),
+            ],
+        );
+
+        let positions: Vec<[f32; 3]> = mesh.positions().unwrap().collect();
+        assert_eq!(positions, [[-1.0, 1.0, -1.0], [1.0, -1.0, 1.0]]);
+        let normals: Vec<[f32; 3]> = mesh.normals().unwrap().collect();
+        assert_eq!(normals, [[1.0, 0.0, 0.0]; 2]);
+        let uvs: Vec<[f32; 2]> = mesh.uvs(1).unwrap().collect();
+        assert_eq!(uvs, [[0.0, 1.0], [0.2, 0.0]]);
+        assert!(mesh.uvs::<[f32; 2]>(2).is_none());
+
+        #[cfg(feature = "glam")]
+        {
+            let uvs: Vec<glam::Vec2> = mesh.uvs(0).unwrap().collect();
+            assert_eq!(uvs, [glam::Vec2::Y, glam::Vec2::ZERO]);
+        }
+
+        #[cfg(feature = "mint")]
+        {
+            let points: Vec<mint::Point3<f32>> = mesh.positions().unwrap().collect();
+            assert_eq!(points[1], mint::Point3::from([1.0, -1.0, 1.0]));
+            let uvs: Vec<mint::Vector2<f32>> = mesh.uvs(1).unwrap().collect();
+            assert_eq!(uvs[1], mint::Vector2::from([0.2, 0.0]));
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_into_split() {
+        use crate::decode_mesh_with_config_sync;
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let result = decode_mesh_with_config_sync(&input).expect("Failed to decode");
+        let split = result.clone().into_split().expect("Failed to split");
+
+        assert_eq!(Some(split.indices.as_slice()), result.index_bytes());
+        assert_eq!(
+            split.index_size_in_bytes(),
+            result.config.index_size_in_bytes()
+        );
+        assert_eq!(split.attributes.len(), result.config.attributes().len());
+        for (index, (attribute, bytes)) in split.attributes.iter().enumerate() {
+            assert_eq!(attribute.offset(), 0);
+            assert_eq!(attribute.lenght() as usize, bytes.len());
+            assert_eq!(Some(bytes.as_slice()), result.attribute_bytes(index));
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_mesh_with_options() {
+        use crate::{
+            DecodeOptions, IndexBuffer, IndexFormat, decode_mesh_with_config_sync,
+            decode_mesh_with_options_sync,
+        };
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let packed = decode_mesh_with_config_sync(&input).expect("Failed to decode");
+        let options = DecodeOptions::new().with_offset_alignment(256);
+        let aligned = decode_mesh_with_options_sync(&input, &options).expect("Failed to decode");
+
+        assert_eq!(aligned.data.len(), aligned.config.buffer_size());
+        assert_eq!(aligned.index_bytes(), packed.index_bytes());
+        assert_eq!(
+            aligned.config.attributes().len(),
+            packed.config.attributes().len()
+        );
+        for (index, attribute) in aligned.config.attributes().iter().enumerate() {
+            assert_eq!(attribute.offset() % 256, 0);
+            assert_eq!(
+                aligned.attribute_bytes(index),
+                packed.attribute_bytes(index)
+            );
+        }
+
+        let options = DecodeOptions::new().with_index_format(IndexFormat::U32);
+        let wide = decode_mesh_with_options_sync(&input, &options).expect("Failed to decode");
+        assert_eq!(packed.config.index_size_in_bytes(), 2);
+        assert_eq!(wide.config.index_size_in_bytes(), 4);
+        assert_eq!(
+            wide.indices().map(IndexBuffer::into_u32),
+            packed.indices().map(IndexBuffer::into_u32)
+        );
+
+        let options = DecodeOptions::new().with_index_format(IndexFormat::U16);
+        let narrow = decode_mesh_with_options_sync(&input, &options).expect("Failed to decode");
+        assert_eq!(narrow.config.index_size_in_bytes(), 2);
+        assert_eq!(narrow.index_bytes(), packed.index_bytes());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_mesh_with_output_type() {
+        use crate::{
+            AttributeDataType, AttributeSemantic, DecodeOptions, EncodeAttribute, EncodeConfig,
+            decode_mesh_with_config_sync, decode_mesh_with_options_sync, encode_mesh,
+        };
+
+        let positions = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0];
+        let uvs = [0.0, 0.0, 0.5, 0.0, 1.0, 1.0, 0.25, 1.0];
+        let encoded = encode_mesh(
+            &positions,
+            &[0, 1, 2, 0, 2, 3],
+            &[EncodeAttribute::new(AttributeSemantic::TexCoord, 2, &uvs)],
+            &EncodeConfig::default(),
+        )
+        .expect("Failed to encode");
+
+        let floats = decode_mesh_with_config_sync(&encoded).expect("Failed to decode");
+        let options = DecodeOptions::new()
+            .with_output_type(AttributeSemantic::TexCoord, AttributeDataType::UInt16);
+        let normalized =
+            decode_mesh_with_options_sync(&encoded, &options).expect("Failed to decode");
+
+        let index = normalized
+            .config
+            .find_attribute(AttributeSemantic::TexCoord)
+            .unwrap();
+        let attribute = normalized.config.get_attribute(index).unwrap();
+        assert_eq!(attribute.data_type(), AttributeDataType::UInt16);
+        assert_eq!(attribute.lenght(), 4 * 2 * 2);
+        assert!(attribute.normalized());
+        assert!(!floats.config.get_attribute(index).unwrap().normalized());
+        assert_eq!(normalized.data.len(), normalized.config.buffer_size());
+
+        let expected: Vec<u16> = floats
+            .view::<f32>(index)
+            .unwrap()
+            .vertices()
+            .flatten()
+            .map(|&value| (value * u16::MAX as f32).round() as u16)
+            .collect();
+        let actual: Vec<u16> = normalized
+            .view::<u16>(index)
+            .unwrap()
+            .vertices()
+            .flatten()
+            .copied()
+            .collect();
+        assert_eq!(actual, expected);
+
+        // Positions keep their decoded type
+        let position = normalized
+            .config
+            .find_attribute(AttributeSemantic::Position)
+            .unwrap();
+        assert_eq!(
+            normalized.attribute_bytes(position),
+            floats.attribute_bytes(position)
+        );
+
+        // Output types by unique id take precedence over the semantic, and
+        // integers that are not normalized keep their values
+        let colors: [u8; 12] = [0, 64, 128, 255, 1, 2, 3, 4, 200, 100, 50, 25];
+        let encoded = encode_mesh(
+            &positions,
+            &[0, 1, 2, 0, 2, 3],
+            &[
+                EncodeAttribute::new(AttributeSemantic::TexCoord, 2, &uvs),
+                EncodeAttribute::new(AttributeSemantic::TexCoord, 2, &uvs),
+                EncodeAttribute::new(AttributeSemantic::Color, 3, &colors),
+            ],
+            &EncodeConfig::default(),
+        )
+        .expect("Failed to encode");
+        let floats = decode_mesh_with_config_sync(&encoded).expect("Failed to decode");
+        let uv_sets: Vec<usize> = (0..floats.config.attributes().len())
+            .filter(|&index| {
+                floats.config.attributes()[index].semantic() == AttributeSemantic::TexCoord
+            })
+            .collect();
+        let second = floats.config.attributes()[uv_sets[1]].unique_id();
+        let options = DecodeOptions::new()
+            .with_output_type(AttributeSemantic::TexCoord, AttributeDataType::UInt16)
+            .with_output_type(AttributeSemantic::Color, AttributeDataType::UInt16)
+            .with_attribute_output_type(second, AttributeDataType::Float32);
+        assert_eq!(
+            options.attribute_output_type(second, AttributeSemantic::TexCoord),
+            Some(AttributeDataType::Float32)
+        );
+        let mixed = decode_mesh_with_options_sync(&encoded, &options).expect("Failed to decode");
+        let types: Vec<AttributeDataType> = mixed
+            .config
+            .attributes()
+            .iter()
+            .map(|attribute| attribute.data_type())
+            .collect();
+        assert_eq!(types[uv_sets[0]], AttributeDataType::UInt16);
+        assert_eq!(types[uv_sets[1]], AttributeDataType::Float32);
+        assert_eq!(
+            mixed.attribute_bytes(uv_sets[1]),
+            floats.attribute_bytes(uv_sets[1])
+        );
+        let color = mixed
+            .config
+            .find_attribute(AttributeSemantic::Color)
+            .unwrap();
+        let widened: Vec<u16> = mixed
+            .view::<u16>(color)
+            .unwrap()
+            .vertices()
+            .flatten()
+            .copied()
+            .collect();
+        assert_eq!(widened, colors.map(u16::from));
+    }
+
+    #[cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_decode_offloaded() {
+        use crate::{decode_mesh_with_config, decode_mesh_with_config_sync};
+
+        let data = std::fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let expected = decode_mesh_with_config_sync(&data).expect("Failed to decode");
+
+        // Both decodes make progress on a single-threaded runtime
+        let (first, second) = tokio::join!(
+            decode_mesh_with_config(&data),
+            decode_mesh_with_config(&data)
+        );
+        assert_eq!(first.expect("Failed to decode").data, expected.data);
+        assert_eq!(second.expect("Failed to decode").data, expected.data);
+        assert!(decode_mesh_with_config(&data[..16]).await.is_none());
+    }
+
+    #[cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
+    #[test]
+    fn test_decode_outside_tokio() {
+        use std::pin::pin;
+        use std::task::{Context, Poll, Waker};
+
+        use crate::{decode_mesh_with_config, decode_mesh_with_config_sync};
+
+        let data = std::fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let expected = decode_mesh_with_config_sync(&data).expect("Failed to decode");
+
+        // Without a runtime there is no blocking pool, so the decode runs
+        // inline on the first poll instead of panicking
+        let mut context = Context::from_waker(Waker::noop());
+        let decode = pin!(decode_mesh_with_config(&data));
+        match decode.poll(&mut context) {
+            Poll::Ready(result) => {
+                assert_eq!(result.expect("Failed to decode").data, expected.data)
+            }
+            Poll::Pending => panic!("decode did not run inline"),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn test_async_executor() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        use crate::executor::run_blocking;
+        use crate::{
+            AsyncExecutor, DecodeOptions, decode_mesh_with_config_sync, decode_mesh_with_options,
+        };
+
+        struct Threads(Arc<AtomicUsize>);
+
+        impl AsyncExecutor for Threads {
+            fn spawn_blocking(&self, task: Box<dyn FnOnce() + Send>) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                std::thread::spawn(task);
+            }
+        }
+
+        struct Dropping;
+
+        impl AsyncExecutor for Dropping {
+            fn spawn_blocking(&self, task: Box<dyn FnOnce() + Send>) {
+                drop(task);
+            }
+        }
+
+        assert_eq!(run_blocking(&Dropping, || 1).await, None);
+        let spawned = Arc::new(AtomicUsize::new(0));
+        assert_eq!(run_blocking(&Threads(spawned.clone()), || 2).await, Some(2));
+
+        let data = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let expected = decode_mesh_with_config_sync(&data).expect("Failed to decode");
+        let options = DecodeOptions::new().with_executor(Threads(spawned.clone()));
+        let result = decode_mesh_with_options(&data, &options).await;
+        assert_eq!(result.expect("Failed to decode").data, expected.data);
+        assert_eq!(spawned.load(Ordering::SeqCst), 2);
+        assert_eq!(options.clone(), options);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_mesh_pad_vec3_to_vec4() {
+        use crate::{
+            AttributeSemantic, DecodeOptions, EncodeAttribute, EncodeConfig,
+            decode_mesh_with_config_sync, decode_mesh_with_options_sync, encode_mesh,
+        };
+
+        let positions = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0];
+        let uvs = [0.0, 0.0, 0.5, 0.0, 1.0, 1.0, 0.25, 1.0];
+        let encoded = encode_mesh(
+            &positions,
+            &[0, 1, 2, 0, 2, 3],
+            &[EncodeAttribute::new(AttributeSemantic::TexCoord, 2, &uvs)],
+            &EncodeConfig::default(),
+        )
+        .expect("Failed to encode");
+
+        let packed = decode_mesh_with_config_sync(&encoded).expect("Failed to decode");
+        let options = DecodeOptions::new().pad_vec3_to_vec4();
+        let padded = decode_mesh_with_options_sync(&encoded, &options).expect("Failed to decode");
+        assert_eq!(padded.data.len(), padded.config.buffer_size());
+
+        let position = padded
+            .config
+            .find_attribute(AttributeSemantic::Position)
+            .unwrap();
+        let attribute = padded.config.get_attribute(position).unwrap();
+        assert!(attribute.padded());
+        assert_eq!(attribute.dim(), 4);
+        assert_eq!(attribute.lenght(), 4 * 16);
+        let view = padded.view::<f32>(position).unwrap();
+        assert!(view.vertices().all(|vertex| vertex[3] == 0.0));
+        assert_eq!(
+            padded.positions::<[f32; 3]>().unwrap().collect::<Vec<_>>(),
+            packed.positions::<[f32; 3]>().unwrap().collect::<Vec<_>>()
+        );
+
+        // Two component attributes are not padded
+        let uv = padded
+            .config
+            .find_attribute(AttributeSemantic::TexCoord)
+            .unwrap();
+        assert!(!padded.config.get_attribute(uv).unwrap().padded());
+        assert_eq!(padded.attribute_bytes(uv), packed.attribute_bytes(uv));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_mesh_axis_convention() {
+        use crate::{
+            AttributeDataType, AttributeSemantic, AxisConvention, DecodeError, DecodeOptions,
+            EncodeAttribute, EncodeConfig, decode_mesh_with_config_sync,
+            decode_mesh_with_options_sync, encode_mesh, try_decode_mesh_with_options_sync,
+        };
+
+        let positions = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
+        let colors = [0.0, 0.5, 1.0, 1.0, 0.5, 0.0, 0.25, 0.75, 1.0];
+        let encoded = encode_mesh(
+            &positions,
+            &[0, 1, 2],
+            &[EncodeAttribute::new(AttributeSemantic::Color, 3, &colors)],
+            &EncodeConfig::default(),
+        )
+        .expect("Failed to encode");
+        let decoded = decode_mesh_with_config_sync(&encoded).expect("Failed to decode");
+        let original: Vec<[f32; 3]> = decoded.positions().unwrap().collect();
+
+        for (convention, remap) in [
+            (
+                AxisConvention::ZUpRightHanded,
+                (|[x, y, z]: [f32; 3]| [x, -z, y]) as fn([f32; 3]) -> [f32; 3],
+            ),
+            (AxisConvention::YUpLeftHanded, |[x, y, z]| [x, y, -z]),
+            (AxisConvention::ZUpLeftHanded, |[x, y, z]| [x, z, y]),
+        ] {
+            let options = DecodeOptions::new().with_axis_convention(convention);
+            assert_eq!(options.axis_convention(), convention);
+            let result =
+                decode_mesh_with_options_sync(&encoded, &options).expect("Failed to decode");
+            let positions: Vec<[f32; 3]> = result.positions().unwrap().collect();
+            let expected: Vec<[f32; 3]> = original.iter().copied().map(remap).collect();
+            assert_eq!(positions, expected);
+
+            // Left-handed conventions mirror the mesh, so they flip the winding
+            let triangles: Vec<[u32; 3]> = result.triangles().collect();
+            let decoded_triangles: Vec<[u32; 3]> = decoded.triangles().collect();
+            let flipped: Vec<[u32; 3]> = decoded_triangles
+                .iter()
+                .map(|&[a, b, c]| [a, c, b])
+                .collect();
+            if convention.is_left_handed() {
+                assert_eq!(triangles, flipped);
+            } else {
+                assert_eq!(triangles, decoded_triangles);
+            }
+            let restored =
+                decode_mesh_with_options_sync(&encoded, &options.clone().flip_winding(true))
+                    .expect("Failed to decode");
+            let restored: Vec<[u32; 3]> = restored.triangles().collect();
+            assert_eq!(restored == decoded_triangles, convention.is_left_handed());
+
+            // Values that cannot be negated are rejected instead of being left
+            // in the decoded axes
+            let position = decoded
+                .config
+                .find_attribute(AttributeSemantic::Position)
+                .unwrap();
+            let position_id = decoded.config.get_attribute(position).unwrap().unique_id();
+            for rejected in [
+                options.clone().keep_quantized(AttributeSemantic::Position),
+                options
+                    .clone()
+                    .with_output_type(AttributeSemantic::Position, AttributeDataType::UInt16),
+            ] {
+                assert_eq!(
+                    try_decode_mesh_with_options_sync(&encoded, &rejected).err(),
+                    Some(DecodeError::AxesNotRemappable {
+                        unique_id: position_id
+                    })
+                );
+            }
+
+            // Other attributes are written as decoded
+            let color = result
+                .config
+                .find_attribute(AttributeSemantic::Color)
+                .unwrap();
+            assert_eq!(
+                result.attribute_bytes(color),
+                decoded.attribute_bytes(color)
+            );
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_mesh_flip_winding() {
+        use crate::{DecodeOptions, decode_mesh_with_config_sync, decode_mesh_with_options_sync};
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let decoded = decode_mesh_with_config_sync(&input).expect("Failed to decode");
+        let options = DecodeOptions::new().flip_winding(true);
+        assert!(options.flips_winding());
+        let flipped = decode_mesh_with_options_sync(&input, &options).expect("Failed to decode");
+
+        let triangles: Vec<[u32; 3]> = decoded.triangles().collect();
+        let flipped: Vec<[u32; 3]> = flipped.triangles().collect();
+        assert_eq!(flipped.len(), 4368 / 3);
+        for (triangle, flipped) in triangles.iter().zip(&flipped) {
+            assert_eq!(*flipped, [triangle[0], triangle[2], triangle[1]]);
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_mesh_with_quota() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::{Arc, Mutex};
+
+        use crate::{
+            DecodeError, DecodeOptions, EncodeConfig, QuotaDecision, QuotaProvider,
+            decode_mesh_with_options_sync, encode_mesh, encode_point_cloud,
+            try_decode_mesh_with_options_sync, try_decode_point_cloud_with_options_sync,
+        };
+
+        struct Budget {
+            limit: usize,
+            requests: Mutex<Vec<(String, usize)>>,
+        }
+
+        impl QuotaProvider for Budget {
+            fn check(&self, tenant_id: &str, bytes: usize) -> QuotaDecision {
+                self.requests
+                    .lock()
+                    .unwrap()
+                    .push((tenant_id.to_string(), bytes));
+                if bytes <= self.limit {
+                    QuotaDecision::Allow
+                } else {
+                    QuotaDecision::Deny
+                }
+            }
+        }
+
+        let positions = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0];
+        let encoded = encode_mesh(
+            &positions,
+            &[0, 1, 2, 0, 2, 3],
+            &[],
+            &EncodeConfig::default(),
+        )
+        .expect("Failed to encode");
+
+        let generous = Arc::new(Budget {
+            limit: usize::MAX,
+            requests: Mutex::new(Vec::new()),
+        });
+        let options = DecodeOptions::new().with_quota(generous.clone(), "tenant-a");
+        assert_eq!(options.tenant_id(), Some("tenant-a"));
+        let result = decode_mesh_with_options_sync(&encoded, &options).expect("Failed to decode");
+        assert_eq!(
+            *generous.requests.lock().unwrap(),
+            [("tenant-a".to_string(), result.config.buffer_size())]
+        );
+
+        let strict = Arc::new(Budget {
+            limit: result.config.buffer_size() - 1,
+            requests: Mutex::new(Vec::new()),
+        });
+        let options = DecodeOptions::new().with_quota(strict.clone(), "tenant-b");
+        assert!(decode_mesh_with_options_sync(&encoded, &options).is_none());
+        assert_eq!(
+            try_decode_mesh_with_options_sync(&encoded, &options).unwrap_err(),
+            DecodeError::QuotaDenied
+        );
+        assert_eq!(strict.requests.lock().unwrap().len(), 2);
+
+        // Point clouds are charged like meshes
+        let points = encode_point_cloud(&positions, &[], &EncodeConfig::default())
+            .expect("Failed to encode");
+        let cloud = try_decode_point_cloud_with_options_sync(&points, &DecodeOptions::new())
+            .expect("Failed to decode");
+        let options = DecodeOptions::new().with_quota(generous.clone(), "tenant-a");
+        assert!(try_decode_point_cloud_with_options_sync(&points, &options).is_ok());
+        assert_eq!(
+            generous.requests.lock().unwrap().last(),
+            Some(&("tenant-a".to_string(), cloud.config.buffer_size()))
+        );
+        let options = DecodeOptions::new().with_quota(strict.clone(), "tenant-b");
+        assert_eq!(
+            try_decode_point_cloud_with_options_sync(&points, &options).unwrap_err(),
+            DecodeError::QuotaDenied
+        );
+
+        // Decodes can be denied before any work is done
+        struct NoDecodes(AtomicUsize);
+
+        impl QuotaProvider for NoDecodes {
+            fn check(&self, _tenant_id: &str, _bytes: usize) -> QuotaDecision {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                QuotaDecision::Allow
+            }
+
+            fn check_decode(&self, _tenant_id: &str, encoded_bytes: usize) -> QuotaDecision {
+                assert!(encoded_bytes > 0);
+                QuotaDecision::Deny
+            }
+        }
+
+        let closed = Arc::new(NoDecodes(AtomicUsize::new(0)));
+        let options = DecodeOptions::new().with_quota(closed.clone(), "tenant-c");
+        assert_eq!(
+            try_decode_mesh_with_options_sync(&encoded, &options).unwrap_err(),
+            DecodeError::QuotaDenied
+        );
+        assert_eq!(
+            try_decode_point_cloud_with_options_sync(&points, &options).unwrap_err(),
+            DecodeError::QuotaDenied
+        );
+        assert_eq!(closed.0.load(Ordering::SeqCst), 0);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_load() {
+        use std::path::Path;
+
+        use crate::{
+            GeometryType, LoadError, LoadOptions, LoadPreset, SourceFormat, load_sync, sniff_format,
+        };
+
+        let mesh = load_sync("assets/20/20_data.bin", &LoadOptions::new()).expect("Failed to load");
+        let mesh = mesh.as_mesh().expect("Expected a mesh");
+        assert_eq!(mesh.config.vertex_count(), 3254);
+
+        let options = LoadOptions::new().with_preset(LoadPreset::Compute);
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        assert_eq!(
+            sniff_format(&input),
+            Some(SourceFormat::Draco(GeometryType::Mesh))
+        );
+        let compute = load_sync(&input, &options).expect("Failed to load");
+        let compute = compute.as_mesh().unwrap();
+        assert_eq!(compute.config.index_size_in_bytes(), 4);
+        assert!(
+            compute
+                .config
+                .attributes()
+                .iter()
+                .all(|attribute| attribute.offset() % 16 == 0 && attribute.dim() != 3)
+        );
+
+        let points =
+            load_sync(Path::new("assets/pointcloud.drc"), &options).expect("Failed to load");
+        assert!(points.as_point_cloud().is_some());
+        assert_eq!(points.meshes().count(), 0);
+
+        assert!(matches!(
+            load_sync(b"solid cube", &options),
+            Err(LoadError::UnknownFormat)
+        ));
+        assert!(matches!(
+            load_sync("assets/missing.drc", &options),
+            Err(LoadError::Io(_))
+        ));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_mesh_progress() {
+        use std::sync::{Arc, Mutex};
+
+        use crate::{
+            DecodeOptions, DecodeStage, decode_mesh_with_options_sync,
+            try_decode_point_cloud_with_options_sync,
+        };
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let sink = reports.clone();
+        let options = DecodeOptions::new().with_progress(move |stage, fraction| {
+            sink.lock().unwrap().push((stage, fraction));
+        });
+        let mesh = decode_mesh_with_options_sync(&input, &options).expect("Failed to decode");
+
+        let stages: Vec<DecodeStage> = reports
+            .lock()
+            .unwrap()
+            .drain(..)
+            .map(|(stage, fraction)| {
+                assert!(fraction > 0.0 && fraction <= 1.0);
+                stage
+            })
+            .collect();
+        let attribute_count = mesh.config.attributes().len();
+        let decoders = stages
+            .iter()
+            .filter(|stage| matches!(stage, DecodeStage::AttributesDecoded(_)))
+            .count();
+        assert!(decoders > 0);
+        let mut expected = vec![DecodeStage::HeaderParsed, DecodeStage::ConnectivityDecoded];
+        expected.extend((0..decoders).map(DecodeStage::AttributesDecoded));
+        expected.push(DecodeStage::IndicesWritten);
+        expected.extend((0..attribute_count).map(DecodeStage::AttributeExtracted));
+        assert_eq!(stages, expected);
+
+        // The decompression is reported as it happens, without large jumps
+        let fractions: Vec<f32> = stages
+            .iter()
+            .map(|stage| {
+                stage.fraction(match stage {
+                    DecodeStage::AttributesDecoded(_) => decoders,
+                    _ => attribute_count,
+                })
+            })
+            .collect();
+        assert!(fractions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(fractions.windows(2).all(|pair| pair[1] - pair[0] <= 0.35));
+        assert_eq!(fractions.last(), Some(&1.0));
+
+        // Point clouds report their stages as well
+        let point_cloud = fs::read("assets/pointcloud.drc").expect("Failed to read pointcloud.drc");
+        let result = try_decode_point_cloud_with_options_sync(&point_cloud, &options)
+            .expect("Failed to decode");
+        let stages: Vec<DecodeStage> = reports
+            .lock()
+            .unwrap()
+            .drain(..)
+            .map(|(stage, _)| stage)
+            .collect();
+        assert_eq!(stages.first(), Some(&DecodeStage::HeaderParsed));
+        assert!(stages.contains(&DecodeStage::AttributesDecoded(0)));
+        assert!(!stages.contains(&DecodeStage::ConnectivityDecoded));
+        assert_eq!(
+            stages.last(),
+            Some(&DecodeStage::AttributeExtracted(
+                result.config.attributes().len() - 1
+            ))
+        );
+
+        // A panicking callback neither unwinds into the native library nor
+        // fails the decode
+        let panicking = DecodeOptions::new().with_progress(|_, _| panic!("progress callback"));
+        let decoded = decode_mesh_with_options_sync(&input, &panicking).expect("Failed to decode");
+        assert_eq!(decoded.data, mesh.data);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_mesh_limits() {
+        use crate::{
+            DecodeError, DecodeOptions, LimitKind, Limits, decode_mesh_with_options_sync,
+            try_decode_mesh_with_options_sync,
+        };
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let unlimited = try_decode_mesh_with_options_sync(&input, &DecodeOptions::new())
+            .expect("Failed to decode");
+        let config = &unlimited.config;
+
+        let exact = Limits {
+            max_vertices: config.vertex_count(),
+            max_indices: config.index_count(),
+            max_buffer_bytes: config.buffer_size(),
+        };
+        let options = DecodeOptions::new().with_limits(exact);
+        assert_eq!(options.limits(), exact);
+        assert!(try_decode_mesh_with_options_sync(&input, &options).is_ok());
+
+        let exceeded = [
+            (
+                Limits {
+                    max_vertices: config.vertex_count() - 1,
+                    ..exact
+                },
+                LimitKind::Vertices,
+                config.vertex_count() as usize,
+            ),
+            (
+                Limits {
+                    max_indices: config.index_count() - 1,
+                    ..exact
+                },
+                LimitKind::Indices,
+                config.index_count() as usize,
+            ),
+            (
+                Limits {
+                    max_buffer_bytes: config.buffer_size() - 1,
+                    ..exact
+                },
+                LimitKind::BufferBytes,
+                config.buffer_size(),
+            ),
+        ];
+        for (limits, kind, actual) in exceeded {
+            let options = DecodeOptions::new().with_limits(limits);
+            assert_eq!(
+                try_decode_mesh_with_options_sync(&input, &options).err(),
+                Some(DecodeError::LimitExceeded {
+                    kind,
+                    actual,
+                    max: actual - 1,
+                })
+            );
+            assert!(decode_mesh_with_options_sync(&input, &options).is_none());
+        }
+
+        // Counts are rejected before Draco decompresses any attribute
+        let stages = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = stages.clone();
+        let options = DecodeOptions::new()
+            .with_limits(Limits {
+                max_vertices: config.vertex_count() - 1,
+                ..exact
+            })
+            .with_progress(move |stage, _| sink.lock().unwrap().push(stage));
+        assert!(matches!(
+            try_decode_mesh_with_options_sync(&input, &options),
+            Err(DecodeError::LimitExceeded {
+                kind: LimitKind::Vertices,
+                ..
+            })
+        ));
+        assert_eq!(*stages.lock().unwrap(), [crate::DecodeStage::HeaderParsed]);
+
+        assert_eq!(
+            try_decode_mesh_with_options_sync(b"not draco", &DecodeOptions::new()).err(),
+            Some(DecodeError::NotDraco)
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_mesh_validate_indices() {
+        use crate::{
+            DecodeError, DecodeOptions, MeshDecodeResult, MeshHandle,
+            try_decode_mesh_with_options_sync,
+        };
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let options = DecodeOptions::new().validate_indices(true);
+        assert!(options.validates_indices());
+        let result = try_decode_mesh_with_options_sync(&input, &options).expect("Failed to decode");
+        assert_eq!(result.config.index_count(), 4368);
+        assert_eq!(result.check_indices(), Ok(()));
+
+        // The handle checks the indices it wrote into the caller's buffer
+        let handle = MeshHandle::decode(&input).unwrap();
+        let mut buffer = vec![0u8; result.config.buffer_size()];
+        assert_eq!(
+            handle.decode_into_with_options(&options, &mut buffer),
+            Ok(result.data.len())
+        );
+        let vertex_count = result.config.vertex_count();
+        assert_eq!(result.config.index_size_in_bytes(), 2);
+        buffer[2..4].copy_from_slice(&(vertex_count as u16).to_ne_bytes());
+        assert_eq!(
+            result.config.check_indices(&buffer),
+            Err(DecodeError::IndexOutOfBounds {
+                index: vertex_count,
+                vertex_count,
+            })
+        );
+
+        let corrupt = MeshDecodeResult::from_parts(3, &[0, 1, 3], Vec::new());
+        assert_eq!(
+            corrupt.check_indices(),
+            Err(DecodeError::IndexOutOfBounds {
+                index: 3,
+                vertex_count: 3,
+            })
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_unchecked_input() {
+        use crate::{Geometry, decode_unchecked_input};
+
+        let mesh = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let points = fs::read("assets/pointcloud.drc").expect("Failed to read point cloud");
+        assert!(matches!(
+            decode_unchecked_input(&mesh),
+            Some(Geometry::Mesh(_))
+        ));
+        assert!(matches!(
+            decode_unchecked_input(&points),
+            Some(Geometry::PointCloud(_))
+        ));
+        assert!(decode_unchecked_input(b"").is_none());
+        assert!(decode_unchecked_input(b"not draco").is_none());
+
+        for input in [&mesh, &points] {
+            for length in (0..input.len()).step_by(97) {
+                let _ = decode_unchecked_input(&input[..length]);
+            }
+            for position in (0..input.len()).step_by(211) {
+                let mut corrupted = input.clone();
+                corrupted[position] ^= 0xa5;
+                let _ = decode_unchecked_input(&corrupted);
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_valid_layout() {
+        use crate::utils::is_valid_layout;
+
+        assert!(is_valid_layout([(0, 12), (12, 36), (48, 16)], 64));
+        assert!(is_valid_layout([(48, 16), (0, 12), (12, 0)], 64));
+        assert!(!is_valid_layout([(0, 12), (8, 36)], 64));
+        assert!(!is_valid_layout([(0, 12), (12, 56)], 64));
+        assert!(!is_valid_layout([(u32::MAX, 1)], u32::MAX as usize));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_mesh_cancelled() {
+        use crate::{
+            CancellationToken, DecodeOptions, EncodeConfig, decode_mesh_with_options_sync,
+            encode_mesh,
+        };
+
+        let positions = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0];
+        let encoded = encode_mesh(&positions, &[0, 1, 2], &[], &EncodeConfig::default())
+            .expect("Failed to encode");
+
+        let token = CancellationToken::new();
+        let options = DecodeOptions::new().with_cancellation(token.clone());
+        assert_eq!(options.cancellation(), Some(&token));
+        assert!(decode_mesh_with_options_sync(&encoded, &options).is_some());
+
+        token.cancel();
+        assert!(options.cancellation().unwrap().is_cancelled());
+        assert!(decode_mesh_with_options_sync(&encoded, &options).is_none());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_analyze() {
+        use crate::{AttributeSemantic, EncodeAttribute, EncodeConfig, analyze, encode_mesh};
+
+        let positions = [0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 2.0, 1.0, 0.0, 0.0, 1.0, 0.5];
+        let uvs = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0];
+        let config =
+            EncodeConfig::default().with_quantization_bits(AttributeSemantic::Position, 12);
+        let encoded = encode_mesh(
+            &positions,
+            &[0, 1, 2, 0, 2, 3],
+            &[EncodeAttribute::new(AttributeSemantic::TexCoord, 2, &uvs)],
+            &config,
+        )
+        .expect("Failed to encode");
+
+        let report = analyze(&encoded).expect("Failed to analyze");
+        assert_eq!(report.total_size, encoded.len());
+        assert!(report.header_size >= 11);
+        assert!(report.connectivity_size > 0);
+        assert_eq!(report.vertex_count, 4);
+        assert_eq!(report.face_count, 2);
+        assert_eq!(report.vertex_reuse(), 1.5);
+
+        assert_eq!(report.attributes.len(), 2);
+        let position = report
+            .attributes
+            .iter()
+            .find(|attribute| attribute.semantic == AttributeSemantic::Position)
+            .unwrap();
+        assert_eq!(position.quantization_bits, Some(12));
+        assert_eq!(position.components, 3);
+        assert!(position.encoded_size > 0);
+        assert!(position.encoded_size <= report.attribute_size());
+        assert!(position.estimated_savings(2) <= position.encoded_size);
+        assert!(report.estimated_savings(2) <= report.attribute_size());
+        assert!(analyze(&encoded[..20]).is_none());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_mesh_compat() {
+        use crate::{CompatShim, decode_mesh_compat_sync, decode_mesh_with_config_sync};
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let plain = decode_mesh_compat_sync(&input).expect("Failed to decode");
+        assert!(plain.shims.is_empty());
+
+        // Newer versions than the bundled decoder supports are not rewritten
+        let mut newer = input.clone();
+        newer[6] = 9;
+        assert!(decode_mesh_with_config_sync(&newer).is_none());
+        assert!(decode_mesh_compat_sync(&newer).is_none());
+
+        // Metadata with an empty entry value, which the decoder rejects
+        let mut metadata = input[..11].to_vec();
+        metadata[10] |= 0x80;
+        metadata.extend_from_slice(&[0, 1, 1, b'a', 0, 0]);
+        metadata.extend_from_slice(&input[11..]);
+        assert!(decode_mesh_with_config_sync(&metadata).is_none());
+        let stripped = decode_mesh_compat_sync(&metadata).expect("Failed to decode");
+        assert_eq!(stripped.shims, [CompatShim::MetadataStripped]);
+        assert_eq!(stripped.result.data, plain.result.data);
+
+        assert!(decode_mesh_compat_sync(b"DRACO").is_none());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_keep_quantized() {
+        use crate::{
+            AttributeDataType, AttributeSemantic, DecodeOptions, EncodeAttribute, EncodeConfig,
+            decode_mesh_with_config_sync, decode_mesh_with_options_sync, encode_mesh,
+        };
+
+        let positions = [0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 2.0, 1.0, 0.0, 0.0, 1.0, 0.5];
+        let uvs = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0];
+        let config =
+            EncodeConfig::default().with_quantization_bits(AttributeSemantic::Position, 12);
+        let encoded = encode_mesh(
+            &positions,
+            &[0, 1, 2, 0, 2, 3],
+            &[EncodeAttribute::new(AttributeSemantic::TexCoord, 2, &uvs)],
+            &config,
+        )
+        .expect("Failed to encode");
+
+        let dequantized = decode_mesh_with_config_sync(&encoded).expect("Failed to decode");
+        let options = DecodeOptions::new().keep_quantized(AttributeSemantic::Position);
+        let quantized =
+            decode_mesh_with_options_sync(&encoded, &options).expect("Failed to decode");
+
+        let index = quantized
+            .config
+            .find_attribute(AttributeSemantic::Position)
+            .unwrap();
+        let attribute = quantized.config.get_attribute(index).unwrap();
+        assert_eq!(attribute.data_type(), AttributeDataType::UInt32);
+        assert_eq!(attribute.dim(), 3);
+        let quantization = attribute.quantization().expect("Missing quantization info");
+        assert_eq!(quantization.bits(), 12);
+        assert_eq!(quantization.min_values().len(), 3);
+
+        let restored: Vec<f32> = quantized
+            .view::<u32>(index)
+            .unwrap()
+            .vertices()
+            .flat_map(|vertex| {
+                vertex
+                    .iter()
+                    .enumerate()
+                    .map(|(component, &value)| quantization.dequantize(component, value).unwrap())
+            })
+            .collect();
+        let expected: Vec<f32> = dequantized
+            .view::<f32>(index)
+            .unwrap()
+            .vertices()
+            .flatten()
+            .copied()
+            .collect();
+        assert_eq!(restored.len(), expected.len());
+        for (restored, expected) in restored.iter().zip(&expected) {
+            assert!((restored - expected).abs() < 1e-5);
+        }
+
+        // Other semantics are still dequantized
+        let uv = quantized
+            .config
+            .find_attribute(AttributeSemantic::TexCoord)
+            .unwrap();
+        let uv = quantized.config.get_attribute(uv).unwrap();
+        assert_eq!(uv.data_type(), AttributeDataType::Float32);
+        assert!(uv.quantization().is_none());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_keep_octahedral_normals() {
+        use crate::{
+            AttributeDataType, AttributeSemantic, DecodeOptions, EncodeAttribute, EncodeConfig,
+            OctahedronInfo, decode_mesh_with_config_sync, decode_mesh_with_options_sync,
+            encode_mesh,
+        };
+
+        // The center of the octahedron maps to +X
+        let info = OctahedronInfo::new(8);
+        assert_eq!(
+            info.decode(info.max_value() / 2, info.max_value() / 2),
+            [1.0, 0.0, 0.0]
+        );
+
+        let positions = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0];
+        let s = std::f32::consts::FRAC_1_SQRT_2;
+        let normals = [0.0, 0.0, 1.0, s, 0.0, s, 0.0, -s, s, -1.0, 0.0, 0.0];
+        let encoded = encode_mesh(
+            &positions,
+            &[0, 1, 2, 0, 2, 3],
+            &[EncodeAttribute::new(AttributeSemantic::Normal, 3, &normals)],
+            &EncodeConfig::default(),
+        )
+        .expect("Failed to encode");
+
+        let expanded = decode_mesh_with_config_sync(&encoded).expect("Failed to decode");
+        let options = DecodeOptions::new().keep_octahedral_normals();
+        let packed = decode_mesh_with_options_sync(&encoded, &options).expect("Failed to decode");
+
+        let index = packed
+            .config
+            .find_attribute(AttributeSemantic::Normal)
+            .unwrap();
+        let attribute = packed.config.get_attribute(index).unwrap();
+        assert_eq!(attribute.dim(), 2);
+        assert_eq!(attribute.data_type(), AttributeDataType::UInt32);
+        let octahedron = attribute.octahedron().expect("Missing octahedron info");
+        assert!(attribute.quantization().is_none());
+
+        let decoded: Vec<[f32; 3]> = packed
+            .view::<u32>(index)
+            .unwrap()
+            .vertices()
+            .map(|st| octahedron.decode(st[0], st[1]))
+            .collect();
+        let expected = expanded.view::<f32>(index).unwrap();
+        assert_eq!(decoded.len(), expected.vertices().count());
+        for (decoded, expected) in decoded.iter().zip(expected.vertices()) {
+            for (a, b) in decoded.iter().zip(expected) {
+                assert!((a - b).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_progressive() {
+        use crate::{PartialGeometry, decode_progressive_sync};
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+
+        let mut stages = Vec::new();
+        assert!(decode_progressive_sync(&input, |partial| stages.push(partial)));
+
+        let [
+            PartialGeometry::Coarse {
+                positions,
+                vertex_count,
+            },
+            PartialGeometry::Full(mesh),
+        ] = stages.as_slice()
+        else {
+            panic!("Expected a coarse sample followed by the full mesh");
+        };
+        assert_eq!(*vertex_count, 3254);
+        assert!(!positions.is_empty() && positions.len() / 3 <= crate::COARSE_SAMPLE_POINTS);
+        assert_eq!(mesh.config.vertex_count(), 3254);
+
+        assert!(!decode_progressive_sync(b"not a draco file", |_| panic!()));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_encode_point_cloud_roundtrip() {
+        use crate::{
+            AttributeSemantic, EncodeAttribute, EncodeConfig, EncodingMethod, decode_header,
+            decode_point_cloud_with_config_sync, encode_point_cloud,
+            encode_point_cloud_with_config,
+        };
+
+        let positions = [0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 2.0, 2.0, 2.0, 3.0, 0.0, 1.0];
+        let intensity: [u16; 4] = [10, 20, 30, 40];
+        let attributes = [EncodeAttribute::new(
+            AttributeSemantic::Generic,
+            1,
+            &intensity,
+        )];
+
+        for method in [EncodingMethod::KdTree, EncodingMethod::Sequential] {
+            let config = EncodeConfig::default().with_encoding_method(method);
+            let encoded =
+                encode_point_cloud(&positions, &attributes, &config).expect("Failed to encode");
+            assert_eq!(decode_header(&encoded).unwrap().encoding_method, method);
+
+            let decoded = decode_point_cloud_with_config_sync(&encoded).expect("Failed to decode");
+            assert_eq!(decoded.config.point_count(), 4);
+            let generic = decoded
+                .config
+                .find_attribute(AttributeSemantic::Generic)
+                .unwrap();
+            let mut values = decoded.view::<u16>(generic).unwrap().into_owned();
+            values.sort();
+            assert_eq!(values, intensity);
+
+            let reencoded = encode_point_cloud_with_config(&decoded, &config).unwrap();
+            let redecoded = decode_point_cloud_with_config_sync(&reencoded).unwrap();
+            assert_eq!(redecoded.config.point_count(), 4);
+        }
+
+        let edgebreaker = EncodeConfig::default().with_encoding_method(EncodingMethod::Edgebreaker);
+        assert!(encode_point_cloud(&positions, &[], &edgebreaker).is_none());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_transcode() {
+        use crate::{
+            AttributeSemantic, EncodeConfig, GeometryType, decode_header,
+            decode_mesh_with_config_sync, transcode,
+        };
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let config = EncodeConfig::default().with_quantization_bits(AttributeSemantic::Position, 8);
+        let transcoded = transcode(&input, &config).expect("Failed to transcode");
+
+        let original = decode_mesh_with_config_sync(&input).unwrap();
+        let result = decode_mesh_with_config_sync(&transcoded).expect("Failed to decode");
+        assert_eq!(result.config.index_count(), original.config.index_count());
+        assert_eq!(
+            result.config.attributes().len(),
+            original.config.attributes().len()
+        );
+
+        let point_cloud = fs::read("assets/pointcloud.drc").expect("Failed to read pointcloud.drc");
+        let transcoded = transcode(&point_cloud, &EncodeConfig::default()).unwrap();
+        assert_eq!(
+            decode_header(&transcoded).unwrap().geometry_type,
+            GeometryType::PointCloud
+        );
+
+        assert!(transcode(b"not a draco file", &EncodeConfig::default()).is_none());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_roundtrip_check() {
+        use crate::{
+            AttributeSemantic, EncodeConfig, Tolerances, decode_mesh_with_config_sync,
+            roundtrip_check,
+        };
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let mesh = decode_mesh_with_config_sync(&input).expect("Failed to decode");
+
+        let lossless = [
+            AttributeSemantic::Position,
+            AttributeSemantic::Normal,
+            AttributeSemantic::Color,
+            AttributeSemantic::TexCoord,
+            AttributeSemantic::Generic,
+        ]
+        .into_iter()
+        .fold(EncodeConfig::default(), |config, semantic| {
+            config.with_quantization_bits(semantic, 0)
+        });
+        let tolerances = Tolerances::default().with_max_error(AttributeSemantic::Position, 0.0);
+        let report = roundtrip_check(&mesh, &lossless, &tolerances).expect("Failed to check");
+        assert_eq!(report.attributes.len(), mesh.config.attributes().len());
+        assert!(report.passed());
+
+        let coarse = EncodeConfig::default().with_quantization_bits(AttributeSemantic::Position, 4);
+        let report = roundtrip_check(&mesh, &coarse, &tolerances).expect("Failed to check");
+        assert!(report.encoded_size > 0);
+        assert!(!report.passed());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_auto_tune_encode() {
+        use crate::{
+            AttributeSemantic, EncodeConfig, QualityTarget, Tolerances, auto_tune_encode,
+            decode_mesh_with_config_sync, encode_mesh_with_config, roundtrip_check,
+        };
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let mesh = decode_mesh_with_config_sync(&input).expect("Failed to decode");
+
+        let tolerances = Tolerances::default().with_max_error(AttributeSemantic::Position, 0.01);
+        let config =
+            auto_tune_encode(&mesh, &QualityTarget::MaxError(tolerances)).expect("Failed to tune");
+        let report = roundtrip_check(&mesh, &config, &tolerances).expect("Failed to check");
+        assert!(report.passed());
+
+        let budget = encode_mesh_with_config(&mesh, &EncodeConfig::default())
+            .expect("Failed to encode")
+            .len();
+        let config =
+            auto_tune_encode(&mesh, &QualityTarget::SizeBudget(budget)).expect("Failed to tune");
+        let encoded = encode_mesh_with_config(&mesh, &config).expect("Failed to encode");
+        assert!(encoded.len() <= budget);
+        assert!(auto_tune_encode(&mesh, &QualityTarget::SizeBudget(16)).is_none());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_invalid_input_returns_none() {
+        use crate::decode_mesh_with_config_sync;
+
+        assert!(decode_mesh_with_config_sync(&[]).is_none());
+        assert!(decode_mesh_with_config_sync(b"not a draco file").is_none());
+
+        let point_cloud = fs::read("assets/pointcloud.drc").expect("Failed to read pointcloud.drc");
+        assert!(decode_mesh_with_config_sync(&point_cloud).is_none());
+
+        let mesh = fs::read("assets/mesh.drc").expect("Failed to read mesh.drc");
+        assert!(decode_mesh_with_config_sync(&mesh[..mesh.len() / 2]).is_none());
+    }
+
+    #[test]
+    fn test_empty_mesh_is_consistent() {
+        use crate::{AttributeDataType, IndexBuffer, MeshAttribute, MeshDecodeResult};
+
+        let position = MeshAttribute::new(3, AttributeDataType::Float32, 0, 0);
+        let empty = MeshDecodeResult::from_parts(0, &[], vec![(position, Vec::new())]);
+
+        assert_eq!(empty.config.vertex_count(), 0);
+        assert_eq!(empty.config.index_length(), 0);
+        assert_eq!(empty.config.buffer_size(), 0);
+        assert!(empty.data.is_empty());
+        assert_eq!(empty.indices(), Some(IndexBuffer::U16(Vec::new())));
+        assert_eq!(empty.triangles().count(), 0);
+        assert!(empty.attribute_values(0).unwrap().is_empty());
+        assert_eq!(empty.view::<f32>(0).unwrap().vertex_count(), 0);
+        assert_eq!(empty.split_by_vertex_cap(3).len(), 1);
+    }
+
+    #[test]
+    fn test_into_chunks() {
+        // A strip of 8 triangles over 10 vertices, plus one out of range
+        let positions: Vec<f32> = (0..30).map(|i| i as f32).collect();
+        let mut indices: Vec<u32> = (0..8).flat_map(|i| [i, i + 1, i + 2]).collect();
+        indices.extend([0, 1, 10]);
+        let mesh = position_mesh(&positions, &indices);
+
+        let mut chunks = mesh.clone().into_chunks(4);
+        let first = chunks.next().unwrap();
+        assert_eq!(first.triangles, 0..2);
+        assert_eq!(first.source_vertices, [0, 1, 2, 3]);
+        assert_eq!(
+            first.result.triangles().collect::<Vec<_>>(),
+            [[0, 1, 2], [1, 2, 3]]
+        );
+        let rest: Vec<_> = chunks.collect();
+        assert_eq!(rest.len(), 3);
+        assert_eq!(rest[2].triangles, 6..9);
+        assert_eq!(rest[2].source_vertices, [6, 7, 8, 9]);
+
+        let whole: Vec<_> = mesh.clone().into_chunks(10).collect();
+        assert_eq!(whole.len(), 1);
+        assert_eq!(whole[0].result.data, mesh.data);
+
+        let points = position_mesh(&positions, &[]);
+        let ranges: Vec<Vec<u32>> = points
+            .into_chunks(4)
+            .map(|chunk| chunk.source_vertices)
+            .collect();
+        assert_eq!(ranges, [vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![8, 9]]);
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let mesh = |positions: &[[f32; 3]]| {
+            position_mesh(positions.as_flattened(), &[0, 1, 2, 3, 4, 5, 2, 1, 6])
+        };
+        let mut positions: Vec<[f32; 3]> = (0..7).map(|i| [i as f32, 0.0, 0.0]).collect();
+
+        let separate = mesh(&positions);
+        assert_eq!(separate.connected_components(), [0, 1, 0]);
+        let parts = separate.split_components();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(
+            parts[0].triangles().collect::<Vec<_>>(),
+            [[0, 1, 2], [2, 1, 3]]
+        );
+        assert_eq!(
+            parts[0].view::<f32>(0).unwrap().vertex(3),
+            Some(&[6.0, 0.0, 0.0][..])
+        );
+        assert_eq!(parts[1].config.vertex_count(), 3);
+
+        // A seam copy of vertex 3 joins both triangles
+        positions[6] = positions[3];
+        assert_eq!(mesh(&positions).connected_components(), [0, 0, 0]);
+        assert_eq!(mesh(&positions).split_components().len(), 1);
+    }
+
+    #[test]
+    fn test_remove_outliers() {
+        use crate::StatisticalOutlierParams;
+
+        // A grid of triangles with one far away triangle
+        let mut positions: Vec<[f32; 3]> = (0..100)
+            .map(|i| [(i % 10) as f32, (i / 10) as f32, 0.0])
+            .collect();
+        positions.extend([[50.0, 50.0, 50.0], [51.0, 50.0, 50.0], [50.0, 51.0, 50.0]]);
+        let mut indices: Vec<u32> = (0..9)
+            .flat_map(|y| (0..9).map(move |x| y * 10 + x))
+            .flat_map(|i| [i, i + 1, i + 10, i + 1, i + 11, i + 10])
+            .collect();
+        indices.extend([100, 101, 102]);
+
+        let mesh = position_mesh(positions.as_flattened(), &indices);
+
+        let cleaned = mesh.remove_outliers(&StatisticalOutlierParams::new());
+        assert_eq!(cleaned.config.vertex_count(), 100);
+        assert_eq!(cleaned.config.index_count(), 81 * 6);
+
+        let strict = StatisticalOutlierParams::new().with_min_component_vertices(101);
+        assert_eq!(mesh.remove_outliers(&strict).config.index_count(), 0);
+    }
+
+    #[cfg(feature = "repair")]
+    #[test]
+    fn test_fill_holes() {
+        // A tetrahedron missing one face, vertex 4 is a seam copy of vertex 0
+        let positions: [f32; 15] = [
+            0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0,
+        ];
+        let mesh = position_mesh(&positions, &[0, 2, 1, 0, 1, 3, 4, 3, 2]);
+        assert_eq!(mesh.boundary_loops(), [vec![2, 1, 3]]);
+
+        let unchanged = mesh.clone().fill_holes(2);
+        assert_eq!(unchanged.config.index_count(), 9);
+
+        let filled = mesh.fill_holes(3);
+        assert_eq!(filled.triangles().last(), Some([2, 3, 1]));
+        assert!(filled.boundary_loops().is_empty());
+        assert_eq!(filled.view::<f32>(0).unwrap().vertex_count(), 5);
+    }
+
+    #[cfg(feature = "repair")]
+    #[test]
+    fn test_fill_holes_avoids_degenerate_fans() {
+        // A unit cube without its top, vertex 8 splits the front top edge
+        let positions: [f32; 27] = [
+            0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0, //
+            0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0, 1.0, 1.0, 0.0, 1.0, 1.0, //
+            0.5, 0.0, 1.0,
+        ];
+        let indices = [
+            0, 2, 1, 0, 3, 2, 0, 1, 5, 0, 5, 8, 0, 8, 4, 2, 3, 7, 2, 7, 6, 1, 2, 6, 1, 6,
+            5, //
+            0, 4, 7, 0, 7, 3,
+        ];
+        let mesh = position_mesh(&positions, &indices);
+        assert_eq!(mesh.boundary_loops()[0].len(), 5);
+
+        let filled = mesh.fill_holes(5);
+        assert_eq!(filled.config.index_count(), 42);
+        assert!(filled.boundary_loops().is_empty());
+        assert_eq!(filled.degenerate_triangles(), Some(Vec::new()));
+    }
+
+    #[cfg(feature = "bake-ao")]
+    #[test]
+    fn test_bake_ambient_occlusion() {
+        use crate::{
+            AmbientOcclusionParams, AttributeDataType, AttributeSemantic, MeshAttribute,
+            MeshDecodeResult,
+        };
+
+        // A unit quad facing up under a large quad facing down
+        let positions: [f32; 24] = [
+            0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0, //
+            -10.0, -10.0, 0.1, 11.0, -10.0, 0.1, 11.0, 11.0, 0.1, -10.0, 11.0, 0.1,
+        ];
+        let position = MeshAttribute::new(3, AttributeDataType::Float32, 0, 0)
+            .with_semantic(AttributeSemantic::Position);
+        let attributes = vec![(position, bytemuck::cast_slice(&positions).to_vec())];
+        let params = AmbientOcclusionParams::new().with_samples(32);
+
+        let open = MeshDecodeResult::from_parts(8, &[0, 1, 2, 0, 2, 3], attributes.clone())
+            .bake_ambient_occlusion(&params);
+        let ao = open.view::<u8>(1).unwrap();
+        assert_eq!(&ao[..4], &[255; 4]);
+        assert!(open.config.get_attribute(1).unwrap().normalized());
+
+        let covered =
+            MeshDecodeResult::from_parts(8, &[0, 1, 2, 0, 2, 3, 4, 6, 5, 4, 7, 6], attributes)
+                .bake_ambient_occlusion(&params);
+        let ao = covered.view::<u8>(1).unwrap();
+        assert!(ao[..4].iter().all(|&value| value < 16));
+        assert_eq!(covered.config.index_count(), 12);
+    }
+
+    #[cfg(feature = "bake-sdf")]
+    #[test]
+    fn test_bake_sdf() {
+        // A closed unit cube
+        let positions: [f32; 24] = [
+            0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0, //
+            0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0, 1.0, 1.0, 0.0, 1.0, 1.0,
+        ];
+        let indices = [
+            0, 2, 1, 0, 3, 2, 4, 5, 6, 4, 6, 7, 0, 1, 5, 0, 5, 4, //
+            2, 3, 7, 2, 7, 6, 1, 2, 6, 1, 6, 5, 0, 4, 7, 0, 7, 3,
+        ];
+        let cube = position_mesh(&positions, &indices);
+
+        let sdf = cube
+            .bake_sdf([4, 4, 4], Some(([-0.5; 3], [1.5; 3])))
+            .unwrap();
+        assert_eq!(sdf.distances.len(), 64);
+        assert_eq!(sdf.cell_size(), [0.5; 3]);
+        assert_eq!(sdf.position([1, 1, 1]), [0.25; 3]);
+        assert!((sdf.distance([1, 1, 1]).unwrap() + 0.25).abs() < 1e-6);
+        assert!((sdf.distance([0, 1, 1]).unwrap() - 0.25).abs() < 1e-6);
+        let corner = sdf.distance([0, 0, 0]).unwrap();
+        assert!((corner - 0.1875f32.sqrt()).abs() < 1e-6);
+        assert!(sdf.distance([4, 0, 0]).is_none());
+        assert!((sdf.sample([0.5; 3]) + 0.25).abs() < 1e-6);
+
+        let fitted = cube.bake_sdf([8, 8, 8], None).unwrap();
+        assert_eq!(fitted.min, [-0.1; 3]);
+        assert!(fitted.distances.iter().any(|&distance| distance < 0.0));
+        assert!(cube.bake_sdf([0, 8, 8], None).is_none());
+    }
+
+    #[cfg(feature = "thumbnail")]
+    #[test]
+    fn test_render_preview() {
+        use crate::{PreviewCamera, render_gbuffer, render_preview};
+
+        // A unit quad seen head-on fills the middle of the image
+        let positions: [f32; 12] = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0];
+        let mesh = position_mesh(&positions, &[0, 1, 2, 0, 2, 3]);
+        let camera = PreviewCamera::new()
+            .with_yaw(0.0)
+            .with_pitch(0.0)
+            .with_background([0, 0, 255, 255]);
+
+        let image = render_preview(&mesh, [48, 32], &camera);
+        assert_eq!(image.dimensions(), (48, 32));
+        assert_eq!(image.get_pixel(0, 0).0, [0, 0, 255, 255]);
+        assert_eq!(image.get_pixel(47, 16).0, [0, 0, 255, 255]);
+        let center = image.get_pixel(24, 16).0;
+        assert_eq!(center[0], center[2]);
+        assert!(center[0] > 100 && center[0] < 200);
+
+        // Seen from behind the quad is lit the same
+        let back = render_preview(&mesh, [48, 32], &camera.with_yaw(std::f32::consts::PI));
+        assert_eq!(back.get_pixel(24, 16).0, center);
+
+        let gbuffer = render_gbuffer(&mesh, [48, 32], &camera);
+        assert_eq!(gbuffer.depth.get_pixel(0, 0).0, [0.0]);
+        assert_eq!(gbuffer.normals.get_pixel(0, 0).0, [0.0; 3]);
+        // The camera fits the circumscribed circle of the quad in view
+        let distance = std::f32::consts::FRAC_1_SQRT_2 / std::f32::consts::FRAC_PI_8.sin();
+        assert!((gbuffer.depth.get_pixel(24, 16).0[0] - distance).abs() < 1e-4);
+        assert_eq!(gbuffer.normals.get_pixel(24, 16).0, [0.0, 0.0, 1.0]);
+        let normal_map = gbuffer.normal_map();
+        assert_eq!(normal_map.get_pixel(24, 16).0, [128, 128, 255, 255]);
+        assert_eq!(normal_map.get_pixel(0, 0).0, [0; 4]);
+
+        // Looking down at the quad from above, its normal points down the
+        // image
+        let above = render_gbuffer(&mesh, [48, 32], &camera.with_pitch(0.5));
+        let [_, y, z] = above.normals.get_pixel(24, 16).0;
+        assert!((y + 0.5f32.sin()).abs() < 1e-4 && (z - 0.5f32.cos()).abs() < 1e-4);
+        // Depth is interpolated perspective correct across the tilted quad:
+        // the ray through the pixel center, half a pixel below the image
+        // center, meets the plane of the quad at this depth
+        let below = 0.5 * std::f32::consts::FRAC_PI_8.tan() / 16.0;
+        let expected = 0.5f32.cos() * distance / (0.5f32.cos() - 0.5f32.sin() * below);
+        let depth = above.depth.get_pixel(24, 16).0[0];
+        assert!((depth - expected).abs() < 1e-4, "{depth} != {expected}");
+    }
+
+    #[test]
+    fn test_compute_curvature() {
+        // A regular octahedron, every vertex has an angle defect of 2π/3
+        let positions: [f32; 18] = [
+            1.0, 0.0, 0.0, -1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, -1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0,
+            -1.0,
+        ];
+        let mesh = position_mesh(
+            &positions,
+            &[
+                0, 2, 4, 1, 4, 2, 0, 4, 3, 1, 3, 4, 0, 5, 2, 1, 2, 5, 0, 3, 5, 1, 5, 3,
+            ],
+        );
+
+        let curvature = mesh.compute_curvature().unwrap();
+        assert_eq!(curvature.len(), 6);
+        for vertex in &curvature {
+            assert!((vertex.gaussian - std::f32::consts::PI / 3.0f32.sqrt()).abs() < 1e-4);
+            assert!(vertex.mean > 0.0);
+        }
+
+        let qualities = mesh.triangle_qualities().unwrap();
+        assert!(
+            qualities
+                .iter()
+                .all(|&quality| (quality - 1.0).abs() < 1e-5)
+        );
+        assert_eq!(mesh.triangle_quality_histogram(4), Some(vec![0, 0, 0, 8]));
+    }
+
+    #[test]
+    fn test_compute_aabb() {
+        use crate::MeshDecodeResult;
+
+        let positions: [f32; 9] = [1.0, -2.0, 0.5, -1.0, 4.0, 0.0, 0.0, 0.0, 3.0];
+        let mesh = position_mesh(&positions, &[0, 1, 2]);
+        assert_eq!(
+            mesh.compute_aabb(),
+            Some(([-1.0, -2.0, 0.0], [1.0, 4.0, 3.0]))
+        );
+        #[cfg(feature = "mint")]
+        assert_eq!(
+            mesh.compute_aabb_mint(),
+            Some((
+                mint::Point3::from([-1.0, -2.0, 0.0]),
+                mint::Point3::from([1.0, 4.0, 3.0])
+            ))
+        );
+
+        let empty = position_mesh(&[], &[]);
+        assert_eq!(empty.compute_aabb(), None);
+        assert_eq!(
+            MeshDecodeResult::from_parts(0, &[], Vec::new()).compute_aabb(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_compute_bounding_sphere() {
+        use crate::MeshDecodeResult;
+
+        let mut positions = vec![
+            1.0, 0.0, 0.0, -1.0, 0.0, 0.0, 0.0, 1.0, 0.0, //
+            0.0, -1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, -1.0,
+        ];
+        for i in 0..64 {
+            let angle = i as f32 * 0.7;
+            let height = (i as f32 / 32.0) - 1.0;
+            let ring = (1.0 - height * height).sqrt();
+            positions.extend([ring * angle.cos(), ring * angle.sin(), height]);
+        }
+        let offset = [10.0, -5.0, 2.0];
+        for (i, value) in positions.iter_mut().enumerate() {
+            *value = *value * 2.0 + offset[i % 3];
+        }
+
+        let mesh = position_mesh(&positions, &[]);
+
+        let (center, radius) = mesh.compute_bounding_sphere().unwrap();
+        assert!((2.0..2.1).contains(&radius), "radius {radius}");
+        for (axis, &value) in center.iter().enumerate() {
+            assert!((value - offset[axis]).abs() < 0.1, "center {center:?}");
+        }
+        for point in positions.chunks_exact(3) {
+            let distance = (0..3)
+                .map(|axis| (point[axis] - center[axis]).powi(2))
+                .sum::<f32>()
+                .sqrt();
+            assert!(distance <= radius);
+        }
+        #[cfg(feature = "mint")]
+        assert_eq!(
+            mesh.compute_bounding_sphere_mint(),
+            Some((center.into(), radius))
+        );
+
+        let single = position_mesh(&offset, &[]);
+        assert_eq!(single.compute_bounding_sphere().map(|s| s.0), Some(offset));
+        assert_eq!(
+            MeshDecodeResult::from_parts(0, &[], Vec::new()).compute_bounding_sphere(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_compute_min_max() {
+        use crate::{
+            AttributeDataType, AttributeSemantic, AttributeValues, MeshAttribute, MeshDecodeResult,
+        };
+
+        let positions: [f32; 9] = [1.0, -2.0, 0.5, -1.0, 4.0, 0.0, 0.0, 0.0, 3.0];
+        let colors: [u8; 12] = [255, 0, 10, 255, 128, 64, 20, 255, 0, 200, 30, 255];
+        let mesh = MeshDecodeResult::from_parts(
+            3,
+            &[0, 1, 2],
+            vec![
+                (
+                    MeshAttribute::new(3, AttributeDataType::Float32, 0, 0)
+                        .with_semantic(AttributeSemantic::Position),
+                    bytemuck::cast_slice(&positions).to_vec(),
+                ),
+                (
+                    MeshAttribute::new(4, AttributeDataType::UInt8, 0, 0)
+                        .with_semantic(AttributeSemantic::Color),
+                    colors.to_vec(),
+                ),
+            ],
+        );
+
+        let position = mesh.config.get_attribute(0).unwrap();
+        assert_eq!(
+            position.compute_min_max(&mesh),
+            Some((
+                AttributeValues::Float32(vec![-1.0, -2.0, 0.0]),
+                AttributeValues::Float32(vec![1.0, 4.0, 3.0]),
+            ))
+        );
+        let color = mesh.config.get_attribute(1).unwrap();
+        assert_eq!(
+            color.compute_min_max(&mesh),
+            Some((
+                AttributeValues::UInt8(vec![0, 0, 10, 255]),
+                AttributeValues::UInt8(vec![255, 200, 30, 255]),
+            ))
+        );
+
+        let empty = MeshDecodeResult::from_parts(0, &[], Vec::new());
+        assert_eq!(position.compute_min_max(&empty), None);
+    }
+
+    #[test]
+    fn test_remove_degenerate_triangles() {
+        use crate::postprocess::remove_degenerate_triangles;
+        use crate::{AttributeDataType, AttributeSemantic, MeshAttribute, MeshDecodeResult};
+
+        let positions: [f32; 15] = [
+            0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, //
+            1.0, 1.0, 1.0, 3.0, 3.0, 3.0,
+        ];
+        let position = MeshAttribute::new(3, AttributeDataType::Float32, 0, 0)
+            .with_semantic(AttributeSemantic::Position);
+        let mut mesh = MeshDecodeResult::from_parts(
+            5,
+            &[0, 1, 2, 0, 3, 4, 1, 1, 2, 2, 1, 3, 0, 1, 9],
+            vec![(position, bytemuck::cast_slice(&positions).to_vec())],
+        );
+        let buffer_size = mesh.data.len();
+
+        assert_eq!(remove_degenerate_triangles(&mut mesh), 3);
+        assert_eq!(mesh.config.index_count(), 6);
+        assert_eq!(mesh.config.index_length(), 12);
+        assert_eq!(mesh.data.len(), buffer_size);
+        assert_eq!(
+            mesh.triangles().collect::<Vec<_>>(),
+            vec![[0, 1, 2], [2, 1, 3]]
+        );
+        assert_eq!(mesh.vectors::<[f32; 3], 3>(0).unwrap().count(), 5);
+        assert_eq!(remove_degenerate_triangles(&mut mesh), 0);
+
+        // Without float positions only repeated corners are detected
+        let position = MeshAttribute::new(3, AttributeDataType::UInt16, 0, 0)
+            .with_semantic(AttributeSemantic::Position);
+        let mut mesh =
+            MeshDecodeResult::from_parts(3, &[0, 1, 2, 0, 0, 1], vec![(position, vec![0; 18])]);
+        assert_eq!(remove_degenerate_triangles(&mut mesh), 1);
+        assert_eq!(mesh.triangles().collect::<Vec<_>>(), vec![[0, 1, 2]]);
+    }
+
+    #[test]
+    fn test_generate_smooth_normals() {
+        use crate::postprocess::generate_smooth_normals;
+        use crate::{AttributeDataType, AttributeSemantic, MeshAttribute, MeshDecodeResult};
+
+        // Two faces meeting at a right angle along the x axis, with the
+        // vertices on the edge split between them
+        let positions: [f32; 18] = [
+            0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, //
+            0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0,
+        ];
+        let position = MeshAttribute::new(3, AttributeDataType::Float32, 0, 0)
+            .with_semantic(AttributeSemantic::Position)
+            .with_unique_id(4);
+        let mesh = MeshDecodeResult::from_parts(
+            6,
+            &[0, 1, 2, 3, 4, 5],
+            vec![(position, bytemuck::cast_slice(&positions).to_vec())],
+        );
+
+        let mut sharp = mesh.clone();
+        assert!(generate_smooth_normals(&mut sharp, 60f32.to_radians()));
+        let normal = sharp
+            .config
+            .find_attribute(AttributeSemantic::Normal)
+            .unwrap();
+        assert_eq!(sharp.config.get_attribute(normal).unwrap().unique_id(), 5);
+        assert_eq!(sharp.config.get_attribute(normal).unwrap().offset() % 4, 0);
+        assert_eq!(sharp.config.buffer_size(), sharp.data.len());
+        let normals: Vec<[f32; 3]> = sharp.normals().unwrap().collect();
+        assert_eq!(normals[..3], [[0.0, 0.0, 1.0]; 3]);
+        assert_eq!(normals[3..], [[0.0, 1.0, 0.0]; 3]);
+        assert!(!generate_smooth_normals(&mut sharp, 60f32.to_radians()));
+
+        let mut smooth = mesh;
+        assert!(generate_smooth_normals(&mut smooth, 100f32.to_radians()));
+        let normals: Vec<[f32; 3]> = smooth.normals().unwrap().collect();
+        let diagonal = std::f32::consts::FRAC_1_SQRT_2;
+        for vertex in [0, 1, 3, 5] {
+            assert!((normals[vertex][1] - diagonal).abs() < 1e-6);
+            assert!((normals[vertex][2] - diagonal).abs() < 1e-6);
+        }
+        assert_eq!(normals[2], [0.0, 0.0, 1.0]);
+        assert_eq!(normals[4], [0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    #[cfg(feature = "mikktspace")]
+    fn test_generate_tangents() {
+        use crate::postprocess::{generate_smooth_normals, generate_tangents};
+        use crate::{AttributeDataType, AttributeSemantic, MeshAttribute, MeshDecodeResult};
+
+        // A quad in the xy plane, mapped with u along x or mirrored
+        let positions: [f32; 12] = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0];
+        let quad = |uvs: [f32; 8]| {
+            MeshDecodeResult::from_parts(
+                4,
+                &[0, 1, 2, 0, 2, 3],
+                vec![
+                    (
+                        MeshAttribute::new(3, AttributeDataType::Float32, 0, 0)
+                            .with_semantic(AttributeSemantic::Position),
+                        bytemuck::cast_slice(&positions).to_vec(),
+                    ),
+                    (
+                        MeshAttribute::new(2, AttributeDataType::Float32, 0, 0)
+                            .with_semantic(AttributeSemantic::TexCoord)
+                            .with_unique_id(1),
+                        bytemuck::cast_slice(&uvs).to_vec(),
+                    ),
+                ],
+            )
+        };
+
+        let mut mesh = quad([0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0]);
+        assert_eq!(generate_tangents(&mut mesh), None);
+        assert!(generate_smooth_normals(&mut mesh, 1.0));
+        let index = generate_tangents(&mut mesh).unwrap();
+        let attribute = mesh.config.get_attribute(index).unwrap();
+        assert_eq!(attribute.dim(), 4);
+        assert_eq!(attribute.unique_id(), 3);
+        assert_eq!(mesh.config.attribute_name(index), Some("TANGENT"));
+        assert_eq!(mesh.config.attribute_name(0), None);
+        let tangents: Vec<[f32; 4]> = mesh.vectors(index).unwrap().collect();
+        assert_eq!(tangents, vec![[1.0, 0.0, 0.0, 1.0]; 4]);
+
+        let mut glb = Vec::new();
+        crate::write_glb(std::slice::from_ref(&mesh), &mut glb).unwrap();
+        let json_length = u32::from_le_bytes(glb[12..16].try_into().unwrap()) as usize;
+        let json: serde_json::Value = serde_json::from_slice(&glb[20..20 + json_length]).unwrap();
+        let attributes = &json["meshes"][0]["primitives"][0]["attributes"];
+        assert!(attributes["TANGENT"].is_u64());
+        assert!(attributes["_ATTRIBUTE_3"].is_null());
+
+        // Names follow the attribute when others are removed
+        let kept = mesh.retain_attributes(|a| a.semantic() != AttributeSemantic::TexCoord);
+        assert_eq!(kept.config.attribute_name(index - 1), Some("TANGENT"));
+        let stripped = mesh.retain_attributes(|a| a.unique_id() != 3);
+        assert_eq!(stripped.config.attribute_name(index - 1), None);
+
+        let mut mirrored = quad([0.0, 0.0, -1.0, 0.0, -1.0, 1.0, 0.0, 1.0]);
+        assert!(generate_smooth_normals(&mut mirrored, 1.0));
+        let index = generate_tangents(&mut mirrored).unwrap();
+        let tangents: Vec<[f32; 4]> = mirrored.vectors(index).unwrap().collect();
+        assert_eq!(tangents, vec![[-1.0, 0.0, 0.0, -1.0]; 4]);
+        assert_eq!(mirrored.config.vertex_count(), 4);
+
+        // Texture coordinates folded back along the diagonal give the two
+        // triangles opposite handedness, so the shared vertices are split
+        let mut folded = quad([0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 1.0, 0.0]);
+        assert!(generate_smooth_normals(&mut folded, 1.0));
+        let index = generate_tangents(&mut folded).unwrap();
+        assert_eq!(folded.config.vertex_count(), 6);
+        assert_eq!(
+            folded.triangles().collect::<Vec<_>>(),
+            vec![[0, 1, 2], [4, 5, 3]]
+        );
+        let tangents: Vec<[f32; 4]> = folded.vectors(index).unwrap().collect();
+        assert_eq!(&tangents[..3], &[[1.0, 0.0, 0.0, 1.0]; 3]);
+        assert_eq!(&tangents[3..], &[[0.0, 1.0, 0.0, -1.0]; 3]);
+        let positions: Vec<[f32; 3]> = folded.positions().unwrap().collect();
+        assert_eq!(positions[4], [0.0, 0.0, 0.0]);
+        assert_eq!(positions[5], [1.0, 1.0, 0.0]);
+        assert_eq!(folded.config.attribute_name(index), Some("TANGENT"));
+    }
+
+    #[test]
+    fn test_degenerate_triangles() {
+        // A regular triangle, a collinear one, one with a repeated corner and
+        // one so small that its area underflows in f32 but is not degenerate
+        let positions: [f32; 18] = [
+            0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, //
+            1.0, 1.0, 1.0, 3.0, 3.0, 3.0, //
+            1e-30, 1e-30, 0.0,
+        ];
+        let mesh = position_mesh(&positions, &[0, 1, 2, 0, 3, 4, 1, 1, 2, 0, 5, 2, 0, 1, 9]);
+
+        assert_eq!(mesh.degenerate_triangles(), Some(vec![1, 2, 4]));
+        let qualities = mesh.triangle_qualities().unwrap();
+        assert!(qualities[0] > 0.5);
+        assert_eq!(&qualities[1..3], &[0.0, 0.0]);
+        assert!(qualities[3] > 0.0);
+        assert_eq!(qualities[4], 0.0);
+    }
+
+    #[test]
+    fn test_detect_planes() {
+        use crate::PlaneDetectionParams;
+
+        // A 20x20 floor, a 15x15 wall and a few stray points
+        let mut positions: Vec<f32> = Vec::new();
+        for i in 0..20 {
+            for j in 0..20 {
+                positions.extend_from_slice(&[i as f32 * 0.1, j as f32 * 0.1, 0.0]);
+            }
+        }
+        for i in 0..15 {
+            for j in 0..15 {
+                positions.extend_from_slice(&[-0.5, i as f32 * 0.1, 0.2 + j as f32 * 0.1]);
+            }
+        }
+        positions.extend_from_slice(&[0.5, 0.5, 0.7, 1.3, 0.2, 0.4, 0.9, 1.1, 1.6]);
+        let mesh = position_mesh(&positions, &[]);
+
+        let params = PlaneDetectionParams::new().with_min_inliers(50);
+        let planes = mesh.detect_planes(&params);
+        assert_eq!(planes.len(), 2);
+        assert_eq!(planes[0].inliers, (0..400).collect::<Vec<u32>>());
+        assert!((planes[0].equation[2].abs() - 1.0).abs() < 1e-4);
+        assert_eq!(planes[1].inliers, (400..625).collect::<Vec<u32>>());
+        assert!(planes[1].distance([-0.5, 7.0, -3.0]).abs() < 1e-4);
+        assert_eq!(mesh.detect_planes(&params), planes);
+    }
+
+    #[test]
+    fn test_crs_and_reproject() {
+        use crate::{
+            AttributeDataType, AttributeSemantic, CoordinateTransformer, Crs, MeshAttribute,
+            MeshDecodeResult, crs_from_metadata,
+        };
+
+        // Header with the metadata flag, no attribute metadata and a `crs`
+        // geometry entry
+        let mut data = b"DRACO\x02\x02\x01\x01\x00\x80".to_vec();
+        data.extend_from_slice(b"\x00\x01\x03crs\x09EPSG:4978\x00");
+        assert_eq!(crs_from_metadata(&data), Some(Crs::ECEF));
+        data[18..27].copy_from_slice(b"  32632  ");
+        assert_eq!(crs_from_metadata(&data), Some(Crs::from_epsg(32632)));
+        let mut binary = data[..14].to_vec();
+        binary.extend_from_slice(b"crs\x04\x72\x13\x00\x00\x00");
+        assert_eq!(crs_from_metadata(&binary), None);
+        data[10] = 0;
+        assert_eq!(crs_from_metadata(&data), None);
+        assert_eq!(Crs::WGS84.to_string(), "EPSG:4326");
+
+        struct Offset;
+
+        impl CoordinateTransformer for Offset {
+            fn transform(&self, [x, y, z]: [f64; 3]) -> [f64; 3] {
+                [x + 4_000_000.0, y, z]
+            }
+
+            fn target_crs(&self) -> Option<Crs> {
+                Some(Crs::ECEF)
+            }
+        }
+
+        let positions: [f32; 8] = [0.0, 1.0, 2.0, 9.0, 3.0, 4.0, 5.0, 9.0];
+        let position = MeshAttribute::new(4, AttributeDataType::Float32, 0, 0)
+            .with_semantic(AttributeSemantic::Position);
+        let mut mesh = MeshDecodeResult::from_parts(
+            2,
+            &[0, 1, 1],
+            vec![(position, bytemuck::cast_slice(&positions).to_vec())],
+        )
+        .with_crs(Crs::from_epsg(32632));
+        assert_eq!(mesh.config.crs(), Some(Crs::from_epsg(32632)));
+
+        assert!(mesh.reproject(Offset));
+        assert_eq!(mesh.config.crs(), Some(Crs::ECEF));
+        let view = mesh.view::<f32>(0).unwrap();
+        assert_eq!(view.vertex(1), Some(&[4_000_003.0, 4.0, 5.0, 9.0][..]));
+
+        assert!(mesh.reproject(|[x, y, z]: [f64; 3]| [x, -y, z]));
+        assert_eq!(mesh.config.crs(), None);
+        assert_eq!(
+            mesh.view::<f32>(0).unwrap().vertex(0),
+            Some(&[4_000_000.0, -1.0, 2.0, 9.0][..])
+        );
+
+        let quantized = MeshAttribute::new(3, AttributeDataType::UInt16, 0, 0)
+            .with_semantic(AttributeSemantic::Position);
+        let mut mesh = MeshDecodeResult::from_parts(1, &[0, 0, 0], vec![(quantized, vec![0; 6])]);
+        assert!(!mesh.reproject(Offset));
+    }
+
+    #[test]
+    fn test_attribute_as_slice() {
+        use crate::{
+            AttributeDataType, AttributeSemantic, CastError, MeshAttribute, MeshDecodeResult,
+            attribute_as_slice,
+        };
+
+        let positions: [f32; 6] = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+        let position = MeshAttribute::new(3, AttributeDataType::Float32, 0, 0)
+            .with_semantic(AttributeSemantic::Position);
+        let color = MeshAttribute::new(1, AttributeDataType::UInt8, 0, 0);
+        // Three u16 indices leave the positions 2 bytes past a 4-byte boundary
+        let mesh = MeshDecodeResult::from_parts(
+            2,
+            &[0, 1, 1],
+            vec![
+                (position, bytemuck::cast_slice(&positions).to_vec()),
+                (color, vec![7, 8]),
+            ],
+        );
+
+        assert_eq!(attribute_as_slice::<u8>(&mesh, 1), Ok(&[7, 8][..]));
+        assert_eq!(attribute_as_slice::<[u8; 1]>(&mesh, 1), Ok(&[[7], [8]][..]));
+        assert_eq!(
+            attribute_as_slice::<i8>(&mesh, 1),
+            Err(CastError::DataType {
+                attribute: AttributeDataType::UInt8,
+                requested: AttributeDataType::Int8,
+            })
+        );
+        assert_eq!(
+            attribute_as_slice::<[f32; 2]>(&mesh, 0),
+            Err(CastError::ElementSize {
+                element_size: 12,
+                requested: 8,
+            })
+        );
+        assert_eq!(
+            attribute_as_slice::<f32>(&mesh, 2),
+            Err(CastError::MissingAttribute(2))
+        );
+
+        // The allocator aligns the buffer to at least 4 bytes, so the
+        // positions are misaligned for floats but fine for bytes
+        assert_eq!(mesh.data.as_ptr() as usize % 4, 0);
+        assert_eq!(
+            attribute_as_slice::<[f32; 3]>(&mesh, 0),
+            Err(CastError::Misaligned {
+                offset: 6,
+                align: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn test_vertex_buffer_layouts() {
+        use crate::{
+            AttributeDataType, AttributeSemantic, MeshAttribute, MeshDecodeResult, VertexFormat,
+            VertexLayoutMode,
+        };
+
+        let positions: [f32; 6] = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+        let position = MeshAttribute::new(2, AttributeDataType::Float32, 0, 0)
+            .with_semantic(AttributeSemantic::Position);
+        let color = MeshAttribute::new(1, AttributeDataType::UInt8, 0, 0)
+            .with_semantic(AttributeSemantic::Color)
+            .with_normalized(true);
+        // RGB colors have no vertex format
+        let rgb = MeshAttribute::new(3, AttributeDataType::UInt8, 0, 0);
+        let mesh = MeshDecodeResult::from_parts(
+            3,
+            &[0, 1, 2],
+            vec![
+                (position, bytemuck::cast_slice(&positions).to_vec()),
+                (color, vec![7, 8, 9]),
+                (rgb, vec![0; 9]),
+            ],
+        );
+
+        let planar = mesh.config.vertex_buffer_layouts(VertexLayoutMode::Planar);
+        assert_eq!(planar.len(), 2);
+        assert_eq!(planar[1].attributes()[0].format, VertexFormat::Unorm8);
+        assert_eq!(planar[1].attributes()[0].shader_location, 1);
+        assert_eq!(planar[1].array_stride(), 4);
+        assert_eq!(planar[1].buffer_offset(), 24);
+
+        let interleaved = mesh
+            .config
+            .vertex_buffer_layouts(VertexLayoutMode::Interleaved);
+        assert_eq!(interleaved.len(), 1);
+        assert_eq!(interleaved[0].array_stride(), 12);
+        assert_eq!(interleaved[0].attributes()[1].offset, 8);
+
+        let data = mesh.vertex_buffer_data(VertexLayoutMode::Interleaved);
+        assert_eq!(data.len(), 36);
+        assert_eq!(&data[8..12], &[7, 0, 0, 0]);
+        assert_eq!(
+            &data[12..20],
+            bytemuck::cast_slice::<f32, u8>(&positions[2..4])
+        );
+        assert_eq!(mesh.vertex_buffer_data(VertexLayoutMode::Planar).len(), 36);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_attribute_values() {
+        use crate::decode_mesh_with_config_sync;
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let result = decode_mesh_with_config_sync(&input).expect("Failed to decode mesh");
+
+        for (index, attribute) in result.config.attributes().iter().enumerate() {
+            let values = result
+                .attribute_values(index)
+                .expect("Failed to extract attribute values");
+            let expected = (attribute.dim() * result.config.vertex_count()) as usize;
+            assert_eq!(values.len(), expected);
+        }
+
+        assert!(
+            result
+                .attribute_values(result.config.attributes().len())
+                .is_none()
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_indices_and_triangles() {
+        use crate::{IndexBuffer, decode_mesh_with_config_sync};
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let result = decode_mesh_with_config_sync(&input).expect("Failed to decode mesh");
+
+        let indices = result.indices().expect("Failed to read indices");
+        assert!(matches!(indices, IndexBuffer::U16(_)));
+        assert_eq!(indices.len(), result.config.index_count() as usize);
+
+        let triangles: Vec<[u32; 3]> = result.triangles().collect();
+        assert_eq!(triangles.len() * 3, indices.len());
+        assert_eq!(triangles[0][1], indices.get(1).unwrap());
+        assert!(
+            triangles
+                .iter()
+                .flatten()
+                .all(|&i| i < result.config.vertex_count())
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_attribute_view() {
+        use crate::{AttributeDataType, AttributeValues, decode_mesh_with_config_sync};
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let result = decode_mesh_with_config_sync(&input).expect("Failed to decode mesh");
+
+        let index = result
+            .config
+            .attributes()
+            .iter()
+            .position(|a| a.data_type() == AttributeDataType::Float32)
+            .expect("Expected a float attribute");
+
+        let view = result.view::<f32>(index).expect("Failed to view attribute");
+        assert_eq!(view.vertex_count(), result.config.vertex_count() as usize);
+        assert_eq!(view.vertices().count(), view.vertex_count());
+
+        let Some(AttributeValues::Float32(values)) = result.attribute_values(index) else {
+            panic!("Expected float values");
+        };
+        assert_eq!(&*view, values.as_slice());
+        assert!(result.view::<u8>(index).is_none());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_prefetch_config() {
+        use crate::{decode_mesh_with_config_sync, prefetch_config};
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let expected = decode_mesh_with_config_sync(&input).unwrap();
+
+        let prefetched = prefetch_config(&input).unwrap();
+        assert_eq!(prefetched.config(), &expected.config);
+        // Prefetching leaves other decodes of the same bytes alone
+        let other = prefetch_config(&input).unwrap();
+        assert_eq!(
+            decode_mesh_with_config_sync(&input).unwrap().data,
+            expected.data
+        );
+
+        let decoded = prefetched.decode().unwrap();
+        assert_eq!(decoded.data, expected.data);
+        assert_eq!(decoded.config, expected.config);
+        assert_eq!(other.decode().unwrap().data, expected.data);
+
+        assert!(prefetch_config(&input[..20]).is_none());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_across_threads() {
+        use crate::{MeshDecoder, MeshHandle, NativeDecoder};
+        use std::sync::Arc;
+
+        let input = Arc::new(fs::read("assets/20/20_data.bin").expect("Failed to read model file"));
+        let expected = NativeDecoder.decode_mesh(&input).unwrap();
+
+        let workers: Vec<_> = (0..4)
+            .map(|_| {
+                let input = Arc::clone(&input);
+                std::thread::spawn(move || NativeDecoder.decode_mesh(&input).unwrap())
+            })
+            .collect();
+
+        for worker in workers {
+            let result = worker.join().unwrap();
+            assert_eq!(result.data, expected.data);
+            assert_eq!(result.config, expected.config);
+        }
+
+        // A handle created on one thread can be finished on another.
+        let handle = MeshHandle::decode(&input).unwrap();
+        let data = std::thread::spawn(move || {
+            let config = handle.config().unwrap();
+            let mut buffer = vec![0u8; config.buffer_size()];
+            let written = handle.decode_into(&mut buffer).unwrap();
+            buffer.truncate(written);
+            buffer
+        })
+        .join()
+        .unwrap();
+        assert_eq!(data, expected.data);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_mesh_chunked() {
+        use crate::{decode_mesh_chunked_sync, decode_mesh_with_config_sync};
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let full = decode_mesh_with_config_sync(&input).unwrap();
+        let chunks = decode_mesh_chunked_sync(&input, 1000).unwrap();
+
+        assert!(chunks.len() > 1);
+        let triangles: usize = chunks.iter().map(|c| c.triangles.len()).sum();
+        assert_eq!(triangles * 3, full.config.index_count() as usize);
+
+        let source: Vec<[u32; 3]> = full.triangles().collect();
+        for chunk in &chunks {
+            let config = &chunk.result.config;
+            assert!(config.vertex_count() <= 1000);
+            assert_eq!(config.buffer_size(), chunk.result.data.len());
+            assert_eq!(config.attributes().len(), full.config.attributes().len());
+
+            for (triangle, expected) in chunk
+                .result
+                .triangles()
+                .zip(&source[chunk.triangles.clone()])
+            {
+                let remapped = triangle.map(|v| chunk.source_vertices[v as usize]);
+                assert_eq!(&remapped, expected);
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_mesh_blocking_chunks() {
+        use crate::{DecodeError, decode_mesh_blocking_chunks, decode_mesh_chunked_sync};
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let expected = decode_mesh_chunked_sync(&input, 1000).unwrap();
+        let chunks: Vec<_> = decode_mesh_blocking_chunks(&input, 1000)
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(chunks.len(), expected.len());
+        for (chunk, expected) in chunks.iter().zip(&expected) {
+            assert_eq!(chunk.triangles, expected.triangles);
+            assert_eq!(chunk.source_vertices, expected.source_vertices);
+            assert_eq!(chunk.result.data, expected.result.data);
+        }
+
+        let errors = |data: &[u8]| -> Vec<Option<DecodeError>> {
+            decode_mesh_blocking_chunks(data, 1000)
+                .map(|item| item.err())
+                .collect()
+        };
+        assert_eq!(errors(b"not draco"), [Some(DecodeError::NotDraco)]);
+        assert_eq!(errors(&input[..64]), [Some(DecodeError::DecodeFailed)]);
+    }
+
+    #[cfg(all(feature = "stress", not(target_arch = "wasm32")))]
+    #[test]
+    fn test_stress_no_leaks() {
+        use crate::stress;
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let report = stress::run([input.as_slice()], 200);
+
+        assert_eq!(report.decodes, 200);
+        assert_eq!(report.failures, 0);
+        assert_eq!(report.leaked_handles, 0);
+    }
+
+    #[cfg(all(feature = "audit", not(target_arch = "wasm32")))]
+    #[test]
+    fn test_decode_mesh_audited() {
+        use crate::{AuditRecord, DecodeOptions, decode_mesh_audited_sync};
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let mut log = Vec::new();
+        let result = decode_mesh_audited_sync(&input, &DecodeOptions::default(), &mut log)
+            .expect("Failed to write log")
+            .expect("Failed to decode");
+        decode_mesh_audited_sync(b"not a draco file", &DecodeOptions::default(), &mut log)
+            .expect("Failed to write log");
+
+        let records: Vec<AuditRecord> = std::str::from_utf8(&log)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("Invalid record"))
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert!(records[0].success);
+        assert_eq!(records[0].backend, "native");
+        assert!(records[0].draco_version.is_some());
+        assert_eq!(records[0].input_size, input.len());
+        assert_eq!(records[0].output_size, result.data.len());
+        assert!(records[0].output_hash.is_some());
+        assert!(!records[1].success);
+        assert_eq!(records[1].output_hash, None);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_crash_diagnostics() {
+        use crate::{
+            decode_mesh_with_config_sync, disable_crash_diagnostics, enable_crash_diagnostics,
+        };
+
+        let dir = std::env::temp_dir().join("draco_decoder_diagnostics");
+        enable_crash_diagnostics(&dir).expect("Failed to enable diagnostics");
+
+        // A valid header followed by a corrupted body
+        let mut input = fs::read("assets/mesh.drc").expect("Failed to read mesh.drc");
+        let end = input.len().min(64);
+        input[16..end].fill(0xff);
+        input.extend_from_slice(b"secret payload");
+        assert!(decode_mesh_with_config_sync(&input).is_none());
+        disable_crash_diagnostics();
+
+        let path = dir.join(format!(
+            "draco-diagnostics-{:016x}.txt",
+            crate::utils::content_hash(&input)
+        ));
+        let bundle = fs::read_to_string(&path).expect("Failed to read bundle");
+        assert!(bundle.contains("backend: native"));
+        assert!(bundle.contains("header_bytes: 44 52 41 43 4f"));
+        assert!(bundle.contains("stage: decode"));
+        assert!(!bundle.contains("secret payload"));
+        fs::remove_file(path).ok();
+    }
+
+    #[cfg(all(feature = "stream", not(target_arch = "wasm32")))]
+    #[tokio::test]
+    async fn test_decode_stream() {
+        use crate::{DecodeError, decode_mesh_with_config_sync, decode_stream};
+        use futures_util::StreamExt;
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let expected = decode_mesh_with_config_sync(&input).unwrap();
+        let inputs = vec![input.clone(), b"glTF".to_vec(), input[..20].to_vec(), input];
+
+        let results: Vec<_> = decode_stream(futures_util::stream::iter(inputs), 2)
+            .collect()
+            .await;
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].as_ref().unwrap().data, expected.data);
+        assert_eq!(results[1].as_ref().unwrap_err(), &DecodeError::NotDraco);
+        assert_eq!(results[2].as_ref().unwrap_err(), &DecodeError::DecodeFailed);
+        assert_eq!(results[3].as_ref().unwrap().config, expected.config);
+    }
+
+    #[cfg(all(feature = "rayon", not(target_arch = "wasm32")))]
+    #[test]
+    fn test_decode_many() {
+        use crate::{DecodeError, decode_many, decode_mesh_with_config_sync};
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let expected = decode_mesh_with_config_sync(&input).unwrap();
+        let inputs: Vec<&[u8]> = vec![&input, b"glTF", &input[..20], &input];
+
+        let results = decode_many(&inputs);
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].as_ref().unwrap().data, expected.data);
+        assert_eq!(results[1].as_ref().unwrap_err(), &DecodeError::NotDraco);
+        assert_eq!(results[2].as_ref().unwrap_err(), &DecodeError::DecodeFailed);
+        assert_eq!(results[3].as_ref().unwrap().config, expected.config);
+        assert!(decode_many(&[]).is_empty());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_mesh_with_options_on_threads() {
+        use crate::{DecodeOptions, decode_mesh_with_options_sync};
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let options = DecodeOptions::new().with_offset_alignment(16);
+        let expected = decode_mesh_with_options_sync(&input, &options).unwrap();
+
+        let (first, second) = std::thread::scope(|scope| {
+            let first = scope.spawn(|| decode_mesh_with_options_sync(&input, &options));
+            let second = scope.spawn(|| decode_mesh_with_options_sync(&input, &options));
+            (first.join().unwrap(), second.join().unwrap())
+        });
+        assert_eq!(first.unwrap().data, expected.data);
+        assert_eq!(second.unwrap().config, expected.config);
+        assert!(decode_mesh_with_options_sync(&input[..20], &options).is_none());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_records_min_max() {
+        use crate::{DecodeOptions, MeshHandle, decode_mesh_with_options_sync};
+
+        let input = fs::read("assets/mesh.drc").expect("Failed to read mesh.drc");
+        let options = DecodeOptions::new().pad_vec3_to_vec4();
+        let plain = decode_mesh_with_options_sync(&input, &options).unwrap();
+        let recorded =
+            decode_mesh_with_options_sync(&input, &options.clone().compute_min_max(true)).unwrap();
+        assert_eq!(recorded.data, plain.data);
+
+        let attributes = plain.config.attributes();
+        assert!(!attributes.is_empty());
+        for (index, attribute) in attributes.iter().enumerate() {
+            assert_eq!(plain.config.min_max(index), None);
+            assert_eq!(
+                recorded.config.min_max(index),
+                attribute.compute_min_max(&plain)
+            );
+        }
+
+        // Writing through a handle returns the layout with the extrema
+        let handle = MeshHandle::decode(&input).unwrap();
+        let mut buffer = vec![0u8; recorded.config.buffer_size()];
+        let (_, config) = handle
+            .write_with_options(&options.compute_min_max(true), &mut buffer)
+            .unwrap();
+        assert_eq!(buffer, recorded.data);
+        for index in 0..attributes.len() {
+            assert_eq!(config.min_max(index), recorded.config.min_max(index));
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_export_reproducer() {
+        use crate::{DecodeError, decode_mesh_blocking_chunks, export_reproducer};
+
+        let dir = std::env::temp_dir().join("draco_decoder_reproducers");
+
+        // Header errors shrink to the complete header
+        let mut header = vec![b'D', b'R', b'A', b'C', b'O', 2, 2, 9, 0, 0, 0];
+        header.extend_from_slice(&[0; 100]);
+        let error = DecodeError::UnknownEncoderType(9);
+        let path = export_reproducer(&header, &error, &dir).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), &header[..11]);
+        fs::remove_file(path).ok();
+
+        // Body failures keep a prefix that still fails the same way
+        let mut input = fs::read("assets/mesh.drc").expect("Failed to read mesh.drc");
+        let end = input.len().min(64);
+        input[16..end].fill(0xff);
+        let error = decode_mesh_blocking_chunks(&input, 1024)
+            .find_map(Result::err)
+            .unwrap();
+        assert_eq!(error, DecodeError::DecodeFailed);
+        let path = export_reproducer(&input, &error, &dir).unwrap();
+        let reproducer = fs::read(&path).unwrap();
+        assert!(reproducer.len() <= input.len());
+        assert!(input.starts_with(&reproducer));
+        assert!(decode_mesh_blocking_chunks(&reproducer, 1024).any(|chunk| chunk.is_err()));
+        fs::remove_file(path).ok();
+
+        let valid = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let error = export_reproducer(&valid, &DecodeError::DecodeFailed, &dir).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_indices_compressed() {
+        use crate::{IndexBuffer, IndexEncoding, decode_indices, decode_mesh_with_config_sync};
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let result = decode_mesh_with_config_sync(&input).expect("Failed to decode");
+        let indices = result.indices().map(IndexBuffer::into_u32).unwrap();
+
+        let compressed = result
+            .indices_compressed(IndexEncoding::DeltaVarint)
+            .unwrap();
+        assert!(compressed.len() < result.config.index_length() as usize);
+        assert_eq!(
+            decode_indices(&compressed, IndexEncoding::DeltaVarint),
+            Some(indices)
+        );
+        assert_eq!(
+            decode_indices(
+                &compressed[..compressed.len() - 1],
+                IndexEncoding::DeltaVarint
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_index_width_by_vertex_count() {
+        use crate::DracoDecodeConfig;
+
+        // More indices than fit in u16 but every vertex index does
+        assert_eq!(
+            DracoDecodeConfig::new(1000, 70_000, 0).index_size_in_bytes(),
+            2
+        );
+        assert_eq!(
+            DracoDecodeConfig::new(65_536, 3, 0).index_size_in_bytes(),
+            2
+        );
+        assert_eq!(
+            DracoDecodeConfig::new(65_537, 3, 0).index_size_in_bytes(),
+            4
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_handle_checks_index_format() {
+        use crate::{
+            AttributeSemantic, DecodeError, DecodeOptions, EncodeConfig, IndexFormat, MeshHandle,
+            encode_mesh,
+        };
+
+        // A separate triangle for every three vertices, stacked along z
+        let triangles = 21_846u32;
+        let positions: Vec<f32> = (0..triangles)
+            .flat_map(|z| {
+                let z = z as f32;
+                [0.0, 0.0, z, 1.0, 0.0, z, 0.0, 1.0, z]
+            })
+            .collect();
+        let indices: Vec<u32> = (0..triangles * 3).collect();
+        let config = EncodeConfig::default().with_quantization_bits(AttributeSemantic::Position, 0);
+        let encoded = encode_mesh(&positions, &indices, &[], &config).expect("Failed to encode");
+
+        let handle = MeshHandle::decode(&encoded).unwrap();
+        let layout = handle.config().unwrap();
+        let vertex_count = layout.vertex_count();
+        assert!(vertex_count > 65_536);
+
+        let options = DecodeOptions::new().with_index_format(IndexFormat::U16);
+        let error = DecodeError::IndicesDoNotFit { vertex_count };
+        assert_eq!(handle.config_with_options(&options), Err(error.clone()));
+        let mut buffer = vec![0u8; layout.buffer_size()];
+        assert_eq!(
+            handle.decode_into_with_options(&options, &mut buffer),
+            Err(error)
+        );
+        assert_eq!(
+            handle.decode_into_with_options(&DecodeOptions::new(), &mut buffer),
+            Ok(layout.buffer_size())
+        );
+    }
+
+    #[test]
+    fn test_index_format_u16() {
+        use crate::{DecodeError, IndexFormat};
+
+        assert_eq!(IndexFormat::U16.index_size(65_536, 70_000), 2);
+        assert_eq!(IndexFormat::U16.check(65_536), Ok(()));
+        assert_eq!(
+            IndexFormat::U16.check(65_537),
+            Err(DecodeError::IndicesDoNotFit {
+                vertex_count: 65_537
+            })
+        );
+        assert_eq!(IndexFormat::Auto.index_size(65_537, 3), 4);
+        assert_eq!(IndexFormat::Auto.check(65_537), Ok(()));
+    }
+
+    #[test]
+    fn test_decoded_mesh_cache_eviction() {
+        use crate::{DecodedMeshCache, DracoDecodeConfig, MeshDecodeResult};
+
+        let mesh = |size: usize| MeshDecodeResult {
+            data: vec![0; size],
+            config: DracoDecodeConfig::new(0, 0, size),
+        };
+
+        let mut cache = DecodedMeshCache::new(100);
+        cache.insert("a", mesh(40));
+        cache.insert("b", mesh(40));
+        assert!(cache.get(&"a").is_some());
+
+        // "b" is the least recently used
+        cache.insert("c", mesh(40));
+        assert!(cache.contains(&"a"));
+        assert!(!cache.contains(&"b"));
+        assert!(cache.contains(&"c"));
+        assert_eq!(cache.used_bytes(), 80);
+        assert_eq!(cache.stats().evictions, 1);
+
+        // Meshes larger than the budget are not cached
+        assert_eq!(cache.insert("d", mesh(200)).data.len(), 200);
+        assert!(!cache.contains(&"d"));
+        assert_eq!(cache.len(), 2);
+
+        cache.set_budget(50);
+        assert_eq!(cache.len(), 1);
+        assert!(cache.contains(&"c"));
+        assert_eq!(cache.used_bytes(), 40);
+    }
+
+    #[test]
+    fn test_map_gltf_attributes() {
+        use crate::{
+            AttributeDataType, DracoDecodeConfig, GltfAttributeError, MeshAttribute,
+            map_gltf_attribute_indices, map_gltf_attributes,
+        };
+        use std::collections::HashMap;
+
+        let mut config = DracoDecodeConfig::new(3, 3, 0);
+        config.add_attribute(MeshAttribute::new(3, AttributeDataType::Float32, 0, 0));
+        config.add_attribute(
+            MeshAttribute::new(2, AttributeDataType::Float32, 0, 0).with_unique_id(4),
+        );
+
+        let extension = HashMap::from([("POSITION".to_string(), 0), ("TEXCOORD_0".to_string(), 4)]);
+        let indices = map_gltf_attribute_indices(&extension, &config).unwrap();
+        assert_eq!(indices["POSITION"], 0);
+        assert_eq!(indices["TEXCOORD_0"], 1);
+
+        let attributes = map_gltf_attributes(&config, &extension);
+        assert_eq!(attributes.len(), 2);
+        assert_eq!(attributes[0].0, "POSITION");
+        assert_eq!(attributes[1].0, "TEXCOORD_0");
+        assert_eq!(attributes[1].1.unique_id(), 4);
+
+        let missing = HashMap::from([("NORMAL".to_string(), 1)]);
+        assert_eq!(
+            map_gltf_attribute_indices(&missing, &config),
+            Err(GltfAttributeError::MissingAttribute {
+                semantic: "NORMAL".to_string(),
+                unique_id: 1
+            })
+        );
+
+        let mismatched = HashMap::from([("NORMAL".to_string(), 4)]);
+        assert_eq!(
+            map_gltf_attribute_indices(&mismatched, &config),
+            Err(GltfAttributeError::ComponentCount {
+                semantic: "NORMAL".to_string(),
+                dim: 2
+            })
+        );
+        let custom = HashMap::from([("_FEATURE_ID".to_string(), 4)]);
+        assert!(map_gltf_attribute_indices(&custom, &config).is_ok());
+    }
+
+    /// Packs assets/20/20.gltf and its external buffer into a GLB.
+    #[cfg(all(feature = "gltf", not(target_arch = "wasm32")))]
+    fn sample_glb() -> Vec<u8> {
+        let mut json: serde_json::Value =
+            serde_json::from_slice(&fs::read("assets/20/20.gltf").expect("Failed to read glTF"))
+                .unwrap();
+        json["buffers"][0].as_object_mut().unwrap().remove("uri");
+        let mut json = serde_json::to_vec(&json).unwrap();
+        json.resize(json.len().next_multiple_of(4), b' ');
+        let mut bin = fs::read("assets/20/20_data.bin").expect("Failed to read buffer");
+        bin.resize(bin.len().next_multiple_of(4), 0);
+
+        let mut glb = b"glTF".to_vec();
+        glb.extend_from_slice(&2u32.to_le_bytes());
+        glb.extend_from_slice(&((12 + 8 + json.len() + 8 + bin.len()) as u32).to_le_bytes());
+        glb.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        glb.extend_from_slice(b"JSON");
+        glb.extend_from_slice(&json);
+        glb.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+        glb.extend_from_slice(b"BIN\0");
+        glb.extend_from_slice(&bin);
+        glb
+    }
+
+    #[cfg(all(feature = "gltf", not(target_arch = "wasm32")))]
+    #[test]
+    fn test_decode_gltf() {
+        use crate::decode_gltf_sync;
+
+        let glb = sample_glb();
+        let gltf = decode_gltf_sync(&glb).expect("Failed to decode glTF");
+        assert_eq!(gltf.primitives.len(), 1);
+        let primitive = &gltf.primitives[0];
+        assert_eq!(primitive.indices, Some(0));
+
+        let position = primitive.attribute("POSITION").unwrap();
+        assert_eq!(position.accessor, 2);
+        assert_eq!(
+            primitive.accessor_bytes(2).map(|bytes| bytes.len()),
+            Some(3254 * 12)
+        );
+        assert_eq!(
+            primitive.accessor_bytes(0).map(|bytes| bytes.len()),
+            Some(4368 * 2)
+        );
+        assert!(primitive.attribute("_BATCHID").is_some());
+        assert!(decode_gltf_sync(b"glTF\x02\0\0\0").is_err());
+    }
+
+    #[cfg(all(feature = "tiles3d", not(target_arch = "wasm32")))]
+    #[test]
+    fn test_decode_b3dm() {
+        use crate::{Tiles3dError, decode_b3dm_sync};
+
+        let glb = sample_glb();
+        let mut feature_json = br#"{"BATCH_LENGTH":1,"RTC_CENTER":{"byteOffset":0}}"#.to_vec();
+        feature_json.resize(feature_json.len().next_multiple_of(8), b' ');
+        let feature_binary: Vec<u8> = [1.0f32, 2.0, 3.0, 0.0]
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+
+        let mut b3dm = b"b3dm".to_vec();
+        let length = 28 + feature_json.len() + feature_binary.len() + glb.len();
+        for word in [1, length, feature_json.len(), feature_binary.len(), 0, 0] {
+            b3dm.extend_from_slice(&(word as u32).to_le_bytes());
+        }
+        b3dm.extend_from_slice(&feature_json);
+        b3dm.extend_from_slice(&feature_binary);
+        b3dm.extend_from_slice(&glb);
+
+        let tile = decode_b3dm_sync(&b3dm).expect("Failed to decode b3dm");
+        assert_eq!(tile.rtc_center, Some([1.0, 2.0, 3.0]));
+        assert_eq!(tile.batch_length, 1);
+        assert_eq!(tile.meshes().count(), 1);
+        assert_eq!(tile.meshes().next().unwrap().config.vertex_count(), 3254);
+
+        assert_eq!(
+            decode_b3dm_sync(&b3dm[..40]).err(),
+            Some(Tiles3dError::InvalidHeader)
+        );
+    }
+
+    #[cfg(all(feature = "tiles3d", not(target_arch = "wasm32")))]
+    #[test]
+    fn test_decode_pnts() {
+        use crate::{decode_pnts_sync, decode_point_cloud_with_config_sync};
+
+        let pnts = |json: String, binary: &[u8]| {
+            let mut json = json.into_bytes();
+            json.resize(json.len().next_multiple_of(8), b' ');
+            let mut tile = b"pnts".to_vec();
+            let length = 28 + json.len() + binary.len();
+            for word in [1, length, json.len(), binary.len(), 0, 0] {
+                tile.extend_from_slice(&(word as u32).to_le_bytes());
+            }
+            tile.extend_from_slice(&json);
+            tile.extend_from_slice(binary);
+            tile
+        };
+
+        // Two quantized points with colors and batch ids
+        let mut binary = Vec::new();
+        for value in [0u16, 0, 0, 65535, 65535, 65535] {
+            binary.extend_from_slice(&value.to_le_bytes());
+        }
+        binary.extend_from_slice(&[255, 0, 0, 0, 0, 255, 0, 1]);
+        let json = r#"{"POINTS_LENGTH":2,"POSITION_QUANTIZED":{"byteOffset":0},
+            "QUANTIZED_VOLUME_OFFSET":[1,1,1],"QUANTIZED_VOLUME_SCALE":[2,4,8],
+            "RGB":{"byteOffset":12},"BATCH_ID":{"byteOffset":18,"componentType":"UNSIGNED_BYTE"},
+            "BATCH_LENGTH":2}"#;
+        let tile = decode_pnts_sync(&pnts(json.to_string(), &binary)).expect("Failed to decode");
+        assert!(!tile.draco_compressed);
+        assert_eq!(tile.positions, [[1.0, 1.0, 1.0], [3.0, 5.0, 9.0]]);
+        assert_eq!(tile.colors, Some(vec![[255, 0, 0, 255], [0, 0, 255, 255]]));
+        assert_eq!(tile.batch_ids, Some(vec![0, 1]));
+        assert_eq!(tile.normals, None);
+
+        // The same points compressed with Draco
+        let input = fs::read("assets/pointcloud.drc").expect("Failed to read pointcloud.drc");
+        let points = decode_point_cloud_with_config_sync(&input).expect("Failed to decode");
+        let position = points.config.attributes()[0].unique_id();
+        let json = format!(
+            r#"{{"POINTS_LENGTH":{},"extensions":{{"3DTILES_draco_point_compression":
+                {{"properties":{{"POSITION":{position}}},"byteOffset":0,"byteLength":{}}}}}}}"#,
+            points.config.point_count(),
+            input.len(),
+        );
+        let tile = decode_pnts_sync(&pnts(json, &input)).expect("Failed to decode");
+        assert!(tile.draco_compressed);
+        assert_eq!(tile.positions.len(), points.config.point_count() as usize);
+    }
+
+    #[cfg(all(feature = "tiles3d", not(target_arch = "wasm32")))]
+    #[test]
+    fn test_decode_i3dm() {
+        use crate::{Tiles3dError, decode_i3dm_sync};
+
+        let i3dm = |json: &[u8], binary: &[u8], gltf_format: u32, body: &[u8]| {
+            let mut json = json.to_vec();
+            json.resize(json.len().next_multiple_of(8), b' ');
+            let mut tile = b"i3dm".to_vec();
+            let length = 32 + json.len() + binary.len() + body.len();
+            for word in [
+                1,
+                length,
+                json.len(),
+                binary.len(),
+                0,
+                0,
+                gltf_format as usize,
+            ] {
+                tile.extend_from_slice(&(word as u32).to_le_bytes());
+            }
+            tile.extend_from_slice(&json);
+            tile.extend_from_slice(binary);
+            tile.extend_from_slice(body);
+            tile
+        };
+
+        // Two instances, the second rotated a quarter turn around z and scaled
+        let binary: Vec<u8> = [
+            1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0, 0.0, 1.0, 0.0, -1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0,
+            1.0, 0.0, 1.0, 2.0,
+        ]
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect();
+        let json = br#"{"INSTANCES_LENGTH":2,"POSITION":{"byteOffset":0},
+            "NORMAL_UP":{"byteOffset":24},"NORMAL_RIGHT":{"byteOffset":48},
+            "SCALE":{"byteOffset":72}}"#;
+        let tile =
+            decode_i3dm_sync(&i3dm(json, &binary, 1, &sample_glb())).expect("Failed to decode");
+        assert_eq!(tile.meshes().count(), 1);
+        assert_eq!(tile.instances.len(), 2);
+        assert_eq!(tile.instances[0].translation, [1.0, 2.0, 3.0]);
+        assert_eq!(
+            tile.instances[1].matrix(),
+            [
+                0.0, 2.0, 0.0, 0.0, -2.0, 0.0, 0.0, 0.0, 0.0, 0.0, 2.0, 0.0, 4.0, 5.0, 6.0, 1.0
+            ]
+        );
+        #[cfg(feature = "mint")]
+        assert_eq!(
+            mint::ColumnMatrix4::from(tile.instances[1]).w,
+            mint::Vector4::from([4.0, 5.0, 6.0, 1.0])
+        );
+
+        assert_eq!(
+            decode_i3dm_sync(&i3dm(b"{}", &[], 0, b"model.glb")).err(),
+            Some(Tiles3dError::ExternalGltf("model.glb".to_string()))
+        );
+    }
+
+    #[cfg(feature = "tiles3d")]
+    #[test]
+    fn test_enu_frame() {
+        use crate::{EnuFrame, PntsTile};
+
+        let close = |a: [f64; 3], b: [f64; 3]| (0..3).all(|i| (a[i] - b[i]).abs() < 1e-6);
+
+        // On the equator at the prime meridian east is +y, north +z and up +x
+        let frame = EnuFrame::new([0.0, 0.0, 0.0]);
+        assert!(close(frame.origin(), [6378137.0, 0.0, 0.0]));
+        assert!(close(
+            frame.to_local_enu([6378147.0, 5.0, 7.0]),
+            [5.0, 7.0, 10.0]
+        ));
+
+        let frame = EnuFrame::new([48.8584, 2.2945, 35.0]);
+        let ecef = frame.to_ecef([120.0, -40.5, 3.25]);
+        assert!(close(frame.to_local_enu(ecef), [120.0, -40.5, 3.25]));
+        assert!(close(
+            EnuFrame::from_ecef(frame.origin()).to_ecef([0.0; 3]),
+            frame.origin()
+        ));
+        let matrix = frame.matrix();
+        assert!(close([matrix[12], matrix[13], matrix[14]], frame.origin()));
+        assert!(close([matrix[8], matrix[9], matrix[10]], frame.axes()[2]));
+        #[cfg(feature = "mint")]
+        {
+            let columns = mint::ColumnMatrix4::from(frame);
+            assert!(close(
+                [columns.w.x, columns.w.y, columns.w.z],
+                frame.origin()
+            ));
+            assert!(close(
+                [columns.z.x, columns.z.y, columns.z.z],
+                frame.axes()[2]
+            ));
+        }
+
+        let tile = PntsTile {
+            positions: vec![[0.0, 0.0, 0.0], [0.0, 0.0, 2.0]],
+            colors: None,
+            normals: None,
+            batch_ids: None,
+            batch_length: 0,
+            rtc_center: Some([6378137.0, 0.0, 0.0]),
+            draco_compressed: false,
+        };
+        let local = tile.to_local_enu([0.0, 0.0, 0.0]);
+        assert!(close(local[0], [0.0; 3]));
+        assert!(close(local[1], [0.0, 2.0, 0.0]));
+    }
+
+    #[cfg(all(feature = "bevy", not(target_arch = "wasm32")))]
+    #[test]
+    fn test_to_bevy_mesh() {
+        use bevy::render::mesh::{Indices, Mesh};
+
+        use crate::decode_mesh_with_config_sync;
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let result = decode_mesh_with_config_sync(&input).expect("Failed to decode");
+        let mesh = result.to_bevy_mesh().expect("Failed to convert");
+
+        assert_eq!(mesh.count_vertices(), result.config.vertex_count() as usize);
+        assert!(mesh.contains_attribute(Mesh::ATTRIBUTE_POSITION));
+        assert!(matches!(mesh.indices(), Some(Indices::U16(_))));
+        assert_eq!(
+            mesh.indices().unwrap().len(),
+            result.config.index_count() as usize
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_layout_descriptor_serde() {
+        use crate::{DracoDecodeConfig, LayoutDescriptor};
+
+        let config = DracoDecodeConfig::new(3, 3, 6);
+        let mut json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["layout"]["version"], LayoutDescriptor::CURRENT_VERSION);
+
+        // Configs serialized before the descriptor existed use layout version 1.
+        json.as_object_mut().unwrap().remove("layout");
+        let legacy: DracoDecodeConfig = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(legacy.layout().version(), 1);
+
+        json["layout"] = serde_json::json!({ "version": LayoutDescriptor::CURRENT_VERSION + 1 });
+        assert!(serde_json::from_value::<DracoDecodeConfig>(json).is_err());
+    }
+
+    #[test]
+    fn test_voxelize() {
+        use crate::VoxelMode;
+
+        // A closed cube from 0 to 2
+        let positions: [f32; 24] = [
+            0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 2.0, 2.0, 0.0, 0.0, 2.0, 0.0, //
+            0.0, 0.0, 2.0, 2.0, 0.0, 2.0, 2.0, 2.0, 2.0, 0.0, 2.0, 2.0,
+        ];
+        let indices = [
+            0, 2, 1, 0, 3, 2, 4, 5, 6, 4, 6, 7, 0, 1, 5, 0, 5, 4, //
+            2, 3, 7, 2, 7, 6, 1, 2, 6, 1, 6, 5, 0, 4, 7, 0, 7, 3,
+        ];
+        let cube = position_mesh(&positions, &indices);
+
+        let surface = cube.voxelize(0.5, VoxelMode::Surface).unwrap();
+        assert_eq!(surface.dimensions, [4, 4, 4]);
+        assert_eq!(surface.origin, [0.0; 3]);
+        assert_eq!(surface.occupied_count(), 64 - 8);
+        assert!(surface.is_occupied([0, 2, 3]));
+        assert!(!surface.is_occupied([1, 2, 2]));
+        assert!(!surface.is_occupied([4, 0, 0]));
+        assert_eq!(surface.cell_center([1, 2, 2]), [0.75, 1.25, 1.25]);
+        assert_eq!(surface.occupied_cells().next(), Some([0, 0, 0]));
+
+        let solid = cube.voxelize(0.5, VoxelMode::Solid).unwrap();
+        assert_eq!(solid.occupied_count(), 64);
+        assert_eq!(solid.to_bits(), vec![0xff; 8]);
+        assert_eq!(surface.to_bits()[3], 0b1111_1001);
+
+        let coarse = cube.voxelize(0.75, VoxelMode::Solid).unwrap();
+        assert_eq!(coarse.dimensions, [3, 3, 3]);
+        assert_eq!(coarse.occupied_count(), 27);
+        assert!(cube.voxelize(0.0, VoxelMode::Surface).is_none());
+    }
+
+    #[test]
+    fn test_write_obj() {
+        use crate::{
+            AttributeDataType, AttributeSemantic, MeshAttribute, MeshDecodeResult, write_obj,
+        };
+
+        let positions: [f32; 9] = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.5, 0.0];
+        let normals: [i8; 9] = [0, 0, 127, 0, 0, 127, 0, 0, 127];
+        let uvs: [f32; 6] = [0.0, 0.0, 1.0, 0.0, 0.0, 1.0];
+        let position = MeshAttribute::new(3, AttributeDataType::Float32, 0, 0)
+            .with_semantic(AttributeSemantic::Position);
+        let normal = MeshAttribute::new(3, AttributeDataType::Int8, 0, 0)
+            .with_semantic(AttributeSemantic::Normal)
+            .with_normalized(true);
+        let uv = MeshAttribute::new(2, AttributeDataType::Float32, 0, 0)
+            .with_semantic(AttributeSemantic::TexCoord);
+        let mesh = MeshDecodeResult::from_parts(
+            3,
+            &[0, 1, 2, 0, 2, 5],
+            vec![
+                (position, bytemuck::cast_slice(&positions).to_vec()),
+                (normal, bytemuck::cast_slice(&normals).to_vec()),
+                (uv, bytemuck::cast_slice(&uvs).to_vec()),
+            ],
+        );
+
+        let mut obj = Vec::new();
+        write_obj(&mesh, &mut obj).unwrap();
+        let obj = String::from_utf8(obj).unwrap();
+        let records: Vec<&str> = obj.lines().skip(1).collect();
+        assert_eq!(
+            records,
+            [
+                "v 0 0 0",
+                "v 1 0 0",
+                "v 0 1.5 0",
+                "vt 0 0",
+                "vt 1 0",
+                "vt 0 1",
+                "vn 0 0 1",
+                "vn 0 0 1",
+                "vn 0 0 1",
+                "f 1/1/1 2/2/2 3/3/3",
+            ]
+        );
+
+        let positions_only = MeshDecodeResult::from_parts(
+            3,
+            &[0, 1, 2],
+            vec![(position, bytemuck::cast_slice(&positions).to_vec())],
+        );
+        let mut obj = Vec::new();
+        write_obj(&positions_only, &mut obj).unwrap();
+        assert!(String::from_utf8(obj).unwrap().ends_with("\nf 1 2 3\n"));
+
+        let empty = MeshDecodeResult::from_parts(0, &[], Vec::new());
+        let error = write_obj(&empty, std::io::sink()).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_write_ply() {
+        use crate::{
+            AttributeDataType, AttributeSemantic, MeshAttribute, MeshDecodeResult, PlyFormat,
+            PointCloudDecodeConfig, PointCloudDecodeResult, write_ply, write_point_cloud_ply,
+        };
+
+        let positions: [f32; 9] = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.5, 0.0];
+        let colors: [u8; 9] = [255, 0, 0, 0, 255, 0, 0, 0, 255];
+        let position = MeshAttribute::new(3, AttributeDataType::Float32, 0, 0)
+            .with_semantic(AttributeSemantic::Position);
+        let color = MeshAttribute::new(3, AttributeDataType::UInt8, 0, 0)
+            .with_semantic(AttributeSemantic::Color)
+            .with_normalized(true);
+        let mesh = MeshDecodeResult::from_parts(
+            3,
+            &[0, 1, 2, 0, 2, 5],
+            vec![
+                (position, bytemuck::cast_slice(&positions).to_vec()),
+                (color, colors.to_vec()),
+            ],
+        );
+
+        let mut ply = Vec::new();
+        write_ply(&mesh, PlyFormat::Ascii, &mut ply).unwrap();
+        let ply = String::from_utf8(ply).unwrap();
+        let body = ply.split_once("end_header\n").unwrap().1;
+        assert!(ply.starts_with("ply\nformat ascii 1.0\n"));
+        assert!(ply.contains("element vertex 3\n"));
+        assert!(ply.contains("property uchar blue\nelement face 1\n"));
+        assert!(!ply.contains("alpha"));
+        assert_eq!(
+            body.lines().collect::<Vec<_>>(),
+            [
+                "0 0 0 255 0 0",
+                "1 0 0 0 255 0",
+                "0 1.5 0 0 0 255",
+                "3 0 1 2"
+            ]
+        );
+
+        let mut binary = Vec::new();
+        write_ply(&mesh, PlyFormat::BinaryLittleEndian, &mut binary).unwrap();
+        let header_end = binary
+            .windows(11)
+            .position(|window| window == b"end_header\n")
+            .unwrap();
+        assert_eq!(binary.len() - header_end - 11, 3 * (12 + 3) + 13);
+
+        let mut config = PointCloudDecodeConfig::new(3, 36);
+        config.add_attribute(position.with_layout(0, 36));
+        let cloud = PointCloudDecodeResult {
+            data: bytemuck::cast_slice(&positions).to_vec(),
+            config,
+        };
+        let mut ply = Vec::new();
+        write_point_cloud_ply(&cloud, PlyFormat::Ascii, &mut ply).unwrap();
+        let ply = String::from_utf8(ply).unwrap();
+        assert!(!ply.contains("element face"));
+        assert!(ply.ends_with("end_header\n0 0 0\n1 0 0\n0 1.5 0\n"));
+
+        let empty = MeshDecodeResult::from_parts(0, &[], Vec::new());
+        let error = write_ply(&empty, PlyFormat::Ascii, std::io::sink()).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_cancellation_token() {
+        use std::future::Future;
+        use std::pin::pin;
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::task::{Context, Poll, Wake, Waker};
+
+        use crate::CancellationToken;
+
+        struct CountingWaker(AtomicUsize);
+
+        impl Wake for CountingWaker {
+            fn wake(self: Arc<Self>) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let waker = Waker::from(counter.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert_eq!(token, clone);
+        assert_ne!(token, CancellationToken::new());
+
+        let mut cancelled = pin!(clone.cancelled());
+        assert!(cancelled.as_mut().poll(&mut cx).is_pending());
+        assert!(cancelled.as_mut().poll(&mut cx).is_pending());
+        assert!(!clone.is_cancelled());
+
+        token.cancel();
+        assert!(clone.is_cancelled());
+        assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+        assert_eq!(cancelled.as_mut().poll(&mut cx), Poll::Ready(()));
+    }
+
+    #[test]
+    fn test_engine_vertex_format() {
+        use std::sync::Arc;
+
+        use crate::{
+            AttributeDataType, AttributeSemantic, ByteOrder, EnginePackError, EngineVertexFormat,
+            MeshAttribute, MeshDecodeResult, PackedFormat, VertexPacker,
+        };
+
+        let positions: [f32; 6] = [1.0, 2.0, 3.0, -1.0, 0.5, 0.0];
+        let normals: [f32; 6] = [0.0, 0.0, 1.0, -1.0, 0.0, 0.0];
+        let colors: [u8; 6] = [255, 0, 128, 0, 255, 0];
+        let uvs: [f32; 4] = [0.0, 1.0, 0.5, -0.25];
+        let mesh = MeshDecodeResult::from_parts(
+            2,
+            &[0, 1, 1],
+            vec![
+                (
+                    MeshAttribute::new(3, AttributeDataType::Float32, 0, 0)
+                        .with_semantic(AttributeSemantic::Position),
+                    bytemuck::cast_slice(&positions).to_vec(),
+                ),
+                (
+                    MeshAttribute::new(3, AttributeDataType::Float32, 0, 0)
+                        .with_semantic(AttributeSemantic::Normal),
+                    bytemuck::cast_slice(&normals).to_vec(),
+                ),
+                (
+                    MeshAttribute::new(3, AttributeDataType::UInt8, 0, 0)
+                        .with_semantic(AttributeSemantic::Color),
+                    colors.to_vec(),
+                ),
+                (
+                    MeshAttribute::new(2, AttributeDataType::Float32, 0, 0)
+                        .with_semantic(AttributeSemantic::TexCoord),
+                    bytemuck::cast_slice(&uvs).to_vec(),
+                ),
+            ],
+        );
+
+        let format = EngineVertexFormat::new()
+            .with_element(AttributeSemantic::Position, 0, PackedFormat::Float32(3))
+            .with_element(AttributeSemantic::Normal, 0, PackedFormat::Snorm1010102)
+            .with_element(AttributeSemantic::Color, 0, PackedFormat::DwordColor)
+            .with_element(AttributeSemantic::TexCoord, 0, PackedFormat::Snorm16(2));
+        assert_eq!(format.stride(), 24);
+        assert_eq!(format.offsets(), [0, 12, 16, 20]);
+
+        let vertices = format.pack(&mesh).unwrap();
+        assert_eq!(vertices.len(), 48);
+        let word =
+            |offset: usize| u32::from_le_bytes(vertices[offset..offset + 4].try_into().unwrap());
+        assert_eq!(word(0), 1.0f32.to_bits());
+        assert_eq!(word(12), 511 << 20);
+        assert_eq!(word(16), 0xFFFF0080);
+        assert_eq!(&vertices[20..24], &[0, 0, 0xFF, 0x7F]);
+        // -1.0 as a signed 10-bit component
+        assert_eq!(word(24 + 12), 0x201);
+        assert_eq!(word(24 + 16), 0xFF00FF00);
+
+        let big_endian = format.clone().with_byte_order(ByteOrder::BigEndian);
+        let swapped = big_endian.pack(&mesh).unwrap();
+        assert_eq!(&swapped[16..20], &[0xFF, 0xFF, 0x00, 0x80]);
+
+        struct Marker;
+
+        impl VertexPacker for Marker {
+            fn size(&self) -> usize {
+                1
+            }
+
+            fn pack(&self, value: &[f32], _order: ByteOrder, out: &mut [u8]) {
+                out[0] = value.len() as u8;
+            }
+        }
+
+        let custom = EngineVertexFormat::new()
+            .with_packer(AttributeSemantic::TexCoord, 0, Arc::new(Marker))
+            .with_stride(4);
+        assert_eq!(custom.pack(&mesh).unwrap(), [2, 0, 0, 0, 2, 0, 0, 0]);
+
+        assert_eq!(
+            EngineVertexFormat::new()
+                .with_element(AttributeSemantic::TexCoord, 1, PackedFormat::Snorm16(2))
+                .pack(&mesh),
+            Err(EnginePackError::MissingAttribute {
+                semantic: AttributeSemantic::TexCoord,
+                set: 1,
+            })
+        );
+        assert_eq!(
+            format.clone().with_stride(8).pack(&mesh),
+            Err(EnginePackError::StrideTooSmall {
+                stride: 8,
+                required: 24,
+            })
+        );
+
+        let huge = EngineVertexFormat::new()
+            .with_element(
+                AttributeSemantic::Position,
+                0,
+                PackedFormat::Float32(usize::MAX / 2),
+            )
+            .with_element(
+                AttributeSemantic::Position,
+                0,
+                PackedFormat::Snorm16(usize::MAX),
+            );
+        assert_eq!(huge.stride(), usize::MAX);
+        assert_eq!(huge.offsets(), [0, usize::MAX]);
+        assert_eq!(
+            huge.pack(&mesh),
+            Err(EnginePackError::TooLarge {
+                stride: usize::MAX,
+                vertex_count: mesh.config.vertex_count() as usize,
+            })
+        );
+    }
+
+    #[test]
+    fn test_retain_attributes() {
+        use crate::{
+            AttributeDataType, AttributeSemantic, MeshAttribute, MeshDecodeResult,
+            PointCloudDecodeConfig, PointCloudDecodeResult,
+        };
+
+        let positions: [f32; 9] = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+        let normals: [f32; 9] = [0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0];
+        let ids: [u16; 3] = [4, 5, 6];
+        let position = MeshAttribute::new(3, AttributeDataType::Float32, 0, 0)
+            .with_semantic(AttributeSemantic::Position);
+        let normal = MeshAttribute::new(3, AttributeDataType::Float32, 0, 0)
+            .with_semantic(AttributeSemantic::Normal);
+        let id = MeshAttribute::new(1, AttributeDataType::UInt16, 0, 0);
+        let mesh = MeshDecodeResult::from_parts(
+            3,
+            &[0, 1, 2],
+            vec![
+                (position, bytemuck::cast_slice(&positions).to_vec()),
+                (normal, bytemuck::cast_slice(&normals).to_vec()),
+                (id, bytemuck::cast_slice(&ids).to_vec()),
+            ],
+        );
+
+        let kept = mesh.retain_attributes(|a| a.semantic() != AttributeSemantic::Normal);
+        assert_eq!(kept.config.attributes().len(), 2);
+        assert_eq!(kept.config.find_attribute(AttributeSemantic::Normal), None);
+        assert_eq!(kept.triangles().collect::<Vec<_>>(), [[0, 1, 2]]);
+        assert_eq!(kept.attribute_bytes(0), mesh.attribute_bytes(0));
+        assert_eq!(kept.attribute_bytes(1), mesh.attribute_bytes(2));
+        assert_eq!(kept.data.len(), 6 + 36 + 6);
+
+        let mut config = PointCloudDecodeConfig::new(3, 72);
+        config.add_attribute(position.with_layout(0, 36));
+        config.add_attribute(normal.with_layout(36, 36));
+        let cloud = PointCloudDecodeResult {
+            data: [positions, normals]
+                .concat()
+                .iter()
+                .flat_map(|c| c.to_ne_bytes())
+                .collect(),
+            config,
+        };
+        let kept = cloud.retain_attributes(|a| a.semantic() == AttributeSemantic::Normal);
+        assert_eq!(kept.config.attributes().len(), 1);
+        assert_eq!(kept.config.buffer_size(), 36);
+        assert_eq!(kept.attribute_bytes(0), cloud.attribute_bytes(1));
+    }
+
+    #[test]
+    fn test_write_glb() {
+        use crate::{
+            AttributeDataType, AttributeSemantic, MeshAttribute, MeshDecodeResult, write_glb,
+        };
+
+        let positions: [f32; 9] = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.5, -2.0];
+        let normals: [i8; 9] = [0, 0, 127, 0, 0, 127, 0, 0, 127];
+        let ids: [u16; 3] = [7, 8, 9];
+        let wide: [u32; 3] = [1, 2, 3];
+        let position = MeshAttribute::new(3, AttributeDataType::Float32, 0, 0)
+            .with_semantic(AttributeSemantic::Position);
+        let normal = MeshAttribute::new(3, AttributeDataType::Int8, 0, 0)
+            .with_semantic(AttributeSemantic::Normal)
+            .with_normalized(true);
+        let id = MeshAttribute::new(1, AttributeDataType::UInt16, 0, 0).with_unique_id(5);
+        let skipped = MeshAttribute::new(1, AttributeDataType::UInt32, 0, 0);
+        let mesh = MeshDecodeResult::from_parts(
+            3,
+            &[0, 1, 2, 0, 2, 5],
+            vec![
+                (position, bytemuck::cast_slice(&positions).to_vec()),
+                (normal, bytemuck::cast_slice(&normals).to_vec()),
+                (id, bytemuck::cast_slice(&ids).to_vec()),
+                (skipped, bytemuck::cast_slice(&wide).to_vec()),
+            ],
+        );
+
+        let mut glb = Vec::new();
+        write_glb(&[mesh.clone(), mesh], &mut glb).unwrap();
+        let word = |offset: usize| u32::from_le_bytes(glb[offset..offset + 4].try_into().unwrap());
+        assert_eq!(&glb[..4], b"glTF");
+        assert_eq!(word(4), 2);
+        assert_eq!(word(8) as usize, glb.len());
+        let json_length = word(12) as usize;
+        assert_eq!(&glb[16..20], b"JSON");
+        assert_eq!(json_length % 4, 0);
+        let json: serde_json::Value = serde_json::from_slice(&glb[20..20 + json_length]).unwrap();
+        let binary = &glb[20 + json_length + 8..];
+        assert_eq!(&glb[20 + json_length + 4..20 + json_length + 8], b"BIN\0");
+
+        assert_eq!(json["asset"]["version"], "2.0");
+        assert_eq!(json["scenes"][0]["nodes"], serde_json::json!([0, 1]));
+        let primitive = &json["meshes"][1]["primitives"][0];
+        let attributes = primitive["attributes"].as_object().unwrap();
+        let names: Vec<&str> = attributes.keys().map(String::as_str).collect();
+        assert_eq!(names.len(), 3);
+        assert!(names.contains(&"NORMAL") && names.contains(&"_ATTRIBUTE_5"));
+        assert_eq!(primitive["mode"], 4);
+
+        let position = &json["accessors"][attributes["POSITION"].as_u64().unwrap() as usize];
+        assert_eq!(position["min"], serde_json::json!([0, 0, -2]));
+        assert_eq!(position["max"], serde_json::json!([1, 1.5, 0]));
+        let view = &json["bufferViews"][position["bufferView"].as_u64().unwrap() as usize];
+        let offset = view["byteOffset"].as_u64().unwrap() as usize;
+        let stored: Vec<f32> = binary[offset..offset + 36]
+            .chunks_exact(4)
+            .map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+        assert_eq!(stored, positions);
+
+        let indices = &json["accessors"][primitive["indices"].as_u64().unwrap() as usize];
+        assert_eq!(indices["componentType"], 5123);
+        assert_eq!(indices["count"], 3);
+        let id = &json["accessors"][attributes["_ATTRIBUTE_5"].as_u64().unwrap() as usize];
+        assert_eq!(id["componentType"], 5123);
+        // Two byte ids are padded to the four byte vertex stride
+        let view = &json["bufferViews"][id["bufferView"].as_u64().unwrap() as usize];
+        assert_eq!(view["byteStride"], 4);
+        assert_eq!(view["byteLength"], 12);
+        let offset = view["byteOffset"].as_u64().unwrap() as usize;
+        assert_eq!(
+            &binary[offset..offset + 12],
+            &[7, 0, 0, 0, 8, 0, 0, 0, 9, 0, 0, 0]
+        );
+        assert!(json["buffers"][0]["byteLength"].as_u64().unwrap() as usize <= binary.len());
+
+        // With 65536 vertices the last index would be the restart value of
+        // unsigned shorts
+        let large = position_mesh(&vec![0.0; 3 << 16], &[0, 1, 65535]);
+        let mut glb = Vec::new();
+        write_glb(&[large], &mut glb).unwrap();
+        let json_length = u32::from_le_bytes(glb[12..16].try_into().unwrap()) as usize;
+        let json: serde_json::Value = serde_json::from_slice(&glb[20..20 + json_length]).unwrap();
+        let indices = json["meshes"][0]["primitives"][0]["indices"]
+            .as_u64()
+            .unwrap();
+        assert_eq!(json["accessors"][indices as usize]["componentType"], 5125);
+
+        // glTF has no empty accessors, so meshes without vertices are left out
+        let empty = position_mesh(&[], &[]);
+        let error = write_glb(std::slice::from_ref(&empty), &mut Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+        let mut glb = Vec::new();
+        write_glb(&[empty, position_mesh(&positions, &[0, 1, 2])], &mut glb).unwrap();
+        let json_length = u32::from_le_bytes(glb[12..16].try_into().unwrap()) as usize;
+        let json: serde_json::Value = serde_json::from_slice(&glb[20..20 + json_length]).unwrap();
+        assert_eq!(json["meshes"].as_array().unwrap().len(), 1);
+        assert_eq!(json["scenes"][0]["nodes"], serde_json::json!([0]));
+        for accessor in json["accessors"].as_array().unwrap() {
+            assert!(accessor["count"].as_u64().unwrap() > 0);
+        }
+        for view in json["bufferViews"].as_array().unwrap() {
+            assert!(view["byteLength"].as_u64().unwrap() > 0);
+        }
+    }
+
+    #[test]
+    fn test_write_stl_binary() {
+        use crate::{MeshDecodeResult, write_stl_binary};
+
+        let positions: [f32; 9] = [0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 2.0, 0.0];
+        let mesh = position_mesh(&positions, &[0, 1, 2, 0, 0, 1, 0, 2, 7]);
+
+        let mut stl = Vec::new();
+        write_stl_binary(&mesh, &mut stl).unwrap();
+        assert_eq!(stl.len(), 84 + 2 * 50);
+        assert!(!stl.starts_with(b"solid"));
+        assert_eq!(u32::from_le_bytes(stl[80..84].try_into().unwrap()), 2);
+        let floats = |record: &[u8]| -> Vec<f32> {
+            record[..48]
+                .chunks_exact(4)
+                .map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap()))
+                .collect()
+        };
+        assert_eq!(
+            floats(&stl[84..134]),
+            [0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 2.0, 0.0]
+        );
+        assert_eq!(&floats(&stl[134..184])[..3], &[0.0; 3]);
+        assert_eq!(&stl[132..134], &[0, 0]);
+
+        let empty = MeshDecodeResult::from_parts(0, &[], Vec::new());
+        let error = write_stl_binary(&empty, std::io::sink()).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_config_to_json() {
+        use crate::{
+            AttributeDataType, AttributeSemantic, Crs, DecodeWarning, MeshAttribute,
+            MeshDecodeResult, WarningAction, WarningReason,
+        };
+
+        let position = MeshAttribute::new(3, AttributeDataType::Float32, 0, 0)
+            .with_semantic(AttributeSemantic::Position)
+            .with_unique_id(2);
+        let mut mesh = MeshDecodeResult::from_parts(1, &[0, 0, 0], vec![(position, vec![0; 12])])
+            .with_crs(Crs::ECEF);
+        mesh.config.add_warning(DecodeWarning {
+            unique_id: 5,
+            semantic: AttributeSemantic::Generic,
+            attribute_index: None,
+            reason: WarningReason::Filtered,
+            action: WarningAction::Skipped,
+        });
+
+        let json = mesh.config.to_json();
+        assert!(json.starts_with(
+            r#"{"summary_version":1,"vertex_count":1,"index_count":3,"index_size":2,"index_length":6,"buffer_size":18,"#
+        ));
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["crs"], "EPSG:4978");
+        assert_eq!(
+            value["attributes"][0],
+            serde_json::json!({
+                "index": 0,
+                "unique_id": 2,
+                "semantic": "Position",
+                "data_type": "Float32",
+                "dim": 3,
+                "normalized": false,
+                "offset": 6,
+                "length": 12,
+            })
+        );
+        assert_eq!(value["warnings"][0], mesh.config.warnings()[0].to_string());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_decode_result_serde() {
+        use crate::{
+            AttributeDataType, AttributeSemantic, DracoDecodeConfig, MeshAttribute,
+            MeshDecodeResult, QuantizationInfo,
+        };
+
+        let position = MeshAttribute::new(3, AttributeDataType::UInt16, 0, 0)
+            .with_semantic(AttributeSemantic::Position)
+            .with_unique_id(4)
+            .with_quantization(QuantizationInfo::new(14, 2.0, &[-1.0, 0.0, 1.0]).unwrap());
+        let mesh = MeshDecodeResult::from_parts(2, &[0, 1, 1], vec![(position, vec![1; 12])]);
+
+        let json = serde_json::to_value(&mesh).unwrap();
+        let attribute = &json["config"]["attributes"][0];
+        for field in [
+            "dim",
+            "data_type",
+            "offset",
+            "length",
+            "semantic",
+            "unique_id",
+        ] {
+            assert!(attribute.get(field).is_some(), "missing {field}");
+        }
+        let restored: MeshDecodeResult = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.data, mesh.data);
+        assert_eq!(restored.config, mesh.config);
+
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Cached {
+            config: DracoDecodeConfig,
+            #[serde(with = "crate::serde_base64")]
+            data: Vec<u8>,
+        }
+
+        let cached = Cached {
+            config: mesh.config.clone(),
+            data: mesh.data.clone(),
+        };
+        let mut json = serde_json::to_value(&cached).unwrap();
+        assert!(json["data"].is_string());
+        let restored: Cached = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(restored.data, mesh.data);
+
+        // Buffers serialized as arrays still load
+        json["data"] = serde_json::to_value(&mesh.data).unwrap();
+        let restored: Cached = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.data, mesh.data);
+    }
+
+    #[cfg(all(feature = "replay", not(target_arch = "wasm32")))]
+    #[test]
+    fn test_replay_decoder() {
+        use crate::{MeshDecoder, NativeDecoder, ReplayDecoder};
+
+        let input = fs::read("assets/20/20_data.bin").expect("Failed to read model file");
+        let recorded = ReplayDecoder::record(&NativeDecoder, [input.as_slice()]);
+        assert_eq!(recorded.len(), 1);
+
+        let fixture = recorded
+            .to_fixture_bytes()
+            .expect("Failed to serialize fixture");
+        let replay = ReplayDecoder::from_fixture_bytes(&fixture).expect("Failed to load fixture");
+
+        let expected = NativeDecoder.decode_mesh(&input).unwrap();
+        let replayed = replay.decode_mesh(&input).expect("Missing recorded result");
+        assert_eq!(replayed.data, expected.data);
+        assert_eq!(replayed.config, expected.config);
+        assert!(replay.decode_mesh(&input[1..]).is_none());
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test]
+    async fn test_decode_mesh_with_config_wasm() {
+        use crate::decode_mesh_with_config;
+        use wasm_bindgen::JsCast;
+        use wasm_bindgen_futures::JsFuture;
+        use web_sys::{Request, RequestInit, RequestMode, Response};
+
+        // Fetch test data
+        let mut opts = RequestInit::new();
+        opts.set_method("GET");
+        opts.set_mode(RequestMode::Cors);
+
+        let request = Request::new_with_str_and_init("assets/20/20_data.bin", &opts).unwrap();
+        let resp_value = JsFuture::from(web_sys::window().unwrap().fetch_with_request(&request))
+            .await
+            .unwrap();
+        let resp: Response = resp_value.dyn_into().unwrap();
+        let buf = JsFuture::from(resp.array_buffer().unwrap()).await.unwrap();
+        let u8_array = js_sys::Uint8Array::new(&buf);
+        let mut input = vec![0; u8_array.length() as usize];
+        u8_array.copy_to(&mut input[..]);
+
+        // Decode
+        let result = decode_mesh_with_config(&input).await;
+
+        assert!(result.is_some(), "Decoding should succeed");
+        let mesh = result.unwrap();
+
+        web_sys::console::log_1(&format!("vertex_count: {}", mesh.config.vertex_count()).into());
+        web_sys::console::log_1(&format!("index_count: {}", mesh.config.index_count()).into());
+        web_sys::console::log_1(&format!("buffer_size: {}", mesh.config.buffer_size()).into());
+        web_sys::console::log_1(&format!("attributes: {}", mesh.config.attributes().len()).into());
+
+        assert_eq!(mesh.config.vertex_count(), 3254);
+        assert_eq!(mesh.config.index_count(), 4368);
+        assert_eq!(mesh.config.attributes().len(), 3);
+        assert_eq!(mesh.data.len(), mesh.config.buffer_size());
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test]
+    async fn test_decode_point_cloud_with_config_wasm() {
+        use crate::{AttributeSemantic, decode_point_cloud_with_config};
+        use wasm_bindgen::JsCast;
+        use wasm_bindgen_futures::JsFuture;
+
+        let resp_value = JsFuture::from(
+            web_sys::window()
+                .unwrap()
+                .fetch_with_str("assets/pointcloud.drc"),
+        )
+        .await
+        .unwrap();
+        let resp: web_sys::Response = resp_value.dyn_into().unwrap();
+        let buf = JsFuture::from(resp.array_buffer().unwrap()).await.unwrap();
+        let input = js_sys::Uint8Array::new(&buf).to_vec();
+
+        let result = decode_point_cloud_with_config(&input)
+            .await
+            .expect("Decoding should succeed");
+
+        assert_eq!(result.config.point_count(), 3);
+        assert_eq!(result.data.len(), result.config.buffer_size());
+        assert_eq!(
+            result.config.find_attribute(AttributeSemantic::Position),
+            Some(0)
+        );
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test]
+    async fn test_ready_decoder() {
+        use crate::WasmDecoder;
+
+        let decoder = WasmDecoder::init().await.expect("Failed to load decoder");
+        assert!(WasmDecoder::init().await.is_some());
+        assert!(decoder.decode_mesh(b"not a draco file").await.is_none());
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test]
+    async fn test_time_sliced_decoder() {
+        use crate::WasmDecoder;
+        use std::time::Duration;
+        use wasm_bindgen::JsCast;
+        use wasm_bindgen_futures::JsFuture;
+
+        let resp_value = JsFuture::from(
+            web_sys::window()
+                .unwrap()
+                .fetch_with_str("assets/20/20_data.bin"),
+        )
+        .await
+        .unwrap();
+        let resp: web_sys::Response = resp_value.dyn_into().unwrap();
+        let buf = JsFuture::from(resp.array_buffer().unwrap()).await.unwrap();
+        let input = js_sys::Uint8Array::new(&buf).to_vec();
+
+        let sliced = WasmDecoder::init_time_sliced(Duration::from_millis(1))
+            .await
+            .expect("Failed to load decoder");
+        let result = sliced
+            .decode_mesh(&input)
+            .await
+            .expect("Decoding should succeed");
+        let expected = WasmDecoder::init()
+            .await
+            .unwrap()
+            .decode_mesh(&input)
+            .await
+            .unwrap();
+
+        assert_eq!(result.config.vertex_count(), 3254);
+        assert_eq!(result.data, expected.data);
+        assert!(sliced.decode_mesh(b"not a draco file").await.is_none());
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test]
+    async fn test_with_worker_error() {
+        use crate::WasmDecoder;
+
+        // Decodes fail instead of hanging when the worker cannot start
+        let worker = web_sys::Worker::new("data:text/javascript,throw new Error('broken')")
+            .expect("Failed to create worker");
+        let decoder = WasmDecoder::with_worker(&worker);
+        assert!(decoder.decode_mesh(b"DRACO").await.is_none());
+        worker.terminate();
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test]
+    fn test_environment_report() {
+        use crate::{DecodeStrategy, environment_report};
+
+        let report = environment_report();
+
+        // Test browsers allow Blob URL workers
+        assert!(report.workers);
+        assert_eq!(report.strategy, DecodeStrategy::BlobWorker);
     }
 }
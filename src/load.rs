@@ -0,0 +1,410 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::io;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::{Path, PathBuf};
+
+use crate::{
+    AttributeDataType, AttributeSemantic, DecodeOptions, GeometryType, IndexFormat,
+    MeshDecodeResult, PointCloudDecodeResult,
+};
+#[cfg(feature = "gltf")]
+use crate::{DecodedGltf, GltfError};
+#[cfg(feature = "tiles3d")]
+use crate::{PntsTile, Tiles3dError};
+
+/// Container formats recognized by [`load`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SourceFormat {
+    /// A bare Draco bitstream, usually a `.drc` file.
+    Draco(GeometryType),
+    /// A binary glTF, possibly with `KHR_draco_mesh_compression` primitives.
+    Glb,
+    /// A 3D Tiles Point Cloud tile, possibly with
+    /// `3DTILES_draco_point_compression`.
+    Pnts,
+}
+
+/// Detects the container format of `data` from its leading bytes.
+///
+/// Returns `None` if the format is not one [`load`] handles.
+///
+/// # Example
+///
+/// ```
+/// use draco_decoder::{SourceFormat, sniff_format};
+///
+/// assert_eq!(sniff_format(b"glTF\x02\0\0\0"), Some(SourceFormat::Glb));
+/// assert_eq!(sniff_format(b"OBJ"), None);
+/// ```
+pub fn sniff_format(data: &[u8]) -> Option<SourceFormat> {
+    if data.starts_with(b"glTF") {
+        Some(SourceFormat::Glb)
+    } else if data.starts_with(b"pnts") {
+        Some(SourceFormat::Pnts)
+    } else {
+        crate::sniff(data).map(SourceFormat::Draco)
+    }
+}
+
+/// Where [`load`] reads its input from.
+///
+/// Byte slices and, on native targets, paths convert into a source, so
+/// `load` accepts either directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadSource<'a> {
+    /// The file contents.
+    Bytes(&'a [u8]),
+    /// A file to read (native only).
+    #[cfg(not(target_arch = "wasm32"))]
+    Path(&'a Path),
+}
+
+impl<'a> From<&'a [u8]> for LoadSource<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        LoadSource::Bytes(bytes)
+    }
+}
+
+impl<'a, const N: usize> From<&'a [u8; N]> for LoadSource<'a> {
+    fn from(bytes: &'a [u8; N]) -> Self {
+        LoadSource::Bytes(bytes)
+    }
+}
+
+impl<'a> From<&'a Vec<u8>> for LoadSource<'a> {
+    fn from(bytes: &'a Vec<u8>) -> Self {
+        LoadSource::Bytes(bytes)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<'a> From<&'a Path> for LoadSource<'a> {
+    fn from(path: &'a Path) -> Self {
+        LoadSource::Path(path)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<'a> From<&'a PathBuf> for LoadSource<'a> {
+    fn from(path: &'a PathBuf) -> Self {
+        LoadSource::Path(path)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<'a> From<&'a str> for LoadSource<'a> {
+    fn from(path: &'a str) -> Self {
+        LoadSource::Path(Path::new(path))
+    }
+}
+
+impl<'a> LoadSource<'a> {
+    /// Returns the bytes of the source, reading the file of a path.
+    fn read(self) -> io::Result<Cow<'a, [u8]>> {
+        match self {
+            LoadSource::Bytes(bytes) => Ok(Cow::Borrowed(bytes)),
+            #[cfg(not(target_arch = "wasm32"))]
+            LoadSource::Path(path) => std::fs::read(path).map(Cow::Owned),
+        }
+    }
+}
+
+/// Buffer layouts for common targets, see [`LoadOptions::with_preset`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LoadPreset {
+    /// The tightly packed default layout of [`DecodeOptions::new`].
+    #[default]
+    Default,
+    /// Every attribute as `Float32`, normalizing integer colors and texture
+    /// coordinates, for renderers that only handle float vertex data.
+    Float32,
+    /// Attribute offsets aligned to 4 bytes, as WebGPU requires for vertex
+    /// buffer offsets.
+    WebGpu,
+    /// Storage buffer layout for compute shaders: 32-bit indices, attribute
+    /// offsets aligned to 16 bytes and `vec3` attributes padded to `vec4`.
+    Compute,
+}
+
+impl LoadPreset {
+    /// Returns the decode options producing the layout of this preset.
+    pub fn decode_options(self) -> DecodeOptions {
+        match self {
+            LoadPreset::Default => DecodeOptions::new(),
+            LoadPreset::Float32 => [
+                AttributeSemantic::Position,
+                AttributeSemantic::Normal,
+                AttributeSemantic::Color,
+                AttributeSemantic::TexCoord,
+                AttributeSemantic::Generic,
+            ]
+            .into_iter()
+            .fold(DecodeOptions::new(), |options, semantic| {
+                options.with_output_type(semantic, AttributeDataType::Float32)
+            }),
+            LoadPreset::WebGpu => DecodeOptions::new().with_offset_alignment(4),
+            LoadPreset::Compute => DecodeOptions::new()
+                .with_index_format(IndexFormat::U32)
+                .with_offset_alignment(16)
+                .pad_vec3_to_vec4(),
+        }
+    }
+}
+
+/// Options for [`load`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LoadOptions {
+    decode_options: DecodeOptions,
+}
+
+impl LoadOptions {
+    /// Creates options decoding in the default layout.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decodes meshes in the layout of `preset`.
+    ///
+    /// Replaces options set with [`LoadOptions::with_decode_options`].
+    pub fn with_preset(mut self, preset: LoadPreset) -> Self {
+        self.decode_options = preset.decode_options();
+        self
+    }
+
+    /// Decodes meshes with `options`, for layouts no preset covers or to set
+    /// a quota or cancellation token.
+    ///
+    /// Replaces the options of a preset set with
+    /// [`LoadOptions::with_preset`].
+    pub fn with_decode_options(mut self, options: DecodeOptions) -> Self {
+        self.decode_options = options;
+        self
+    }
+
+    /// Returns the options meshes are decoded with.
+    pub fn decode_options(&self) -> &DecodeOptions {
+        &self.decode_options
+    }
+}
+
+/// Geometry returned by [`load`], depending on the format of the input.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum Geometry {
+    /// A Draco mesh.
+    Mesh(MeshDecodeResult),
+    /// A Draco point cloud.
+    PointCloud(PointCloudDecodeResult),
+    /// A GLB with its Draco primitives decoded (requires the `gltf` feature).
+    #[cfg(feature = "gltf")]
+    Gltf(DecodedGltf),
+    /// A 3D Tiles Point Cloud tile (requires the `tiles3d` feature).
+    #[cfg(feature = "tiles3d")]
+    Pnts(PntsTile),
+}
+
+impl Geometry {
+    /// Returns the mesh of a Draco mesh input.
+    pub fn as_mesh(&self) -> Option<&MeshDecodeResult> {
+        match self {
+            Geometry::Mesh(mesh) => Some(mesh),
+            _ => None,
+        }
+    }
+
+    /// Returns the point cloud of a Draco point cloud input.
+    pub fn as_point_cloud(&self) -> Option<&PointCloudDecodeResult> {
+        match self {
+            Geometry::PointCloud(points) => Some(points),
+            _ => None,
+        }
+    }
+
+    /// Returns every decoded mesh: the mesh of a Draco mesh input or the
+    /// Draco primitives of a GLB.
+    pub fn meshes(&self) -> impl Iterator<Item = &MeshDecodeResult> {
+        let meshes: Vec<&MeshDecodeResult> = match self {
+            Geometry::Mesh(mesh) => vec![mesh],
+            #[cfg(feature = "gltf")]
+            Geometry::Gltf(gltf) => gltf
+                .primitives
+                .iter()
+                .map(|primitive| &primitive.result)
+                .collect(),
+            _ => Vec::new(),
+        };
+        meshes.into_iter()
+    }
+}
+
+/// Errors reported by [`load`].
+#[derive(Debug)]
+pub enum LoadError {
+    /// The file could not be read.
+    Io(io::Error),
+    /// The input is not a format [`load`] recognizes.
+    UnknownFormat,
+    /// The format is recognized, but decoding it requires a disabled crate
+    /// feature.
+    FeatureDisabled {
+        /// The detected format.
+        format: SourceFormat,
+        /// The feature to enable.
+        feature: &'static str,
+    },
+    /// The Draco data could not be decoded.
+    DecodeFailed,
+    /// The GLB could not be decoded.
+    #[cfg(feature = "gltf")]
+    Gltf(GltfError),
+    /// The pnts tile could not be decoded.
+    #[cfg(feature = "tiles3d")]
+    Tiles3d(Tiles3dError),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io(err) => write!(f, "failed to read input: {err}"),
+            LoadError::UnknownFormat => write!(f, "input is not Draco, GLB or pnts"),
+            LoadError::FeatureDisabled { format, feature } => {
+                write!(f, "decoding {format:?} requires the `{feature}` feature")
+            }
+            LoadError::DecodeFailed => write!(f, "Draco data failed to decode"),
+            #[cfg(feature = "gltf")]
+            LoadError::Gltf(err) => write!(f, "glTF: {err}"),
+            #[cfg(feature = "tiles3d")]
+            LoadError::Tiles3d(err) => write!(f, "3D Tiles: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadError::Io(err) => Some(err),
+            #[cfg(feature = "gltf")]
+            LoadError::Gltf(err) => Some(err),
+            #[cfg(feature = "tiles3d")]
+            LoadError::Tiles3d(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for LoadError {
+    fn from(err: io::Error) -> Self {
+        LoadError::Io(err)
+    }
+}
+
+#[cfg(feature = "gltf")]
+impl From<GltfError> for LoadError {
+    fn from(err: GltfError) -> Self {
+        LoadError::Gltf(err)
+    }
+}
+
+#[cfg(feature = "tiles3d")]
+impl From<Tiles3dError> for LoadError {
+    fn from(err: Tiles3dError) -> Self {
+        LoadError::Tiles3d(err)
+    }
+}
+
+/// Returns the format of `data`, or an error if it cannot be decoded with
+/// the enabled features.
+fn detect(data: &[u8]) -> Result<SourceFormat, LoadError> {
+    let format = sniff_format(data).ok_or(LoadError::UnknownFormat)?;
+    let feature = match format {
+        SourceFormat::Glb if cfg!(not(feature = "gltf")) => "gltf",
+        SourceFormat::Pnts if cfg!(not(feature = "tiles3d")) => "tiles3d",
+        _ => return Ok(format),
+    };
+    Err(LoadError::FeatureDisabled { format, feature })
+}
+
+/// Loads Draco compressed geometry from a file or bytes in one call.
+///
+/// The format is detected from the contents with [`sniff_format`]: bare
+/// Draco meshes and point clouds (`.drc`), GLB files with
+/// `KHR_draco_mesh_compression` primitives (requires the `gltf` feature)
+/// and 3D Tiles pnts tiles (requires the `tiles3d` feature). Meshes and
+/// glTF primitives are decoded with the [`DecodeOptions`] of `options`,
+/// usually set from a [`LoadPreset`]; point clouds always use the default
+/// layout.
+///
+/// Paths are only accepted on native targets and the file is read on the
+/// calling thread.
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::prelude::*;
+///
+/// let geometry = load("bunny.drc", &LoadOptions::new().with_preset(LoadPreset::WebGpu)).await?;
+/// for mesh in geometry.meshes() {
+///     println!("{} vertices", mesh.config.vertex_count());
+/// }
+/// ```
+pub async fn load<'a>(
+    source: impl Into<LoadSource<'a>>,
+    options: &LoadOptions,
+) -> Result<Geometry, LoadError> {
+    let data = source.into().read()?;
+    let decode_options = options.decode_options();
+    match detect(&data)? {
+        SourceFormat::Draco(GeometryType::Mesh) => {
+            crate::decode_mesh_with_options(&data, decode_options)
+                .await
+                .map(Geometry::Mesh)
+                .ok_or(LoadError::DecodeFailed)
+        }
+        SourceFormat::Draco(GeometryType::PointCloud) => {
+            crate::decode_point_cloud_with_config(&data)
+                .await
+                .map(Geometry::PointCloud)
+                .ok_or(LoadError::DecodeFailed)
+        }
+        #[cfg(feature = "gltf")]
+        SourceFormat::Glb => Ok(Geometry::Gltf(
+            crate::gltf::decode_gltf_with_options(&data, decode_options).await?,
+        )),
+        #[cfg(feature = "tiles3d")]
+        SourceFormat::Pnts => Ok(Geometry::Pnts(crate::decode_pnts(&data).await?)),
+        #[allow(unreachable_patterns)]
+        _ => unreachable!("detect rejects formats of disabled features"),
+    }
+}
+
+/// Synchronous version of [`load`] (native only).
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_sync<'a>(
+    source: impl Into<LoadSource<'a>>,
+    options: &LoadOptions,
+) -> Result<Geometry, LoadError> {
+    let data = source.into().read()?;
+    let decode_options = options.decode_options();
+    match detect(&data)? {
+        SourceFormat::Draco(GeometryType::Mesh) => {
+            crate::decode_mesh_with_options_sync(&data, decode_options)
+                .map(Geometry::Mesh)
+                .ok_or(LoadError::DecodeFailed)
+        }
+        SourceFormat::Draco(GeometryType::PointCloud) => {
+            crate::decode_point_cloud_with_config_sync(&data)
+                .map(Geometry::PointCloud)
+                .ok_or(LoadError::DecodeFailed)
+        }
+        #[cfg(feature = "gltf")]
+        SourceFormat::Glb => Ok(Geometry::Gltf(crate::gltf::decode_gltf_with_options_sync(
+            &data,
+            decode_options,
+        )?)),
+        #[cfg(feature = "tiles3d")]
+        SourceFormat::Pnts => Ok(Geometry::Pnts(crate::decode_pnts_sync(&data)?)),
+        #[allow(unreachable_patterns)]
+        _ => unreachable!("detect rejects formats of disabled features"),
+    }
+}
@@ -0,0 +1,84 @@
+//! Small vector helpers shared by the geometry passes.
+
+use std::ops::{Add, Div, Mul, Sub};
+
+use crate::{AttributeDataType, MeshAttribute};
+
+pub(crate) type Vec3 = [f32; 3];
+
+/// The float types the helpers work on.
+pub(crate) trait Scalar:
+    Copy
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    const ZERO: Self;
+
+    fn sqrt(self) -> Self;
+
+    fn is_finite(self) -> bool;
+}
+
+impl Scalar for f32 {
+    const ZERO: Self = 0.0;
+
+    fn sqrt(self) -> Self {
+        f32::sqrt(self)
+    }
+
+    fn is_finite(self) -> bool {
+        f32::is_finite(self)
+    }
+}
+
+impl Scalar for f64 {
+    const ZERO: Self = 0.0;
+
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+
+    fn is_finite(self) -> bool {
+        f64::is_finite(self)
+    }
+}
+
+pub(crate) fn sub<T: Scalar>(a: [T; 3], b: [T; 3]) -> [T; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+pub(crate) fn dot<T: Scalar>(a: [T; 3], b: [T; 3]) -> T {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+pub(crate) fn cross<T: Scalar>(a: [T; 3], b: [T; 3]) -> [T; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+pub(crate) fn length<T: Scalar>(a: [T; 3]) -> T {
+    dot(a, a).sqrt()
+}
+
+/// Scales `a` to unit length, `None` if it has no finite, non-zero length.
+pub(crate) fn normalize<T: Scalar>(a: [T; 3]) -> Option<[T; 3]> {
+    let length = length(a);
+    (length > T::ZERO && length.is_finite()).then(|| a.map(|c| c / length))
+}
+
+/// Returns the first three components of `Float32` positions, `None` without
+/// such an attribute.
+pub(crate) fn float_positions(attribute: Option<&MeshAttribute>, data: &[u8]) -> Option<Vec<Vec3>> {
+    let attribute = attribute?;
+    if attribute.data_type() != AttributeDataType::Float32 || attribute.dim() < 3 {
+        return None;
+    }
+    let view = attribute.view_in::<f32>(data)?;
+    Some(view.vertices().map(|v| [v[0], v[1], v[2]]).collect())
+}
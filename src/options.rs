@@ -0,0 +1,817 @@
+use std::fmt;
+use std::sync::Arc;
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::executor::{AsyncExecutor, Executor};
+use crate::progress::Progress;
+use crate::quota::Quota;
+use crate::{
+    AttributeDataType, AttributeSemantic, CancellationToken, DecodeError, DecodeStage,
+    MeshAttribute, QuotaProvider,
+};
+
+/// Width of the indices in a decoded mesh buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IndexFormat {
+    /// 16-bit indices when every vertex index fits in `u16`, 32-bit
+    /// otherwise.
+    #[default]
+    Auto,
+    /// Always 16-bit indices. Decoding fails with
+    /// [`DecodeError::IndicesDoNotFit`] when a vertex index does not fit in
+    /// `u16`.
+    U16,
+    /// Always 32-bit indices.
+    U32,
+    /// 16-bit indices when the index count fits in `u16`, 32-bit otherwise.
+    ///
+    /// This is how the index width was chosen before it was based on the
+    /// vertex count, kept for consumers relying on the old layout.
+    ByIndexCount,
+}
+
+impl IndexFormat {
+    /// Returns the index size in bytes used for a mesh of the given size.
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+    pub(crate) fn index_size(self, vertex_count: u32, index_count: u32) -> usize {
+        let fits_u16 = match self {
+            IndexFormat::Auto => vertex_count <= u16::MAX as u32 + 1,
+            IndexFormat::U16 => true,
+            IndexFormat::U32 => false,
+            IndexFormat::ByIndexCount => index_count <= u16::MAX as u32,
+        };
+        if fits_u16 { 2 } else { 4 }
+    }
+
+    /// Checks that every vertex index of a mesh with `vertex_count` vertices
+    /// can be written in this format.
+    pub(crate) fn check(self, vertex_count: u32) -> Result<(), DecodeError> {
+        if self == IndexFormat::U16 && vertex_count > u16::MAX as u32 + 1 {
+            return Err(DecodeError::IndicesDoNotFit { vertex_count });
+        }
+        Ok(())
+    }
+}
+
+/// Coordinate system positions and normals are written in, see
+/// [`DecodeOptions::with_axis_convention`].
+///
+/// Decoded meshes are assumed to be +Y up and right-handed, like glTF and
+/// the assets most Draco files are exported from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AxisConvention {
+    /// +Y up, right-handed. Values are written as decoded.
+    #[default]
+    YUpRightHanded,
+    /// +Z up, right-handed: `(x, y, z)` is written as `(x, -z, y)`.
+    ZUpRightHanded,
+    /// +Y up, left-handed: `(x, y, z)` is written as `(x, y, -z)`.
+    YUpLeftHanded,
+    /// +Z up, left-handed: `(x, y, z)` is written as `(x, z, y)`.
+    ZUpLeftHanded,
+}
+
+impl AxisConvention {
+    /// Returns the source axis of each written component, 1-based and
+    /// negative where the component is negated, or `None` for the decoded
+    /// axes.
+    pub(crate) fn axis_map(self) -> Option<[i32; 3]> {
+        match self {
+            AxisConvention::YUpRightHanded => None,
+            AxisConvention::ZUpRightHanded => Some([1, -3, 2]),
+            AxisConvention::YUpLeftHanded => Some([1, 2, -3]),
+            AxisConvention::ZUpLeftHanded => Some([1, 3, 2]),
+        }
+    }
+
+    /// Returns `true` for the left-handed conventions, which mirror the mesh.
+    pub fn is_left_handed(self) -> bool {
+        matches!(
+            self,
+            AxisConvention::YUpLeftHanded | AxisConvention::ZUpLeftHanded
+        )
+    }
+
+    /// Checks that every position and normal attribute can be remapped to
+    /// this convention.
+    pub(crate) fn check(self, attributes: &[MeshAttribute]) -> Result<(), DecodeError> {
+        if self.axis_map().is_none() {
+            return Ok(());
+        }
+        match attributes.iter().find(|attribute| {
+            matches!(
+                attribute.semantic(),
+                AttributeSemantic::Position | AttributeSemantic::Normal
+            ) && !remappable(attribute)
+        }) {
+            Some(attribute) => Err(DecodeError::AxesNotRemappable {
+                unique_id: attribute.unique_id(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Returns `true` if the components of `attribute` can be swapped and
+/// negated: three component vectors of `Float32` or signed integers that are
+/// not quantized or octahedron encoded.
+fn remappable(attribute: &MeshAttribute) -> bool {
+    let signed = matches!(
+        attribute.data_type(),
+        AttributeDataType::Float32
+            | AttributeDataType::Int8
+            | AttributeDataType::Int16
+            | AttributeDataType::Int32
+    );
+    signed
+        && attribute.dim() - u32::from(attribute.padded()) == 3
+        && attribute.quantization().is_none()
+        && attribute.octahedron().is_none()
+}
+
+/// Upper bounds on the size of a decoded mesh, see
+/// [`DecodeOptions::with_limits`] for when they are checked.
+///
+/// The default is unlimited, so limits can be set selectively:
+///
+/// ```
+/// use draco_decoder::Limits;
+///
+/// let limits = Limits {
+///     max_vertices: 1 << 20,
+///     ..Limits::default()
+/// };
+/// assert_eq!(limits.max_buffer_bytes, usize::MAX);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Limits {
+    /// Maximum number of vertices, or points of a point cloud.
+    pub max_vertices: u32,
+    /// Maximum number of indices, three per triangle.
+    pub max_indices: u32,
+    /// Maximum size of the decoded buffer in bytes.
+    pub max_buffer_bytes: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_vertices: u32::MAX,
+            max_indices: u32::MAX,
+            max_buffer_bytes: usize::MAX,
+        }
+    }
+}
+
+impl Limits {
+    /// Checks the size of a decoded mesh against the limits.
+    pub(crate) fn check(
+        &self,
+        vertex_count: u32,
+        index_count: u32,
+        buffer_size: usize,
+    ) -> Result<(), DecodeError> {
+        let checks = [
+            (
+                LimitKind::Vertices,
+                vertex_count as usize,
+                self.max_vertices as usize,
+            ),
+            (
+                LimitKind::Indices,
+                index_count as usize,
+                self.max_indices as usize,
+            ),
+            (LimitKind::BufferBytes, buffer_size, self.max_buffer_bytes),
+        ];
+        match checks.into_iter().find(|&(_, actual, max)| actual > max) {
+            Some((kind, actual, max)) => Err(DecodeError::LimitExceeded { kind, actual, max }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A limit of [`Limits`], reported by [`DecodeError::LimitExceeded`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LimitKind {
+    /// [`Limits::max_vertices`]
+    Vertices,
+    /// [`Limits::max_indices`]
+    Indices,
+    /// [`Limits::max_buffer_bytes`]
+    BufferBytes,
+}
+
+impl fmt::Display for LimitKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LimitKind::Vertices => "vertices",
+            LimitKind::Indices => "indices",
+            LimitKind::BufferBytes => "buffer bytes",
+        })
+    }
+}
+
+/// Options controlling the layout of a decoded mesh buffer.
+///
+/// Used with [`decode_mesh_with_options`](crate::decode_mesh_with_options).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DecodeOptions {
+    offset_alignment: u32,
+    index_format: IndexFormat,
+    output_types: [Option<AttributeDataType>; 5],
+    #[cfg_attr(feature = "serde", serde(default))]
+    attribute_output_types: Vec<(u32, AttributeDataType)>,
+    keep_quantized: [bool; 5],
+    pad_vec3: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    axis_convention: AxisConvention,
+    #[cfg_attr(feature = "serde", serde(default))]
+    flip_winding: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    limits: Limits,
+    #[cfg_attr(feature = "serde", serde(default))]
+    validate_indices: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    compute_min_max: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    quota: Option<Quota>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    cancellation: Option<CancellationToken>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    progress: Option<Progress>,
+    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    executor: Option<Executor>,
+}
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        Self {
+            offset_alignment: 1,
+            index_format: IndexFormat::Auto,
+            output_types: [None; 5],
+            attribute_output_types: Vec::new(),
+            keep_quantized: [false; 5],
+            pad_vec3: false,
+            axis_convention: AxisConvention::YUpRightHanded,
+            flip_winding: false,
+            limits: Limits::default(),
+            validate_indices: false,
+            compute_min_max: false,
+            quota: None,
+            cancellation: None,
+            progress: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            executor: None,
+        }
+    }
+}
+
+impl DecodeOptions {
+    /// Creates options producing the default, tightly packed layout.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the alignment of attribute offsets in the decoded buffer.
+    ///
+    /// Each attribute starts at a multiple of `alignment` bytes, with zeroed
+    /// padding inserted after the preceding section. WebGPU for example
+    /// requires vertex buffer offsets aligned to 4 bytes, and 256 bytes for
+    /// bind group offsets. The default of 1 packs attributes tightly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alignment` is 0.
+    pub fn with_offset_alignment(mut self, alignment: u32) -> Self {
+        assert!(alignment > 0, "offset alignment must be at least 1");
+        self.offset_alignment = alignment;
+        self
+    }
+
+    /// Sets the width of the decoded indices.
+    ///
+    /// Renderers that only consume 32-bit index buffers can request
+    /// [`IndexFormat::U32`] instead of widening the indices after decoding.
+    pub fn with_index_format(mut self, format: IndexFormat) -> Self {
+        self.index_format = format;
+        self
+    }
+
+    /// Sets the data type attributes with the given semantic are written as.
+    ///
+    /// Values are converted while the buffer is written, so for example
+    /// 16-bit texture coordinates can be decoded straight into `Float32`, or
+    /// `Float32` colors into `UInt8`. Conversions between integer and
+    /// floating point types are normalized: unsigned integers map to
+    /// `0.0..=1.0` and signed integers to `-1.0..=1.0`. Normalized integers
+    /// converted to another integer type are rescaled to its range, other
+    /// integers keep their values. Converted integers are rounded and clamped
+    /// to the range of the requested type.
+    pub fn with_output_type(
+        mut self,
+        semantic: AttributeSemantic,
+        data_type: AttributeDataType,
+    ) -> Self {
+        self.output_types[semantic as usize] = Some(data_type);
+        self
+    }
+
+    /// Sets the data type the attribute with the given unique id is written
+    /// as, taking precedence over [`DecodeOptions::with_output_type`] for its
+    /// semantic.
+    ///
+    /// Useful when attributes sharing a semantic need different types, for
+    /// example two texture coordinate sets of which only one is normalized.
+    pub fn with_attribute_output_type(
+        mut self,
+        unique_id: u32,
+        data_type: AttributeDataType,
+    ) -> Self {
+        self.attribute_output_types
+            .retain(|&(id, _)| id != unique_id);
+        self.attribute_output_types.push((unique_id, data_type));
+        self
+    }
+
+    /// Keeps the quantized integer values of attributes with the given
+    /// semantic instead of dequantizing them (native only).
+    ///
+    /// Quantized attributes are decoded as `UInt32` and carry a
+    /// [`QuantizationInfo`](crate::QuantizationInfo) on their
+    /// [`MeshAttribute`](crate::MeshAttribute), so pipelines such as
+    /// `KHR_mesh_quantization` can dequantize on the GPU. Normals are
+    /// usually octahedron encoded instead, see
+    /// [`DecodeOptions::keep_octahedral_normals`]. Attributes that were not
+    /// quantized by the encoder are unaffected.
+    ///
+    /// The WASM worker always dequantizes, so this has no effect there.
+    pub fn keep_quantized(mut self, semantic: AttributeSemantic) -> Self {
+        self.keep_quantized[semantic as usize] = true;
+        self
+    }
+
+    /// Keeps octahedron encoded normals as two quantized `UInt32` coordinates
+    /// instead of expanding them to three floats (native only).
+    ///
+    /// The normal attribute carries an
+    /// [`OctahedronInfo`](crate::OctahedronInfo) with the parameters needed to
+    /// decode the normals in a shader. Normals quantized without the
+    /// octahedral encoding keep their quantized values as with
+    /// [`DecodeOptions::keep_quantized`], which this is a shorthand for.
+    pub fn keep_octahedral_normals(self) -> Self {
+        self.keep_quantized(AttributeSemantic::Normal)
+    }
+
+    /// Pads every three component attribute to four components.
+    ///
+    /// The std140 and std430 layouts of storage buffers align `vec3` to 16
+    /// bytes, so tightly packed positions and normals would otherwise need a
+    /// CPU repack before a compute shader can read them. A zero fourth
+    /// component is written while the buffer is filled, and the attribute
+    /// reports four components with [`MeshAttribute::padded`](crate::MeshAttribute::padded)
+    /// set. Combine with [`DecodeOptions::with_output_type`] to pad `Float32`
+    /// attributes to 16 bytes per vertex.
+    pub fn pad_vec3_to_vec4(mut self) -> Self {
+        self.pad_vec3 = true;
+        self
+    }
+
+    /// Writes positions and normals in the coordinate system of `convention`.
+    ///
+    /// The components are swapped and negated while the buffer is filled, so
+    /// a Z-up engine does not need another pass over millions of vertices.
+    /// [`AttributeSemantic::Position`] and [`AttributeSemantic::Normal`]
+    /// attributes must be three component vectors written as `Float32` or
+    /// signed integers; decoding fails with
+    /// [`DecodeError::AxesNotRemappable`] for unsigned, quantized or
+    /// octahedron encoded values, which cannot be negated. Request `Float32`
+    /// with [`DecodeOptions::with_output_type`] to remap them. Tangents
+    /// generated with
+    /// [`postprocess::generate_tangents`](crate::postprocess::generate_tangents)
+    /// afterwards are in the new coordinate system.
+    ///
+    /// Left-handed conventions mirror the mesh, so the winding of the
+    /// triangles is flipped as well to keep their front faces, see
+    /// [`DecodeOptions::flip_winding`].
+    pub fn with_axis_convention(mut self, convention: AxisConvention) -> Self {
+        self.axis_convention = convention;
+        self
+    }
+
+    /// Swaps the second and third index of every triangle while the index
+    /// buffer is written.
+    ///
+    /// Draco meshes from glTF are wound counter-clockwise, so engines that
+    /// treat clockwise triangles as front faces, or meshes mirrored by
+    /// [`DecodeOptions::with_axis_convention`], would otherwise need a pass
+    /// over the indices or a different cull mode.
+    ///
+    /// With a left-handed
+    /// [`DecodeOptions::with_axis_convention`], whose winding is already
+    /// flipped, this restores the decoded winding.
+    pub fn flip_winding(mut self, flip: bool) -> Self {
+        self.flip_winding = flip;
+        self
+    }
+
+    /// Rejects meshes larger than `limits`.
+    ///
+    /// Natively the vertex and index limits are checked as soon as Draco has
+    /// decoded the connectivity, before it decompresses any attribute
+    /// values, and the buffer limit once the layout of the decoded buffer is
+    /// computed, before the buffer is allocated. A hostile file claiming
+    /// absurd counts thus fails with [`DecodeError::LimitExceeded`] from
+    /// [`try_decode_mesh_with_options`](crate::try_decode_mesh_with_options)
+    /// instead of allocating gigabytes; the other decode functions return
+    /// `None`. Only the connectivity Draco read so far is allocated at that
+    /// point. On WASM the worker has already decoded the mesh and written
+    /// the buffer when the limits are checked.
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Checks every decoded index against the vertex count.
+    ///
+    /// Corrupt files can decode to indices past the last vertex, which GPU
+    /// uploads turn into out of bounds reads. With validation enabled such a
+    /// mesh fails with [`DecodeError::IndexOutOfBounds`] from
+    /// [`try_decode_mesh_with_options`](crate::try_decode_mesh_with_options)
+    /// and the `MeshHandle` methods writing into a buffer; the other decode
+    /// functions return `None`. The check is an extra pass
+    /// over the index buffer, so it is off by default.
+    pub fn validate_indices(mut self, validate: bool) -> Self {
+        self.validate_indices = validate;
+        self
+    }
+
+    /// Records the component-wise minimum and maximum of every attribute
+    /// while it is written to the decoded buffer.
+    ///
+    /// The extrema are returned by
+    /// [`DracoDecodeConfig::min_max`](crate::DracoDecodeConfig::min_max) and
+    /// used by [`MeshAttribute::compute_min_max`](crate::MeshAttribute::compute_min_max),
+    /// so glTF accessor bounds need no second pass over the buffer. They
+    /// describe the buffer as decoded: passes that change the data in place
+    /// do not update them. On WASM they are computed after the worker
+    /// returns.
+    pub fn compute_min_max(mut self, compute: bool) -> Self {
+        self.compute_min_max = compute;
+        self
+    }
+
+    /// Charges decodes with these options to `tenant_id` of `provider`.
+    ///
+    /// The provider is asked before decoding starts and again before the
+    /// decoded buffer is allocated, see [`QuotaProvider`]. A
+    /// [`QuotaDecision::Deny`](crate::QuotaDecision::Deny) makes the decode
+    /// return `None`, or [`DecodeError::QuotaDenied`] from the functions
+    /// reporting errors. On WASM the worker allocates its own buffer, so the
+    /// second check only keeps the result and its relayout from being handed
+    /// out.
+    pub fn with_quota(
+        mut self,
+        provider: Arc<dyn QuotaProvider>,
+        tenant_id: impl Into<String>,
+    ) -> Self {
+        self.quota = Some(Quota {
+            provider,
+            tenant_id: tenant_id.into(),
+        });
+        self
+    }
+
+    /// Aborts decodes with these options once `token` is cancelled.
+    ///
+    /// A cancelled decode returns `None`, see [`CancellationToken`] for when
+    /// the token is checked.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Calls `callback` with each completed [`DecodeStage`] and the fraction
+    /// of the decode done so far.
+    ///
+    /// The fraction grows with every stage and reaches 1.0 once the last
+    /// attribute is extracted, so UIs can drive a loading bar for very large
+    /// meshes and point clouds. Draco reports its decompression as the
+    /// connectivity and each group of attributes is done. The callback runs
+    /// on the decoding thread; a panic in it is caught and does not affect
+    /// the decode. On WASM the worker does not report while it decodes, so
+    /// [`DecodeStage::AttributesDecoded`] is skipped and the later stages are
+    /// reported once its result arrives.
+    pub fn with_progress(
+        mut self,
+        callback: impl Fn(DecodeStage, f32) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(Progress(Arc::new(callback)));
+        self
+    }
+
+    /// Runs the native async decodes with these options on `executor`
+    /// (native only).
+    ///
+    /// Without an executor the decode runs on the polling thread, or with the
+    /// `tokio` feature on the blocking thread pool of the current Tokio
+    /// runtime. The executor only applies to decodes with these options, see
+    /// [`AsyncExecutor`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_executor(mut self, executor: impl AsyncExecutor + 'static) -> Self {
+        self.executor = Some(Executor(Arc::new(executor)));
+        self
+    }
+
+    /// Returns the alignment of attribute offsets in bytes.
+    pub fn offset_alignment(&self) -> u32 {
+        self.offset_alignment
+    }
+
+    /// Returns the requested index width.
+    pub fn index_format(&self) -> IndexFormat {
+        self.index_format
+    }
+
+    /// Returns the data type requested for attributes with the given
+    /// semantic, if any.
+    pub fn output_type(&self, semantic: AttributeSemantic) -> Option<AttributeDataType> {
+        self.output_types[semantic as usize]
+    }
+
+    /// Returns the data type requested for the attribute with the given
+    /// unique id and semantic, if any.
+    pub fn attribute_output_type(
+        &self,
+        unique_id: u32,
+        semantic: AttributeSemantic,
+    ) -> Option<AttributeDataType> {
+        self.attribute_output_types
+            .iter()
+            .find(|&&(id, _)| id == unique_id)
+            .map(|&(_, data_type)| data_type)
+            .or(self.output_type(semantic))
+    }
+
+    /// Returns the data types requested with
+    /// [`DecodeOptions::with_attribute_output_type`] by unique id.
+    pub(crate) fn attribute_output_types(&self) -> &[(u32, AttributeDataType)] {
+        &self.attribute_output_types
+    }
+
+    /// Returns `true` if attributes with the given semantic keep their
+    /// quantized values.
+    pub fn keeps_quantized(&self, semantic: AttributeSemantic) -> bool {
+        self.keep_quantized[semantic as usize]
+    }
+
+    /// Returns `true` if three component attributes are padded to four.
+    pub fn pads_vec3(&self) -> bool {
+        self.pad_vec3
+    }
+
+    /// Returns the coordinate system positions and normals are written in.
+    pub fn axis_convention(&self) -> AxisConvention {
+        self.axis_convention
+    }
+
+    /// Returns `true` if [`DecodeOptions::flip_winding`] is set.
+    pub fn flips_winding(&self) -> bool {
+        self.flip_winding
+    }
+
+    /// Returns `true` if the triangles are written with the opposite winding,
+    /// from [`DecodeOptions::flip_winding`] or a left-handed axis convention.
+    pub(crate) fn writes_flipped_winding(&self) -> bool {
+        self.flip_winding != self.axis_convention.is_left_handed()
+    }
+
+    /// Returns the limits decoded meshes are checked against.
+    pub fn limits(&self) -> Limits {
+        self.limits
+    }
+
+    /// Returns `true` if decoded indices are checked against the vertex
+    /// count.
+    pub fn validates_indices(&self) -> bool {
+        self.validate_indices
+    }
+
+    /// Returns `true` if the extrema of the attributes are recorded while
+    /// decoding.
+    pub fn computes_min_max(&self) -> bool {
+        self.compute_min_max
+    }
+
+    /// Returns the tenant decodes are charged to, if a quota is set.
+    pub fn tenant_id(&self) -> Option<&str> {
+        self.quota.as_ref().map(|quota| quota.tenant_id.as_str())
+    }
+
+    /// Returns the token cancelling decodes with these options, if any.
+    pub fn cancellation(&self) -> Option<&CancellationToken> {
+        self.cancellation.as_ref()
+    }
+
+    /// Returns `true` if the cancellation token has been cancelled.
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+
+    /// Reports `stage` to the progress callback, if any. See
+    /// [`DecodeStage::fraction`] for `count`.
+    pub(crate) fn report_progress(&self, stage: DecodeStage, count: usize) {
+        if let Some(progress) = &self.progress {
+            progress.report(stage, count);
+        }
+    }
+
+    /// Returns the progress callback, if any.
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub(crate) fn progress(&self) -> Option<&Progress> {
+        self.progress.as_ref()
+    }
+
+    /// Returns the executor set with [`Self::with_executor`], if any.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn executor(&self) -> Option<&Executor> {
+        self.executor.as_ref()
+    }
+
+    /// Fails with [`DecodeError::QuotaDenied`] if the quota denies decoding
+    /// `encoded_bytes` bytes of Draco data.
+    pub(crate) fn check_quota_decode(&self, encoded_bytes: usize) -> Result<(), DecodeError> {
+        match &self.quota {
+            Some(quota) if !quota.allows_decode(encoded_bytes) => Err(DecodeError::QuotaDenied),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fails with [`DecodeError::QuotaDenied`] if the quota denies allocating
+    /// a decoded buffer of `bytes` bytes.
+    pub(crate) fn check_quota(&self, bytes: usize) -> Result<(), DecodeError> {
+        match &self.quota {
+            Some(quota) if !quota.allows(bytes) => Err(DecodeError::QuotaDenied),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl crate::MeshDecodeResult {
+    /// Rewrites the buffer in the layout requested by `options`.
+    ///
+    /// Used on wasm, where the worker writes the requested output types but
+    /// otherwise always the default layout.
+    pub(crate) fn relayout(self, options: &DecodeOptions) -> Self {
+        let vertex_count = self.config.vertex_count();
+        let index_count = self.config.index_count();
+        let index_size = options.index_format().index_size(vertex_count, index_count);
+        let alignment = options.offset_alignment() as usize;
+
+        let pads = options.pads_vec3()
+            && self
+                .config
+                .attributes()
+                .iter()
+                .any(|attribute| attribute.dim() == 3);
+        let axis_map = options.axis_convention().axis_map();
+        if alignment <= 1
+            && index_size == self.config.index_size_in_bytes()
+            && !pads
+            && axis_map.is_none()
+            && !options.writes_flipped_winding()
+        {
+            return self;
+        }
+
+        let mut data = Vec::with_capacity(self.data.len());
+        if index_size == self.config.index_size_in_bytes() && !options.writes_flipped_winding() {
+            data.extend_from_slice(self.index_bytes().unwrap_or(&[]));
+        } else {
+            let mut indices = self
+                .indices()
+                .map(|indices| indices.into_u32())
+                .unwrap_or_default();
+            if options.writes_flipped_winding() {
+                for triangle in indices.chunks_exact_mut(3) {
+                    triangle.swap(1, 2);
+                }
+            }
+            for index in indices {
+                if index_size == 2 {
+                    data.extend_from_slice(&(index as u16).to_ne_bytes());
+                } else {
+                    data.extend_from_slice(&index.to_ne_bytes());
+                }
+            }
+        }
+        let index_length = data.len() as u32;
+
+        let mut attributes = Vec::new();
+        for (index, attribute) in self.config.attributes().into_iter().enumerate() {
+            let bytes = self.attribute_bytes(index).unwrap_or(&[]);
+            data.resize(data.len().next_multiple_of(alignment.max(1)), 0);
+            let offset = data.len() as u32;
+            // The worker already wrote the requested output types
+            if !(options.pads_vec3() && attribute.dim() == 3) {
+                data.extend_from_slice(bytes);
+                attributes.push(attribute.with_layout(offset, bytes.len() as u32));
+                continue;
+            }
+
+            // Append a zero fourth component to every element
+            let component_size = attribute.data_type().size_in_bytes();
+            for element in bytes.chunks_exact(3 * component_size) {
+                data.extend_from_slice(element);
+                data.resize(data.len() + component_size, 0);
+            }
+            attributes.push(
+                crate::MeshAttribute::new(
+                    4,
+                    attribute.data_type(),
+                    offset,
+                    data.len() as u32 - offset,
+                )
+                .with_semantic(attribute.semantic())
+                .with_unique_id(attribute.unique_id())
+                .with_normalized(attribute.normalized())
+                .with_padded(true),
+            );
+        }
+
+        if let Some(map) = axis_map {
+            for attribute in &attributes {
+                let start = attribute.offset() as usize;
+                let end = start + attribute.lenght() as usize;
+                remap_axes(&mut data[start..end], attribute, map);
+            }
+        }
+
+        let mut config = crate::DracoDecodeConfig::new(vertex_count, index_count, data.len())
+            .with_index_length(index_length);
+        for attribute in attributes {
+            config.add_attribute(attribute);
+        }
+        config.inherit(&self.config);
+
+        Self { data, config }
+    }
+}
+
+/// Remaps the components of a position or normal attribute as the native
+/// library does for [`DecodeOptions::with_axis_convention`].
+#[cfg(target_arch = "wasm32")]
+fn remap_axes(section: &mut [u8], attribute: &MeshAttribute, map: [i32; 3]) {
+    let data_type = attribute.data_type();
+    if !matches!(
+        attribute.semantic(),
+        AttributeSemantic::Position | AttributeSemantic::Normal
+    ) || !remappable(attribute)
+    {
+        return;
+    }
+
+    let size = data_type.size_in_bytes();
+    for element in section.chunks_exact_mut(attribute.element_size()) {
+        let mut source = [0u8; 12];
+        source[..3 * size].copy_from_slice(&element[..3 * size]);
+        for (axis, &from) in map.iter().enumerate() {
+            let from = from.unsigned_abs() as usize - 1;
+            let component = &mut element[axis * size..(axis + 1) * size];
+            component.copy_from_slice(&source[from * size..(from + 1) * size]);
+            if map[axis] < 0 {
+                negate_component(component, data_type);
+            }
+        }
+    }
+}
+
+/// Negates a signed component in place, saturating the lowest integer.
+#[cfg(target_arch = "wasm32")]
+fn negate_component(component: &mut [u8], data_type: AttributeDataType) {
+    match (data_type, &mut *component) {
+        (AttributeDataType::Float32, &mut [a, b, c, d]) => {
+            component.copy_from_slice(&(-f32::from_ne_bytes([a, b, c, d])).to_ne_bytes())
+        }
+        (AttributeDataType::Int8, &mut [a]) => {
+            component.copy_from_slice(&(a as i8).saturating_neg().to_ne_bytes())
+        }
+        (AttributeDataType::Int16, &mut [a, b]) => {
+            component.copy_from_slice(&i16::from_ne_bytes([a, b]).saturating_neg().to_ne_bytes())
+        }
+        (AttributeDataType::Int32, &mut [a, b, c, d]) => component.copy_from_slice(
+            &i32::from_ne_bytes([a, b, c, d])
+                .saturating_neg()
+                .to_ne_bytes(),
+        ),
+        _ => {}
+    }
+}
@@ -0,0 +1,327 @@
+use std::collections::BinaryHeap;
+
+use crate::math::float_positions;
+use crate::{AttributeSemantic, MeshDecodeResult, PointCloudDecodeConfig, PointCloudDecodeResult};
+
+const UNMAPPED: u32 = u32::MAX;
+
+/// Parameters of statistical outlier removal, see
+/// [`PointCloudDecodeResult::remove_outliers`] and
+/// [`MeshDecodeResult::remove_outliers`].
+///
+/// A point is an outlier if the mean distance to its nearest neighbors
+/// exceeds the average over all points by more than `std_ratio` standard
+/// deviations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StatisticalOutlierParams {
+    neighbors: usize,
+    std_ratio: f32,
+    min_component_vertices: u32,
+}
+
+impl Default for StatisticalOutlierParams {
+    fn default() -> Self {
+        Self {
+            neighbors: 8,
+            std_ratio: 2.0,
+            min_component_vertices: 0,
+        }
+    }
+}
+
+impl StatisticalOutlierParams {
+    /// Creates parameters with 8 neighbors and a ratio of 2 standard
+    /// deviations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of nearest neighbors the mean distance is taken over.
+    pub fn with_neighbors(mut self, neighbors: usize) -> Self {
+        self.neighbors = neighbors;
+        self
+    }
+
+    /// Sets how many standard deviations above the average mean distance a
+    /// point is considered an outlier.
+    pub fn with_std_ratio(mut self, std_ratio: f32) -> Self {
+        self.std_ratio = std_ratio;
+        self
+    }
+
+    /// Sets the vertex count below which mesh components are removed
+    /// regardless of their distances. 0 by default.
+    pub fn with_min_component_vertices(mut self, min_component_vertices: u32) -> Self {
+        self.min_component_vertices = min_component_vertices;
+        self
+    }
+
+    /// Returns the number of nearest neighbors.
+    pub fn neighbors(&self) -> usize {
+        self.neighbors
+    }
+
+    /// Returns the standard deviation ratio.
+    pub fn std_ratio(&self) -> f32 {
+        self.std_ratio
+    }
+
+    /// Returns the smallest vertex count of kept mesh components.
+    pub fn min_component_vertices(&self) -> u32 {
+        self.min_component_vertices
+    }
+
+    /// Flags the statistical outliers among `points`.
+    fn outliers(&self, points: &[[f32; 3]]) -> Vec<bool> {
+        let neighbors = self.neighbors.min(points.len().saturating_sub(1));
+        if neighbors == 0 {
+            return vec![false; points.len()];
+        }
+
+        let tree = KdTree::new(points);
+        let mean_distances: Vec<f64> = (0..points.len())
+            .map(|index| {
+                let nearest = tree.nearest(index, neighbors);
+                let sum: f64 = nearest
+                    .iter()
+                    .map(|&distance| (f32::from_bits(distance) as f64).sqrt())
+                    .sum();
+                sum / nearest.len() as f64
+            })
+            .collect();
+
+        let count = mean_distances.len() as f64;
+        let mean = mean_distances.iter().sum::<f64>() / count;
+        let variance = mean_distances
+            .iter()
+            .map(|distance| (distance - mean).powi(2))
+            .sum::<f64>()
+            / count;
+        let threshold = mean + self.std_ratio as f64 * variance.sqrt();
+
+        mean_distances
+            .into_iter()
+            .map(|distance| distance > threshold)
+            .collect()
+    }
+}
+
+/// A k-d tree over points, stored as a permutation of their indices with the
+/// median of each subtree in its middle.
+struct KdTree<'a> {
+    points: &'a [[f32; 3]],
+    order: Vec<u32>,
+}
+
+impl<'a> KdTree<'a> {
+    fn new(points: &'a [[f32; 3]]) -> Self {
+        fn build(points: &[[f32; 3]], order: &mut [u32], axis: usize) {
+            if order.len() <= 1 {
+                return;
+            }
+            let middle = order.len() / 2;
+            order.select_nth_unstable_by(middle, |&a, &b| {
+                points[a as usize][axis].total_cmp(&points[b as usize][axis])
+            });
+            let (left, right) = order.split_at_mut(middle);
+            build(points, left, (axis + 1) % 3);
+            build(points, &mut right[1..], (axis + 1) % 3);
+        }
+
+        let mut order: Vec<u32> = (0..points.len() as u32).collect();
+        build(points, &mut order, 0);
+        Self { points, order }
+    }
+
+    /// Returns the squared distances, as `f32` bits, from the point at
+    /// `index` to its `k` nearest other points.
+    fn nearest(&self, index: usize, k: usize) -> Vec<u32> {
+        // Bits of non-negative floats order like the floats themselves
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        self.search(&self.order, 0, index, k, &mut heap);
+        heap.into_vec()
+    }
+
+    fn search(
+        &self,
+        order: &[u32],
+        axis: usize,
+        index: usize,
+        k: usize,
+        heap: &mut BinaryHeap<u32>,
+    ) {
+        if order.is_empty() {
+            return;
+        }
+        let middle = order.len() / 2;
+        let point = order[middle] as usize;
+        let query = self.points[index];
+
+        if point != index {
+            let distance: f32 = (0..3)
+                .map(|i| (self.points[point][i] - query[i]).powi(2))
+                .sum();
+            heap.push(distance.to_bits());
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+
+        let offset = query[axis] - self.points[point][axis];
+        let (near, far) = if offset < 0.0 {
+            (&order[..middle], &order[middle + 1..])
+        } else {
+            (&order[middle + 1..], &order[..middle])
+        };
+        let next = (axis + 1) % 3;
+        self.search(near, next, index, k, heap);
+        let worst = heap
+            .peek()
+            .map_or(f32::INFINITY, |&bits| f32::from_bits(bits));
+        if heap.len() < k || offset * offset < worst {
+            self.search(far, next, index, k, heap);
+        }
+    }
+}
+
+impl PointCloudDecodeResult {
+    /// Removes statistical outliers, such as the stray returns of a lidar
+    /// scan.
+    ///
+    /// Every attribute of the kept points is copied into a new tightly packed
+    /// buffer. Point clouds without `Float32` positions are returned
+    /// unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use draco_decoder::StatisticalOutlierParams;
+    ///
+    /// let params = StatisticalOutlierParams::new().with_neighbors(16).with_std_ratio(1.5);
+    /// let cleaned = points.remove_outliers(&params);
+    /// ```
+    pub fn remove_outliers(&self, params: &StatisticalOutlierParams) -> PointCloudDecodeResult {
+        let position = self.config.find_attribute(AttributeSemantic::Position);
+        let Some(points) = float_positions(
+            position.and_then(|index| self.config.get_attribute(index)),
+            &self.data,
+        ) else {
+            return self.clone();
+        };
+
+        let kept: Vec<usize> = params
+            .outliers(&points)
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, outlier)| !outlier)
+            .map(|(index, _)| index)
+            .collect();
+
+        let mut data = Vec::new();
+        let mut attributes = Vec::new();
+        for (index, attribute) in self.config.attributes().into_iter().enumerate() {
+            let bytes = self.attribute_bytes(index).unwrap_or(&[]);
+            let element_size = attribute.element_size();
+            let offset = data.len();
+            for &point in &kept {
+                let start = point * element_size;
+                if let Some(element) = bytes.get(start..start + element_size) {
+                    data.extend_from_slice(element);
+                }
+            }
+            attributes.push(attribute.with_layout(offset as u32, (data.len() - offset) as u32));
+        }
+
+        let mut config = PointCloudDecodeConfig::new(kept.len() as u32, data.len());
+        for attribute in attributes {
+            config.add_attribute(attribute);
+        }
+        config.inherit(&self.config);
+        PointCloudDecodeResult { data, config }
+    }
+}
+
+impl MeshDecodeResult {
+    /// Removes small disconnected components that float away from the rest
+    /// of the mesh, such as the debris around a scanned object.
+    ///
+    /// Statistical outliers are found among the distinct vertex positions,
+    /// and a connected component is removed if most of its vertices are
+    /// outliers or it has fewer than
+    /// [`StatisticalOutlierParams::min_component_vertices`] vertices. The
+    /// remaining triangles and the vertices they reference are copied into
+    /// a new mesh. Meshes without `Float32` positions are returned unchanged.
+    pub fn remove_outliers(&self, params: &StatisticalOutlierParams) -> MeshDecodeResult {
+        let position = self.config.find_attribute(AttributeSemantic::Position);
+        let Some(points) = float_positions(
+            position.and_then(|index| self.config.get_attribute(index)),
+            &self.data,
+        ) else {
+            return self.clone();
+        };
+        let vertex_count = self.config.vertex_count();
+        let welded = self.welded_vertices();
+
+        // Statistics over distinct positions, seam copies would be their own
+        // nearest neighbors
+        let distinct: Vec<u32> = (0..vertex_count)
+            .filter(|&vertex| welded[vertex as usize] == vertex)
+            .collect();
+        let distinct_points: Vec<[f32; 3]> = distinct
+            .iter()
+            .filter_map(|&vertex| points.get(vertex as usize).copied())
+            .collect();
+        let mut outlier = vec![false; vertex_count as usize];
+        for (&vertex, is_outlier) in distinct.iter().zip(params.outliers(&distinct_points)) {
+            outlier[vertex as usize] = is_outlier;
+        }
+
+        let components = self.connected_components();
+        let component_count = components.iter().max().map_or(0, |&max| max as usize + 1);
+        let mut component_of = vec![UNMAPPED; vertex_count as usize];
+        for (triangle, &component) in self.triangles().zip(&components) {
+            for vertex in triangle {
+                if let Some(slot) = component_of.get_mut(vertex as usize) {
+                    *slot = component;
+                }
+            }
+        }
+
+        // (distinct vertices, outliers) of every component
+        let mut counts = vec![(0u32, 0u32); component_count];
+        for &vertex in &distinct {
+            if let Some(count) = counts.get_mut(component_of[vertex as usize] as usize) {
+                count.0 += 1;
+                count.1 += outlier[vertex as usize] as u32;
+            }
+        }
+        let removed: Vec<bool> = counts
+            .iter()
+            .map(|&(vertices, outliers)| {
+                vertices < params.min_component_vertices || outliers * 2 > vertices
+            })
+            .collect();
+        if !removed.contains(&true) {
+            return self.clone();
+        }
+
+        let mut remap = vec![UNMAPPED; vertex_count as usize];
+        let mut source_vertices = Vec::new();
+        let mut indices = Vec::new();
+        for (triangle, component) in self.triangles().zip(components) {
+            if removed[component as usize] || triangle.iter().any(|&v| v >= vertex_count) {
+                continue;
+            }
+            for vertex in triangle {
+                let slot = &mut remap[vertex as usize];
+                if *slot == UNMAPPED {
+                    *slot = source_vertices.len() as u32;
+                    source_vertices.push(vertex);
+                }
+                indices.push(*slot);
+            }
+        }
+        self.gather(&source_vertices, &indices)
+    }
+}
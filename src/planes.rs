@@ -0,0 +1,295 @@
+use crate::math::{cross, sub};
+use crate::{MeshDecodeResult, PointCloudDecodeResult};
+
+/// Probability of drawing at least one sample of inliers only, at which
+/// RANSAC stops early.
+const CONFIDENCE: f64 = 0.99;
+
+/// Parameters of [`PointCloudDecodeResult::detect_planes`] and
+/// [`MeshDecodeResult::detect_planes`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlaneDetectionParams {
+    distance_threshold: f32,
+    min_inliers: usize,
+    max_planes: usize,
+    iterations: u32,
+    seed: u64,
+}
+
+impl Default for PlaneDetectionParams {
+    fn default() -> Self {
+        Self {
+            distance_threshold: 0.01,
+            min_inliers: 100,
+            max_planes: 8,
+            iterations: 1000,
+            seed: 0x5eed,
+        }
+    }
+}
+
+impl PlaneDetectionParams {
+    /// Creates parameters for up to 8 planes of at least 100 points within
+    /// 0.01 units.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the largest distance of an inlier from its plane, in the units
+    /// of the positions.
+    pub fn with_distance_threshold(mut self, distance_threshold: f32) -> Self {
+        self.distance_threshold = distance_threshold;
+        self
+    }
+
+    /// Sets the number of inliers below which a plane is not reported.
+    pub fn with_min_inliers(mut self, min_inliers: usize) -> Self {
+        self.min_inliers = min_inliers;
+        self
+    }
+
+    /// Sets the largest number of planes to detect.
+    pub fn with_max_planes(mut self, max_planes: usize) -> Self {
+        self.max_planes = max_planes;
+        self
+    }
+
+    /// Sets the largest number of candidate planes sampled per detected
+    /// plane.
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets the seed of the sampling, the same seed detecting the same
+    /// planes.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Returns the inlier distance threshold.
+    pub fn distance_threshold(&self) -> f32 {
+        self.distance_threshold
+    }
+
+    /// Returns the smallest number of inliers of a plane.
+    pub fn min_inliers(&self) -> usize {
+        self.min_inliers
+    }
+
+    /// Returns the largest number of planes.
+    pub fn max_planes(&self) -> usize {
+        self.max_planes
+    }
+
+    /// Returns the number of sampled candidates per plane.
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Returns the sampling seed.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+/// A plane found by [`PointCloudDecodeResult::detect_planes`] or
+/// [`MeshDecodeResult::detect_planes`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectedPlane {
+    /// Coefficients `[a, b, c, d]` of the plane `ax + by + cz + d = 0`, with
+    /// `[a, b, c]` a unit normal.
+    pub equation: [f32; 4],
+    /// Indices of the points or vertices within the distance threshold, in
+    /// ascending order.
+    pub inliers: Vec<u32>,
+}
+
+impl DetectedPlane {
+    /// Returns the signed distance of a point from the plane.
+    pub fn distance(&self, point: [f32; 3]) -> f32 {
+        distance(self.equation, point)
+    }
+}
+
+fn distance([a, b, c, d]: [f32; 4], point: [f32; 3]) -> f32 {
+    a * point[0] + b * point[1] + c * point[2] + d
+}
+
+/// The xorshift64* generator, good enough to draw samples.
+struct Random(u64);
+
+impl Random {
+    fn below(&mut self, bound: usize) -> usize {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        (self.0.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 32) as usize % bound
+    }
+}
+
+/// Returns the plane through a point with the given normal, `None` for a
+/// degenerate normal.
+fn plane(point: [f32; 3], normal: [f32; 3]) -> Option<[f32; 4]> {
+    let length = (normal[0] * normal[0] + normal[1] * normal[1] + normal[2] * normal[2]).sqrt();
+    if length == 0.0 || !length.is_finite() {
+        return None;
+    }
+    let [a, b, c] = normal.map(|c| c / length);
+    Some([a, b, c, -(a * point[0] + b * point[1] + c * point[2])])
+}
+
+/// Least squares plane through points, the normal being the direction of
+/// least variance.
+fn fit_plane(points: &[[f32; 3]], indices: &[u32]) -> Option<[f32; 4]> {
+    let count = indices.len() as f64;
+    let mut centroid = [0.0f64; 3];
+    for &index in indices {
+        for axis in 0..3 {
+            centroid[axis] += points[index as usize][axis] as f64 / count;
+        }
+    }
+
+    let (mut xx, mut xy, mut xz, mut yy, mut yz, mut zz) = (0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+    for &index in indices {
+        let [x, y, z] =
+            std::array::from_fn(|axis| points[index as usize][axis] as f64 - centroid[axis]);
+        xx += x * x;
+        xy += x * y;
+        xz += x * z;
+        yy += y * y;
+        yz += y * z;
+        zz += z * z;
+    }
+
+    // Solve for the normal with the largest of its components fixed to 1
+    let determinants = [yy * zz - yz * yz, xx * zz - xz * xz, xx * yy - xy * xy];
+    let normal = match determinants
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))?
+    {
+        (0, &det) => [det, xz * yz - xy * zz, xy * yz - xz * yy],
+        (1, &det) => [xz * yz - xy * zz, det, xy * xz - yz * xx],
+        (_, &det) => [xy * yz - xz * yy, xy * xz - yz * xx, det],
+    };
+    plane(centroid.map(|c| c as f32), normal.map(|c| c as f32))
+}
+
+/// Detects planes in `points` one after another, each from the points no
+/// earlier plane claimed.
+fn detect_planes(points: &[[f32; 3]], params: &PlaneDetectionParams) -> Vec<DetectedPlane> {
+    let threshold = params.distance_threshold;
+    let inliers_of = |equation: [f32; 4], candidates: &[u32]| -> Vec<u32> {
+        candidates
+            .iter()
+            .copied()
+            .filter(|&index| distance(equation, points[index as usize]).abs() <= threshold)
+            .collect()
+    };
+
+    let mut random = Random(params.seed | 1);
+    let mut remaining: Vec<u32> = (0..points.len() as u32).collect();
+    let mut planes = Vec::new();
+    while planes.len() < params.max_planes && remaining.len() >= params.min_inliers.max(3) {
+        let mut best: Option<([f32; 4], usize)> = None;
+        let mut iterations = params.iterations as u64;
+        let mut iteration = 0;
+        while iteration < iterations {
+            iteration += 1;
+            let sample = [(); 3].map(|_| points[remaining[random.below(remaining.len())] as usize]);
+            let Some(equation) = plane(
+                sample[0],
+                cross(sub(sample[1], sample[0]), sub(sample[2], sample[0])),
+            ) else {
+                continue;
+            };
+
+            let count = inliers_of(equation, &remaining).len();
+            if best.is_none_or(|(_, best_count)| count > best_count) {
+                best = Some((equation, count));
+                // Stop once another sample is unlikely to find more inliers
+                let ratio = count as f64 / remaining.len() as f64;
+                let needed = (1.0 - CONFIDENCE).ln() / (1.0 - ratio.powi(3)).ln();
+                if needed.is_finite() {
+                    iterations = iterations.min(needed.ceil().max(1.0) as u64);
+                }
+            }
+        }
+
+        let Some((equation, count)) = best else {
+            break;
+        };
+        if count < params.min_inliers.max(3) {
+            break;
+        }
+
+        // Refine on the inliers, keeping the fit if it does not lose any
+        let mut plane = DetectedPlane {
+            equation,
+            inliers: inliers_of(equation, &remaining),
+        };
+        if let Some(refined) = fit_plane(points, &plane.inliers) {
+            let inliers = inliers_of(refined, &remaining);
+            if inliers.len() >= plane.inliers.len() {
+                plane = DetectedPlane {
+                    equation: refined,
+                    inliers,
+                };
+            }
+        }
+        remaining.retain(|index| plane.inliers.binary_search(index).is_err());
+        planes.push(plane);
+    }
+    planes
+}
+
+impl PointCloudDecodeResult {
+    /// Detects planes such as the walls and floors of a scanned building
+    /// with RANSAC.
+    ///
+    /// Planes are found one after another, each the best supported among the
+    /// points no earlier plane claimed, so every point is an inlier of at
+    /// most one plane. Candidate planes through random samples of three
+    /// points are scored by their inliers, and the best one is refined by a
+    /// least squares fit. Detection stops at
+    /// [`PlaneDetectionParams::max_planes`] or when no plane has
+    /// [`PlaneDetectionParams::min_inliers`] inliers. Results are
+    /// deterministic for a given [`PlaneDetectionParams::seed`].
+    ///
+    /// Returns no planes for point clouds without three component positions.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use draco_decoder::PlaneDetectionParams;
+    ///
+    /// let params = PlaneDetectionParams::new().with_distance_threshold(0.02);
+    /// for plane in scan.detect_planes(&params) {
+    ///     println!("{:?} with {} points", plane.equation, plane.inliers.len());
+    /// }
+    /// ```
+    pub fn detect_planes(&self, params: &PlaneDetectionParams) -> Vec<DetectedPlane> {
+        let points: Vec<[f32; 3]> = self
+            .positions()
+            .map(|positions| positions.collect())
+            .unwrap_or_default();
+        detect_planes(&points, params)
+    }
+}
+
+impl MeshDecodeResult {
+    /// Detects planes among the vertices of the mesh with RANSAC.
+    ///
+    /// The inliers are vertex indices. See
+    /// [`PointCloudDecodeResult::detect_planes`].
+    pub fn detect_planes(&self, params: &PlaneDetectionParams) -> Vec<DetectedPlane> {
+        let points: Vec<[f32; 3]> = self
+            .positions()
+            .map(|positions| positions.collect())
+            .unwrap_or_default();
+        detect_planes(&points, params)
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{
+    AttributeComponent, AttributeSemantic, AttributeValues, AttributeView, Crs, DecodeWarning,
+    MeshAttribute,
+};
+
+/// Configuration and metadata for a decoded Draco point cloud.
+///
+/// The decoded buffer contains each attribute tightly packed in order of its
+/// unique id, starting at offset 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PointCloudDecodeConfig {
+    point_count: u32,
+    buffer_size: usize,
+    attributes: Vec<MeshAttribute>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    warnings: Vec<DecodeWarning>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    crs: Option<Crs>,
+}
+
+impl PointCloudDecodeConfig {
+    /// Creates a new config with a pre-computed buffer size.
+    ///
+    /// Used internally when decoding from C++ FFI.
+    pub(crate) fn new(point_count: u32, buffer_size: usize) -> Self {
+        Self {
+            point_count,
+            buffer_size,
+            attributes: Vec::new(),
+            warnings: Vec::new(),
+            crs: None,
+        }
+    }
+
+    /// Adds an attribute with specified offset and length.
+    pub(crate) fn add_attribute(&mut self, attribute: MeshAttribute) {
+        self.attributes.push(attribute);
+    }
+
+    /// Returns the number of points.
+    pub fn point_count(&self) -> u32 {
+        self.point_count
+    }
+
+    /// Returns the total buffer size required for the decoded point cloud.
+    pub fn buffer_size(&self) -> usize {
+        self.buffer_size
+    }
+
+    /// Returns the attribute at the given index, if it exists.
+    pub fn get_attribute(&self, index: usize) -> Option<&MeshAttribute> {
+        self.attributes.get(index)
+    }
+
+    /// Returns the index of the first attribute with the given semantic.
+    pub fn find_attribute(&self, semantic: AttributeSemantic) -> Option<usize> {
+        self.attributes
+            .iter()
+            .position(|a| a.semantic() == semantic)
+    }
+
+    /// Returns a vector of all attributes.
+    pub fn attributes(&self) -> Vec<MeshAttribute> {
+        self.attributes.clone()
+    }
+
+    /// Returns `true` if every attribute lies within the buffer and none of
+    /// them overlap.
+    pub(crate) fn has_valid_layout(&self) -> bool {
+        crate::utils::is_valid_layout(
+            self.attributes
+                .iter()
+                .map(|attribute| (attribute.offset(), attribute.lenght())),
+            self.buffer_size,
+        )
+    }
+
+    /// Records an attribute that was skipped or converted while decoding.
+    pub(crate) fn add_warning(&mut self, warning: DecodeWarning) {
+        self.warnings.push(warning);
+    }
+
+    /// Returns the attributes that were skipped or converted while decoding.
+    pub fn warnings(&self) -> &[DecodeWarning] {
+        &self.warnings
+    }
+
+    /// Sets the coordinate reference system of the positions.
+    pub(crate) fn set_crs(&mut self, crs: Option<Crs>) {
+        self.crs = crs;
+    }
+
+    /// Copies the warnings and coordinate reference system of `source`, for
+    /// results derived from another one.
+    pub(crate) fn inherit(&mut self, source: &Self) {
+        self.warnings.extend_from_slice(&source.warnings);
+        self.crs = source.crs;
+    }
+
+    /// Returns the coordinate reference system of the positions, read from
+    /// the geometry metadata or attached with
+    /// [`PointCloudDecodeResult::with_crs`].
+    pub fn crs(&self) -> Option<Crs> {
+        self.crs
+    }
+}
+
+/// Result of decoding a Draco point cloud.
+///
+/// Contains the decoded attribute buffer and metadata describing its layout.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PointCloudDecodeResult {
+    /// The decoded buffer containing attribute data.
+    pub data: Vec<u8>,
+    /// Metadata describing the point count and attribute layouts.
+    pub config: PointCloudDecodeConfig,
+}
+
+impl PointCloudDecodeResult {
+    /// Returns the raw bytes of the attribute at the given index.
+    ///
+    /// Returns `None` if the attribute does not exist or its byte range lies
+    /// outside of the decoded buffer.
+    pub fn attribute_bytes(&self, index: usize) -> Option<&[u8]> {
+        self.config.get_attribute(index)?.bytes_in(&self.data)
+    }
+
+    /// Keeps only the attributes for which `keep` returns `true`.
+    ///
+    /// See [`MeshDecodeResult::retain_attributes`](crate::MeshDecodeResult::retain_attributes).
+    pub fn retain_attributes(&self, mut keep: impl FnMut(&MeshAttribute) -> bool) -> Self {
+        let mut config = PointCloudDecodeConfig::new(self.config.point_count(), 0);
+        let mut data = Vec::new();
+        for attribute in self.config.attributes.iter().filter(|a| keep(a)) {
+            let Some(bytes) = attribute.bytes_in(&self.data) else {
+                continue;
+            };
+            config.add_attribute(attribute.with_layout(data.len() as u32, bytes.len() as u32));
+            data.extend_from_slice(bytes);
+        }
+        config.buffer_size = data.len();
+        config.inherit(&self.config);
+        Self { data, config }
+    }
+
+    /// Returns a typed view of the attribute at the given index.
+    ///
+    /// See [`MeshDecodeResult::view`](crate::MeshDecodeResult::view).
+    pub fn view<T: AttributeComponent>(&self, index: usize) -> Option<AttributeView<'_, T>> {
+        self.config.get_attribute(index)?.view_in(&self.data)
+    }
+
+    /// Returns the typed values of the attribute at the given index.
+    ///
+    /// See [`MeshDecodeResult::attribute_values`](crate::MeshDecodeResult::attribute_values).
+    pub fn attribute_values(&self, index: usize) -> Option<AttributeValues> {
+        self.config.get_attribute(index)?.values_in(&self.data)
+    }
+}
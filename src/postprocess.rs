@@ -0,0 +1,338 @@
+//! Passes that clean up or complete a decoded mesh in place.
+//!
+//! Draco quantization snaps nearby positions together, which collapses thin
+//! triangles and leaves meshes that renderers and mesh processing handle
+//! poorly. The functions here fix such meshes after decoding without
+//! rewriting the layout of the decoded buffer more than needed.
+//!
+//! Many assets are also exported with positions only, so the attributes a
+//! renderer needs can be generated here.
+//!
+//! ```ignore
+//! use draco_decoder::postprocess;
+//!
+//! let mut mesh = decode_mesh_with_config(&data).await.unwrap();
+//! let removed = postprocess::remove_degenerate_triangles(&mut mesh);
+//! postprocess::generate_smooth_normals(&mut mesh, 60f32.to_radians());
+//! let tangents = postprocess::generate_tangents(&mut mesh);
+//! ```
+
+use std::collections::HashMap;
+
+use crate::math::{self, cross, dot, sub};
+use crate::{AttributeDataType, AttributeSemantic, MeshAttribute, MeshDecodeResult};
+
+/// Scales `a` to unit length, zero if it has no length.
+fn normalize(a: [f32; 3]) -> [f32; 3] {
+    math::normalize(a).unwrap_or([0.0; 3])
+}
+
+/// Removes the degenerate triangles of `result` and returns how many were
+/// removed.
+///
+/// A triangle is degenerate if it repeats a corner, references a vertex
+/// outside the mesh, or, for meshes with `Float32` positions, has collinear
+/// corners as decided by
+/// [`MeshDecodeResult::degenerate_triangles`]. Meshes without `Float32`
+/// positions only lose triangles of the first two kinds.
+///
+/// The remaining indices are moved to the start of the index section and
+/// the index count and length are updated. Attributes keep their offsets, so
+/// the freed index bytes are left as zeroed padding in front of the first
+/// attribute.
+pub fn remove_degenerate_triangles(result: &mut MeshDecodeResult) -> usize {
+    let vertex_count = result.config.vertex_count();
+    let degenerate = result.degenerate_triangles().unwrap_or_else(|| {
+        result
+            .triangles()
+            .enumerate()
+            .filter(|(_, [a, b, c])| {
+                a == b || b == c || c == a || [a, b, c].iter().any(|&&v| v >= vertex_count)
+            })
+            .map(|(index, _)| index)
+            .collect()
+    });
+    if degenerate.is_empty() {
+        return 0;
+    }
+
+    let index_size = result.config.index_size_in_bytes();
+    let triangle_size = 3 * index_size;
+    let index_length = result.config.index_length() as usize;
+    let Some(indices) = result.data.get_mut(..index_length) else {
+        return 0;
+    };
+
+    let mut degenerate = degenerate.into_iter().peekable();
+    let mut kept = 0;
+    for triangle in 0..index_length / triangle_size {
+        if degenerate.next_if_eq(&triangle).is_some() {
+            continue;
+        }
+        let start = triangle * triangle_size;
+        indices.copy_within(start..start + triangle_size, kept * triangle_size);
+        kept += 1;
+    }
+    indices[kept * triangle_size..].fill(0);
+
+    let removed = index_length / triangle_size - kept;
+    result.config.set_index_count(kept as u32 * 3);
+    removed
+}
+
+/// Returns a unique id not used by any attribute of `result`.
+fn next_unique_id(result: &MeshDecodeResult) -> u32 {
+    result
+        .config
+        .attributes()
+        .iter()
+        .map(|attribute| attribute.unique_id() + 1)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Adds smooth vertex normals to a mesh without normals and returns `true`
+/// if they were added.
+///
+/// The normal of a vertex is the area-weighted average of the normals of
+/// the triangles around its position. Draco splits vertices along texture
+/// and normal seams, so the triangles around vertices with the same
+/// position are averaged too, unless their normal differs by more than
+/// `angle_threshold` radians from every triangle using the vertex itself.
+/// Hard edges whose vertices are split therefore stay sharp, while seams
+/// in the texture coordinates do not show. Vertices shared by the triangles
+/// on both sides of an edge always get the averaged normal, since no
+/// vertices are added.
+///
+/// The normals are appended to the end of the decoded buffer as a `Float32`
+/// [`AttributeSemantic::Normal`] attribute, at an offset aligned to 4 bytes.
+/// Vertices without triangles get a zero normal. Returns `false`, leaving
+/// `result` unchanged, if it already has a normal attribute or has no three
+/// component positions.
+pub fn generate_smooth_normals(result: &mut MeshDecodeResult, angle_threshold: f32) -> bool {
+    if result
+        .config
+        .find_attribute(AttributeSemantic::Normal)
+        .is_some()
+    {
+        return false;
+    }
+    let Some(positions) = result.positions::<[f32; 3]>() else {
+        return false;
+    };
+    let positions: Vec<[f32; 3]> = positions.collect();
+    let vertex_count = result.config.vertex_count();
+
+    // Area-weighted normal of every triangle with valid corners
+    let triangles: Vec<([u32; 3], [f32; 3])> = result
+        .triangles()
+        .filter(|triangle| triangle.iter().all(|&vertex| vertex < vertex_count))
+        .filter_map(|triangle| {
+            let [a, b, c] = triangle.map(|vertex| positions.get(vertex as usize).copied());
+            let (a, b, c) = (a?, b?, c?);
+            Some((triangle, cross(sub(b, a), sub(c, a))))
+        })
+        .collect();
+    let unit: Vec<[f32; 3]> = triangles
+        .iter()
+        .map(|&(_, normal)| normalize(normal))
+        .collect();
+
+    let welded = result.welded_vertices();
+    let position_of = |vertex: u32| welded.get(vertex as usize).copied().unwrap_or(vertex);
+    let mut around_vertex: HashMap<u32, Vec<usize>> = HashMap::new();
+    let mut around_position: HashMap<u32, Vec<usize>> = HashMap::new();
+    for (face, (triangle, _)) in triangles.iter().enumerate() {
+        for &vertex in triangle {
+            around_vertex.entry(vertex).or_default().push(face);
+            around_position
+                .entry(position_of(vertex))
+                .or_default()
+                .push(face);
+        }
+    }
+
+    let min_cos = angle_threshold.cos();
+    let mut bytes = Vec::with_capacity(vertex_count as usize * 12);
+    for vertex in 0..vertex_count {
+        let own = around_vertex.get(&vertex).map_or(&[][..], Vec::as_slice);
+        let shared = around_position
+            .get(&position_of(vertex))
+            .map_or(&[][..], Vec::as_slice);
+        let mut sum = [0.0; 3];
+        let mut previous = None;
+        for &face in shared {
+            // A triangle using the vertex twice is only counted once
+            if previous == Some(face) {
+                continue;
+            }
+            previous = Some(face);
+            let smooth = own
+                .iter()
+                .any(|&other| dot(unit[face], unit[other]) >= min_cos);
+            if smooth {
+                let normal = triangles[face].1;
+                sum = std::array::from_fn(|axis| sum[axis] + normal[axis]);
+            }
+        }
+        for value in normalize(sum) {
+            bytes.extend_from_slice(&value.to_ne_bytes());
+        }
+    }
+
+    result.append_attribute(
+        MeshAttribute::new(3, AttributeDataType::Float32, 0, 0)
+            .with_semantic(AttributeSemantic::Normal)
+            .with_unique_id(next_unique_id(result)),
+        &bytes,
+    );
+    true
+}
+
+/// The corners of a mesh as seen by MikkTSpace, collecting a tangent per
+/// corner.
+#[cfg(feature = "mikktspace")]
+struct TangentGeometry<'a> {
+    triangles: &'a [[u32; 3]],
+    positions: &'a [[f32; 3]],
+    normals: &'a [[f32; 3]],
+    uvs: &'a [[f32; 2]],
+    tangents: Vec<[f32; 4]>,
+}
+
+#[cfg(feature = "mikktspace")]
+impl bevy_mikktspace::Geometry for TangentGeometry<'_> {
+    fn num_faces(&self) -> usize {
+        self.triangles.len()
+    }
+
+    fn num_vertices_of_face(&self, _face: usize) -> usize {
+        3
+    }
+
+    fn position(&self, face: usize, vert: usize) -> [f32; 3] {
+        self.positions[self.triangles[face][vert] as usize]
+    }
+
+    fn normal(&self, face: usize, vert: usize) -> [f32; 3] {
+        self.normals[self.triangles[face][vert] as usize]
+    }
+
+    fn tex_coord(&self, face: usize, vert: usize) -> [f32; 2] {
+        self.uvs[self.triangles[face][vert] as usize]
+    }
+
+    fn set_tangent_encoded(&mut self, tangent: [f32; 4], face: usize, vert: usize) {
+        self.tangents[face * 3 + vert] = tangent;
+    }
+}
+
+/// Adds MikkTSpace tangents for normal mapping and returns the index of the
+/// new attribute.
+///
+/// The tangents are computed from the positions, normals and first texture
+/// coordinate set by the reference MikkTSpace algorithm, so they match
+/// normal maps baked by Blender, Substance and other MikkTSpace tools. The
+/// fourth component is the handedness as in glTF, so the bitangent is
+/// `w * cross(normal, tangent)`.
+///
+/// MikkTSpace computes a tangent per triangle corner. Vertices whose
+/// corners get different tangents, such as vertices shared by mirrored
+/// texture coordinates, are split: the vertex keeps the tangent of its
+/// first corner and a copy with all of its attributes is appended for
+/// every other tangent, and the indices are rewritten to use the copies.
+/// Meshes without such vertices keep their layout. Triangles referencing
+/// missing vertices are dropped, and vertices without triangles get an
+/// arbitrary tangent perpendicular to their normal.
+///
+/// Draco has no tangent semantic, so the tangents are appended to the end
+/// of the decoded buffer as a four component `Float32`
+/// [`AttributeSemantic::Generic`] attribute named `TANGENT`, see
+/// [`DracoDecodeConfig::attribute_name`](crate::DracoDecodeConfig::attribute_name),
+/// at an offset aligned to 4 bytes. Returns `None`, leaving `result`
+/// unchanged, if the mesh has no triangles, no three component positions,
+/// no normals or no two component texture coordinates;
+/// [`generate_smooth_normals`] adds the normals first.
+///
+/// Requires the `mikktspace` feature.
+#[cfg(feature = "mikktspace")]
+pub fn generate_tangents(result: &mut MeshDecodeResult) -> Option<usize> {
+    let positions: Vec<[f32; 3]> = result.positions()?.collect();
+    let normals: Vec<[f32; 3]> = result.normals()?.collect();
+    let uvs: Vec<[f32; 2]> = result.uvs(0)?.collect();
+    let vertex_count = (result.config.vertex_count() as usize)
+        .min(positions.len())
+        .min(normals.len())
+        .min(uvs.len());
+
+    let triangles: Vec<[u32; 3]> = result
+        .triangles()
+        .filter(|triangle| {
+            triangle
+                .iter()
+                .all(|&vertex| (vertex as usize) < vertex_count)
+        })
+        .collect();
+    let mut geometry = TangentGeometry {
+        triangles: &triangles,
+        positions: &positions,
+        normals: &normals,
+        uvs: &uvs,
+        tangents: vec![[0.0; 4]; triangles.len() * 3],
+    };
+    if triangles.is_empty() || !bevy_mikktspace::generate_tangents(&mut geometry) {
+        return None;
+    }
+
+    // The first tangent of every vertex, and a copy of the vertex for every
+    // other tangent its corners get
+    let mut tangents: Vec<Option<[f32; 4]>> = vec![None; vertex_count];
+    let mut copies: HashMap<(u32, [u32; 4]), u32> = HashMap::new();
+    let mut source_vertices: Vec<u32> = (0..vertex_count as u32).collect();
+    let mut indices = Vec::with_capacity(geometry.tangents.len());
+    for (&vertex, &tangent) in triangles.iter().flatten().zip(&geometry.tangents) {
+        let own = tangents[vertex as usize].get_or_insert(tangent);
+        if own.map(f32::to_bits) == tangent.map(f32::to_bits) {
+            indices.push(vertex);
+            continue;
+        }
+        let copy = *copies
+            .entry((vertex, tangent.map(f32::to_bits)))
+            .or_insert_with(|| {
+                source_vertices.push(vertex);
+                tangents.push(Some(tangent));
+                source_vertices.len() as u32 - 1
+            });
+        indices.push(copy);
+    }
+
+    let mut bytes = Vec::with_capacity(tangents.len() * 16);
+    for (vertex, tangent) in tangents.iter().enumerate() {
+        let tangent = tangent.unwrap_or_else(|| {
+            // Any direction in the tangent plane, away from the normal
+            let normal = normalize(normals[vertex]);
+            let axis = if normal[0].abs() < 0.9 {
+                [1.0, 0.0, 0.0]
+            } else {
+                [0.0, 1.0, 0.0]
+            };
+            let [x, y, z] = normalize(cross(axis, normal));
+            [x, y, z, 1.0]
+        });
+        for value in tangent {
+            bytes.extend_from_slice(&value.to_ne_bytes());
+        }
+    }
+
+    let vertices_changed = source_vertices.len() != result.config.vertex_count() as usize;
+    if vertices_changed || indices.len() != result.config.index_count() as usize {
+        *result = result.gather(&source_vertices, &indices);
+    }
+    let unique_id = next_unique_id(result);
+    result.append_attribute(
+        MeshAttribute::new(4, AttributeDataType::Float32, 0, 0).with_unique_id(unique_id),
+        &bytes,
+    );
+    result.config.set_attribute_name(unique_id, "TANGENT");
+    Some(result.config.attributes().len() - 1)
+}
@@ -0,0 +1,16 @@
+use robust::Coord;
+
+/// Returns `true` if the three points lie exactly on a line, using exact
+/// orientation predicates so the verdict does not depend on rounding.
+///
+/// A triangle is degenerate in 3D exactly when its projections onto the
+/// three axis planes are all degenerate.
+pub(crate) fn is_degenerate(a: [f32; 3], b: [f32; 3], c: [f32; 3]) -> bool {
+    [(0, 1), (1, 2), (2, 0)].into_iter().all(|(u, v)| {
+        let coord = |point: [f32; 3]| Coord {
+            x: point[u],
+            y: point[v],
+        };
+        robust::orient2d(coord(a), coord(b), coord(c)) == 0.0
+    })
+}
@@ -0,0 +1,62 @@
+use crate::{DracoDecodeConfig, MeshDecodeResult, MeshHandle};
+
+/// A mesh whose decode stopped before the output buffer was allocated,
+/// returned by [`prefetch_config`] (native only).
+///
+/// The Draco mesh is held by the native library until [`PrefetchedMesh::decode`]
+/// fills the buffer or the value is dropped.
+pub struct PrefetchedMesh {
+    handle: MeshHandle,
+    config: DracoDecodeConfig,
+}
+
+impl PrefetchedMesh {
+    /// Returns the layout of the decoded buffer.
+    pub fn config(&self) -> &DracoDecodeConfig {
+        &self.config
+    }
+
+    /// Allocates the decoded buffer and fills it, the only stage left.
+    ///
+    /// Returns `None` if the mesh does not fit into the buffer.
+    pub fn decode(self) -> Option<MeshDecodeResult> {
+        let mut buffer = vec![0u8; self.config.buffer_size()];
+        let written = self.handle.decode_into(&mut buffer)?;
+        buffer.truncate(written);
+        Some(MeshDecodeResult {
+            data: buffer,
+            config: self.config,
+        })
+    }
+}
+
+/// Decodes a mesh up to the layout of the decoded buffer, deferring the
+/// allocation and filling of the buffer (native only).
+///
+/// Two-phase loaders first budget memory for a batch of meshes and decode
+/// them afterwards. This runs the Draco decode and computes the layout with
+/// the default options, which is most of the work of a decode, and returns
+/// both in a [`PrefetchedMesh`]. Its [`PrefetchedMesh::config`] sizes the
+/// budget and [`PrefetchedMesh::decode`] then only fills the buffer. Nothing
+/// is cached: dropping the prefetched mesh frees it, and other decodes of
+/// the same bytes are unaffected.
+///
+/// Returns `None` if the data is not a valid Draco mesh.
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::prefetch_config;
+///
+/// let prefetched: Vec<_> = tiles.iter().filter_map(|tile| prefetch_config(tile)).collect();
+/// let budget: usize = prefetched.iter().map(|mesh| mesh.config().buffer_size()).sum();
+/// for mesh in prefetched {
+///     let mesh = mesh.decode();
+/// }
+/// ```
+pub fn prefetch_config(data: &[u8]) -> Option<PrefetchedMesh> {
+    let handle = MeshHandle::decode(data)?;
+    let mut config = handle.config()?;
+    config.set_crs(crate::crs_from_metadata(data));
+    Some(PrefetchedMesh { handle, config })
+}
@@ -0,0 +1,21 @@
+//! The types and functions most applications need, for a single glob import.
+//!
+//! ```ignore
+//! use draco_decoder::prelude::*;
+//!
+//! let geometry = load(&bytes, &LoadOptions::new()).await?;
+//! ```
+
+#[cfg(feature = "tiles3d")]
+pub use crate::PntsTile;
+pub use crate::{
+    AttributeDataType, AttributeSemantic, CancellationToken, DecodeOptions, DracoDecodeConfig,
+    Geometry, IndexFormat, LoadError, LoadOptions, LoadPreset, LoadSource, MeshAttribute,
+    MeshDecodeResult, PointCloudDecodeConfig, PointCloudDecodeResult, SourceFormat,
+    decode_mesh_with_config, decode_mesh_with_options, decode_point_cloud_with_config, load,
+    sniff_format,
+};
+#[cfg(feature = "gltf")]
+pub use crate::{DecodedGltf, DecodedPrimitive};
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::{decode_mesh_with_options_sync, decode_point_cloud_with_config_sync, load_sync};
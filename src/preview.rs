@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use crate::{
+    AttributeDataType, AttributeSemantic, DecodeWarning, MeshDecodeResult, WarningAction,
+    WarningReason,
+};
+
+impl MeshDecodeResult {
+    /// Drops every attribute except the positions.
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+    pub(crate) fn into_positions_only(self) -> MeshDecodeResult {
+        let Some(position) = self.config.find_attribute(AttributeSemantic::Position) else {
+            return self;
+        };
+        if self.config.attributes().len() == 1 {
+            return self;
+        }
+
+        let indices: Vec<u32> = self.triangles().flatten().collect();
+        let attribute = *self.config.get_attribute(position).unwrap();
+        let bytes = self.attribute_bytes(position).unwrap_or_default().to_vec();
+
+        let mut result = MeshDecodeResult::from_parts(
+            self.config.vertex_count(),
+            &indices,
+            vec![(attribute, bytes)],
+        );
+        result.config.inherit(&self.config);
+        for (index, filtered) in self.config.attributes().iter().enumerate() {
+            if index != position {
+                result.config.add_warning(DecodeWarning {
+                    unique_id: filtered.unique_id(),
+                    semantic: filtered.semantic(),
+                    attribute_index: None,
+                    reason: WarningReason::Filtered,
+                    action: WarningAction::Skipped,
+                });
+            }
+        }
+        result
+    }
+
+    /// Reduces the mesh to at most `max_vertices` vertices by vertex
+    /// clustering.
+    ///
+    /// Vertices are snapped to a uniform grid over the bounding box with at
+    /// most `max_vertices` cells, each cell is replaced by the average of its
+    /// vertices and triangles that collapse are dropped. Only the position
+    /// attribute is kept. Meshes that already fit the budget, or without
+    /// `Float32` positions, are returned unchanged.
+    pub(crate) fn decimate(self, max_vertices: u32) -> MeshDecodeResult {
+        let vertex_count = self.config.vertex_count();
+        if vertex_count <= max_vertices {
+            return self;
+        }
+
+        let Some(position) = self.config.find_attribute(AttributeSemantic::Position) else {
+            return self;
+        };
+        let attribute = *self.config.get_attribute(position).unwrap();
+        if attribute.data_type() != AttributeDataType::Float32 || attribute.dim() < 3 {
+            return self;
+        }
+        let Some(positions) = self.view::<f32>(position) else {
+            return self;
+        };
+
+        let mut min = [f32::INFINITY; 3];
+        let mut max = [f32::NEG_INFINITY; 3];
+        for vertex in positions.vertices() {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(vertex[axis]);
+                max[axis] = max[axis].max(vertex[axis]);
+            }
+        }
+
+        // res^3 <= max_vertices bounds the number of occupied cells
+        let resolution = ((max_vertices as f64).cbrt().floor() as u64).max(1);
+        let cell_of = |value: f32, axis: usize| -> u64 {
+            let extent = max[axis] - min[axis];
+            if extent > 0.0 {
+                (((value - min[axis]) / extent * resolution as f32) as u64).min(resolution - 1)
+            } else {
+                0
+            }
+        };
+
+        let mut cells: HashMap<u64, u32> = HashMap::new();
+        let mut sums: Vec<[f64; 4]> = Vec::new();
+        let mut remap = Vec::with_capacity(vertex_count as usize);
+
+        for vertex in positions.vertices() {
+            let key = cell_of(vertex[0], 0)
+                + resolution * (cell_of(vertex[1], 1) + resolution * cell_of(vertex[2], 2));
+            let cluster = *cells.entry(key).or_insert_with(|| {
+                sums.push([0.0; 4]);
+                sums.len() as u32 - 1
+            });
+
+            let sum = &mut sums[cluster as usize];
+            for axis in 0..3 {
+                sum[axis] += vertex[axis] as f64;
+            }
+            sum[3] += 1.0;
+            remap.push(cluster);
+        }
+
+        let indices: Vec<u32> = self
+            .triangles()
+            .filter(|triangle| triangle.iter().all(|&vertex| vertex < vertex_count))
+            .map(|triangle| triangle.map(|vertex| remap[vertex as usize]))
+            .filter(|[a, b, c]| a != b && b != c && a != c)
+            .flatten()
+            .collect();
+
+        let clustered: Vec<f32> = sums
+            .iter()
+            .flat_map(|sum| (0..3).map(|axis| (sum[axis] / sum[3]) as f32))
+            .collect();
+
+        let attribute = crate::MeshAttribute::new(3, AttributeDataType::Float32, 0, 0)
+            .with_semantic(AttributeSemantic::Position)
+            .with_unique_id(attribute.unique_id());
+
+        let mut result = MeshDecodeResult::from_parts(
+            sums.len() as u32,
+            &indices,
+            vec![(attribute, bytemuck::cast_slice(&clustered).to_vec())],
+        );
+        result.config.inherit(&self.config);
+        result
+    }
+}
@@ -0,0 +1,78 @@
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+
+/// A step of the decode pipeline, reported to the callback set with
+/// [`DecodeOptions::with_progress`](crate::DecodeOptions::with_progress).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DecodeStage {
+    /// The Draco header was parsed.
+    HeaderParsed,
+    /// Draco decompressed the connectivity of the mesh. Point clouds have no
+    /// connectivity and skip this stage.
+    ConnectivityDecoded,
+    /// Draco decompressed the values of the attributes read by the
+    /// attributes decoder with this index. Bitstreams usually have one
+    /// decoder per attribute, and decompression takes most of the decode.
+    AttributesDecoded(usize),
+    /// The indices were written to the decoded buffer. Point clouds have no
+    /// indices and skip this stage.
+    IndicesWritten,
+    /// The attribute with this index in
+    /// [`DracoDecodeConfig::attributes`](crate::DracoDecodeConfig::attributes)
+    /// was written to the decoded buffer.
+    AttributeExtracted(usize),
+}
+
+impl DecodeStage {
+    /// Returns the fraction of the decode completed once this stage is done.
+    ///
+    /// `count` is the number of attributes decoders for
+    /// [`DecodeStage::AttributesDecoded`] and the number of attributes for
+    /// [`DecodeStage::AttributeExtracted`]. The weights approximate the time
+    /// spent in each stage of a large mesh.
+    pub(crate) fn fraction(self, count: usize) -> f32 {
+        // Share of the stages with this index done out of `count`
+        let done = |index: usize| (index + 1).min(count.max(1)) as f32 / count.max(1) as f32;
+        match self {
+            DecodeStage::HeaderParsed => 0.05,
+            DecodeStage::ConnectivityDecoded => 0.35,
+            DecodeStage::AttributesDecoded(index) => 0.35 + 0.35 * done(index),
+            DecodeStage::IndicesWritten => 0.75,
+            DecodeStage::AttributeExtracted(index) => 0.75 + 0.25 * done(index),
+        }
+    }
+}
+
+/// A progress callback, see
+/// [`DecodeOptions::with_progress`](crate::DecodeOptions::with_progress).
+#[derive(Clone)]
+pub(crate) struct Progress(pub(crate) Arc<dyn Fn(DecodeStage, f32) + Send + Sync>);
+
+impl Progress {
+    /// Calls the callback for `stage`, see [`DecodeStage::fraction`] for
+    /// `count`.
+    ///
+    /// Reports are made from within the native decode, so a panic in the
+    /// callback is caught and only drops the report instead of unwinding
+    /// into the native library.
+    pub(crate) fn report(&self, stage: DecodeStage, count: usize) {
+        let fraction = stage.fraction(count);
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| (self.0)(stage, fraction)));
+    }
+}
+
+impl fmt::Debug for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Progress").finish_non_exhaustive()
+    }
+}
+
+/// Callbacks are equal if they are clones of each other.
+impl PartialEq for Progress {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Progress {}
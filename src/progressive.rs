@@ -0,0 +1,44 @@
+use crate::{AttributeSemantic, MeshDecodeResult};
+
+/// Maximum number of points in a [`PartialGeometry::Coarse`] sample.
+pub const COARSE_SAMPLE_POINTS: usize = 4096;
+
+/// A stage of a progressive decode, see [`decode_progressive`](crate::decode_progressive).
+#[derive(Debug, Clone)]
+pub enum PartialGeometry {
+    /// An evenly strided subset of the vertex positions, suitable for
+    /// drawing a point preview while the full mesh is being prepared.
+    Coarse {
+        /// Sampled positions as `x, y, z` triples.
+        positions: Vec<f32>,
+        /// Number of vertices of the full mesh.
+        vertex_count: u32,
+    },
+    /// The fully decoded mesh.
+    Full(MeshDecodeResult),
+}
+
+impl PartialGeometry {
+    /// Samples at most [`COARSE_SAMPLE_POINTS`] positions of `mesh`.
+    ///
+    /// Returns `None` if the mesh has no three component `Float32` positions.
+    pub(crate) fn coarse(mesh: &MeshDecodeResult) -> Option<Self> {
+        let position = mesh.config.find_attribute(AttributeSemantic::Position)?;
+        let view = mesh.view::<f32>(position)?;
+        if view.dim() < 3 {
+            return None;
+        }
+
+        let stride = view.vertex_count().div_ceil(COARSE_SAMPLE_POINTS).max(1);
+        let positions = view
+            .vertices()
+            .step_by(stride)
+            .flat_map(|vertex| vertex[..3].iter().copied())
+            .collect();
+
+        Some(PartialGeometry::Coarse {
+            positions,
+            vertex_count: mesh.config.vertex_count(),
+        })
+    }
+}
@@ -0,0 +1,100 @@
+use std::fmt;
+use std::sync::Arc;
+
+/// Whether a decode may allocate its output buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaDecision {
+    /// The decode continues.
+    Allow,
+    /// The decode is abandoned and returns `None`, or
+    /// [`DecodeError::QuotaDenied`](crate::DecodeError::QuotaDenied) from the
+    /// functions reporting errors.
+    Deny,
+}
+
+/// Enforces per-tenant budgets on decoding.
+///
+/// Services decoding meshes on behalf of several customers can attach a
+/// provider to [`DecodeOptions`](crate::DecodeOptions) with
+/// [`DecodeOptions::with_quota`](crate::DecodeOptions::with_quota). Each
+/// decode of a mesh or point cloud consults it twice: with
+/// [`QuotaProvider::check_decode`] before any decoding work, and with
+/// [`QuotaProvider::check`] once the layout of the decoded buffer is known
+/// and before the buffer is allocated. Counting `check_decode` calls gives a
+/// per-tenant decode budget, summing the sizes passed to `check` a memory
+/// budget.
+///
+/// # Example
+///
+/// ```ignore
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use draco_decoder::{DecodeOptions, QuotaDecision, QuotaProvider};
+///
+/// struct MemoryBudget {
+///     remaining: AtomicUsize,
+/// }
+///
+/// impl QuotaProvider for MemoryBudget {
+///     fn check(&self, _tenant_id: &str, bytes: usize) -> QuotaDecision {
+///         let update = |remaining: usize| remaining.checked_sub(bytes);
+///         match self.remaining.fetch_update(Ordering::Relaxed, Ordering::Relaxed, update) {
+///             Ok(_) => QuotaDecision::Allow,
+///             Err(_) => QuotaDecision::Deny,
+///         }
+///     }
+/// }
+///
+/// let budget = Arc::new(MemoryBudget { remaining: AtomicUsize::new(64 << 20) });
+/// let options = DecodeOptions::new().with_quota(budget, "customer-42");
+/// ```
+pub trait QuotaProvider: Send + Sync {
+    /// Decides whether `tenant_id` may allocate a decoded buffer of `bytes`
+    /// bytes.
+    fn check(&self, tenant_id: &str, bytes: usize) -> QuotaDecision;
+
+    /// Decides whether `tenant_id` may start decoding `encoded_bytes` bytes
+    /// of Draco data, before the CPU time of the decode is spent.
+    ///
+    /// Allows every decode by default.
+    fn check_decode(&self, tenant_id: &str, encoded_bytes: usize) -> QuotaDecision {
+        let _ = (tenant_id, encoded_bytes);
+        QuotaDecision::Allow
+    }
+}
+
+/// A quota provider and the tenant decodes are charged to.
+#[derive(Clone)]
+pub(crate) struct Quota {
+    pub(crate) provider: Arc<dyn QuotaProvider>,
+    pub(crate) tenant_id: String,
+}
+
+impl Quota {
+    /// Asks the provider whether a buffer of `bytes` bytes may be allocated.
+    pub(crate) fn allows(&self, bytes: usize) -> bool {
+        self.provider.check(&self.tenant_id, bytes) == QuotaDecision::Allow
+    }
+
+    /// Asks the provider whether `encoded_bytes` bytes may be decoded.
+    pub(crate) fn allows_decode(&self, encoded_bytes: usize) -> bool {
+        self.provider.check_decode(&self.tenant_id, encoded_bytes) == QuotaDecision::Allow
+    }
+}
+
+impl fmt::Debug for Quota {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Quota")
+            .field("tenant_id", &self.tenant_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Quotas are equal if they charge the same tenant of the same provider.
+impl PartialEq for Quota {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.provider, &other.provider) && self.tenant_id == other.tenant_id
+    }
+}
+
+impl Eq for Quota {}
@@ -0,0 +1,143 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::predicates::is_degenerate;
+use crate::{MeshAttribute, MeshDecodeResult};
+
+/// Triangulates a boundary loop as a fan from the vertex at `start`, wound
+/// against the loop like the triangles around it.
+fn fan(vertices: &[u32], start: usize) -> Vec<[u32; 3]> {
+    let vertex = |i: usize| vertices[(start + i) % vertices.len()];
+    (1..vertices.len() - 1)
+        .map(|i| [vertex(0), vertex(i + 1), vertex(i)])
+        .collect()
+}
+
+impl MeshDecodeResult {
+    /// Returns the boundary loops of the mesh, the outlines of its holes.
+    ///
+    /// Each loop lists its vertices in the direction of the boundary edges of
+    /// the adjacent triangles. Vertices with identical positions are treated
+    /// as one, so the seams Draco creates by splitting vertices with
+    /// different normals or texture coordinates are not reported. Chains of
+    /// boundary edges that do not close, which only occur around
+    /// non-manifold vertices, are left out.
+    pub fn boundary_loops(&self) -> Vec<Vec<u32>> {
+        let vertex_count = self.config.vertex_count();
+        let welded = self.welded_vertices();
+        let triangles: Vec<[u32; 3]> = self
+            .triangles()
+            .filter(|triangle| triangle.iter().all(|&vertex| vertex < vertex_count))
+            .collect();
+
+        let edges: HashSet<(u32, u32)> = triangles
+            .iter()
+            .flat_map(|&[a, b, c]| [(a, b), (b, c), (c, a)])
+            .map(|(a, b)| (welded[a as usize], welded[b as usize]))
+            .collect();
+
+        // (welded start, welded end, original start) of every boundary edge
+        let mut boundary: Vec<(u32, u32, u32)> = Vec::new();
+        let mut outgoing: HashMap<u32, Vec<usize>> = HashMap::new();
+        for &[a, b, c] in &triangles {
+            for (from, to) in [(a, b), (b, c), (c, a)] {
+                let (welded_from, welded_to) = (welded[from as usize], welded[to as usize]);
+                if welded_from != welded_to && !edges.contains(&(welded_to, welded_from)) {
+                    outgoing
+                        .entry(welded_from)
+                        .or_default()
+                        .push(boundary.len());
+                    boundary.push((welded_from, welded_to, from));
+                }
+            }
+        }
+
+        let mut used = vec![false; boundary.len()];
+        let mut loops = Vec::new();
+        for first in 0..boundary.len() {
+            if used[first] {
+                continue;
+            }
+
+            let mut vertices = Vec::new();
+            let mut edge = first;
+            loop {
+                used[edge] = true;
+                vertices.push(boundary[edge].2);
+                let to = boundary[edge].1;
+                if to == boundary[first].0 {
+                    loops.push(vertices);
+                    break;
+                }
+                let next = outgoing
+                    .get(&to)
+                    .and_then(|edges| edges.iter().copied().find(|&next| !used[next]));
+                match next {
+                    Some(next) => edge = next,
+                    None => break,
+                }
+            }
+        }
+        loops
+    }
+
+    /// Closes holes outlined by at most `max_boundary_edges` edges.
+    ///
+    /// Each boundary loop from [`MeshDecodeResult::boundary_loops`] that is
+    /// short enough is triangulated as a fan, wound like the surrounding
+    /// triangles. The fan starts at the first loop vertex that creates the
+    /// fewest degenerate triangles, decided with exact predicates, so
+    /// boundaries with collinear vertices are not filled with slivers where
+    /// it can be avoided. This is meant for the pinholes of scanned meshes;
+    /// large or strongly curved holes need a proper remeshing. No vertices
+    /// are added, the existing ones are reused.
+    ///
+    /// The indices and attributes are rewritten to a tightly packed buffer.
+    /// A mesh without such holes is returned unchanged.
+    pub fn fill_holes(self, max_boundary_edges: usize) -> MeshDecodeResult {
+        let loops: Vec<Vec<u32>> = self
+            .boundary_loops()
+            .into_iter()
+            .filter(|vertices| vertices.len() >= 3 && vertices.len() <= max_boundary_edges)
+            .collect();
+        if loops.is_empty() {
+            return self;
+        }
+
+        let points = self.float_positions();
+        let degenerate_count = |vertices: &[u32], start: usize| {
+            let Some(points) = &points else {
+                return 0;
+            };
+            fan(vertices, start)
+                .into_iter()
+                .filter(|triangle| {
+                    let [a, b, c] = triangle.map(|vertex| points.get(vertex as usize).copied());
+                    match (a, b, c) {
+                        (Some(a), Some(b), Some(c)) => is_degenerate(a, b, c),
+                        _ => false,
+                    }
+                })
+                .count()
+        };
+
+        let mut indices: Vec<u32> = self.triangles().flatten().collect();
+        for vertices in &loops {
+            let start = (0..vertices.len())
+                .min_by_key(|&start| degenerate_count(vertices, start))
+                .unwrap_or(0);
+            indices.extend(fan(vertices, start).into_iter().flatten());
+        }
+
+        let attributes: Vec<(MeshAttribute, Vec<u8>)> = (0..self.config.attributes().len())
+            .filter_map(|index| {
+                let attribute = *self.config.get_attribute(index)?;
+                Some((attribute, self.attribute_bytes(index)?.to_vec()))
+            })
+            .collect();
+
+        let mut result =
+            MeshDecodeResult::from_parts(self.config.vertex_count(), &indices, attributes);
+        result.config.inherit(&self.config);
+        result
+    }
+}
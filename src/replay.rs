@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::content_hash;
+use crate::{MeshDecodeResult, MeshDecoder};
+
+#[derive(Serialize, Deserialize)]
+struct FixtureEntry {
+    input_hash: u64,
+    result: MeshDecodeResult,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Fixture {
+    entries: Vec<FixtureEntry>,
+}
+
+/// A decoder that returns pre-recorded results instead of decoding.
+///
+/// Results are keyed by a hash of the encoded input, so the same bytes that were
+/// recorded must be passed to [`MeshDecoder::decode_mesh`]. Inputs that were not
+/// recorded decode to `None`.
+///
+/// Fixtures are stored as JSON and can be produced with [`ReplayDecoder::record`]
+/// followed by [`ReplayDecoder::save_fixture`].
+#[derive(Debug, Default, Clone)]
+pub struct ReplayDecoder {
+    results: HashMap<u64, MeshDecodeResult>,
+}
+
+static_assertions::assert_impl_all!(ReplayDecoder: Send, Sync);
+
+impl ReplayDecoder {
+    /// Creates an empty replay decoder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a replay decoder from a JSON fixture file.
+    pub fn from_fixture(path: impl AsRef<Path>) -> io::Result<Self> {
+        let bytes = std::fs::read(path)?;
+        Self::from_fixture_bytes(&bytes)
+    }
+
+    /// Loads a replay decoder from JSON fixture bytes.
+    pub fn from_fixture_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let fixture: Fixture = serde_json::from_slice(bytes)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        Ok(Self {
+            results: fixture
+                .entries
+                .into_iter()
+                .map(|entry| (entry.input_hash, entry.result))
+                .collect(),
+        })
+    }
+
+    /// Decodes each input with `decoder` and records the successful results.
+    pub fn record<'a, D: MeshDecoder>(
+        decoder: &D,
+        inputs: impl IntoIterator<Item = &'a [u8]>,
+    ) -> Self {
+        let mut replay = Self::new();
+        for input in inputs {
+            if let Some(result) = decoder.decode_mesh(input) {
+                replay.insert(input, result);
+            }
+        }
+        replay
+    }
+
+    /// Records the result to return for the given input.
+    pub fn insert(&mut self, input: &[u8], result: MeshDecodeResult) {
+        self.results.insert(content_hash(input), result);
+    }
+
+    /// Returns the number of recorded results.
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Returns `true` if no results are recorded.
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Serializes the recorded results to JSON fixture bytes.
+    pub fn to_fixture_bytes(&self) -> io::Result<Vec<u8>> {
+        let mut entries: Vec<FixtureEntry> = self
+            .results
+            .iter()
+            .map(|(&input_hash, result)| FixtureEntry {
+                input_hash,
+                result: result.clone(),
+            })
+            .collect();
+        entries.sort_by_key(|entry| entry.input_hash);
+
+        serde_json::to_vec(&Fixture { entries })
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Writes the recorded results to a JSON fixture file.
+    pub fn save_fixture(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_fixture_bytes()?)
+    }
+}
+
+impl MeshDecoder for ReplayDecoder {
+    fn decode_mesh(&self, data: &[u8]) -> Option<MeshDecodeResult> {
+        self.results.get(&content_hash(data)).cloned()
+    }
+}
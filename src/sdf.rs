@@ -0,0 +1,173 @@
+use crate::MeshDecodeResult;
+use crate::bvh::Bvh;
+use crate::math::Vec3;
+
+/// Ray directions for the inside test, tilted off the axes so rays rarely
+/// graze edges or vertices of axis aligned geometry.
+const INSIDE_RAYS: [Vec3; 3] = [
+    [1.0, 0.000_131, 0.000_217],
+    [0.000_173, 1.0, -0.000_109],
+    [-0.000_191, 0.000_157, 1.0],
+];
+
+/// A grid of signed distances to the surface of a mesh, baked by
+/// [`MeshDecodeResult::bake_sdf`].
+///
+/// Samples sit at the centers of the cells of a box split into `resolution`
+/// cells per axis, stored with `x` varying fastest, then `y`, then `z`.
+/// Distances are negative inside the mesh.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignedDistanceField {
+    /// Number of samples along each axis.
+    pub resolution: [u32; 3],
+    /// Minimum corner of the sampled box.
+    pub min: [f32; 3],
+    /// Maximum corner of the sampled box.
+    pub max: [f32; 3],
+    /// Signed distance of every sample.
+    pub distances: Vec<f32>,
+}
+
+impl SignedDistanceField {
+    /// Returns the size of a cell along each axis.
+    pub fn cell_size(&self) -> [f32; 3] {
+        std::array::from_fn(|axis| (self.max[axis] - self.min[axis]) / self.resolution[axis] as f32)
+    }
+
+    /// Returns the position of the sample at the given cell.
+    pub fn position(&self, cell: [u32; 3]) -> [f32; 3] {
+        let size = self.cell_size();
+        std::array::from_fn(|axis| self.min[axis] + (cell[axis] as f32 + 0.5) * size[axis])
+    }
+
+    /// Returns the distance stored for the given cell, if it is in the grid.
+    pub fn distance(&self, cell: [u32; 3]) -> Option<f32> {
+        if (0..3).any(|axis| cell[axis] >= self.resolution[axis]) {
+            return None;
+        }
+        let [width, height, _] = self.resolution.map(|n| n as usize);
+        let [x, y, z] = cell.map(|n| n as usize);
+        self.distances.get(x + width * (y + height * z)).copied()
+    }
+
+    /// Interpolates the distance at any point trilinearly, clamping points
+    /// outside the grid to its border samples.
+    pub fn sample(&self, point: [f32; 3]) -> f32 {
+        let size = self.cell_size();
+        let mut cells = [[0u32; 2]; 3];
+        let mut weights = [0.0f32; 3];
+        for axis in 0..3 {
+            let last = self.resolution[axis].saturating_sub(1) as f32;
+            let local = ((point[axis] - self.min[axis]) / size[axis] - 0.5).clamp(0.0, last);
+            let local = if local.is_finite() { local } else { 0.0 };
+            let floor = local.floor();
+            cells[axis] = [floor as u32, (floor as u32 + 1).min(last as u32)];
+            weights[axis] = local - floor;
+        }
+
+        let mut distance = 0.0;
+        for corner in 0..8 {
+            let pick = [corner & 1, (corner >> 1) & 1, corner >> 2];
+            let cell = std::array::from_fn(|axis| cells[axis][pick[axis]]);
+            let weight: f32 = (0..3)
+                .map(|axis| match pick[axis] {
+                    0 => 1.0 - weights[axis],
+                    _ => weights[axis],
+                })
+                .product();
+            distance += weight * self.distance(cell).unwrap_or(0.0);
+        }
+        distance
+    }
+}
+
+impl MeshDecodeResult {
+    /// Bakes a signed distance field of the mesh, for collision proxies and
+    /// distance field rendering.
+    ///
+    /// Distances to the triangles are found through a bounding volume
+    /// hierarchy. A sample counts as inside if most of three rays cast from
+    /// it cross the surface an odd number of times, so signs are only
+    /// meaningful for closed meshes. Positions are converted like
+    /// [`MeshDecodeResult::positions`].
+    ///
+    /// Without `bounds`, the bounding box of the mesh grown by a tenth of its
+    /// largest extent on every side is sampled, keeping the surface away from
+    /// the border. Returns `None` if a resolution is zero, the bounds are
+    /// empty, or the mesh has no positions or triangles.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let sdf = result.bake_sdf([64, 64, 64], None).unwrap();
+    /// let inside = sdf.sample([0.0, 0.0, 0.0]) < 0.0;
+    /// ```
+    pub fn bake_sdf(
+        &self,
+        resolution: [u32; 3],
+        bounds: Option<([f32; 3], [f32; 3])>,
+    ) -> Option<SignedDistanceField> {
+        if resolution.contains(&0) {
+            return None;
+        }
+        let points: Vec<Vec3> = self.positions()?.collect();
+        let triangles: Vec<[Vec3; 3]> = self
+            .triangles()
+            .filter_map(|triangle| {
+                let [a, b, c] = triangle.map(|vertex| points.get(vertex as usize).copied());
+                Some([a?, b?, c?])
+            })
+            .collect();
+        if triangles.is_empty() {
+            return None;
+        }
+
+        let (min, max) = match bounds {
+            Some(bounds) => bounds,
+            None => {
+                let (mut min, mut max) = ([f32::INFINITY; 3], [f32::NEG_INFINITY; 3]);
+                for point in triangles.iter().flatten() {
+                    for axis in 0..3 {
+                        min[axis] = min[axis].min(point[axis]);
+                        max[axis] = max[axis].max(point[axis]);
+                    }
+                }
+                let margin = (0..3).map(|axis| max[axis] - min[axis]).fold(0.0, f32::max) * 0.1;
+                (min.map(|c| c - margin), max.map(|c| c + margin))
+            }
+        };
+        let valid = (0..3).all(|axis| {
+            let extent = max[axis] - min[axis];
+            extent > 0.0 && extent.is_finite()
+        });
+        if !valid {
+            return None;
+        }
+
+        let bvh = Bvh::new(triangles);
+        let mut field = SignedDistanceField {
+            resolution,
+            min,
+            max,
+            distances: Vec::new(),
+        };
+        let [width, height, depth] = resolution;
+        let mut distances = Vec::with_capacity(resolution.iter().map(|&n| n as usize).product());
+        for z in 0..depth {
+            for y in 0..height {
+                for x in 0..width {
+                    let point = field.position([x, y, z]);
+                    let distance = bvh.distance(point);
+                    let inside = INSIDE_RAYS
+                        .iter()
+                        .filter(|&&direction| bvh.crossings(point, direction) % 2 == 1)
+                        .count()
+                        >= 2;
+                    distances.push(if inside { -distance } else { distance });
+                }
+            }
+        }
+        field.distances = distances;
+        Some(field)
+    }
+}
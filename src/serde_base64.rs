@@ -0,0 +1,79 @@
+//! Serializes decoded buffers as base64 strings in human-readable formats.
+//!
+//! The `data` of [`MeshDecodeResult`](crate::MeshDecodeResult) and
+//! [`PointCloudDecodeResult`](crate::PointCloudDecodeResult) serializes as
+//! an array of numbers, which is several times larger than the buffer in
+//! JSON. Store the config and the buffer in your own type with this module
+//! to get a base64 string instead, or leave the buffer out and store it
+//! separately as raw bytes. Binary formats get the bytes as they are.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use draco_decoder::DracoDecodeConfig;
+//!
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct CachedMesh {
+//!     config: DracoDecodeConfig,
+//!     #[serde(with = "draco_decoder::serde_base64")]
+//!     data: Vec<u8>,
+//! }
+//! ```
+
+use std::fmt;
+
+use base64::Engine;
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::ser::Serializer;
+
+/// Serializes bytes as a base64 string, or as bytes in binary formats.
+pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&base64::engine::general_purpose::STANDARD.encode(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+/// Deserializes bytes written by [`serialize`].
+///
+/// Arrays of numbers, as written without this module, are accepted too.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(BytesVisitor)
+    } else {
+        deserializer.deserialize_byte_buf(BytesVisitor)
+    }
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a base64 string or bytes")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Vec<u8>, E> {
+        base64::engine::general_purpose::STANDARD
+            .decode(value)
+            .map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(value.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, value: Vec<u8>) -> Result<Vec<u8>, E> {
+        Ok(value)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,86 @@
+use crate::{IndexBuffer, MeshAttribute, MeshDecodeResult};
+
+/// A decoded mesh with the indices and each attribute in separate buffers.
+///
+/// Returned by [`MeshDecodeResult::into_split`]. Every attribute has an offset
+/// of 0 and a length equal to its buffer, so the buffers can be uploaded as
+/// individual GPU buffers as they are.
+#[derive(Debug, Clone)]
+pub struct SplitMesh {
+    /// The index data, see [`SplitMesh::index_size_in_bytes`].
+    pub indices: Vec<u8>,
+    /// Each attribute paired with its tightly packed vertex data.
+    pub attributes: Vec<(MeshAttribute, Vec<u8>)>,
+    vertex_count: u32,
+    index_count: u32,
+}
+
+impl SplitMesh {
+    /// Returns the number of vertices in the mesh.
+    pub fn vertex_count(&self) -> u32 {
+        self.vertex_count
+    }
+
+    /// Returns the number of indices in the mesh.
+    pub fn index_count(&self) -> u32 {
+        self.index_count
+    }
+
+    /// Returns the size in bytes of a single index (2 for `u16`, 4 for `u32`).
+    pub fn index_size_in_bytes(&self) -> usize {
+        (self.indices.len() as u32)
+            .checked_div(self.index_count)
+            .map_or(2, |size| size as usize)
+    }
+
+    /// Returns the indices as 16-bit or 32-bit values.
+    ///
+    /// Returns `None` if the index buffer uses an unexpected index size.
+    pub fn index_buffer(&self) -> Option<IndexBuffer> {
+        match self.index_size_in_bytes() {
+            2 => Some(IndexBuffer::U16(bytemuck::pod_collect_to_vec(
+                &self.indices,
+            ))),
+            4 => Some(IndexBuffer::U32(bytemuck::pod_collect_to_vec(
+                &self.indices,
+            ))),
+            _ => None,
+        }
+    }
+}
+
+impl MeshDecodeResult {
+    /// Splits the decoded buffer into one buffer for the indices and one per
+    /// attribute.
+    ///
+    /// The index buffer reuses the allocation of the decoded buffer, attribute
+    /// data is copied out of it.
+    ///
+    /// Returns `None` if the index data or an attribute lies outside of the
+    /// decoded buffer.
+    pub fn into_split(self) -> Option<SplitMesh> {
+        let attributes = self
+            .config
+            .attributes()
+            .into_iter()
+            .map(|attribute| {
+                let bytes = attribute.bytes_in(&self.data)?.to_vec();
+                Some((attribute.with_layout(0, bytes.len() as u32), bytes))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let index_length = self.config.index_length() as usize;
+        if index_length > self.data.len() {
+            return None;
+        }
+        let mut indices = self.data;
+        indices.truncate(index_length);
+
+        Some(SplitMesh {
+            indices,
+            attributes,
+            vertex_count: self.config.vertex_count(),
+            index_count: self.config.index_count(),
+        })
+    }
+}
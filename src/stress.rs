@@ -0,0 +1,130 @@
+//! Soak testing utilities for detecting leaks across the FFI boundary.
+//!
+//! Every decode creates a `DracoMesh` handle on the C++ side that is released
+//! through a `UniquePtr` when decoding finishes. The native library keeps a
+//! count of live handles, which [`leak_check`] compares before and after a
+//! closure runs. [`run`] repeats decoding over a corpus and additionally tracks
+//! the resident set size of the process where the platform exposes it.
+
+use crate::ffi;
+
+/// Returns the number of `DracoMesh` handles currently alive in the native library.
+pub fn live_mesh_handles() -> i64 {
+    ffi::live_mesh_count()
+}
+
+/// Returns the resident set size of the current process in bytes.
+///
+/// Returns `None` on platforms where it is not available (only Linux is
+/// currently supported).
+pub fn resident_set_size() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+        let kilobytes: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+        Some(kilobytes * 1024)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Outcome of running a closure under [`leak_check`].
+#[derive(Debug)]
+pub struct LeakCheck<R> {
+    /// Value returned by the closure.
+    pub value: R,
+    /// Live `DracoMesh` handles before the closure ran.
+    pub handles_before: i64,
+    /// Live `DracoMesh` handles after the closure returned.
+    pub handles_after: i64,
+}
+
+impl<R> LeakCheck<R> {
+    /// Returns the number of handles created but not released by the closure.
+    pub fn leaked_handles(&self) -> i64 {
+        self.handles_after - self.handles_before
+    }
+
+    /// Returns `true` if the closure released every handle it created.
+    pub fn is_clean(&self) -> bool {
+        self.leaked_handles() == 0
+    }
+}
+
+/// Runs `f` and reports whether it leaked `DracoMesh` handles.
+///
+/// Handles are counted process wide, so decodes running concurrently on other
+/// threads affect the result.
+pub fn leak_check<R>(f: impl FnOnce() -> R) -> LeakCheck<R> {
+    let handles_before = live_mesh_handles();
+    let value = f();
+    let handles_after = live_mesh_handles();
+
+    LeakCheck {
+        value,
+        handles_before,
+        handles_after,
+    }
+}
+
+/// Summary of a soak run produced by [`run`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StressReport {
+    /// Number of decodes performed.
+    pub decodes: usize,
+    /// Number of decodes that returned `None`.
+    pub failures: usize,
+    /// Handles still alive after the run that were not alive before it.
+    pub leaked_handles: i64,
+    /// Resident set size before the run, if available.
+    pub rss_start: Option<u64>,
+    /// Highest resident set size sampled during the run, if available.
+    pub rss_peak: Option<u64>,
+    /// Resident set size after the run, if available.
+    pub rss_end: Option<u64>,
+}
+
+impl StressReport {
+    /// Returns the growth of the resident set size over the run, if available.
+    pub fn rss_growth(&self) -> Option<i64> {
+        Some(self.rss_end? as i64 - self.rss_start? as i64)
+    }
+}
+
+/// Decodes every mesh in `corpus` `iterations` times and reports leaks.
+///
+/// The resident set size is sampled after each pass over the corpus.
+pub fn run<'a>(corpus: impl IntoIterator<Item = &'a [u8]>, iterations: usize) -> StressReport {
+    let corpus: Vec<&[u8]> = corpus.into_iter().collect();
+    let rss_start = resident_set_size();
+    let mut rss_peak = rss_start;
+    let mut decodes = 0;
+    let mut failures = 0;
+
+    let check = leak_check(|| {
+        for _ in 0..iterations {
+            for data in &corpus {
+                decodes += 1;
+                if ffi::decode_mesh_with_config(data).is_none() {
+                    failures += 1;
+                }
+            }
+            if let Some(rss) = resident_set_size() {
+                rss_peak = Some(rss_peak.map_or(rss, |peak| peak.max(rss)));
+            }
+        }
+    });
+
+    StressReport {
+        decodes,
+        failures,
+        leaked_handles: check.leaked_handles(),
+        rss_start,
+        rss_peak,
+        rss_end: resident_set_size(),
+    }
+}
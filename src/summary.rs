@@ -0,0 +1,118 @@
+use std::fmt::Write;
+
+use crate::{AttributeDataType, AttributeSemantic, DracoDecodeConfig};
+
+/// Version of the [`DracoDecodeConfig::to_json`] format, raised whenever a
+/// key changes meaning or is removed.
+const SUMMARY_VERSION: u32 = 1;
+
+/// Writes `text` as a JSON string.
+fn write_string(json: &mut String, text: &str) {
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+/// Returns the summary name of a semantic, independent of its `Debug` output.
+fn semantic_name(semantic: AttributeSemantic) -> &'static str {
+    match semantic {
+        AttributeSemantic::Position => "Position",
+        AttributeSemantic::Normal => "Normal",
+        AttributeSemantic::Color => "Color",
+        AttributeSemantic::TexCoord => "TexCoord",
+        AttributeSemantic::Generic => "Generic",
+    }
+}
+
+/// Returns the summary name of a data type, independent of its `Debug`
+/// output.
+fn data_type_name(data_type: AttributeDataType) -> &'static str {
+    match data_type {
+        AttributeDataType::Int8 => "Int8",
+        AttributeDataType::UInt8 => "UInt8",
+        AttributeDataType::Int16 => "Int16",
+        AttributeDataType::UInt16 => "UInt16",
+        AttributeDataType::Int32 => "Int32",
+        AttributeDataType::UInt32 => "UInt32",
+        AttributeDataType::Float32 => "Float32",
+    }
+}
+
+impl DracoDecodeConfig {
+    /// Describes the decoded mesh as a single line of JSON, to store next to
+    /// the decoded buffer for debugging and cache validation.
+    ///
+    /// The keys and their order are stable: `summary_version`,
+    /// `vertex_count`, `index_count`, `index_size`, `index_length`,
+    /// `buffer_size`, `layout_version`, `crs` (`null` if unknown),
+    /// `attributes` and `warnings`. Each attribute lists its `index`,
+    /// `unique_id`, `semantic`, `data_type`, `dim`, `normalized`, `offset`
+    /// and `length`, semantics and data types spelled like their variants.
+    /// Warnings are their messages. Keys are only ever added, and
+    /// `summary_version` changes if one changes meaning. Available without
+    /// the `serde` feature.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// std::fs::write("mesh.bin", &result.data)?;
+    /// std::fs::write("mesh.json", result.config.to_json())?;
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        let _ = write!(
+            json,
+            "{{\"summary_version\":{SUMMARY_VERSION},\"vertex_count\":{},\"index_count\":{},\
+             \"index_size\":{},\"index_length\":{},\"buffer_size\":{},\"layout_version\":{},\
+             \"crs\":",
+            self.vertex_count(),
+            self.index_count(),
+            self.index_size_in_bytes(),
+            self.index_length(),
+            self.buffer_size(),
+            self.layout().version(),
+        );
+        match self.crs() {
+            Some(crs) => write_string(&mut json, &crs.to_string()),
+            None => json.push_str("null"),
+        }
+
+        json.push_str(",\"attributes\":[");
+        for (index, attribute) in self.attributes().iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            let _ = write!(
+                json,
+                "{{\"index\":{index},\"unique_id\":{},\"semantic\":\"{}\",\"data_type\":\"{}\",\
+                 \"dim\":{},\"normalized\":{},\"offset\":{},\"length\":{}}}",
+                attribute.unique_id(),
+                semantic_name(attribute.semantic()),
+                data_type_name(attribute.data_type()),
+                attribute.dim(),
+                attribute.normalized(),
+                attribute.offset(),
+                attribute.lenght(),
+            );
+        }
+
+        json.push_str("],\"warnings\":[");
+        for (index, warning) in self.warnings().iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            write_string(&mut json, &warning.to_string());
+        }
+        json.push_str("]}");
+        json
+    }
+}
@@ -0,0 +1,360 @@
+use std::f32::consts::{FRAC_PI_4, FRAC_PI_6};
+
+use image::{ImageBuffer, Luma, Rgb32FImage, Rgba, RgbaImage};
+
+use crate::MeshDecodeResult;
+use crate::math::{self, cross, dot, sub};
+
+/// Fraction of the light that reaches faces turned away from it.
+const AMBIENT: f32 = 0.25;
+
+/// Viewpoint and colors of [`render_preview`] and [`render_gbuffer`].
+///
+/// The camera orbits the center of the bounding box of the mesh, at the
+/// distance where the whole mesh fits the image, with the y axis up as in
+/// glTF.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PreviewCamera {
+    yaw: f32,
+    pitch: f32,
+    field_of_view: f32,
+    background: [u8; 4],
+    color: [u8; 3],
+}
+
+impl Default for PreviewCamera {
+    fn default() -> Self {
+        Self {
+            yaw: FRAC_PI_4,
+            pitch: FRAC_PI_6,
+            field_of_view: FRAC_PI_4,
+            background: [0, 0, 0, 0],
+            color: [200, 200, 200],
+        }
+    }
+}
+
+impl PreviewCamera {
+    /// Creates a camera looking down at the mesh from the front right, on a
+    /// transparent background.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the angle around the y axis in radians, 0 looking along -z.
+    pub fn with_yaw(mut self, yaw: f32) -> Self {
+        self.yaw = yaw;
+        self
+    }
+
+    /// Sets the angle above the horizon in radians.
+    pub fn with_pitch(mut self, pitch: f32) -> Self {
+        self.pitch = pitch;
+        self
+    }
+
+    /// Sets the vertical field of view in radians, clamped to less than a
+    /// half turn.
+    pub fn with_field_of_view(mut self, field_of_view: f32) -> Self {
+        self.field_of_view = field_of_view;
+        self
+    }
+
+    /// Sets the color of the pixels the mesh does not cover.
+    pub fn with_background(mut self, background: [u8; 4]) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Sets the color of fully lit faces.
+    pub fn with_color(mut self, color: [u8; 3]) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Returns the angle around the y axis.
+    pub fn yaw(&self) -> f32 {
+        self.yaw
+    }
+
+    /// Returns the angle above the horizon.
+    pub fn pitch(&self) -> f32 {
+        self.pitch
+    }
+
+    /// Returns the vertical field of view.
+    pub fn field_of_view(&self) -> f32 {
+        self.field_of_view
+    }
+
+    /// Returns the background color.
+    pub fn background(&self) -> [u8; 4] {
+        self.background
+    }
+
+    /// Returns the color of fully lit faces.
+    pub fn color(&self) -> [u8; 3] {
+        self.color
+    }
+}
+
+fn normalize(a: [f32; 3]) -> [f32; 3] {
+    math::normalize(a).unwrap_or(a)
+}
+
+/// Camera placement fitted to a mesh, and the triangles it sees.
+struct View {
+    width: u32,
+    height: u32,
+    eye: [f32; 3],
+    right: [f32; 3],
+    up: [f32; 3],
+    forward: [f32; 3],
+    scale: f32,
+}
+
+impl View {
+    /// Places the camera around the mesh, `None` for an empty image.
+    fn new(points: &[[f32; 3]], size: [u32; 2], camera: &PreviewCamera) -> Option<Self> {
+        let [width, height] = size;
+        if points.is_empty() || width == 0 || height == 0 {
+            return None;
+        }
+
+        // Fit a sphere around the center of the bounding box into the view
+        let mut min = [f32::INFINITY; 3];
+        let mut max = [f32::NEG_INFINITY; 3];
+        for point in points {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(point[axis]);
+                max[axis] = max[axis].max(point[axis]);
+            }
+        }
+        let center: [f32; 3] = std::array::from_fn(|axis| (min[axis] + max[axis]) / 2.0);
+        let radius = points
+            .iter()
+            .map(|&point| dot(sub(point, center), sub(point, center)))
+            .fold(0.0f32, f32::max)
+            .sqrt()
+            .max(f32::MIN_POSITIVE);
+        let half_fov = (camera.field_of_view / 2.0).clamp(0.01, 1.55);
+        let distance = radius / half_fov.sin();
+
+        let (sin_yaw, cos_yaw) = camera.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = camera.pitch.sin_cos();
+        let backward = [cos_pitch * sin_yaw, sin_pitch, cos_pitch * cos_yaw];
+        let forward = backward.map(|c| -c);
+        // Looking straight down or up, orient the image with -z up
+        let right = match normalize(cross(forward, [0.0, 1.0, 0.0])) {
+            right if dot(right, right) > 0.0 => right,
+            _ => [1.0, 0.0, 0.0],
+        };
+
+        Some(Self {
+            width,
+            height,
+            eye: std::array::from_fn(|axis| center[axis] + backward[axis] * distance),
+            right,
+            up: cross(right, forward),
+            forward,
+            scale: width.min(height) as f32 / 2.0 / half_fov.tan(),
+        })
+    }
+
+    /// Converts a direction to camera space: right, up and towards the
+    /// camera.
+    fn to_camera(&self, direction: [f32; 3]) -> [f32; 3] {
+        [
+            dot(direction, self.right),
+            dot(direction, self.up),
+            -dot(direction, self.forward),
+        ]
+    }
+
+    /// Returns the pixel coordinates and the depth of a point.
+    fn project(&self, point: [f32; 3]) -> [f32; 3] {
+        let [x, y, z] = self.to_camera(sub(point, self.eye));
+        let depth = -z;
+        [
+            self.width as f32 / 2.0 + x / depth * self.scale,
+            self.height as f32 / 2.0 - y / depth * self.scale,
+            depth,
+        ]
+    }
+
+    /// Rasterizes the triangles of the mesh, calling `plot` with the pixel
+    /// index, the depth and the camera space face normal, turned towards
+    /// the camera, of every pixel closer than those plotted before.
+    fn rasterize(
+        &self,
+        result: &MeshDecodeResult,
+        points: &[[f32; 3]],
+        mut plot: impl FnMut(usize, f32, [f32; 3]),
+    ) {
+        let screen: Vec<[f32; 3]> = points.iter().map(|&point| self.project(point)).collect();
+        let mut depths = vec![f32::INFINITY; self.width as usize * self.height as usize];
+        for triangle in result.triangles() {
+            let corners = triangle.map(|vertex| vertex as usize);
+            if corners.iter().any(|&vertex| vertex >= points.len()) {
+                continue;
+            }
+            let [a, b, c] = corners.map(|vertex| screen[vertex]);
+            if a[2] <= 0.0 || b[2] <= 0.0 || c[2] <= 0.0 {
+                continue;
+            }
+            let area = (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+            if area == 0.0 || !area.is_finite() {
+                continue;
+            }
+
+            let [pa, pb, pc] = corners.map(|vertex| points[vertex]);
+            let mut normal = self.to_camera(normalize(cross(sub(pb, pa), sub(pc, pa))));
+            if normal[2] < 0.0 {
+                normal = normal.map(|c| -c);
+            }
+
+            let x_start = a[0].min(b[0]).min(c[0]).floor().max(0.0) as u32;
+            let x_end = (a[0].max(b[0]).max(c[0]).ceil() as u32).min(self.width);
+            let y_start = a[1].min(b[1]).min(c[1]).floor().max(0.0) as u32;
+            let y_end = (a[1].max(b[1]).max(c[1]).ceil() as u32).min(self.height);
+            for y in y_start..y_end {
+                for x in x_start..x_end {
+                    // Barycentric weights at the pixel center
+                    let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+                    let wa = ((b[0] - px) * (c[1] - py) - (b[1] - py) * (c[0] - px)) / area;
+                    let wb = ((c[0] - px) * (a[1] - py) - (c[1] - py) * (a[0] - px)) / area;
+                    let wc = 1.0 - wa - wb;
+                    if wa < 0.0 || wb < 0.0 || wc < 0.0 {
+                        continue;
+                    }
+                    // Depth is not linear in screen space but its reciprocal is
+                    let depth = 1.0 / (wa / a[2] + wb / b[2] + wc / c[2]);
+                    let index = y as usize * self.width as usize + x as usize;
+                    if depth < depths[index] {
+                        depths[index] = depth;
+                        plot(index, depth, normal);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Renders a flat shaded preview of a mesh in software, for thumbnails of
+/// uploaded assets on servers without a GPU.
+///
+/// `size` is the `[width, height]` of the image. Faces are lit from behind
+/// the camera and drawn from both sides, so meshes with inconsistent winding
+/// still look solid. Returns an image of the background color if the mesh
+/// has no three component positions or no triangles.
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::{PreviewCamera, render_preview};
+///
+/// let camera = PreviewCamera::new().with_background([255, 255, 255, 255]);
+/// let image = render_preview(&result, [256, 256], &camera);
+/// image.save("preview.png")?;
+/// ```
+pub fn render_preview(
+    result: &MeshDecodeResult,
+    size: [u32; 2],
+    camera: &PreviewCamera,
+) -> RgbaImage {
+    let [width, height] = size;
+    let mut image = RgbaImage::from_pixel(width, height, Rgba(camera.background));
+    let points: Vec<[f32; 3]> = result
+        .positions()
+        .map(|positions| positions.collect())
+        .unwrap_or_default();
+    let Some(view) = View::new(&points, size, camera) else {
+        return image;
+    };
+
+    // Light from behind the camera, slightly above and to the left
+    let light = normalize([-0.3, 0.5, 1.0]);
+    let pixels = image.as_flat_samples_mut().samples;
+    view.rasterize(result, &points, |index, _, normal| {
+        let intensity = AMBIENT + (1.0 - AMBIENT) * dot(normal, light).max(0.0);
+        let color = camera.color.map(|c| (c as f32 * intensity).round() as u8);
+        pixels[index * 4..index * 4 + 4].copy_from_slice(&[color[0], color[1], color[2], 255]);
+    });
+    image
+}
+
+/// Depth and normals of a mesh rendered by [`render_gbuffer`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GBuffer {
+    /// Distance of each pixel from the camera plane along the view
+    /// direction, in the units of the positions. 0 where no triangle was
+    /// drawn.
+    pub depth: ImageBuffer<Luma<f32>, Vec<f32>>,
+    /// Unit face normal of each pixel in camera space, with x to the right,
+    /// y up and z towards the camera. Zero where no triangle was drawn.
+    pub normals: Rgb32FImage,
+}
+
+impl GBuffer {
+    /// Encodes the normals as a common 8-bit normal map, each component
+    /// mapped from `-1.0..=1.0` to `0..=255`, transparent where no triangle
+    /// was drawn.
+    pub fn normal_map(&self) -> RgbaImage {
+        let (width, height) = self.normals.dimensions();
+        RgbaImage::from_fn(width, height, |x, y| {
+            let normal = self.normals.get_pixel(x, y).0;
+            if normal == [0.0; 3] {
+                return Rgba([0, 0, 0, 0]);
+            }
+            let [r, g, b] = normal.map(|c| ((c + 1.0) / 2.0 * 255.0).round() as u8);
+            Rgba([r, g, b, 255])
+        })
+    }
+}
+
+/// Renders the depth and normals of a mesh, for example to turn a corpus
+/// of Draco meshes into training data.
+///
+/// The camera is placed like for [`render_preview`], fitting the whole mesh
+/// into the view, so one camera per viewpoint renders every mesh of a
+/// corpus. Faces are drawn from both sides, their normals turned towards
+/// the camera. Returns empty buffers if the mesh has no three component
+/// positions or no triangles.
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::{PreviewCamera, render_gbuffer};
+///
+/// for view in 0..8 {
+///     let camera = PreviewCamera::new().with_yaw(view as f32 * std::f32::consts::FRAC_PI_4);
+///     let gbuffer = render_gbuffer(&result, [128, 128], &camera);
+///     gbuffer.normal_map().save(format!("normals_{view}.png"))?;
+/// }
+/// ```
+pub fn render_gbuffer(
+    result: &MeshDecodeResult,
+    size: [u32; 2],
+    camera: &PreviewCamera,
+) -> GBuffer {
+    let [width, height] = size;
+    let mut gbuffer = GBuffer {
+        depth: ImageBuffer::new(width, height),
+        normals: Rgb32FImage::new(width, height),
+    };
+    let points: Vec<[f32; 3]> = result
+        .positions()
+        .map(|positions| positions.collect())
+        .unwrap_or_default();
+    let Some(view) = View::new(&points, size, camera) else {
+        return gbuffer;
+    };
+
+    let depths = gbuffer.depth.as_flat_samples_mut().samples;
+    let normals = gbuffer.normals.as_flat_samples_mut().samples;
+    view.rasterize(result, &points, |index, depth, normal| {
+        depths[index] = depth;
+        normals[index * 3..index * 3 + 3].copy_from_slice(&normal);
+    });
+    gbuffer
+}
@@ -0,0 +1,759 @@
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+use crate::math::cross;
+use crate::{
+    AttributeDataType, AttributeValues, DecodedGltf, EnuFrame, GltfError, MeshDecodeResult,
+    PointCloudDecodeResult,
+};
+
+const B3DM_MAGIC: &[u8; 4] = b"b3dm";
+const B3DM_HEADER_LENGTH: usize = 28;
+const PNTS_MAGIC: &[u8; 4] = b"pnts";
+const PNTS_HEADER_LENGTH: usize = 28;
+const I3DM_MAGIC: &[u8; 4] = b"i3dm";
+const I3DM_HEADER_LENGTH: usize = 32;
+const DRACO_EXTENSION: &str = "3DTILES_draco_point_compression";
+
+/// Errors reported by [`decode_b3dm`], [`decode_pnts`] and [`decode_i3dm`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Tiles3dError {
+    /// The tile header is truncated, has the wrong magic or its section
+    /// lengths exceed the tile.
+    InvalidHeader,
+    /// The feature table could not be parsed.
+    InvalidFeatureTable(String),
+    /// The embedded glTF could not be decoded.
+    Gltf(GltfError),
+    /// The Draco compressed points could not be decoded.
+    DecodeFailed,
+    /// The instanced model is referenced by this URI instead of embedded,
+    /// which is not supported.
+    ExternalGltf(String),
+}
+
+impl fmt::Display for Tiles3dError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tiles3dError::InvalidHeader => write!(f, "3D Tiles header is malformed"),
+            Tiles3dError::InvalidFeatureTable(err) => write!(f, "feature table is invalid: {err}"),
+            Tiles3dError::Gltf(err) => write!(f, "embedded glTF: {err}"),
+            Tiles3dError::DecodeFailed => write!(f, "Draco compressed points failed to decode"),
+            Tiles3dError::ExternalGltf(uri) => write!(f, "external glTF {uri} is not supported"),
+        }
+    }
+}
+
+impl std::error::Error for Tiles3dError {}
+
+impl From<GltfError> for Tiles3dError {
+    fn from(err: GltfError) -> Self {
+        Tiles3dError::Gltf(err)
+    }
+}
+
+/// A decoded Batched 3D Model (b3dm) tile.
+#[derive(Debug, Clone)]
+pub struct B3dmTile {
+    /// The embedded glTF with its Draco primitives decoded.
+    pub gltf: DecodedGltf,
+    /// Center the positions are relative to, from the feature table's
+    /// `RTC_CENTER`.
+    pub rtc_center: Option<[f64; 3]>,
+    /// Number of models in the batch, from the feature table's
+    /// `BATCH_LENGTH`.
+    pub batch_length: u32,
+}
+
+impl B3dmTile {
+    /// Returns the decoded Draco primitives of the tile, in document order.
+    pub fn meshes(&self) -> impl Iterator<Item = &MeshDecodeResult> {
+        self.gltf
+            .primitives
+            .iter()
+            .map(|primitive| &primitive.result)
+    }
+}
+
+/// The feature table of a tile: JSON plus a binary body that properties can
+/// refer to by `byteOffset`.
+pub(crate) struct FeatureTable<'a> {
+    json: Value,
+    binary: &'a [u8],
+}
+
+impl<'a> FeatureTable<'a> {
+    pub(crate) fn parse(json: &[u8], binary: &'a [u8]) -> Result<Self, Tiles3dError> {
+        // The JSON is padded with spaces, an empty table has no JSON at all
+        let json = if json.iter().all(u8::is_ascii_whitespace) {
+            Value::Object(Default::default())
+        } else {
+            serde_json::from_slice(json)
+                .map_err(|err| Tiles3dError::InvalidFeatureTable(err.to_string()))?
+        };
+        Ok(Self { json, binary })
+    }
+
+    /// Returns a global property, `None` if the table does not have it.
+    pub(crate) fn property(&self, name: &str) -> Option<&Value> {
+        self.json.get(name)
+    }
+
+    /// Returns the binary body starting at a property's `byteOffset`.
+    pub(crate) fn binary_at(&self, name: &str) -> Result<Option<&'a [u8]>, Tiles3dError> {
+        let Some(property) = self.property(name) else {
+            return Ok(None);
+        };
+        let offset = property["byteOffset"]
+            .as_u64()
+            .ok_or_else(|| Tiles3dError::InvalidFeatureTable(format!("{name} has no byteOffset")))?
+            as usize;
+        self.binary
+            .get(offset..)
+            .map(Some)
+            .ok_or_else(|| Tiles3dError::InvalidFeatureTable(format!("{name} is out of range")))
+    }
+
+    /// Returns an integer property given inline.
+    pub(crate) fn u32(&self, name: &str) -> Option<u32> {
+        self.property(name)?.as_u64().map(|value| value as u32)
+    }
+
+    /// Returns a `FLOAT` `VEC3` property given inline or in the binary body.
+    pub(crate) fn vec3(&self, name: &str) -> Result<Option<[f64; 3]>, Tiles3dError> {
+        let Some(property) = self.property(name) else {
+            return Ok(None);
+        };
+        let invalid = || Tiles3dError::InvalidFeatureTable(format!("{name} is not a VEC3"));
+
+        if let Some(values) = property.as_array() {
+            let values: Vec<f64> = values.iter().filter_map(Value::as_f64).collect();
+            return <[f64; 3]>::try_from(values)
+                .map(Some)
+                .map_err(|_| invalid());
+        }
+
+        let bytes = self.binary_at(name)?.ok_or_else(invalid)?;
+        let values = crate::utils::copy_components::<f32>(bytes.get(..12).ok_or_else(invalid)?)
+            .ok_or_else(invalid)?;
+        Ok(Some([values[0] as f64, values[1] as f64, values[2] as f64]))
+    }
+}
+
+/// Reads the little-endian `u32` at `offset`.
+pub(crate) fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    let word = bytes.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes([word[0], word[1], word[2], word[3]]))
+}
+
+/// Splits a tile with the common 3D Tiles header into its feature table and
+/// body, skipping the batch table.
+///
+/// `header_length` is the size of the header, whose words 3 to 6 are the
+/// feature table and batch table lengths.
+pub(crate) fn split_tile<'a>(
+    bytes: &'a [u8],
+    magic: &[u8; 4],
+    header_length: usize,
+) -> Result<(FeatureTable<'a>, &'a [u8]), Tiles3dError> {
+    if !bytes.starts_with(magic) {
+        return Err(Tiles3dError::InvalidHeader);
+    }
+    let word = |index: usize| read_u32(bytes, index * 4).ok_or(Tiles3dError::InvalidHeader);
+    let byte_length = word(2)? as usize;
+    let lengths = [
+        word(3)? as usize,
+        word(4)? as usize,
+        word(5)? as usize,
+        word(6)? as usize,
+    ];
+
+    let tile = bytes
+        .get(..byte_length)
+        .ok_or(Tiles3dError::InvalidHeader)?;
+    let mut sections = [&[][..]; 4];
+    let mut offset = header_length;
+    for (section, length) in sections.iter_mut().zip(lengths) {
+        let end = offset
+            .checked_add(length)
+            .ok_or(Tiles3dError::InvalidHeader)?;
+        *section = tile.get(offset..end).ok_or(Tiles3dError::InvalidHeader)?;
+        offset = end;
+    }
+    let body = tile.get(offset..).ok_or(Tiles3dError::InvalidHeader)?;
+
+    Ok((FeatureTable::parse(sections[0], sections[1])?, body))
+}
+
+impl B3dmTile {
+    fn new(gltf: DecodedGltf, feature_table: &FeatureTable) -> Result<Self, Tiles3dError> {
+        Ok(Self {
+            gltf,
+            rtc_center: feature_table.vec3("RTC_CENTER")?,
+            batch_length: feature_table.u32("BATCH_LENGTH").unwrap_or(0),
+        })
+    }
+}
+
+/// Decodes a Batched 3D Model (b3dm) tile of 3D Tiles.
+///
+/// The header, feature table and batch table are stripped and the Draco
+/// primitives of the embedded GLB are decoded with
+/// [`decode_gltf`](crate::decode_gltf). `RTC_CENTER` and `BATCH_LENGTH` are
+/// read from the feature table; the batch table is skipped.
+///
+/// Only the current 28-byte header is supported, not the legacy headers of
+/// pre-1.0 tilesets.
+pub async fn decode_b3dm(bytes: &[u8]) -> Result<B3dmTile, Tiles3dError> {
+    let (feature_table, glb) = split_tile(bytes, B3DM_MAGIC, B3DM_HEADER_LENGTH)?;
+    B3dmTile::new(crate::decode_gltf(glb).await?, &feature_table)
+}
+
+/// Synchronous version of [`decode_b3dm`] (native only).
+#[cfg(not(target_arch = "wasm32"))]
+pub fn decode_b3dm_sync(bytes: &[u8]) -> Result<B3dmTile, Tiles3dError> {
+    let (feature_table, glb) = split_tile(bytes, B3DM_MAGIC, B3DM_HEADER_LENGTH)?;
+    B3dmTile::new(crate::decode_gltf_sync(glb)?, &feature_table)
+}
+
+/// A decoded Point Cloud (pnts) tile.
+///
+/// Per-point properties are in the same order, colors are expanded to RGBA.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PntsTile {
+    /// Point positions, relative to `rtc_center` if it is set.
+    pub positions: Vec<[f32; 3]>,
+    /// Point colors from `RGBA`, `RGB`, `RGB565` or `CONSTANT_RGBA`.
+    pub colors: Option<Vec<[u8; 4]>>,
+    /// Point normals from `NORMAL` or `NORMAL_OCT16P`.
+    pub normals: Option<Vec<[f32; 3]>>,
+    /// Batch id of each point, from `BATCH_ID`.
+    pub batch_ids: Option<Vec<u32>>,
+    /// Number of batches, from `BATCH_LENGTH`.
+    pub batch_length: u32,
+    /// Center the positions are relative to, from `RTC_CENTER`.
+    pub rtc_center: Option<[f64; 3]>,
+    /// Whether the tile uses `3DTILES_draco_point_compression`.
+    pub draco_compressed: bool,
+}
+
+/// Reads per-point or per-instance properties from the Draco compressed
+/// points or the feature table binary body.
+struct TileProperties<'a> {
+    table: &'a FeatureTable<'a>,
+    draco: Option<(&'a PointCloudDecodeResult, &'a Map<String, Value>)>,
+    count: usize,
+}
+
+impl TileProperties<'_> {
+    /// Returns the values of a property, `None` if the tile does not have it.
+    ///
+    /// Uncompressed properties are read as `data_type`, compressed ones keep
+    /// the type Draco decodes them to.
+    fn values(
+        &self,
+        name: &str,
+        data_type: AttributeDataType,
+        components: usize,
+    ) -> Result<Option<AttributeValues>, Tiles3dError> {
+        let invalid = || Tiles3dError::InvalidFeatureTable(format!("{name} is truncated"));
+
+        if let Some((points, properties)) = self.draco
+            && let Some(unique_id) = properties.get(name).and_then(Value::as_u64)
+        {
+            let index = points
+                .config
+                .attributes()
+                .iter()
+                .position(|attribute| u64::from(attribute.unique_id()) == unique_id)
+                .ok_or_else(|| {
+                    Tiles3dError::InvalidFeatureTable(format!("{name} is not in the Draco points"))
+                })?;
+            let values = points.attribute_values(index).ok_or_else(invalid)?;
+            if values.len() < self.count * components {
+                return Err(invalid());
+            }
+            return Ok(Some(values));
+        }
+
+        let Some(bytes) = self.table.binary_at(name)? else {
+            return Ok(None);
+        };
+        let length = self.count * components * data_type.size_in_bytes();
+        let bytes = bytes.get(..length).ok_or_else(invalid)?;
+        Ok(Some(AttributeValues::from_bytes(data_type, bytes)))
+    }
+
+    fn positions(&self) -> Result<Vec<[f32; 3]>, Tiles3dError> {
+        if let Some(values) = self.values("POSITION", AttributeDataType::Float32, 3)? {
+            return Ok(vec3s(&floats(values)));
+        }
+        let Some(values) = self.values("POSITION_QUANTIZED", AttributeDataType::UInt16, 3)? else {
+            return Err(Tiles3dError::InvalidFeatureTable(
+                "no POSITION or POSITION_QUANTIZED".to_string(),
+            ));
+        };
+
+        let volume = |name: &str| {
+            self.table.vec3(name)?.ok_or_else(|| {
+                Tiles3dError::InvalidFeatureTable(format!("POSITION_QUANTIZED needs {name}"))
+            })
+        };
+        let offset = volume("QUANTIZED_VOLUME_OFFSET")?;
+        let scale = volume("QUANTIZED_VOLUME_SCALE")?;
+        let mut positions = vec3s(&floats(values));
+        for position in &mut positions {
+            for i in 0..3 {
+                position[i] = (offset[i] + position[i] as f64 * scale[i] / 65535.0) as f32;
+            }
+        }
+        Ok(positions)
+    }
+
+    fn colors(&self) -> Result<Option<Vec<[u8; 4]>>, Tiles3dError> {
+        if let Some(values) = self.values("RGBA", AttributeDataType::UInt8, 4)? {
+            let values = integers(values);
+            return Ok(Some(
+                values
+                    .chunks_exact(4)
+                    .map(|c| [c[0] as u8, c[1] as u8, c[2] as u8, c[3] as u8])
+                    .collect(),
+            ));
+        }
+        if let Some(values) = self.values("RGB", AttributeDataType::UInt8, 3)? {
+            let values = integers(values);
+            return Ok(Some(
+                values
+                    .chunks_exact(3)
+                    .map(|c| [c[0] as u8, c[1] as u8, c[2] as u8, 255])
+                    .collect(),
+            ));
+        }
+        if let Some(values) = self.values("RGB565", AttributeDataType::UInt16, 1)? {
+            let expand = |value: u32, bits: u32| (value * 255 / ((1 << bits) - 1)) as u8;
+            return Ok(Some(
+                integers(values)
+                    .into_iter()
+                    .map(|c| {
+                        [
+                            expand(c >> 11 & 0x1f, 5),
+                            expand(c >> 5 & 0x3f, 6),
+                            expand(c & 0x1f, 5),
+                            255,
+                        ]
+                    })
+                    .collect(),
+            ));
+        }
+        if let Some(constant) = self.table.property("CONSTANT_RGBA") {
+            let rgba: Vec<u8> = constant
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_u64)
+                .map(|value| value as u8)
+                .collect();
+            let rgba = <[u8; 4]>::try_from(rgba).map_err(|_| {
+                Tiles3dError::InvalidFeatureTable("CONSTANT_RGBA is not a VEC4".to_string())
+            })?;
+            return Ok(Some(vec![rgba; self.count]));
+        }
+        Ok(None)
+    }
+
+    fn normals(&self) -> Result<Option<Vec<[f32; 3]>>, Tiles3dError> {
+        if let Some(values) = self.values("NORMAL", AttributeDataType::Float32, 3)? {
+            return Ok(Some(vec3s(&floats(values))));
+        }
+        let Some(values) = self.values("NORMAL_OCT16P", AttributeDataType::UInt8, 2)? else {
+            return Ok(None);
+        };
+        Ok(Some(
+            integers(values)
+                .chunks_exact(2)
+                .map(|c| oct_decode(c[0], c[1], 255.0))
+                .collect(),
+        ))
+    }
+
+    fn batch_ids(&self) -> Result<Option<Vec<u32>>, Tiles3dError> {
+        let data_type = match self
+            .table
+            .property("BATCH_ID")
+            .and_then(|property| property["componentType"].as_str())
+        {
+            Some("UNSIGNED_BYTE") => AttributeDataType::UInt8,
+            Some("UNSIGNED_INT") => AttributeDataType::UInt32,
+            _ => AttributeDataType::UInt16,
+        };
+        Ok(self.values("BATCH_ID", data_type, 1)?.map(integers))
+    }
+
+    /// Returns the rotation of each instance as its right, up and forward
+    /// axes.
+    fn rotations(
+        &self,
+        positions: &[[f32; 3]],
+        rtc_center: Option<[f64; 3]>,
+    ) -> Result<Vec<[[f32; 3]; 3]>, Tiles3dError> {
+        let axes = match (
+            self.values("NORMAL_UP", AttributeDataType::Float32, 3)?,
+            self.values("NORMAL_RIGHT", AttributeDataType::Float32, 3)?,
+        ) {
+            (Some(up), Some(right)) => Some((vec3s(&floats(up)), vec3s(&floats(right)))),
+            _ => match (
+                self.values("NORMAL_UP_OCT32P", AttributeDataType::UInt16, 2)?,
+                self.values("NORMAL_RIGHT_OCT32P", AttributeDataType::UInt16, 2)?,
+            ) {
+                (Some(up), Some(right)) => Some((oct32p(up), oct32p(right))),
+                _ => None,
+            },
+        };
+
+        if let Some((up, right)) = axes {
+            return Ok(up
+                .into_iter()
+                .zip(right)
+                .map(|(up, right)| [right, up, cross(right, up)])
+                .collect());
+        }
+        if self.table.property("EAST_NORTH_UP") == Some(&Value::Bool(true)) {
+            let center = rtc_center.unwrap_or_default();
+            return Ok(positions
+                .iter()
+                .map(|position| {
+                    east_north_up(std::array::from_fn(|i| center[i] + position[i] as f64))
+                })
+                .collect());
+        }
+        Ok(vec![IDENTITY; self.count])
+    }
+
+    /// Returns the scale of each instance.
+    fn scales(&self) -> Result<Vec<[f32; 3]>, Tiles3dError> {
+        if let Some(values) = self.values("SCALE_NON_UNIFORM", AttributeDataType::Float32, 3)? {
+            return Ok(vec3s(&floats(values)));
+        }
+        if let Some(values) = self.values("SCALE", AttributeDataType::Float32, 1)? {
+            return Ok(floats(values).into_iter().map(|scale| [scale; 3]).collect());
+        }
+        Ok(vec![[1.0; 3]; self.count])
+    }
+}
+
+const IDENTITY: [[f32; 3]; 3] = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+/// Decodes `OCT32P` normals of instances.
+fn oct32p(values: AttributeValues) -> Vec<[f32; 3]> {
+    integers(values)
+        .chunks_exact(2)
+        .map(|c| oct_decode(c[0], c[1], 65535.0))
+        .collect()
+}
+
+/// Returns the east, north and up axes of the WGS84 ellipsoid at an earth
+/// centered position, which orient instances with `EAST_NORTH_UP`.
+fn east_north_up(position: [f64; 3]) -> [[f32; 3]; 3] {
+    EnuFrame::from_ecef(position)
+        .axes()
+        .map(|axis| axis.map(|component| component as f32))
+}
+
+/// Converts values of any type to floats.
+fn floats(values: AttributeValues) -> Vec<f32> {
+    match values {
+        AttributeValues::Int8(v) => v.into_iter().map(f32::from).collect(),
+        AttributeValues::UInt8(v) => v.into_iter().map(f32::from).collect(),
+        AttributeValues::Int16(v) => v.into_iter().map(f32::from).collect(),
+        AttributeValues::UInt16(v) => v.into_iter().map(f32::from).collect(),
+        AttributeValues::Int32(v) => v.into_iter().map(|x| x as f32).collect(),
+        AttributeValues::UInt32(v) => v.into_iter().map(|x| x as f32).collect(),
+        AttributeValues::Float32(v) => v,
+    }
+}
+
+/// Converts values of any type to unsigned integers.
+fn integers(values: AttributeValues) -> Vec<u32> {
+    match values {
+        AttributeValues::Int8(v) => v.into_iter().map(|x| x as u32).collect(),
+        AttributeValues::UInt8(v) => v.into_iter().map(u32::from).collect(),
+        AttributeValues::Int16(v) => v.into_iter().map(|x| x as u32).collect(),
+        AttributeValues::UInt16(v) => v.into_iter().map(u32::from).collect(),
+        AttributeValues::Int32(v) => v.into_iter().map(|x| x as u32).collect(),
+        AttributeValues::UInt32(v) => v,
+        AttributeValues::Float32(v) => v.into_iter().map(|x| x as u32).collect(),
+    }
+}
+
+fn vec3s(values: &[f32]) -> Vec<[f32; 3]> {
+    values.chunks_exact(3).map(|c| [c[0], c[1], c[2]]).collect()
+}
+
+/// Decodes an octahedron encoded normal with components up to `max`, 255
+/// for `NORMAL_OCT16P` and 65535 for the `OCT32P` normals of instances.
+fn oct_decode(x: u32, y: u32, max: f32) -> [f32; 3] {
+    let x = x as f32 / max * 2.0 - 1.0;
+    let y = y as f32 / max * 2.0 - 1.0;
+    let z = 1.0 - x.abs() - y.abs();
+    let (x, y) = if z < 0.0 {
+        ((1.0 - y.abs()) * x.signum(), (1.0 - x.abs()) * y.signum())
+    } else {
+        (x, y)
+    };
+    let length = (x * x + y * y + z * z).sqrt();
+    if length == 0.0 {
+        return [0.0; 3];
+    }
+    [x / length, y / length, z / length]
+}
+
+/// The `3DTILES_draco_point_compression` extension of a feature table.
+struct DracoPoints<'a> {
+    /// The Draco compressed points.
+    data: &'a [u8],
+    /// Unique ids of the compressed properties, by property name.
+    properties: Map<String, Value>,
+}
+
+/// Returns the Draco compressed points of a feature table, `None` without
+/// `3DTILES_draco_point_compression`.
+fn draco_points<'a>(table: &FeatureTable<'a>) -> Result<Option<DracoPoints<'a>>, Tiles3dError> {
+    let Some(extension) = table
+        .property("extensions")
+        .and_then(|extensions| extensions.get(DRACO_EXTENSION))
+    else {
+        return Ok(None);
+    };
+    let invalid =
+        |what: &str| Tiles3dError::InvalidFeatureTable(format!("{DRACO_EXTENSION} {what}"));
+
+    let properties = extension["properties"]
+        .as_object()
+        .cloned()
+        .ok_or_else(|| invalid("has no properties"))?;
+    let offset = extension["byteOffset"]
+        .as_u64()
+        .ok_or_else(|| invalid("has no byteOffset"))? as usize;
+    let length = extension["byteLength"]
+        .as_u64()
+        .ok_or_else(|| invalid("has no byteLength"))? as usize;
+    let data = offset
+        .checked_add(length)
+        .and_then(|end| table.binary.get(offset..end))
+        .ok_or_else(|| invalid("is out of range"))?;
+    Ok(Some(DracoPoints { data, properties }))
+}
+
+impl PntsTile {
+    fn new(
+        table: &FeatureTable,
+        draco: Option<(&PointCloudDecodeResult, &Map<String, Value>)>,
+    ) -> Result<Self, Tiles3dError> {
+        let point_count = table.u32("POINTS_LENGTH").ok_or_else(|| {
+            Tiles3dError::InvalidFeatureTable("POINTS_LENGTH is missing".to_string())
+        })?;
+        let properties = TileProperties {
+            table,
+            draco,
+            count: point_count as usize,
+        };
+
+        Ok(Self {
+            positions: properties.positions()?,
+            colors: properties.colors()?,
+            normals: properties.normals()?,
+            batch_ids: properties.batch_ids()?,
+            batch_length: table.u32("BATCH_LENGTH").unwrap_or(0),
+            rtc_center: table.vec3("RTC_CENTER")?,
+            draco_compressed: draco.is_some(),
+        })
+    }
+}
+
+/// Decodes a Point Cloud (pnts) tile of 3D Tiles.
+///
+/// Positions, colors, normals and batch ids are read from the feature
+/// table. With `3DTILES_draco_point_compression` the compressed properties
+/// are decoded with
+/// [`decode_point_cloud_with_config`](crate::decode_point_cloud_with_config)
+/// and the others are read from the binary body as usual. Quantized
+/// positions are dequantized and `NORMAL_OCT16P` normals decoded; the batch
+/// table is skipped.
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::decode_pnts;
+///
+/// let tile = decode_pnts(&bytes).await?;
+/// println!("{} points", tile.positions.len());
+/// ```
+pub async fn decode_pnts(bytes: &[u8]) -> Result<PntsTile, Tiles3dError> {
+    let (table, _) = split_tile(bytes, PNTS_MAGIC, PNTS_HEADER_LENGTH)?;
+    let Some(draco) = draco_points(&table)? else {
+        return PntsTile::new(&table, None);
+    };
+    let points = crate::decode_point_cloud_with_config(draco.data)
+        .await
+        .ok_or(Tiles3dError::DecodeFailed)?;
+    PntsTile::new(&table, Some((&points, &draco.properties)))
+}
+
+/// Synchronous version of [`decode_pnts`] (native only).
+#[cfg(not(target_arch = "wasm32"))]
+pub fn decode_pnts_sync(bytes: &[u8]) -> Result<PntsTile, Tiles3dError> {
+    let (table, _) = split_tile(bytes, PNTS_MAGIC, PNTS_HEADER_LENGTH)?;
+    let Some(draco) = draco_points(&table)? else {
+        return PntsTile::new(&table, None);
+    };
+    let points =
+        crate::decode_point_cloud_with_config_sync(draco.data).ok_or(Tiles3dError::DecodeFailed)?;
+    PntsTile::new(&table, Some((&points, &draco.properties)))
+}
+
+/// Placement of one instance of an [`I3dmTile`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InstanceTransform {
+    /// Position of the instance, relative to the tile's `rtc_center` if it
+    /// is set.
+    pub translation: [f32; 3],
+    /// The right, up and forward axes of the instance.
+    pub rotation: [[f32; 3]; 3],
+    /// Scale along each axis.
+    pub scale: [f32; 3],
+}
+
+impl InstanceTransform {
+    /// Returns the column-major 4x4 matrix of the transform, as used for
+    /// per-instance vertex buffers.
+    pub fn matrix(&self) -> [f32; 16] {
+        let [right, up, forward] = self.rotation;
+        let column = |axis: [f32; 3], scale: f32| axis.map(|component| component * scale);
+        let columns = [
+            column(right, self.scale[0]),
+            column(up, self.scale[1]),
+            column(forward, self.scale[2]),
+        ];
+        std::array::from_fn(|i| match (i / 4, i % 4) {
+            (3, 3) => 1.0,
+            (_, 3) => 0.0,
+            (3, row) => self.translation[row],
+            (col, row) => columns[col][row],
+        })
+    }
+}
+
+/// Converts the transform to its [`InstanceTransform::matrix`] (requires the
+/// `mint` feature).
+#[cfg(feature = "mint")]
+impl From<InstanceTransform> for mint::ColumnMatrix4<f32> {
+    fn from(transform: InstanceTransform) -> Self {
+        transform.matrix().into()
+    }
+}
+
+/// A decoded Instanced 3D Model (i3dm) tile.
+#[derive(Debug, Clone)]
+pub struct I3dmTile {
+    /// The instanced glTF with its Draco primitives decoded.
+    pub gltf: DecodedGltf,
+    /// Placement of every instance.
+    pub instances: Vec<InstanceTransform>,
+    /// Batch id of each instance, from `BATCH_ID`.
+    pub batch_ids: Option<Vec<u32>>,
+    /// Number of batches, from `BATCH_LENGTH`.
+    pub batch_length: u32,
+    /// Center the instance positions are relative to, from `RTC_CENTER`.
+    pub rtc_center: Option<[f64; 3]>,
+}
+
+impl I3dmTile {
+    /// Returns the decoded Draco primitives of the instanced model, in
+    /// document order.
+    pub fn meshes(&self) -> impl Iterator<Item = &MeshDecodeResult> {
+        self.gltf
+            .primitives
+            .iter()
+            .map(|primitive| &primitive.result)
+    }
+
+    fn new(gltf: DecodedGltf, table: &FeatureTable) -> Result<Self, Tiles3dError> {
+        let instance_count = table.u32("INSTANCES_LENGTH").ok_or_else(|| {
+            Tiles3dError::InvalidFeatureTable("INSTANCES_LENGTH is missing".to_string())
+        })?;
+        let properties = TileProperties {
+            table,
+            draco: None,
+            count: instance_count as usize,
+        };
+        let rtc_center = table.vec3("RTC_CENTER")?;
+
+        let positions = properties.positions()?;
+        let rotations = properties.rotations(&positions, rtc_center)?;
+        let instances = positions
+            .into_iter()
+            .zip(rotations)
+            .zip(properties.scales()?)
+            .map(|((translation, rotation), scale)| InstanceTransform {
+                translation,
+                rotation,
+                scale,
+            })
+            .collect();
+
+        Ok(Self {
+            gltf,
+            instances,
+            batch_ids: properties.batch_ids()?,
+            batch_length: table.u32("BATCH_LENGTH").unwrap_or(0),
+            rtc_center,
+        })
+    }
+}
+
+/// Splits an i3dm tile into its feature table and embedded GLB.
+fn split_i3dm(bytes: &[u8]) -> Result<(FeatureTable<'_>, &[u8]), Tiles3dError> {
+    let (table, body) = split_tile(bytes, I3DM_MAGIC, I3DM_HEADER_LENGTH)?;
+    match read_u32(bytes, 28) {
+        Some(1) => Ok((table, body)),
+        Some(0) => {
+            let uri = String::from_utf8_lossy(body);
+            Err(Tiles3dError::ExternalGltf(
+                uri.trim_end_matches(['\0', ' ']).to_string(),
+            ))
+        }
+        _ => Err(Tiles3dError::InvalidHeader),
+    }
+}
+
+/// Decodes an Instanced 3D Model (i3dm) tile of 3D Tiles.
+///
+/// The Draco primitives of the embedded GLB are decoded with
+/// [`decode_gltf`](crate::decode_gltf), and the position, orientation and
+/// scale of every instance is read from the feature table: quantized
+/// positions are dequantized, `OCT32P` normals decoded and `EAST_NORTH_UP`
+/// instances oriented on the WGS84 ellipsoid. Instances without an
+/// orientation are not rotated. The batch table is skipped.
+///
+/// Models referenced by URI are reported as [`Tiles3dError::ExternalGltf`].
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::decode_i3dm;
+///
+/// let tile = decode_i3dm(&bytes).await?;
+/// let matrices: Vec<[f32; 16]> = tile.instances.iter().map(|i| i.matrix()).collect();
+/// ```
+pub async fn decode_i3dm(bytes: &[u8]) -> Result<I3dmTile, Tiles3dError> {
+    let (table, glb) = split_i3dm(bytes)?;
+    I3dmTile::new(crate::decode_gltf(glb).await?, &table)
+}
+
+/// Synchronous version of [`decode_i3dm`] (native only).
+#[cfg(not(target_arch = "wasm32"))]
+pub fn decode_i3dm_sync(bytes: &[u8]) -> Result<I3dmTile, Tiles3dError> {
+    let (table, glb) = split_i3dm(bytes)?;
+    I3dmTile::new(crate::decode_gltf_sync(glb)?, &table)
+}
@@ -1,5 +1,25 @@
+use std::borrow::Cow;
+use std::ops::Deref;
+
+use crate::{Crs, DecodeError, DecodeWarning};
+
+/// Computes a stable 64-bit FNV-1a hash of the given bytes.
+///
+/// Used to key recorded results, diagnostics and reproducers by their
+/// encoded input.
+#[cfg(any(not(target_arch = "wasm32"), feature = "audit", feature = "replay"))]
+pub(crate) fn content_hash(data: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    data.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(PRIME)
+    })
+}
+
 /// Data types for mesh attributes.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AttributeDataType {
     /// Signed 8-bit integer
     Int8,
@@ -28,15 +48,268 @@ impl AttributeDataType {
     }
 }
 
+/// What the data of an attribute represents.
+///
+/// Mirrors Draco's `GeometryAttribute::Type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AttributeSemantic {
+    /// Vertex positions
+    Position,
+    /// Vertex normals
+    Normal,
+    /// Vertex colors
+    Color,
+    /// Texture coordinates
+    TexCoord,
+    /// Any other per-vertex data, such as intensity or batch ids
+    #[default]
+    Generic,
+}
+
+impl AttributeSemantic {
+    /// Converts a Draco `GeometryAttribute::Type` value.
+    ///
+    /// Types without a dedicated variant map to [`AttributeSemantic::Generic`].
+    pub(crate) fn from_draco(value: i32) -> Self {
+        match value {
+            0 => AttributeSemantic::Position,
+            1 => AttributeSemantic::Normal,
+            2 => AttributeSemantic::Color,
+            3 => AttributeSemantic::TexCoord,
+            _ => AttributeSemantic::Generic,
+        }
+    }
+
+    /// Converts to a Draco `GeometryAttribute::Type` value.
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub(crate) fn to_draco(self) -> i32 {
+        match self {
+            AttributeSemantic::Position => 0,
+            AttributeSemantic::Normal => 1,
+            AttributeSemantic::Color => 2,
+            AttributeSemantic::TexCoord => 3,
+            AttributeSemantic::Generic => 4,
+        }
+    }
+}
+
+/// Component types that can be read directly out of a decoded attribute.
+///
+/// Implemented for every primitive type that [`AttributeDataType`] describes.
+pub trait AttributeComponent: bytemuck::Pod {
+    /// The attribute data type matching this component type.
+    const DATA_TYPE: AttributeDataType;
+}
+
+macro_rules! impl_attribute_component {
+    ($($ty:ty => $data_type:ident),* $(,)?) => {
+        $(
+            impl AttributeComponent for $ty {
+                const DATA_TYPE: AttributeDataType = AttributeDataType::$data_type;
+            }
+        )*
+    };
+}
+
+impl_attribute_component!(
+    i8 => Int8,
+    u8 => UInt8,
+    i16 => Int16,
+    u16 => UInt16,
+    i32 => Int32,
+    u32 => UInt32,
+    f32 => Float32,
+);
+
+/// Reinterprets bytes as a slice of components without copying.
+///
+/// Returns `None` if the bytes are not aligned for `T` or their length is not a
+/// multiple of the size of `T`.
+pub fn cast_components<T: AttributeComponent>(bytes: &[u8]) -> Option<&[T]> {
+    bytemuck::try_cast_slice(bytes).ok()
+}
+
+/// Copies bytes into a vector of components, regardless of their alignment.
+///
+/// Returns `None` if the length of the bytes is not a multiple of the size of `T`.
+pub fn copy_components<T: AttributeComponent>(bytes: &[u8]) -> Option<Vec<T>> {
+    if !bytes.len().is_multiple_of(std::mem::size_of::<T>()) {
+        return None;
+    }
+    Some(bytemuck::pod_collect_to_vec(bytes))
+}
+
+/// Most components of a [`QuantizationInfo`].
+pub const MAX_QUANTIZED_COMPONENTS: usize = 16;
+
+/// Quantization parameters of an attribute decoded without dequantization.
+///
+/// Component `i` of a quantized value `q` dequantizes to
+/// `min_values()[i] + q * scale()`, which maps directly onto the translation
+/// and scale of a `KHR_mesh_quantization` node transform.
+///
+/// Parameters are compared by their bit patterns, so equality is reflexive
+/// even for NaN values read from a malformed bitstream.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuantizationInfo {
+    bits: u32,
+    range: f32,
+    components: u32,
+    min_values: [f32; MAX_QUANTIZED_COMPONENTS],
+}
+
+impl PartialEq for QuantizationInfo {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+            && self.range.to_bits() == other.range.to_bits()
+            && self.components == other.components
+            && self
+                .min_values
+                .iter()
+                .zip(&other.min_values)
+                .all(|(a, b)| a.to_bits() == b.to_bits())
+    }
+}
+
+impl Eq for QuantizationInfo {}
+
+impl QuantizationInfo {
+    /// Creates quantization parameters.
+    ///
+    /// Returns `None` if there are more than [`MAX_QUANTIZED_COMPONENTS`]
+    /// minimum values.
+    pub fn new(bits: u32, range: f32, min_values: &[f32]) -> Option<Self> {
+        let mut values = [0.0; MAX_QUANTIZED_COMPONENTS];
+        values
+            .get_mut(..min_values.len())?
+            .copy_from_slice(min_values);
+        Some(Self {
+            bits,
+            range,
+            components: min_values.len() as u32,
+            min_values: values,
+        })
+    }
+
+    /// Returns the number of quantization bits.
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Returns the extent of the quantized range, the same for every
+    /// component.
+    pub fn range(&self) -> f32 {
+        self.range
+    }
+
+    /// Returns the minimum value of every component.
+    pub fn min_values(&self) -> &[f32] {
+        &self.min_values[..self.components as usize]
+    }
+
+    /// Returns the size of one quantization step.
+    pub fn scale(&self) -> f32 {
+        let max_quantized = (1u64 << self.bits.min(32)) - 1;
+        if max_quantized == 0 {
+            return 0.0;
+        }
+        self.range / max_quantized as f32
+    }
+
+    /// Dequantizes a value of the given component.
+    ///
+    /// Returns `None` if the component does not exist.
+    pub fn dequantize(&self, component: usize, value: u32) -> Option<f32> {
+        Some(self.min_values().get(component)? + value as f32 * self.scale())
+    }
+}
+
+/// Parameters of normals kept in Draco's octahedral encoding.
+///
+/// Each normal is stored as two quantized coordinates `s` and `t` on an
+/// unwrapped octahedron, halving the bandwidth of three float components.
+/// [`OctahedronInfo::decode`] is the reference implementation of the decoding
+/// a shader has to perform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OctahedronInfo {
+    bits: u32,
+}
+
+impl OctahedronInfo {
+    /// Creates octahedron parameters for coordinates with the given number of
+    /// quantization bits.
+    pub fn new(bits: u32) -> Self {
+        Self { bits }
+    }
+
+    /// Returns the number of quantization bits of each coordinate.
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Returns the largest coordinate value, which maps to `1.0`.
+    ///
+    /// Coordinates are scaled to `-1.0..=1.0` by `value / max_value * 2 - 1`.
+    pub fn max_value(&self) -> u32 {
+        (1u32 << self.bits.min(31)).saturating_sub(2)
+    }
+
+    /// Decodes quantized octahedral coordinates to a unit normal.
+    ///
+    /// Returns a zero vector for degenerate coordinates, like Draco.
+    pub fn decode(&self, s: u32, t: u32) -> [f32; 3] {
+        let scale = 1.0 / self.max_value().max(1) as f32;
+        let mut y = s as f32 * scale * 2.0 - 1.0;
+        let mut z = t as f32 * scale * 2.0 - 1.0;
+
+        // Points outside the central diamond wrap around to the other
+        // hemisphere of the octahedron
+        let x = 1.0 - y.abs() - z.abs();
+        let x_offset = (-x).max(0.0);
+        y += if y < 0.0 { x_offset } else { -x_offset };
+        z += if z < 0.0 { x_offset } else { -x_offset };
+
+        let norm_squared = x * x + y * y + z * z;
+        if norm_squared < 1e-6 {
+            return [0.0; 3];
+        }
+        let d = 1.0 / norm_squared.sqrt();
+        [x * d, y * d, z * d]
+    }
+}
+
 /// Describes a single attribute in a decoded mesh.
 ///
 /// An attribute represents per-vertex data such as positions, normals, or texture coordinates.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MeshAttribute {
     dim: u32,
     data_type: AttributeDataType,
     offset: u32,
+    #[cfg_attr(feature = "serde", serde(rename = "length"))]
     lenght: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    semantic: AttributeSemantic,
+    #[cfg_attr(feature = "serde", serde(default))]
+    unique_id: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    normalized: bool,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    quantization: Option<QuantizationInfo>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    octahedron: Option<OctahedronInfo>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    padded: bool,
 }
 
 impl MeshAttribute {
@@ -54,9 +327,96 @@ impl MeshAttribute {
             data_type,
             offset,
             lenght,
+            semantic: AttributeSemantic::Generic,
+            unique_id: 0,
+            normalized: false,
+            quantization: None,
+            octahedron: None,
+            padded: false,
         }
     }
 
+    /// Sets the semantic of this attribute.
+    pub fn with_semantic(mut self, semantic: AttributeSemantic) -> Self {
+        self.semantic = semantic;
+        self
+    }
+
+    /// Sets the Draco unique id of this attribute.
+    pub fn with_unique_id(mut self, unique_id: u32) -> Self {
+        self.unique_id = unique_id;
+        self
+    }
+
+    /// Sets whether the integer values of this attribute are normalized.
+    pub fn with_normalized(mut self, normalized: bool) -> Self {
+        self.normalized = normalized;
+        self
+    }
+
+    /// Sets the quantization parameters of this attribute.
+    pub fn with_quantization(mut self, quantization: QuantizationInfo) -> Self {
+        self.quantization = Some(quantization);
+        self
+    }
+
+    /// Returns the quantization parameters if the attribute holds quantized
+    /// values, see [`DecodeOptions::keep_quantized`](crate::DecodeOptions::keep_quantized).
+    pub fn quantization(&self) -> Option<QuantizationInfo> {
+        self.quantization
+    }
+
+    /// Sets the octahedron parameters of this attribute.
+    pub fn with_octahedron(mut self, octahedron: OctahedronInfo) -> Self {
+        self.octahedron = Some(octahedron);
+        self
+    }
+
+    /// Marks the last component of this attribute as zero padding.
+    pub fn with_padded(mut self, padded: bool) -> Self {
+        self.padded = padded;
+        self
+    }
+
+    /// Returns `true` if a three component attribute was padded to four
+    /// components, see
+    /// [`DecodeOptions::pad_vec3_to_vec4`](crate::DecodeOptions::pad_vec3_to_vec4).
+    ///
+    /// [`dim`](Self::dim) includes the padding, whose value is always zero.
+    pub fn padded(&self) -> bool {
+        self.padded
+    }
+
+    /// Returns the octahedron parameters if the attribute holds octahedron
+    /// encoded normals, see
+    /// [`DecodeOptions::keep_octahedral_normals`](crate::DecodeOptions::keep_octahedral_normals).
+    pub fn octahedron(&self) -> Option<OctahedronInfo> {
+        self.octahedron
+    }
+
+    /// Returns what the attribute data represents.
+    pub fn semantic(&self) -> AttributeSemantic {
+        self.semantic
+    }
+
+    /// Returns the unique id of the attribute in the Draco bitstream.
+    ///
+    /// glTF's `KHR_draco_mesh_compression` refers to attributes by this id.
+    pub fn unique_id(&self) -> u32 {
+        self.unique_id
+    }
+
+    /// Returns `true` if the integer values of this attribute are normalized,
+    /// mapping unsigned values to `0.0..=1.0` and signed values to
+    /// `-1.0..=1.0`.
+    ///
+    /// This is the `normalized` flag of a glTF accessor, typically set for
+    /// 8 and 16-bit colors and texture coordinates. Always `false` for
+    /// `Float32` attributes.
+    pub fn normalized(&self) -> bool {
+        self.normalized
+    }
+
     /// Returns the byte offset of this attribute in the decoded buffer.
     pub fn offset(&self) -> u32 {
         self.offset
@@ -76,19 +436,176 @@ impl MeshAttribute {
     pub fn dim(&self) -> u32 {
         self.dim
     }
+
+    /// Returns the size in bytes of the attribute data for a single vertex.
+    pub fn element_size(&self) -> usize {
+        self.dim as usize * self.data_type.size_in_bytes()
+    }
+
+    /// Returns the bytes of this attribute within a decoded buffer.
+    pub(crate) fn bytes_in<'a>(&self, data: &'a [u8]) -> Option<&'a [u8]> {
+        let start = self.offset as usize;
+        let end = start.checked_add(self.lenght as usize)?;
+        data.get(start..end)
+    }
+
+    /// Returns a typed view of this attribute within a decoded buffer.
+    pub(crate) fn view_in<'a, T: AttributeComponent>(
+        &self,
+        data: &'a [u8],
+    ) -> Option<AttributeView<'a, T>> {
+        if self.data_type != T::DATA_TYPE {
+            return None;
+        }
+
+        let dim = self.dim as usize;
+        let bytes = self.bytes_in(data)?;
+        if dim == 0 || !(bytes.len() / std::mem::size_of::<T>()).is_multiple_of(dim) {
+            return None;
+        }
+
+        let values = match cast_components(bytes) {
+            Some(values) => Cow::Borrowed(values),
+            None => Cow::Owned(copy_components(bytes)?),
+        };
+
+        Some(AttributeView { values, dim })
+    }
+
+    /// Returns the typed values of this attribute within a decoded buffer.
+    pub(crate) fn values_in(&self, data: &[u8]) -> Option<AttributeValues> {
+        let bytes = self.bytes_in(data)?;
+        if !bytes.len().is_multiple_of(self.element_size().max(1)) {
+            return None;
+        }
+
+        Some(AttributeValues::from_bytes(self.data_type, bytes))
+    }
+
+    /// Returns a copy of this attribute placed at a different position in the buffer.
+    pub(crate) fn with_layout(mut self, offset: u32, length: u32) -> Self {
+        self.offset = offset;
+        self.lenght = length;
+        self
+    }
+}
+
+/// Describes the byte layout of a decoded buffer.
+///
+/// The descriptor is stored in [`DracoDecodeConfig`] so that serialized
+/// results carry the layout they were written with. Deserializing a descriptor
+/// with a newer version than this crate understands fails instead of silently
+/// misinterpreting the buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "LayoutDescriptorRepr")
+)]
+pub struct LayoutDescriptor {
+    version: u32,
+}
+
+impl LayoutDescriptor {
+    /// The layout version written by this crate.
+    ///
+    /// Version 1: indices first (16-bit when the index count fits in `u16`,
+    /// 32-bit otherwise), followed by each attribute tightly packed in order of
+    /// its unique id.
+    ///
+    /// Version 2: as version 1, but 16-bit indices are used whenever every
+    /// vertex index fits in `u16`. The index width is always
+    /// `index_length / index_count`.
+    ///
+    /// Version 3: as version 2, but 16-bit indices are also used for every
+    /// mesh decoded with [`IndexFormat::U16`](crate::IndexFormat::U16).
+    pub const CURRENT_VERSION: u32 = 3;
+
+    /// Returns the layout version.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns `true` if this crate can interpret buffers with this layout.
+    pub fn is_supported(&self) -> bool {
+        (1..=Self::CURRENT_VERSION).contains(&self.version)
+    }
+}
+
+impl Default for LayoutDescriptor {
+    fn default() -> Self {
+        Self {
+            version: Self::CURRENT_VERSION,
+        }
+    }
+}
+
+/// Unchecked serialized form of [`LayoutDescriptor`].
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct LayoutDescriptorRepr {
+    version: u32,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<LayoutDescriptorRepr> for LayoutDescriptor {
+    type Error = String;
+
+    fn try_from(repr: LayoutDescriptorRepr) -> Result<Self, Self::Error> {
+        let layout = LayoutDescriptor {
+            version: repr.version,
+        };
+        if layout.is_supported() {
+            Ok(layout)
+        } else {
+            Err(format!(
+                "unsupported decoded layout version {} (supported: 1..={})",
+                repr.version,
+                LayoutDescriptor::CURRENT_VERSION
+            ))
+        }
+    }
 }
 
+/// Layout assumed for serialized configs written before the descriptor existed.
+#[cfg(feature = "serde")]
+fn legacy_layout() -> LayoutDescriptor {
+    LayoutDescriptor { version: 1 }
+}
+
+/// Attribute index and the bytes of the minimum and maximum of an attribute
+/// recorded while decoding.
+pub(crate) type RecordedMinMax = (usize, Vec<u8>, Vec<u8>);
+
 /// Configuration and metadata for a decoded Draco mesh.
 ///
 /// This struct contains all the information needed to interpret the decoded
 /// mesh buffer, including vertex count, index count, and attribute layouts.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DracoDecodeConfig {
     vertex_count: u32,
     index_count: u32,
     index_length: u32,
     buffer_size: usize,
     attributes: Vec<MeshAttribute>,
+    #[cfg_attr(feature = "serde", serde(default = "legacy_layout"))]
+    layout: LayoutDescriptor,
+    #[cfg_attr(feature = "serde", serde(default))]
+    warnings: Vec<DecodeWarning>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    crs: Option<Crs>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    min_max: Vec<RecordedMinMax>,
+    /// Unique id and name of attributes added after decoding
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    attribute_names: Vec<(u32, String)>,
 }
 
 impl DracoDecodeConfig {
@@ -96,7 +613,7 @@ impl DracoDecodeConfig {
     ///
     /// Used internally when decoding from C++ FFI.
     pub(crate) fn new(vertex_count: u32, index_count: u32, buffer_size: usize) -> Self {
-        let index_length = if index_count <= u16::MAX as u32 {
+        let index_length = if vertex_count <= u16::MAX as u32 + 1 {
             index_count as usize * 2
         } else {
             index_count as usize * 4
@@ -108,30 +625,81 @@ impl DracoDecodeConfig {
             index_length,
             buffer_size,
             attributes: Vec::new(),
+            layout: LayoutDescriptor::default(),
+            warnings: Vec::new(),
+            crs: None,
+            min_max: Vec::new(),
+            attribute_names: Vec::new(),
         }
     }
 
+    /// Overrides the index length computed from the index count, for layouts
+    /// with a forced index width.
+    pub(crate) fn with_index_length(mut self, index_length: u32) -> Self {
+        self.index_length = index_length;
+        self
+    }
+
+    /// Sets the index count, keeping the index width.
+    ///
+    /// Used by passes that drop triangles from the index section.
+    pub(crate) fn set_index_count(&mut self, index_count: u32) {
+        self.index_length = index_count * self.index_size_in_bytes() as u32;
+        self.index_count = index_count;
+    }
+
+    /// Returns the descriptor of the layout of the decoded buffer.
+    pub fn layout(&self) -> LayoutDescriptor {
+        self.layout
+    }
+
     /// Returns the total byte length of the index data.
     pub fn index_length(&self) -> u32 {
         self.index_length
     }
 
+    /// Returns the size in bytes of a single index (2 for `u16`, 4 for `u32`).
+    pub fn index_size_in_bytes(&self) -> usize {
+        self.index_length
+            .checked_div(self.index_count)
+            .map_or(2, |size| size as usize)
+    }
+
+    /// Returns an error if an index of `data`, a buffer in this layout, is
+    /// not less than the vertex count.
+    ///
+    /// Reads the index bytes in place, so buffers written by
+    /// [`MeshHandle::decode_into_with_options`](crate::MeshHandle::decode_into_with_options)
+    /// are checked without a copy.
+    pub(crate) fn check_indices(&self, data: &[u8]) -> Result<(), DecodeError> {
+        let vertex_count = self.vertex_count;
+        let bytes = data
+            .get(..self.index_length as usize)
+            .ok_or(DecodeError::DecodeFailed)?;
+        let out_of_bounds = match self.index_size_in_bytes() {
+            2 => bytes
+                .chunks_exact(2)
+                .map(|index| u32::from(u16::from_ne_bytes([index[0], index[1]])))
+                .find(|&index| index >= vertex_count),
+            4 => bytes
+                .chunks_exact(4)
+                .map(|index| u32::from_ne_bytes([index[0], index[1], index[2], index[3]]))
+                .find(|&index| index >= vertex_count),
+            _ => return Err(DecodeError::DecodeFailed),
+        };
+        match out_of_bounds {
+            Some(index) => Err(DecodeError::IndexOutOfBounds {
+                index,
+                vertex_count,
+            }),
+            None => Ok(()),
+        }
+    }
+
     /// Adds an attribute with specified offset and length.
     ///
     /// Used internally when receiving attribute data from C++ FFI.
-    pub(crate) fn add_attribute(
-        &mut self,
-        dim: u32,
-        data_type: AttributeDataType,
-        offset: u32,
-        length: u32,
-    ) {
-        let attribute = MeshAttribute {
-            dim,
-            data_type,
-            offset,
-            lenght: length,
-        };
+    pub(crate) fn add_attribute(&mut self, attribute: MeshAttribute) {
         self.attributes.push(attribute);
     }
 
@@ -140,11 +708,119 @@ impl DracoDecodeConfig {
         self.attributes.get(index)
     }
 
+    /// Returns the index of the first attribute with the given semantic.
+    pub fn find_attribute(&self, semantic: AttributeSemantic) -> Option<usize> {
+        self.attributes
+            .iter()
+            .position(|a| a.semantic() == semantic)
+    }
+
     /// Returns a vector of all attributes.
     pub fn attributes(&self) -> Vec<MeshAttribute> {
         self.attributes.clone()
     }
 
+    /// Returns the component-wise `(min, max)` of the attribute at the given
+    /// index, recorded while decoding with
+    /// [`DecodeOptions::compute_min_max`](crate::DecodeOptions::compute_min_max).
+    ///
+    /// The values are typed like
+    /// [`MeshAttribute::compute_min_max`](crate::MeshAttribute::compute_min_max).
+    /// Returns `None` if they were not recorded or the attribute has no
+    /// vertices.
+    pub fn min_max(&self, index: usize) -> Option<(AttributeValues, AttributeValues)> {
+        let attribute = self.attributes.get(index)?;
+        let (_, min, max) = self.min_max.iter().find(|(i, _, _)| *i == index)?;
+        Some((
+            AttributeValues::from_bytes(attribute.data_type(), min),
+            AttributeValues::from_bytes(attribute.data_type(), max),
+        ))
+    }
+
+    /// Returns the name of the attribute at the given index.
+    ///
+    /// Draco attributes are identified by their semantic and unique id only,
+    /// so names are given to attributes added after decoding whose meaning
+    /// the semantic cannot express, like the `TANGENT` attribute of
+    /// [`generate_tangents`](crate::postprocess::generate_tangents). Names
+    /// follow the glTF attribute names.
+    pub fn attribute_name(&self, index: usize) -> Option<&str> {
+        let unique_id = self.attributes.get(index)?.unique_id();
+        self.attribute_names
+            .iter()
+            .find(|(id, _)| *id == unique_id)
+            .map(|(_, name)| name.as_str())
+    }
+
+    /// Names the attribute with the given unique id.
+    #[cfg(feature = "mikktspace")]
+    pub(crate) fn set_attribute_name(&mut self, unique_id: u32, name: &str) {
+        self.attribute_names.retain(|(id, _)| *id != unique_id);
+        self.attribute_names.push((unique_id, name.to_string()));
+    }
+
+    /// Records the bytes of the extrema of the attribute at `index`.
+    pub(crate) fn set_min_max(&mut self, index: usize, min: Vec<u8>, max: Vec<u8>) {
+        self.min_max.retain(|(i, _, _)| *i != index);
+        self.min_max.push((index, min, max));
+    }
+
+    /// Returns `true` if the indices and every attribute lie within the
+    /// buffer and none of them overlap.
+    ///
+    /// Layouts reported by the decoder are checked with this before a buffer
+    /// is allocated for them, so a corrupt layout fails the decode instead
+    /// of reaching the accessors.
+    pub(crate) fn has_valid_layout(&self) -> bool {
+        is_valid_layout(
+            std::iter::once((0, self.index_length)).chain(
+                self.attributes
+                    .iter()
+                    .map(|attribute| (attribute.offset(), attribute.lenght())),
+            ),
+            self.buffer_size,
+        )
+    }
+
+    /// Records an attribute that was skipped or converted while decoding.
+    pub(crate) fn add_warning(&mut self, warning: DecodeWarning) {
+        self.warnings.push(warning);
+    }
+
+    /// Returns the attributes that were skipped or converted while decoding.
+    ///
+    /// Empty if every attribute of the bitstream is part of the result as
+    /// stored.
+    pub fn warnings(&self) -> &[DecodeWarning] {
+        &self.warnings
+    }
+
+    /// Sets the coordinate reference system of the positions.
+    pub(crate) fn set_crs(&mut self, crs: Option<Crs>) {
+        self.crs = crs;
+    }
+
+    /// Copies the warnings, coordinate reference system and the names of the
+    /// kept attributes of `source`, for results derived from another one.
+    pub(crate) fn inherit(&mut self, source: &Self) {
+        self.warnings.extend_from_slice(&source.warnings);
+        self.crs = source.crs;
+        let kept: Vec<(u32, String)> = source
+            .attribute_names
+            .iter()
+            .filter(|(id, _)| self.attributes.iter().any(|a| a.unique_id() == *id))
+            .cloned()
+            .collect();
+        self.attribute_names.extend(kept);
+    }
+
+    /// Returns the coordinate reference system of the positions, read from
+    /// the geometry metadata or attached with
+    /// [`MeshDecodeResult::with_crs`].
+    pub fn crs(&self) -> Option<Crs> {
+        self.crs
+    }
+
     /// Returns the number of vertices in the mesh.
     pub fn vertex_count(&self) -> u32 {
         self.vertex_count
@@ -170,8 +846,50 @@ impl DracoDecodeConfig {
     }
 }
 
+/// Index data of a decoded mesh.
+///
+/// The decoder writes 16-bit indices when they fit and 32-bit indices otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndexBuffer {
+    /// 16-bit indices
+    U16(Vec<u16>),
+    /// 32-bit indices
+    U32(Vec<u32>),
+}
+
+impl IndexBuffer {
+    /// Returns the number of indices.
+    pub fn len(&self) -> usize {
+        match self {
+            IndexBuffer::U16(v) => v.len(),
+            IndexBuffer::U32(v) => v.len(),
+        }
+    }
+
+    /// Returns `true` if there are no indices.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the index at the given position widened to `u32`.
+    pub fn get(&self, index: usize) -> Option<u32> {
+        match self {
+            IndexBuffer::U16(v) => v.get(index).map(|&i| i as u32),
+            IndexBuffer::U32(v) => v.get(index).copied(),
+        }
+    }
+
+    /// Converts the indices to `u32`, widening 16-bit indices if needed.
+    pub fn into_u32(self) -> Vec<u32> {
+        match self {
+            IndexBuffer::U16(v) => v.into_iter().map(u32::from).collect(),
+            IndexBuffer::U32(v) => v,
+        }
+    }
+}
+
 /// Typed values for a decoded mesh attribute.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum AttributeValues {
     /// Signed 8-bit integer values
     Int8(Vec<i8>),
@@ -189,13 +907,280 @@ pub enum AttributeValues {
     Float32(Vec<f32>),
 }
 
+/// A typed view of a decoded attribute.
+///
+/// The view borrows the decoded buffer when the attribute data is suitably
+/// aligned for `T`, and holds a copy otherwise. It dereferences to the flat
+/// slice of component values.
+#[derive(Debug, Clone)]
+pub struct AttributeView<'a, T: AttributeComponent> {
+    values: Cow<'a, [T]>,
+    dim: usize,
+}
+
+impl<'a, T: AttributeComponent> AttributeView<'a, T> {
+    /// Returns the number of components per vertex.
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Returns the number of vertices in the view.
+    pub fn vertex_count(&self) -> usize {
+        self.values.len().checked_div(self.dim).unwrap_or(0)
+    }
+
+    /// Returns the components of the vertex at the given index.
+    pub fn vertex(&self, index: usize) -> Option<&[T]> {
+        let start = index.checked_mul(self.dim)?;
+        self.values.get(start..start + self.dim)
+    }
+
+    /// Returns an iterator over the components of each vertex.
+    pub fn vertices(&self) -> impl Iterator<Item = &[T]> {
+        self.values.chunks_exact(self.dim.max(1))
+    }
+
+    /// Returns `true` if the view borrows the decoded buffer without copying.
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.values, Cow::Borrowed(_))
+    }
+
+    /// Converts the view into an owned vector of component values.
+    pub fn into_owned(self) -> Vec<T> {
+        self.values.into_owned()
+    }
+}
+
+impl<T: AttributeComponent> Deref for AttributeView<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.values
+    }
+}
+
 /// Result of decoding a Draco mesh.
 ///
 /// Contains the decoded mesh buffer and metadata describing its layout.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MeshDecodeResult {
     /// The decoded mesh buffer containing indices and attribute data.
     pub data: Vec<u8>,
     /// Metadata describing the mesh structure and attribute layouts.
     pub config: DracoDecodeConfig,
 }
+
+impl AttributeValues {
+    /// Returns the number of component values.
+    pub fn len(&self) -> usize {
+        match self {
+            AttributeValues::Int8(v) => v.len(),
+            AttributeValues::UInt8(v) => v.len(),
+            AttributeValues::Int16(v) => v.len(),
+            AttributeValues::UInt16(v) => v.len(),
+            AttributeValues::Int32(v) => v.len(),
+            AttributeValues::UInt32(v) => v.len(),
+            AttributeValues::Float32(v) => v.len(),
+        }
+    }
+
+    /// Returns `true` if there are no component values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reinterprets decoded attribute bytes as typed values.
+    ///
+    /// The caller must ensure `bytes.len()` is a multiple of the data type size.
+    pub(crate) fn from_bytes(data_type: AttributeDataType, bytes: &[u8]) -> Self {
+        match data_type {
+            AttributeDataType::Int8 => AttributeValues::Int8(bytemuck::pod_collect_to_vec(bytes)),
+            AttributeDataType::UInt8 => AttributeValues::UInt8(bytes.to_vec()),
+            AttributeDataType::Int16 => AttributeValues::Int16(bytemuck::pod_collect_to_vec(bytes)),
+            AttributeDataType::UInt16 => {
+                AttributeValues::UInt16(bytemuck::pod_collect_to_vec(bytes))
+            }
+            AttributeDataType::Int32 => AttributeValues::Int32(bytemuck::pod_collect_to_vec(bytes)),
+            AttributeDataType::UInt32 => {
+                AttributeValues::UInt32(bytemuck::pod_collect_to_vec(bytes))
+            }
+            AttributeDataType::Float32 => {
+                AttributeValues::Float32(bytemuck::pod_collect_to_vec(bytes))
+            }
+        }
+    }
+}
+
+impl MeshDecodeResult {
+    /// Builds a result from indices and per-attribute vertex data.
+    ///
+    /// The layout matches the decoders: indices first, followed by each
+    /// attribute tightly packed in the given order. Only the dimension, data
+    /// type and descriptive fields of each attribute are kept; offsets and
+    /// lengths are recomputed.
+    pub(crate) fn from_parts(
+        vertex_count: u32,
+        indices: &[u32],
+        attributes: Vec<(MeshAttribute, Vec<u8>)>,
+    ) -> Self {
+        let attribute_bytes: usize = attributes.iter().map(|(_, bytes)| bytes.len()).sum();
+        let mut config = DracoDecodeConfig::new(vertex_count, indices.len() as u32, 0);
+        let mut data = Vec::with_capacity(config.index_length() as usize + attribute_bytes);
+
+        if config.index_size_in_bytes() == 2 {
+            for &index in indices {
+                data.extend_from_slice(&(index as u16).to_ne_bytes());
+            }
+        } else {
+            for &index in indices {
+                data.extend_from_slice(&index.to_ne_bytes());
+            }
+        }
+
+        for (attribute, bytes) in attributes {
+            config.add_attribute(attribute.with_layout(data.len() as u32, bytes.len() as u32));
+            data.extend_from_slice(&bytes);
+        }
+
+        config.buffer_size = data.len();
+        Self { data, config }
+    }
+
+    /// Appends an attribute to the end of the buffer, at an offset aligned to
+    /// 4 bytes.
+    pub(crate) fn append_attribute(&mut self, attribute: MeshAttribute, bytes: &[u8]) {
+        self.data.resize(self.data.len().next_multiple_of(4), 0);
+        self.config
+            .add_attribute(attribute.with_layout(self.data.len() as u32, bytes.len() as u32));
+        self.data.extend_from_slice(bytes);
+        self.config.buffer_size = self.data.len();
+    }
+
+    /// Keeps only the attributes for which `keep` returns `true`, for
+    /// example to drop normals or generic attributes before exporting.
+    ///
+    /// The remaining attributes are repacked after the indices in their
+    /// original order; warnings and the coordinate reference system are kept.
+    /// Attributes whose bytes lie outside of the decoded buffer are dropped.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use draco_decoder::AttributeSemantic;
+    ///
+    /// let result = result.retain_attributes(|a| a.semantic() != AttributeSemantic::Normal);
+    /// ```
+    pub fn retain_attributes(&self, mut keep: impl FnMut(&MeshAttribute) -> bool) -> Self {
+        let indices: Vec<u32> = self.triangles().flatten().collect();
+        let attributes = self
+            .config
+            .attributes()
+            .into_iter()
+            .filter(|attribute| keep(attribute))
+            .filter_map(|attribute| Some((attribute, attribute.bytes_in(&self.data)?.to_vec())))
+            .collect();
+        let mut result = Self::from_parts(self.config.vertex_count(), &indices, attributes);
+        result.config.inherit(&self.config);
+        result
+    }
+
+    /// Returns the raw bytes of the index data at the start of the decoded buffer.
+    ///
+    /// Returns `None` if the decoded buffer is shorter than `index_length`.
+    pub fn index_bytes(&self) -> Option<&[u8]> {
+        self.data.get(..self.config.index_length() as usize)
+    }
+
+    /// Returns the decoded indices as 16-bit or 32-bit values.
+    ///
+    /// Returns `None` if the index data lies outside of the decoded buffer or
+    /// uses an unexpected index size.
+    pub fn indices(&self) -> Option<IndexBuffer> {
+        let bytes = self.index_bytes()?;
+        match self.config.index_size_in_bytes() {
+            2 => Some(IndexBuffer::U16(bytemuck::pod_collect_to_vec(bytes))),
+            4 => Some(IndexBuffer::U32(bytemuck::pod_collect_to_vec(bytes))),
+            _ => None,
+        }
+    }
+
+    /// Returns an error if an index is not less than the vertex count.
+    ///
+    /// Used for [`DecodeOptions::validate_indices`](crate::DecodeOptions::validate_indices).
+    pub(crate) fn check_indices(&self) -> Result<(), DecodeError> {
+        self.config.check_indices(&self.data)
+    }
+
+    /// Returns an iterator over the triangles of the mesh.
+    ///
+    /// Each triangle is yielded as three vertex indices widened to `u32`. The
+    /// iterator is empty if the index data lies outside of the decoded buffer
+    /// or uses an unexpected index size.
+    pub fn triangles(&self) -> impl Iterator<Item = [u32; 3]> + '_ {
+        let index_size = self.config.index_size_in_bytes();
+        let bytes = match index_size {
+            2 | 4 => self.index_bytes().unwrap_or(&[]),
+            _ => &[],
+        };
+        let index_size = index_size.max(1);
+
+        bytes.chunks_exact(index_size * 3).map(move |triangle| {
+            let mut out = [0u32; 3];
+            for (value, bytes) in out.iter_mut().zip(triangle.chunks_exact(index_size)) {
+                *value = match *bytes {
+                    [a, b] => u16::from_ne_bytes([a, b]) as u32,
+                    [a, b, c, d] => u32::from_ne_bytes([a, b, c, d]),
+                    _ => 0,
+                };
+            }
+            out
+        })
+    }
+
+    /// Returns the raw bytes of the attribute at the given index.
+    ///
+    /// Returns `None` if the attribute does not exist or its byte range lies
+    /// outside of the decoded buffer.
+    pub fn attribute_bytes(&self, index: usize) -> Option<&[u8]> {
+        self.config.get_attribute(index)?.bytes_in(&self.data)
+    }
+
+    /// Returns a typed view of the attribute at the given index.
+    ///
+    /// The view borrows the decoded buffer when the attribute is aligned for
+    /// `T` and falls back to copying the values otherwise.
+    ///
+    /// Returns `None` if the attribute does not exist, its data type does not
+    /// match `T`, or its byte range is invalid.
+    pub fn view<T: AttributeComponent>(&self, index: usize) -> Option<AttributeView<'_, T>> {
+        self.config.get_attribute(index)?.view_in(&self.data)
+    }
+
+    /// Returns the typed values of the attribute at the given index.
+    ///
+    /// The attribute bytes are copied out of the decoded buffer, so the result
+    /// does not depend on the alignment of the attribute offset.
+    ///
+    /// Returns `None` if the attribute does not exist, its byte range lies
+    /// outside of the decoded buffer, or its length is not a whole number of
+    /// `dim` component values.
+    pub fn attribute_values(&self, index: usize) -> Option<AttributeValues> {
+        self.config.get_attribute(index)?.values_in(&self.data)
+    }
+}
+
+/// Returns `true` if the sections, given as `(offset, length)` pairs, lie
+/// within `buffer_size` bytes and do not overlap each other.
+pub(crate) fn is_valid_layout(
+    sections: impl IntoIterator<Item = (u32, u32)>,
+    buffer_size: usize,
+) -> bool {
+    let mut ranges: Vec<(u64, u64)> = sections
+        .into_iter()
+        .map(|(offset, length)| (u64::from(offset), u64::from(offset) + u64::from(length)))
+        .collect();
+    ranges.sort_unstable();
+    ranges.windows(2).all(|pair| pair[0].1 <= pair[1].0)
+        && ranges.iter().all(|&(_, end)| end <= buffer_size as u64)
+}
@@ -0,0 +1,251 @@
+use crate::{
+    AttributeDataType, AttributeSemantic, MeshAttribute, MeshDecodeResult, PointCloudDecodeResult,
+};
+
+/// A vector type with `N` `f32` components.
+///
+/// Typed attribute iterators such as [`MeshDecodeResult::vectors`] yield any
+/// type implementing this trait, so decoded data can be consumed directly as
+/// the math types of an engine. Implementations for `[f32; N]` are always
+/// available; the `glam`, `nalgebra` and `mint` features add implementations
+/// for the vector types of those crates. With `mint`, points can be read as
+/// `mint::Point3` as well.
+pub trait VectorLike<const N: usize>: Sized {
+    /// Creates a vector from its components.
+    fn from_array(components: [f32; N]) -> Self;
+
+    /// Returns the components of the vector.
+    fn to_array(&self) -> [f32; N];
+}
+
+impl<const N: usize> VectorLike<N> for [f32; N] {
+    fn from_array(components: [f32; N]) -> Self {
+        components
+    }
+
+    fn to_array(&self) -> [f32; N] {
+        *self
+    }
+}
+
+macro_rules! impl_vector_like {
+    ($feature:literal, $n:literal, $ty:ty) => {
+        #[cfg(feature = $feature)]
+        impl VectorLike<$n> for $ty {
+            fn from_array(components: [f32; $n]) -> Self {
+                components.into()
+            }
+
+            fn to_array(&self) -> [f32; $n] {
+                (*self).into()
+            }
+        }
+    };
+}
+
+impl_vector_like!("glam", 2, glam::Vec2);
+impl_vector_like!("glam", 3, glam::Vec3);
+impl_vector_like!("glam", 4, glam::Vec4);
+impl_vector_like!("nalgebra", 2, nalgebra::Vector2<f32>);
+impl_vector_like!("nalgebra", 3, nalgebra::Vector3<f32>);
+impl_vector_like!("nalgebra", 4, nalgebra::Vector4<f32>);
+impl_vector_like!("mint", 2, mint::Vector2<f32>);
+impl_vector_like!("mint", 3, mint::Vector3<f32>);
+impl_vector_like!("mint", 4, mint::Vector4<f32>);
+impl_vector_like!("mint", 2, mint::Point2<f32>);
+impl_vector_like!("mint", 3, mint::Point3<f32>);
+
+/// Iterates over the values of a `Float32` attribute with `N` components.
+fn vectors_in<'a, V: VectorLike<N>, const N: usize>(
+    attribute: &MeshAttribute,
+    data: &'a [u8],
+) -> Option<impl Iterator<Item = V> + 'a> {
+    if attribute.data_type() != AttributeDataType::Float32 || attribute.dim() as usize != N {
+        return None;
+    }
+
+    let bytes = attribute.bytes_in(data)?;
+    Some(bytes.chunks_exact(N * 4).map(|element| {
+        let mut components = [0.0; N];
+        for (component, bytes) in components.iter_mut().zip(element.chunks_exact(4)) {
+            *component = f32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+        V::from_array(components)
+    }))
+}
+
+/// Reads a component as `f64`.
+fn component(data_type: AttributeDataType, bytes: &[u8]) -> f64 {
+    match (data_type, bytes) {
+        (AttributeDataType::Int8, &[a]) => a as i8 as f64,
+        (AttributeDataType::UInt8, &[a]) => a as f64,
+        (AttributeDataType::Int16, &[a, b]) => i16::from_ne_bytes([a, b]) as f64,
+        (AttributeDataType::UInt16, &[a, b]) => u16::from_ne_bytes([a, b]) as f64,
+        (AttributeDataType::Int32, &[a, b, c, d]) => i32::from_ne_bytes([a, b, c, d]) as f64,
+        (AttributeDataType::UInt32, &[a, b, c, d]) => u32::from_ne_bytes([a, b, c, d]) as f64,
+        (AttributeDataType::Float32, &[a, b, c, d]) => f32::from_ne_bytes([a, b, c, d]) as f64,
+        _ => 0.0,
+    }
+}
+
+/// Largest value of a data type, the divisor of normalized integers.
+fn normalization_max(data_type: AttributeDataType) -> f64 {
+    match data_type {
+        AttributeDataType::Int8 => i8::MAX as f64,
+        AttributeDataType::UInt8 => u8::MAX as f64,
+        AttributeDataType::Int16 => i16::MAX as f64,
+        AttributeDataType::UInt16 => u16::MAX as f64,
+        AttributeDataType::Int32 => i32::MAX as f64,
+        AttributeDataType::UInt32 => u32::MAX as f64,
+        AttributeDataType::Float32 => 1.0,
+    }
+}
+
+/// Iterates over the values of an attribute with `N` components of any data
+/// type, converted to floats.
+///
+/// Quantized values are dequantized, normalized integers are mapped to
+/// `0.0..=1.0` or `-1.0..=1.0`, and octahedral normals are decoded to three
+/// components. The zero component of padded attributes is skipped.
+pub(crate) fn converted_vectors_in<'a, V: VectorLike<N>, const N: usize>(
+    attribute: &MeshAttribute,
+    data: &'a [u8],
+) -> Option<impl Iterator<Item = V> + 'a> {
+    let octahedron = attribute
+        .octahedron()
+        .filter(|_| N == 3 && attribute.dim() == 2);
+    // Padded vec3 attributes are read without their zero fourth component
+    let dim = (attribute.dim() as usize).saturating_sub(usize::from(attribute.padded()));
+    if octahedron.is_none() && dim != N {
+        return None;
+    }
+
+    let data_type = attribute.data_type();
+    let component_size = data_type.size_in_bytes();
+    let quantization = attribute.quantization();
+    let normalized = attribute.normalized() && data_type != AttributeDataType::Float32;
+    let bytes = attribute.bytes_in(data)?;
+    Some(
+        bytes
+            .chunks_exact(attribute.element_size())
+            .map(move |element| {
+                let raw = |i: usize| {
+                    component(
+                        data_type,
+                        &element[i * component_size..(i + 1) * component_size],
+                    )
+                };
+                let mut components = [0.0; N];
+                if let Some(octahedron) = octahedron {
+                    let normal = octahedron.decode(raw(0) as u32, raw(1) as u32);
+                    components.copy_from_slice(&normal[..N]);
+                    return V::from_array(components);
+                }
+                for (i, value) in components.iter_mut().enumerate() {
+                    *value = match &quantization {
+                        Some(quantization) => {
+                            quantization.dequantize(i, raw(i) as u32).unwrap_or(0.0)
+                        }
+                        None if normalized => {
+                            (raw(i) / normalization_max(data_type)).max(-1.0) as f32
+                        }
+                        None => raw(i) as f32,
+                    };
+                }
+                V::from_array(components)
+            }),
+    )
+}
+
+impl MeshDecodeResult {
+    /// Returns an iterator over the values of the attribute at the given index
+    /// as vectors of type `V`.
+    ///
+    /// The values are read from the decoded buffer without copying it, so the
+    /// attribute offset does not need to be aligned.
+    ///
+    /// Returns `None` if the attribute does not exist, is not `Float32`, does
+    /// not have exactly `N` components, or its byte range is invalid.
+    pub fn vectors<V: VectorLike<N>, const N: usize>(
+        &self,
+        index: usize,
+    ) -> Option<impl Iterator<Item = V> + '_> {
+        vectors_in(self.config.get_attribute(index)?, &self.data)
+    }
+
+    /// Returns an iterator over the vertex positions as vectors of type `V`.
+    ///
+    /// Positions of any data type are converted to floats: quantized
+    /// positions are dequantized and normalized integers mapped to
+    /// `0.0..=1.0` or `-1.0..=1.0`.
+    ///
+    /// Returns `None` if the mesh has no three component position attribute.
+    pub fn positions<V: VectorLike<3>>(&self) -> Option<impl Iterator<Item = V> + '_> {
+        self.semantic_vectors(AttributeSemantic::Position, 0)
+    }
+
+    /// Returns an iterator over the vertex normals as vectors of type `V`.
+    ///
+    /// Normals are converted like [`MeshDecodeResult::positions`], and
+    /// normals kept in octahedral encoding are decoded.
+    ///
+    /// Returns `None` if the mesh has no normal attribute with three
+    /// components or octahedral encoding.
+    pub fn normals<V: VectorLike<3>>(&self) -> Option<impl Iterator<Item = V> + '_> {
+        self.semantic_vectors(AttributeSemantic::Normal, 0)
+    }
+
+    /// Returns an iterator over the texture coordinates of the given set as
+    /// vectors of type `V`, set 0 being the first texture coordinate
+    /// attribute.
+    ///
+    /// Texture coordinates are converted like
+    /// [`MeshDecodeResult::positions`].
+    ///
+    /// Returns `None` if the mesh has no such set with two components.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // With the `glam` feature
+    /// let uvs: Vec<glam::Vec2> = result.uvs(0).unwrap().collect();
+    /// ```
+    pub fn uvs<V: VectorLike<2>>(&self, set: usize) -> Option<impl Iterator<Item = V> + '_> {
+        self.semantic_vectors(AttributeSemantic::TexCoord, set)
+    }
+
+    /// Returns the converted values of the `set`-th attribute with the given
+    /// semantic.
+    fn semantic_vectors<V: VectorLike<N>, const N: usize>(
+        &self,
+        semantic: AttributeSemantic,
+        set: usize,
+    ) -> Option<impl Iterator<Item = V> + '_> {
+        let attribute = (0..self.config.attributes().len())
+            .filter_map(|index| self.config.get_attribute(index))
+            .filter(|attribute| attribute.semantic() == semantic)
+            .nth(set)?;
+        converted_vectors_in(attribute, &self.data)
+    }
+}
+
+impl PointCloudDecodeResult {
+    /// Returns an iterator over the values of the attribute at the given index
+    /// as vectors of type `V`.
+    ///
+    /// See [`MeshDecodeResult::vectors`].
+    pub fn vectors<V: VectorLike<N>, const N: usize>(
+        &self,
+        index: usize,
+    ) -> Option<impl Iterator<Item = V> + '_> {
+        vectors_in(self.config.get_attribute(index)?, &self.data)
+    }
+
+    /// Returns an iterator over the point positions as vectors of type `V`.
+    ///
+    /// See [`MeshDecodeResult::positions`].
+    pub fn positions<V: VectorLike<3>>(&self) -> Option<impl Iterator<Item = V> + '_> {
+        let position = self.config.find_attribute(AttributeSemantic::Position)?;
+        converted_vectors_in(self.config.get_attribute(position)?, &self.data)
+    }
+}
@@ -0,0 +1,308 @@
+use crate::{AttributeDataType, AttributeSemantic, DracoDecodeConfig, MeshDecodeResult};
+
+/// wgpu requires vertex strides and attribute offsets to be multiples of 4.
+const VERTEX_ALIGNMENT: u64 = 4;
+
+macro_rules! vertex_formats {
+    ($($format:ident => ($data_type:ident, $normalized:literal, $dim:literal)),* $(,)?) => {
+        /// Vertex attribute formats, named and laid out like `wgpu::VertexFormat`.
+        ///
+        /// Only the formats a decoded attribute can have are listed.
+        /// Three-component 8 and 16-bit attributes, such as RGB colors, have
+        /// no vertex format.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum VertexFormat {
+            $($format,)*
+        }
+
+        impl VertexFormat {
+            /// Returns the format of a decoded attribute, `None` if it has no
+            /// matching vertex format.
+            pub fn from_attribute(
+                data_type: AttributeDataType,
+                dim: u32,
+                normalized: bool,
+            ) -> Option<Self> {
+                // Normalization only applies to 8 and 16-bit integers
+                let normalized = normalized && data_type.size_in_bytes() < 4;
+                match (data_type, normalized, dim) {
+                    $((AttributeDataType::$data_type, $normalized, $dim) => Some(VertexFormat::$format),)*
+                    _ => None,
+                }
+            }
+
+            /// Returns the size in bytes of one vertex in this format.
+            pub fn size(&self) -> u64 {
+                match self {
+                    $(VertexFormat::$format => {
+                        $dim * AttributeDataType::$data_type.size_in_bytes() as u64
+                    })*
+                }
+            }
+
+            /// Converts to the matching `wgpu::VertexFormat`.
+            #[cfg(feature = "wgpu")]
+            pub fn to_wgpu(self) -> wgpu::VertexFormat {
+                match self {
+                    $(VertexFormat::$format => wgpu::VertexFormat::$format,)*
+                }
+            }
+        }
+    };
+}
+
+vertex_formats!(
+    Uint8 => (UInt8, false, 1),
+    Uint8x2 => (UInt8, false, 2),
+    Uint8x4 => (UInt8, false, 4),
+    Sint8 => (Int8, false, 1),
+    Sint8x2 => (Int8, false, 2),
+    Sint8x4 => (Int8, false, 4),
+    Unorm8 => (UInt8, true, 1),
+    Unorm8x2 => (UInt8, true, 2),
+    Unorm8x4 => (UInt8, true, 4),
+    Snorm8 => (Int8, true, 1),
+    Snorm8x2 => (Int8, true, 2),
+    Snorm8x4 => (Int8, true, 4),
+    Uint16 => (UInt16, false, 1),
+    Uint16x2 => (UInt16, false, 2),
+    Uint16x4 => (UInt16, false, 4),
+    Sint16 => (Int16, false, 1),
+    Sint16x2 => (Int16, false, 2),
+    Sint16x4 => (Int16, false, 4),
+    Unorm16 => (UInt16, true, 1),
+    Unorm16x2 => (UInt16, true, 2),
+    Unorm16x4 => (UInt16, true, 4),
+    Snorm16 => (Int16, true, 1),
+    Snorm16x2 => (Int16, true, 2),
+    Snorm16x4 => (Int16, true, 4),
+    Float32 => (Float32, false, 1),
+    Float32x2 => (Float32, false, 2),
+    Float32x3 => (Float32, false, 3),
+    Float32x4 => (Float32, false, 4),
+    Uint32 => (UInt32, false, 1),
+    Uint32x2 => (UInt32, false, 2),
+    Uint32x3 => (UInt32, false, 3),
+    Uint32x4 => (UInt32, false, 4),
+    Sint32 => (Int32, false, 1),
+    Sint32x2 => (Int32, false, 2),
+    Sint32x3 => (Int32, false, 3),
+    Sint32x4 => (Int32, false, 4),
+);
+
+/// How the vertex data of [`MeshDecodeResult::vertex_buffer_data`] is
+/// arranged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VertexLayoutMode {
+    /// One block per attribute, each bound as its own vertex buffer.
+    #[default]
+    Planar,
+    /// All attributes of a vertex next to each other in a single vertex
+    /// buffer.
+    Interleaved,
+}
+
+/// Placement of one attribute within a [`VertexBufferLayout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VertexAttributeLayout {
+    /// Format of the attribute.
+    pub format: VertexFormat,
+    /// Byte offset of the attribute from the start of a vertex.
+    pub offset: u64,
+    /// Suggested `@location` in the vertex shader. Described attributes are
+    /// numbered from 0 in the order of the decoded attributes.
+    pub shader_location: u32,
+    /// Semantic of the attribute.
+    pub semantic: AttributeSemantic,
+    /// Index of the attribute in [`DracoDecodeConfig::attributes`].
+    pub attribute: usize,
+}
+
+/// A vertex buffer described like `wgpu::VertexBufferLayout`, along with its
+/// place in the data of [`MeshDecodeResult::vertex_buffer_data`].
+///
+/// Vertices are always stepped per vertex.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VertexBufferLayout {
+    array_stride: u64,
+    buffer_offset: u64,
+    buffer_size: u64,
+    attributes: Vec<VertexAttributeLayout>,
+    #[cfg(feature = "wgpu")]
+    wgpu_attributes: Vec<wgpu::VertexAttribute>,
+}
+
+impl VertexBufferLayout {
+    fn new(array_stride: u64, vertex_count: u64, attributes: Vec<VertexAttributeLayout>) -> Self {
+        Self {
+            array_stride,
+            buffer_offset: 0,
+            buffer_size: array_stride * vertex_count,
+            #[cfg(feature = "wgpu")]
+            wgpu_attributes: attributes
+                .iter()
+                .map(|attribute| wgpu::VertexAttribute {
+                    format: attribute.format.to_wgpu(),
+                    offset: attribute.offset,
+                    shader_location: attribute.shader_location,
+                })
+                .collect(),
+            attributes,
+        }
+    }
+
+    /// Returns the distance in bytes between consecutive vertices.
+    pub fn array_stride(&self) -> u64 {
+        self.array_stride
+    }
+
+    /// Returns the byte offset of this buffer within the vertex data, the
+    /// offset to bind it at.
+    pub fn buffer_offset(&self) -> u64 {
+        self.buffer_offset
+    }
+
+    /// Returns the size in bytes of this buffer.
+    pub fn buffer_size(&self) -> u64 {
+        self.buffer_size
+    }
+
+    /// Returns the attributes stored in this buffer.
+    pub fn attributes(&self) -> &[VertexAttributeLayout] {
+        &self.attributes
+    }
+
+    /// Returns the layout as a `wgpu::VertexBufferLayout`, to be listed in
+    /// the vertex state of a render pipeline.
+    #[cfg(feature = "wgpu")]
+    pub fn to_wgpu(&self) -> wgpu::VertexBufferLayout<'_> {
+        wgpu::VertexBufferLayout {
+            array_stride: self.array_stride,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &self.wgpu_attributes,
+        }
+    }
+}
+
+impl DracoDecodeConfig {
+    /// Describes the vertex buffers of [`MeshDecodeResult::vertex_buffer_data`]
+    /// for a render pipeline.
+    ///
+    /// In [`VertexLayoutMode::Planar`] mode every attribute gets its own
+    /// layout, in [`VertexLayoutMode::Interleaved`] mode a single layout holds
+    /// all of them. Strides and offsets are padded to multiples of 4 bytes as
+    /// wgpu requires. Attributes without a [`VertexFormat`] are left out;
+    /// decode them as `Float32` with [`crate::DecodeOptions::with_output_type`]
+    /// to include them.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use draco_decoder::VertexLayoutMode;
+    ///
+    /// let layouts = result.config.vertex_buffer_layouts(VertexLayoutMode::Interleaved);
+    /// let buffers: Vec<_> = layouts.iter().map(|layout| layout.to_wgpu()).collect();
+    /// ```
+    pub fn vertex_buffer_layouts(&self, mode: VertexLayoutMode) -> Vec<VertexBufferLayout> {
+        let vertex_count = self.vertex_count() as u64;
+        let mut attributes: Vec<VertexAttributeLayout> = self
+            .attributes()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, attribute)| {
+                let format = VertexFormat::from_attribute(
+                    attribute.data_type(),
+                    attribute.dim(),
+                    attribute.normalized(),
+                )?;
+                Some((index, attribute.semantic(), format))
+            })
+            .enumerate()
+            .map(
+                |(location, (attribute, semantic, format))| VertexAttributeLayout {
+                    format,
+                    offset: 0,
+                    shader_location: location as u32,
+                    semantic,
+                    attribute,
+                },
+            )
+            .collect();
+
+        match mode {
+            VertexLayoutMode::Planar => {
+                let mut buffer_offset = 0;
+                attributes
+                    .into_iter()
+                    .map(|attribute| {
+                        let stride = attribute.format.size().next_multiple_of(VERTEX_ALIGNMENT);
+                        let mut layout =
+                            VertexBufferLayout::new(stride, vertex_count, vec![attribute]);
+                        layout.buffer_offset = buffer_offset;
+                        buffer_offset += layout.buffer_size;
+                        layout
+                    })
+                    .collect()
+            }
+            VertexLayoutMode::Interleaved => {
+                if attributes.is_empty() {
+                    return Vec::new();
+                }
+                let mut stride = 0;
+                for attribute in &mut attributes {
+                    attribute.offset = stride;
+                    stride += attribute.format.size().next_multiple_of(VERTEX_ALIGNMENT);
+                }
+                vec![VertexBufferLayout::new(stride, vertex_count, attributes)]
+            }
+        }
+    }
+}
+
+impl MeshDecodeResult {
+    /// Returns the vertex data arranged as described by
+    /// [`DracoDecodeConfig::vertex_buffer_layouts`], ready to be uploaded to
+    /// a single GPU buffer.
+    ///
+    /// Padding bytes are zero. The indices are not included, see
+    /// [`MeshDecodeResult::index_bytes`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use draco_decoder::VertexLayoutMode;
+    /// use wgpu::util::DeviceExt;
+    ///
+    /// let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+    ///     label: Some("draco vertices"),
+    ///     contents: &result.vertex_buffer_data(VertexLayoutMode::Interleaved),
+    ///     usage: wgpu::BufferUsages::VERTEX,
+    /// });
+    /// ```
+    pub fn vertex_buffer_data(&self, mode: VertexLayoutMode) -> Vec<u8> {
+        let layouts = self.config.vertex_buffer_layouts(mode);
+        let size = layouts
+            .last()
+            .map_or(0, |layout| layout.buffer_offset + layout.buffer_size);
+        let mut data = vec![0u8; size as usize];
+
+        for layout in &layouts {
+            for attribute in &layout.attributes {
+                let Some(bytes) = self.attribute_bytes(attribute.attribute) else {
+                    continue;
+                };
+                let element_size = attribute.format.size() as usize;
+                let elements = bytes
+                    .chunks_exact(element_size)
+                    .take(self.config.vertex_count() as usize);
+                for (vertex, element) in elements.enumerate() {
+                    let start = (layout.buffer_offset
+                        + vertex as u64 * layout.array_stride
+                        + attribute.offset) as usize;
+                    data[start..start + element_size].copy_from_slice(element);
+                }
+            }
+        }
+        data
+    }
+}
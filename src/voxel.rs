@@ -0,0 +1,283 @@
+use crate::MeshDecodeResult;
+use crate::math::{Vec3, cross, dot, sub};
+
+/// Which cells [`MeshDecodeResult::voxelize`] marks as occupied.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VoxelMode {
+    /// Cells touched by a triangle, a hollow shell of the mesh.
+    #[default]
+    Surface,
+    /// Cells touched by a triangle or with their center inside the mesh.
+    /// Meant for closed meshes.
+    Solid,
+}
+
+/// A grid of occupied cubic cells, built by [`MeshDecodeResult::voxelize`].
+///
+/// Occupancy is stored per cell with `x` varying fastest, then `y`, then `z`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoxelGrid {
+    /// Minimum corner of the grid.
+    pub origin: [f32; 3],
+    /// Edge length of a cell.
+    pub cell_size: f32,
+    /// Number of cells along each axis.
+    pub dimensions: [u32; 3],
+    /// Whether each cell is occupied.
+    pub occupancy: Vec<bool>,
+}
+
+impl VoxelGrid {
+    fn index(&self, cell: [u32; 3]) -> Option<usize> {
+        if (0..3).any(|axis| cell[axis] >= self.dimensions[axis]) {
+            return None;
+        }
+        let [width, height, _] = self.dimensions.map(|n| n as usize);
+        let [x, y, z] = cell.map(|n| n as usize);
+        Some(x + width * (y + height * z))
+    }
+
+    /// Returns `true` if the cell is in the grid and occupied.
+    pub fn is_occupied(&self, cell: [u32; 3]) -> bool {
+        self.index(cell)
+            .and_then(|index| self.occupancy.get(index))
+            .is_some_and(|&occupied| occupied)
+    }
+
+    /// Returns the number of occupied cells.
+    pub fn occupied_count(&self) -> usize {
+        self.occupancy.iter().filter(|&&occupied| occupied).count()
+    }
+
+    /// Returns an iterator over the occupied cells in storage order.
+    pub fn occupied_cells(&self) -> impl Iterator<Item = [u32; 3]> + '_ {
+        let [width, height, _] = self.dimensions.map(|n| n as usize);
+        self.occupancy
+            .iter()
+            .enumerate()
+            .filter(|(_, occupied)| **occupied)
+            .map(move |(index, _)| {
+                [
+                    (index % width) as u32,
+                    (index / width % height) as u32,
+                    (index / (width * height)) as u32,
+                ]
+            })
+    }
+
+    /// Returns the center of the given cell.
+    pub fn cell_center(&self, cell: [u32; 3]) -> [f32; 3] {
+        std::array::from_fn(|axis| self.origin[axis] + (cell[axis] as f32 + 0.5) * self.cell_size)
+    }
+
+    /// Packs the occupancy into bits, eight cells per byte in storage order
+    /// with the first cell in the least significant bit, for exporting the
+    /// grid to simulation tools.
+    pub fn to_bits(&self) -> Vec<u8> {
+        let mut bits = vec![0u8; self.occupancy.len().div_ceil(8)];
+        for (index, _) in self.occupancy.iter().enumerate().filter(|(_, o)| **o) {
+            bits[index / 8] |= 1 << (index % 8);
+        }
+        bits
+    }
+}
+
+/// Separating axis test of a triangle against an axis aligned cube
+/// (Akenine-Möller), counting touching as overlapping.
+fn overlaps_cube(triangle: &[Vec3; 3], center: Vec3, half: f32) -> bool {
+    let v = triangle.map(|point| sub(point, center));
+    let edges = [sub(v[1], v[0]), sub(v[2], v[1]), sub(v[0], v[2])];
+    let separated = |axis: Vec3| {
+        let projections = v.map(|point| dot(axis, point));
+        let radius = half * (axis[0].abs() + axis[1].abs() + axis[2].abs());
+        let min = projections.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = projections
+            .iter()
+            .copied()
+            .fold(f32::NEG_INFINITY, f32::max);
+        min > radius || max < -radius
+    };
+
+    let unit_axes = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    for edge in edges {
+        for unit in unit_axes {
+            if separated(cross(unit, edge)) {
+                return false;
+            }
+        }
+    }
+    !unit_axes.into_iter().any(separated) && !separated(cross(edges[0], edges[1]))
+}
+
+/// Finds where the triangle crosses the line through `(y, z)` parallel to
+/// the x axis, returning the x coordinate.
+///
+/// Points on an edge count for exactly one of the triangles sharing that
+/// edge, so closed surfaces are crossed an even number of times.
+fn crossing_x(triangle: &[Vec3; 3], y: f32, z: f32) -> Option<f32> {
+    let [a, mut b, mut c] = *triangle;
+    let area = (b[1] - a[1]) * (c[2] - a[2]) - (b[2] - a[2]) * (c[1] - a[1]);
+    if area == 0.0 {
+        return None;
+    }
+    if area < 0.0 {
+        std::mem::swap(&mut b, &mut c);
+    }
+    let area = area.abs();
+
+    // Edge function of the edge from `from` to `to`, where points on the
+    // edge belong to only one of its two directions
+    let edge = |from: Vec3, to: Vec3| {
+        let (dy, dz) = (to[1] - from[1], to[2] - from[2]);
+        let value = dy * (z - from[2]) - dz * (y - from[1]);
+        let owned = value > 0.0 || (value == 0.0 && (dz < 0.0 || (dz == 0.0 && dy > 0.0)));
+        owned.then_some(value)
+    };
+    let weight_c = edge(a, b)?;
+    let weight_a = edge(b, c)?;
+    let weight_b = edge(c, a)?;
+    Some((a[0] * weight_a + b[0] * weight_b + c[0] * weight_c) / area)
+}
+
+impl MeshDecodeResult {
+    /// Voxelizes the mesh into cubic cells of the given size, for
+    /// simulations that need an occupancy grid of a model.
+    ///
+    /// The grid starts at the minimum corner of the mesh bounds and covers
+    /// all triangles. [`VoxelMode::Surface`] marks every cell a triangle
+    /// touches; [`VoxelMode::Solid`] also fills the cells whose centers lie
+    /// inside the mesh, found by counting surface crossings along rows of
+    /// cells, so it expects a closed mesh. Positions are converted like
+    /// [`MeshDecodeResult::positions`], and triangles referencing missing
+    /// vertices are skipped.
+    ///
+    /// Returns `None` if `cell_size` is not positive, the mesh has no
+    /// positions or triangles, or the grid would have more than `u32::MAX`
+    /// cells.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use draco_decoder::VoxelMode;
+    ///
+    /// let grid = result.voxelize(0.25, VoxelMode::Solid).unwrap();
+    /// std::fs::write("occupancy.bin", grid.to_bits())?;
+    /// ```
+    pub fn voxelize(&self, cell_size: f32, mode: VoxelMode) -> Option<VoxelGrid> {
+        if !(cell_size > 0.0 && cell_size.is_finite()) {
+            return None;
+        }
+        let points: Vec<Vec3> = self.positions()?.collect();
+        let triangles: Vec<[Vec3; 3]> = self
+            .triangles()
+            .filter_map(|triangle| {
+                let [a, b, c] = triangle.map(|vertex| points.get(vertex as usize).copied());
+                Some([a?, b?, c?])
+            })
+            .collect();
+        if triangles.is_empty() {
+            return None;
+        }
+
+        let (mut min, mut max) = ([f32::INFINITY; 3], [f32::NEG_INFINITY; 3]);
+        for point in triangles.iter().flatten() {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(point[axis]);
+                max[axis] = max[axis].max(point[axis]);
+            }
+        }
+        let extent = sub(max, min);
+        if !extent.iter().all(|c| c.is_finite()) {
+            return None;
+        }
+        let dimensions = extent.map(|c| ((c / cell_size).ceil() as u64).max(1));
+        let cell_count = dimensions.iter().product::<u64>();
+        if dimensions.iter().any(|&n| n > u32::MAX as u64) || cell_count > u32::MAX as u64 {
+            return None;
+        }
+
+        let mut grid = VoxelGrid {
+            origin: min,
+            cell_size,
+            dimensions: dimensions.map(|n| n as u32),
+            occupancy: vec![false; cell_count as usize],
+        };
+        let cell_of = |coordinate: f32, axis: usize| {
+            let cell = ((coordinate - min[axis]) / cell_size).floor().max(0.0) as u32;
+            cell.min(grid.dimensions[axis] - 1)
+        };
+
+        let half = cell_size * 0.5;
+        for triangle in &triangles {
+            let first: [u32; 3] = std::array::from_fn(|axis| {
+                let low = triangle
+                    .iter()
+                    .map(|p| p[axis])
+                    .fold(f32::INFINITY, f32::min);
+                cell_of(low, axis)
+            });
+            let last: [u32; 3] = std::array::from_fn(|axis| {
+                let high = triangle
+                    .iter()
+                    .map(|p| p[axis])
+                    .fold(f32::NEG_INFINITY, f32::max);
+                cell_of(high, axis)
+            });
+            for z in first[2]..=last[2] {
+                for y in first[1]..=last[1] {
+                    for x in first[0]..=last[0] {
+                        let cell = [x, y, z];
+                        if overlaps_cube(triangle, grid.cell_center(cell), half)
+                            && let Some(index) = grid.index(cell)
+                        {
+                            grid.occupancy[index] = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        if mode == VoxelMode::Solid {
+            let [width, height, depth] = grid.dimensions;
+            let mut crossings = vec![Vec::new(); (height * depth) as usize];
+            for triangle in &triangles {
+                let (low_y, high_y) = triangle
+                    .iter()
+                    .fold((f32::INFINITY, f32::NEG_INFINITY), |(l, h), p| {
+                        (l.min(p[1]), h.max(p[1]))
+                    });
+                let (low_z, high_z) = triangle
+                    .iter()
+                    .fold((f32::INFINITY, f32::NEG_INFINITY), |(l, h), p| {
+                        (l.min(p[2]), h.max(p[2]))
+                    });
+                for z in cell_of(low_z, 2)..=cell_of(high_z, 2) {
+                    for y in cell_of(low_y, 1)..=cell_of(high_y, 1) {
+                        let [_, center_y, center_z] = grid.cell_center([0, y, z]);
+                        if let Some(x) = crossing_x(triangle, center_y, center_z) {
+                            crossings[(y + height * z) as usize].push(x);
+                        }
+                    }
+                }
+            }
+
+            for (row, row_crossings) in crossings.iter_mut().enumerate() {
+                row_crossings.sort_by(f32::total_cmp);
+                for span in row_crossings.chunks_exact(2) {
+                    // Cells whose centers lie between the two crossings
+                    let start = ((span[0] - min[0]) / cell_size - 0.5).ceil().max(0.0) as u32;
+                    let end = ((span[1] - min[0]) / cell_size - 0.5).floor();
+                    if end < 0.0 {
+                        continue;
+                    }
+                    let end = (end as u32).min(width - 1);
+                    let offset = row * width as usize;
+                    for x in start..=end {
+                        grid.occupancy[offset + x as usize] = true;
+                    }
+                }
+            }
+        }
+        Some(grid)
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt;
+
+use crate::{AttributeDataType, AttributeSemantic};
+
+/// Why an attribute was not decoded as stored in the bitstream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WarningReason {
+    /// The attribute uses a Draco data type without an [`AttributeDataType`]
+    /// equivalent. Holds the raw `draco::DataType` value.
+    UnsupportedDataType(u8),
+    /// The attribute was excluded by the decode mode, such as a fast preview.
+    Filtered,
+}
+
+/// What was done with an attribute reported by a [`DecodeWarning`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WarningAction {
+    /// The attribute is missing from the result.
+    Skipped,
+    /// The attribute was converted to the given data type.
+    Converted(AttributeDataType),
+}
+
+/// A note about an attribute that did not make it into the result unchanged.
+///
+/// Collected on [`DracoDecodeConfig::warnings`](crate::DracoDecodeConfig::warnings)
+/// and [`PointCloudDecodeConfig::warnings`](crate::PointCloudDecodeConfig::warnings).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DecodeWarning {
+    /// Unique id of the attribute in the Draco bitstream.
+    pub unique_id: u32,
+    /// What the attribute represents.
+    pub semantic: AttributeSemantic,
+    /// Index of the attribute in the result, `None` if it was skipped.
+    pub attribute_index: Option<usize>,
+    /// Why the attribute was not decoded as stored.
+    pub reason: WarningReason,
+    /// What was done with the attribute.
+    pub action: WarningAction,
+}
+
+impl fmt::Display for DecodeWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} attribute {}", self.semantic, self.unique_id)?;
+        match self.reason {
+            WarningReason::UnsupportedDataType(data_type) => {
+                write!(f, " has unsupported Draco data type {data_type}")?
+            }
+            WarningReason::Filtered => write!(f, " was filtered out")?,
+        }
+        match self.action {
+            WarningAction::Skipped => write!(f, ", skipped"),
+            WarningAction::Converted(data_type) => write!(f, ", converted to {data_type:?}"),
+        }
+    }
+}
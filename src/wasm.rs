@@ -1,20 +1,43 @@
 use js_sys::{Array, Object, Promise, Uint8Array};
 use std::cell::RefCell;
+use std::future::poll_fn;
+use std::pin::pin;
+use std::task::Poll;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
 
-use crate::{AttributeDataType, DracoDecodeConfig};
+use crate::{
+    AttributeDataType, AttributeSemantic, CancellationToken, DecodeWarning, DracoDecodeConfig,
+    MeshAttribute, PointCloudDecodeConfig, WarningAction, WarningReason,
+};
 
 thread_local! {
-    static DRACO_DECODE_FUNC_MODULE: RefCell<Option<JsValue>> = RefCell::new(None);
+    // The pending or resolved import of the embedded module. Storing the
+    // promise rather than the module makes concurrent first calls share a
+    // single import instead of racing to evaluate the bundle.
+    static DRACO_DECODE_FUNC_MODULE: RefCell<Option<Promise>> = const { RefCell::new(None) };
 }
 
 async fn get_js_module() -> Result<JsValue, JsValue> {
-    if let Some(module) = DRACO_DECODE_FUNC_MODULE.with(|m| m.borrow().clone()) {
-        return Ok(module);
+    let module_promise = match DRACO_DECODE_FUNC_MODULE.with(|m| m.borrow().clone()) {
+        Some(promise) => promise,
+        None => {
+            let promise = import_js_module()?;
+            DRACO_DECODE_FUNC_MODULE.with(|m| m.replace(Some(promise.clone())));
+            promise
+        }
+    };
+
+    let module = JsFuture::from(module_promise).await;
+    if module.is_err() {
+        // Allow a later call to retry the import
+        DRACO_DECODE_FUNC_MODULE.with(|m| m.replace(None));
     }
+    module
+}
 
+fn import_js_module() -> Result<Promise, JsValue> {
     let js_code = include_str!("../javascript/index.es.js");
     let escaped = js_code.replace("\\", "\\\\").replace("`", "\\`");
 
@@ -32,97 +55,688 @@ async fn get_js_module() -> Result<JsValue, JsValue> {
     "#
     );
 
-    let js_module = js_sys::eval(&setup_code)?;
-    let module_promise: Promise = js_module.dyn_into()?;
-    let module = JsFuture::from(module_promise).await?;
+    js_sys::eval(&setup_code)?.dyn_into()
+}
 
-    DRACO_DECODE_FUNC_MODULE.with(|m| m.replace(Some(module.clone())));
+/// Entry point for the type-state WASM decoder API.
+///
+/// The embedded decoder module has to be loaded before anything can be
+/// decoded. [`WasmDecoder::init`] performs the load and returns a
+/// [`ReadyDecoder`], so decoding before the module is available cannot
+/// compile.
+///
+/// # Example
+///
+/// ```ignore
+/// use draco_decoder::WasmDecoder;
+///
+/// let decoder = WasmDecoder::init().await.expect("Failed to load decoder");
+/// let mesh = decoder.decode_mesh(&data).await;
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct WasmDecoder;
 
-    Ok(module)
+impl WasmDecoder {
+    /// Loads the embedded decoder module.
+    ///
+    /// The module is loaded once per thread and shared with the free decode
+    /// functions. Returns `None` if it cannot be loaded, for example because
+    /// the Content Security Policy forbids `eval` or Blob URLs.
+    pub async fn init() -> Option<ReadyDecoder> {
+        match get_js_module().await {
+            Ok(module) => Some(ReadyDecoder { module }),
+            Err(err) => {
+                web_sys::console::error_1(&err);
+                None
+            }
+        }
+    }
 }
 
-async fn decode_draco_mesh_from_embedded_js_with_config(
-    data: &js_sys::Uint8Array,
-) -> Result<(Vec<u8>, DracoDecodeConfig), JsValue> {
-    let module = get_js_module().await?;
+impl WasmDecoder {
+    /// Loads the embedded decoder module to decode on the calling thread
+    /// instead of in a Worker.
+    ///
+    /// For pages where Workers are unavailable, for example because the
+    /// Content Security Policy forbids them. Draco decompresses the encoded
+    /// data in one step, after which the indices and attribute values are
+    /// extracted in chunks. Between chunks, decoding yields to the event loop
+    /// with `requestIdleCallback`, or `setTimeout` where it is unavailable,
+    /// whenever `slice` has elapsed, so the page stays responsive while
+    /// large meshes are extracted.
+    ///
+    /// Returns `None` if the module cannot be loaded.
+    pub async fn init_time_sliced(slice: std::time::Duration) -> Option<ReadyDecoder> {
+        let module = get_js_module().await.and_then(|module| {
+            js_sys::Reflect::get(&module, &JsValue::from_str("createTimeSlicedDecoder"))?
+                .dyn_into::<js_sys::Function>()?
+                .call1(
+                    &JsValue::NULL,
+                    &JsValue::from_f64(slice.as_secs_f64() * 1000.0),
+                )
+        });
+        match module {
+            Ok(module) => Some(ReadyDecoder { module }),
+            Err(err) => {
+                web_sys::console::error_1(&err);
+                None
+            }
+        }
+    }
+}
+
+// Sends requests to a user supplied worker and resolves them from its replies,
+// exposing the same functions as the embedded module. Shipped as a module
+// snippet by wasm-bindgen, so no `eval` is needed.
+#[wasm_bindgen(inline_js = r#"
+export function createWorkerClient(worker) {
+    let next = 0;
+    const pending = new Map();
+    const fail = error => {
+        const requests = [...pending.values()];
+        pending.clear();
+        for (const request of requests) request.reject(error);
+    };
+    worker.addEventListener("message", event => {
+        const { id, success, decoded, config, error } = event.data;
+        const request = pending.get(id);
+        if (!request) return;
+        pending.delete(id);
+        if (success) {
+            request.resolve({ decoded, config });
+        } else {
+            request.reject(error);
+        }
+    });
+    worker.addEventListener("error", event => fail(event.message || "Draco worker failed"));
+    worker.addEventListener("messageerror", () => fail("Draco worker reply could not be received"));
+    const call = pointCloud => (view, signal, outputTypes) => new Promise((resolve, reject) => {
+        if (signal && signal.aborted) return reject("Draco decode cancelled");
+        const id = next++;
+        pending.set(id, { resolve, reject });
+        if (signal) {
+            signal.addEventListener("abort", () => {
+                if (pending.delete(id)) reject("Draco decode cancelled");
+            }, { once: true });
+        }
+        worker.postMessage({ id, view, withConfig: true, pointCloud, outputTypes }, [view.buffer]);
+    });
+    return {
+        decodeDracoMeshInWorkerWithConfig: call(false),
+        decodeDracoPointCloudInWorkerWithConfig: call(true),
+    };
+}
+"#)]
+extern "C" {
+    #[wasm_bindgen(js_name = createWorkerClient)]
+    fn create_worker_client(worker: &web_sys::Worker) -> JsValue;
+}
+
+impl WasmDecoder {
+    /// Decodes in a Worker constructed by the caller instead of the embedded
+    /// one.
+    ///
+    /// Bundlers such as Vite or webpack handle workers created with
+    /// `new Worker(new URL("./draco-worker.js", import.meta.url), { type: "module" })`
+    /// as part of the asset graph. Such a worker can run the embedded
+    /// decoder by calling `serveDecoder()` from `javascript/index.es.js` in
+    /// this crate, which loads it from a Blob URL. A custom worker must
+    /// implement the same protocol:
+    ///
+    /// - requests are `{ id, view, withConfig: true, pointCloud, outputTypes }`
+    ///   messages, where `view` is a `Uint8Array` with the encoded data and
+    ///   its buffer is transferred, and `outputTypes`, if set, is
+    ///   `{ semantics, attributes }` with the `draco.DataType` requested per
+    ///   attribute type (-1 to keep the decoded type) and
+    ///   `[unique_id, data_type]` pairs for single attributes;
+    /// - replies are `{ id, success: true, decoded, config }` with the
+    ///   decoded `Uint8Array` and the layout reported by the embedded worker,
+    ///   or `{ id, success: false, error }`.
+    ///
+    /// A cancelled decode only drops the reply to its request, the worker is
+    /// not terminated since it is owned by the caller. Pending decodes fail
+    /// when the worker reports an `error` or a reply cannot be deserialized.
+    pub fn with_worker(worker: &web_sys::Worker) -> ReadyDecoder {
+        ReadyDecoder {
+            module: create_worker_client(worker),
+        }
+    }
+}
+
+/// A decoder whose JavaScript module has been loaded, see [`WasmDecoder`].
+///
+/// Decoding runs in a Worker, or on the calling thread for a decoder created
+/// with [`WasmDecoder::init_time_sliced`].
+#[derive(Debug, Clone)]
+pub struct ReadyDecoder {
+    module: JsValue,
+}
+
+impl ReadyDecoder {
+    /// Decodes a Draco compressed mesh in a Worker.
+    ///
+    /// Returns `None` if decoding fails.
+    pub async fn decode_mesh(&self, data: &[u8]) -> Option<crate::MeshDecodeResult> {
+        self.decode_mesh_cancellable(data, &crate::DecodeOptions::default())
+            .await
+    }
+
+    /// Decodes with the output types and cancellation token of `options`,
+    /// in the default layout otherwise.
+    async fn decode_mesh_cancellable(
+        &self,
+        data: &[u8],
+        options: &crate::DecodeOptions,
+    ) -> Option<crate::MeshDecodeResult> {
+        let js_array = Uint8Array::from(data);
+        let cancel = options.cancellation();
+
+        match decode_draco_mesh_from_embedded_js_with_config(
+            &self.module,
+            &js_array,
+            &output_types(options),
+            cancel,
+        )
+        .await
+        {
+            Ok((decoded, mut config)) => {
+                config.set_crs(crate::crs_from_metadata(data));
+                Some(crate::MeshDecodeResult {
+                    data: decoded,
+                    config,
+                })
+            }
+            // A cancelled decode is not an error
+            Err(_) if cancel.is_some_and(CancellationToken::is_cancelled) => None,
+            Err(err) => {
+                web_sys::console::error_1(&err);
+                None
+            }
+        }
+    }
+
+    /// Decodes a Draco compressed mesh in a Worker with the buffer layout
+    /// requested by `options`.
+    ///
+    /// Returns `None` if decoding fails, the mesh exceeds the limits or the
+    /// quota of `options` denies the decode, or its cancellation token is
+    /// cancelled.
+    pub async fn decode_mesh_with_options(
+        &self,
+        data: &[u8],
+        options: &crate::DecodeOptions,
+    ) -> Option<crate::MeshDecodeResult> {
+        self.try_decode_mesh_with_options(data, options).await.ok()
+    }
+
+    /// Decodes like [`ReadyDecoder::decode_mesh_with_options`], reporting
+    /// why decoding failed.
+    pub(crate) async fn try_decode_mesh_with_options(
+        &self,
+        data: &[u8],
+        options: &crate::DecodeOptions,
+    ) -> Result<crate::MeshDecodeResult, crate::DecodeError> {
+        use crate::DecodeError;
+
+        if options.is_cancelled() {
+            return Err(DecodeError::DecodeFailed);
+        }
+        let header = crate::decode_header(data);
+        if header.is_ok() {
+            options.report_progress(crate::DecodeStage::HeaderParsed, 0);
+        }
+        options.check_quota_decode(data.len())?;
+        let result = self
+            .decode_mesh_cancellable(data, options)
+            .await
+            .ok_or_else(|| header.err().unwrap_or(DecodeError::DecodeFailed))?;
+        options.check_quota(result.config.buffer_size())?;
+        options.index_format().check(result.config.vertex_count())?;
+        options
+            .axis_convention()
+            .check(&result.config.attributes())?;
+        let mut result = result.relayout(options);
+        if options.computes_min_max() {
+            result.record_min_max();
+        }
+        options.limits().check(
+            result.config.vertex_count(),
+            result.config.index_count(),
+            result.config.buffer_size(),
+        )?;
+        if options.validates_indices() {
+            result.check_indices()?;
+        }
+
+        // The worker does not report progress, so the remaining stages are
+        // reported at once
+        let attribute_count = result.config.attributes().len();
+        options.report_progress(crate::DecodeStage::ConnectivityDecoded, 0);
+        options.report_progress(crate::DecodeStage::IndicesWritten, 0);
+        for index in 0..attribute_count {
+            options.report_progress(
+                crate::DecodeStage::AttributeExtracted(index),
+                attribute_count,
+            );
+        }
+        Ok(result)
+    }
+
+    /// Decodes like [`ReadyDecoder::decode_point_cloud`], honoring the
+    /// limits, quota, cancellation and progress callback of `options` and
+    /// reporting why decoding failed.
+    pub(crate) async fn try_decode_point_cloud_with_options(
+        &self,
+        data: &[u8],
+        options: &crate::DecodeOptions,
+    ) -> Result<crate::PointCloudDecodeResult, crate::DecodeError> {
+        use crate::DecodeError;
+
+        if options.is_cancelled() {
+            return Err(DecodeError::DecodeFailed);
+        }
+        let header = crate::decode_header(data);
+        if header.is_ok() {
+            options.report_progress(crate::DecodeStage::HeaderParsed, 0);
+        }
+        options.check_quota_decode(data.len())?;
+        let result = self
+            .decode_point_cloud(data)
+            .await
+            .ok_or_else(|| header.err().unwrap_or(DecodeError::DecodeFailed))?;
+        if options.is_cancelled() {
+            return Err(DecodeError::DecodeFailed);
+        }
+        options.check_quota(result.config.buffer_size())?;
+        options
+            .limits()
+            .check(result.config.point_count(), 0, result.config.buffer_size())?;
+
+        let attribute_count = result.config.attributes().len();
+        for index in 0..attribute_count {
+            options.report_progress(
+                crate::DecodeStage::AttributeExtracted(index),
+                attribute_count,
+            );
+        }
+        Ok(result)
+    }
+
+    /// Decodes a Draco compressed point cloud in a Worker.
+    ///
+    /// Returns `None` if decoding fails.
+    pub async fn decode_point_cloud(&self, data: &[u8]) -> Option<crate::PointCloudDecodeResult> {
+        let js_array = Uint8Array::from(data);
+
+        match decode_draco_point_cloud_from_embedded_js_with_config(&self.module, &js_array).await {
+            Ok((decoded, mut config)) => {
+                config.set_crs(crate::crs_from_metadata(data));
+                Some(crate::PointCloudDecodeResult {
+                    data: decoded,
+                    config,
+                })
+            }
+            Err(err) => {
+                web_sys::console::error_1(&err);
+                None
+            }
+        }
+    }
+}
+
+/// Returns the `draco.DataType` value of a data type.
+fn draco_data_type(data_type: AttributeDataType) -> i32 {
+    match data_type {
+        AttributeDataType::Int8 => 1,
+        AttributeDataType::UInt8 => 2,
+        AttributeDataType::Int16 => 3,
+        AttributeDataType::UInt16 => 4,
+        AttributeDataType::Int32 => 5,
+        AttributeDataType::UInt32 => 6,
+        AttributeDataType::Float32 => 9,
+    }
+}
+
+/// Describes the output types requested by `options` for the worker, as
+/// `{ semantics, attributes }`, see [`WasmDecoder::with_worker`].
+fn output_types(options: &crate::DecodeOptions) -> JsValue {
+    const SEMANTICS: [AttributeSemantic; 5] = [
+        AttributeSemantic::Position,
+        AttributeSemantic::Normal,
+        AttributeSemantic::Color,
+        AttributeSemantic::TexCoord,
+        AttributeSemantic::Generic,
+    ];
+
+    let semantics: Array = SEMANTICS
+        .into_iter()
+        .map(|semantic| JsValue::from(options.output_type(semantic).map_or(-1, draco_data_type)))
+        .collect();
+    let attributes: Array = options
+        .attribute_output_types()
+        .iter()
+        .map(|&(unique_id, data_type)| {
+            Array::of2(
+                &JsValue::from(unique_id),
+                &JsValue::from(draco_data_type(data_type)),
+            )
+        })
+        .collect();
+    let types = Object::new();
+    let _ = js_sys::Reflect::set(&types, &JsValue::from_str("semantics"), &semantics);
+    let _ = js_sys::Reflect::set(&types, &JsValue::from_str("attributes"), &attributes);
+    types.into()
+}
 
-    // Call the decode function with config from the module
-    let decode_fn = js_sys::Reflect::get(
-        &module,
-        &JsValue::from_str("decodeDracoMeshInWorkerWithConfig"),
-    )?
-    .dyn_into::<js_sys::Function>()?;
+/// Calls an exported worker function and returns its `{ decoded, config }`
+/// result.
+///
+/// The function is passed an `AbortSignal` that is aborted once `cancel` is
+/// cancelled, which makes the worker module drop the job, and
+/// `output_types`.
+async fn call_worker(
+    module: &JsValue,
+    function: &str,
+    data: &js_sys::Uint8Array,
+    output_types: &JsValue,
+    cancel: Option<&CancellationToken>,
+) -> Result<(Uint8Array, JsValue), JsValue> {
+    let decode_fn = js_sys::Reflect::get(module, &JsValue::from_str(function))?
+        .dyn_into::<js_sys::Function>()?;
 
+    let controller = web_sys::AbortController::new()?;
     let this = JsValue::NULL;
-    let result = decode_fn.call1(&this, data)?;
+    let result = decode_fn.call3(&this, data, &controller.signal(), output_types)?;
     let decode_promise: Promise = result.dyn_into()?;
-    let out_obj = JsFuture::from(decode_promise).await?;
+    let mut decode = pin!(JsFuture::from(decode_promise));
+    let out_obj = match cancel {
+        Some(token) => {
+            let mut cancelled = pin!(token.cancelled());
+            poll_fn(|cx| {
+                if cancelled.as_mut().poll(cx).is_ready() {
+                    return Poll::Ready(None);
+                }
+                decode.as_mut().poll(cx).map(Some)
+            })
+            .await
+        }
+        None => Some(decode.await),
+    };
+    let Some(out_obj) = out_obj else {
+        controller.abort();
+        return Err(JsValue::from_str("Draco decode cancelled"));
+    };
+    let out_obj = out_obj?;
 
     // Parse the result: { decoded: Uint8Array, config: Object }
     let decoded_array =
         js_sys::Reflect::get(&out_obj, &JsValue::from_str("decoded"))?.dyn_into::<Uint8Array>()?;
     let config_obj = js_sys::Reflect::get(&out_obj, &JsValue::from_str("config"))?;
 
-    // Convert config from JS to Rust
-    let vertex_count = js_sys::Reflect::get(&config_obj, &JsValue::from_str("vertex_count"))?
-        .as_f64()
-        .unwrap_or(0.0) as u32;
-    let index_count = js_sys::Reflect::get(&config_obj, &JsValue::from_str("index_count"))?
-        .as_f64()
-        .unwrap_or(0.0) as u32;
-    let buffer_size = js_sys::Reflect::get(&config_obj, &JsValue::from_str("buffer_size"))?
-        .as_f64()
-        .unwrap_or(0.0) as usize;
+    Ok((decoded_array, config_obj))
+}
+
+fn get_f64(obj: &JsValue, key: &str) -> Result<Option<f64>, JsValue> {
+    Ok(js_sys::Reflect::get(obj, &JsValue::from_str(key))?.as_f64())
+}
 
+fn get_u32(obj: &JsValue, key: &str) -> Result<u32, JsValue> {
+    Ok(get_f64(obj, key)?.unwrap_or(0.0) as u32)
+}
+
+/// Converts the attribute descriptions reported by the worker.
+///
+/// Attributes with data types the worker cannot decode are left as zeros in
+/// the buffer, they are reported as warnings instead.
+fn parse_attributes(
+    config_obj: &JsValue,
+) -> Result<(Vec<MeshAttribute>, Vec<DecodeWarning>), JsValue> {
     let attributes_array =
-        js_sys::Reflect::get(&config_obj, &JsValue::from_str("attributes"))?.dyn_into::<Array>()?;
+        js_sys::Reflect::get(config_obj, &JsValue::from_str("attributes"))?.dyn_into::<Array>()?;
 
-    let mut config = DracoDecodeConfig::new(vertex_count, index_count, buffer_size);
+    let mut attributes = Vec::with_capacity(attributes_array.length() as usize);
+    let mut warnings = Vec::new();
 
     for i in 0..attributes_array.length() {
-        let attr_obj = attributes_array.get(i).dyn_into::<Object>()?;
-
-        let dim = js_sys::Reflect::get(&attr_obj, &JsValue::from_str("dim"))?
-            .as_f64()
-            .unwrap_or(0.0) as u32;
-        let data_type = js_sys::Reflect::get(&attr_obj, &JsValue::from_str("data_type"))?
-            .as_f64()
-            .unwrap_or(0.0) as i32;
-        let offset = js_sys::Reflect::get(&attr_obj, &JsValue::from_str("offset"))?
-            .as_f64()
-            .unwrap_or(0.0) as u32;
-        let length = js_sys::Reflect::get(&attr_obj, &JsValue::from_str("length"))?
-            .as_f64()
-            .unwrap_or(0.0) as u32;
+        let attr_obj: JsValue = attributes_array.get(i).dyn_into::<Object>()?.into();
 
+        let dim = get_u32(&attr_obj, "dim")?;
+        let data_type = get_f64(&attr_obj, "data_type")?.unwrap_or(0.0) as i32;
+        let offset = get_u32(&attr_obj, "offset")?;
+        let length = get_u32(&attr_obj, "length")?;
+        let unique_id = get_u32(&attr_obj, "unique_id")?;
+        let normalized = js_sys::Reflect::get(&attr_obj, &JsValue::from_str("normalized"))?
+            .as_bool()
+            .unwrap_or(false);
+        let attribute_type = get_f64(&attr_obj, "attribute_type")?.map_or(-1, |value| value as i32);
+        let semantic = AttributeSemantic::from_draco(attribute_type);
+
+        // The worker reports draco::DataType values
         let attr_data_type = match data_type {
-            0 => AttributeDataType::Int8,
-            1 => AttributeDataType::UInt8,
-            2 => AttributeDataType::Int16,
-            3 => AttributeDataType::UInt16,
-            4 => AttributeDataType::Int32,
-            5 => AttributeDataType::UInt32,
-            6 => AttributeDataType::Float32,
-            _ => AttributeDataType::Float32,
+            1 => AttributeDataType::Int8,
+            2 => AttributeDataType::UInt8,
+            3 => AttributeDataType::Int16,
+            4 => AttributeDataType::UInt16,
+            5 => AttributeDataType::Int32,
+            6 => AttributeDataType::UInt32,
+            9 => AttributeDataType::Float32,
+            _ => {
+                warnings.push(DecodeWarning {
+                    unique_id,
+                    semantic,
+                    attribute_index: None,
+                    reason: WarningReason::UnsupportedDataType(data_type as u8),
+                    action: WarningAction::Skipped,
+                });
+                continue;
+            }
         };
 
-        config.add_attribute(dim, attr_data_type, offset, length);
+        attributes.push(
+            MeshAttribute::new(dim, attr_data_type, offset, length)
+                .with_semantic(semantic)
+                .with_unique_id(unique_id)
+                .with_normalized(normalized && attr_data_type != AttributeDataType::Float32),
+        );
+    }
+
+    Ok((attributes, warnings))
+}
+
+async fn decode_draco_mesh_from_embedded_js_with_config(
+    module: &JsValue,
+    data: &js_sys::Uint8Array,
+    output_types: &JsValue,
+    cancel: Option<&CancellationToken>,
+) -> Result<(Vec<u8>, DracoDecodeConfig), JsValue> {
+    let (decoded_array, config_obj) = call_worker(
+        module,
+        "decodeDracoMeshInWorkerWithConfig",
+        data,
+        output_types,
+        cancel,
+    )
+    .await?;
+
+    // Convert config from JS to Rust
+    let vertex_count = get_u32(&config_obj, "vertex_count")?;
+    let index_count = get_u32(&config_obj, "index_count")?;
+    let buffer_size = get_f64(&config_obj, "buffer_size")?.unwrap_or(0.0) as usize;
+
+    let mut config = DracoDecodeConfig::new(vertex_count, index_count, buffer_size);
+    let (attributes, warnings) = parse_attributes(&config_obj)?;
+    for attribute in attributes {
+        config.add_attribute(attribute);
+    }
+    for warning in warnings {
+        config.add_warning(warning);
+    }
+    if !config.has_valid_layout() {
+        return Err(JsValue::from_str(
+            "worker reported an invalid buffer layout",
+        ));
+    }
+
+    Ok((decoded_array.to_vec(), config))
+}
+
+async fn decode_draco_point_cloud_from_embedded_js_with_config(
+    module: &JsValue,
+    data: &js_sys::Uint8Array,
+) -> Result<(Vec<u8>, PointCloudDecodeConfig), JsValue> {
+    let (decoded_array, config_obj) = call_worker(
+        module,
+        "decodeDracoPointCloudInWorkerWithConfig",
+        data,
+        &JsValue::UNDEFINED,
+        None,
+    )
+    .await?;
+
+    let point_count = get_u32(&config_obj, "point_count")?;
+    let buffer_size = get_f64(&config_obj, "buffer_size")?.unwrap_or(0.0) as usize;
+
+    let mut config = PointCloudDecodeConfig::new(point_count, buffer_size);
+    let (attributes, warnings) = parse_attributes(&config_obj)?;
+    for attribute in attributes {
+        config.add_attribute(attribute);
+    }
+    for warning in warnings {
+        config.add_warning(warning);
+    }
+    if !config.has_valid_layout() {
+        return Err(JsValue::from_str(
+            "worker reported an invalid buffer layout",
+        ));
     }
 
     Ok((decoded_array.to_vec(), config))
 }
 
 pub async fn decode_mesh_wasm_worker_with_config(data: &[u8]) -> Option<crate::MeshDecodeResult> {
-    let js_array = Uint8Array::from(data);
+    WasmDecoder::init().await?.decode_mesh(data).await
+}
 
-    match decode_draco_mesh_from_embedded_js_with_config(&js_array).await {
-        Ok((decoded, config)) => Some(crate::MeshDecodeResult {
-            data: decoded,
-            config,
-        }),
-        Err(err) => {
-            web_sys::console::error_1(&err);
-            None
-        }
+pub async fn decode_point_cloud_wasm_worker_with_config(
+    data: &[u8],
+) -> Option<crate::PointCloudDecodeResult> {
+    WasmDecoder::init().await?.decode_point_cloud(data).await
+}
+
+/// How meshes and point clouds will be decoded in the current environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeStrategy {
+    /// The decoder runs in a Worker created from a Blob URL.
+    BlobWorker,
+    /// Workers cannot be created from Blob URLs, the Worker is created from
+    /// a `data:` URL.
+    DataUrlWorker,
+    /// No Worker can be started. Decoding only succeeds on the main thread
+    /// with [`WasmDecoder::init_time_sliced`], if Blob URLs are available.
+    Unsupported,
+}
+
+/// Browser capabilities relevant to decoding, as reported by
+/// [`environment_report`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnvironmentReport {
+    /// `Worker` is available.
+    pub workers: bool,
+    /// Workers can be created with `type: "module"`.
+    pub module_workers: bool,
+    /// `SharedArrayBuffer` is available and the page is cross-origin isolated.
+    pub shared_array_buffer: bool,
+    /// The WebAssembly engine supports 128-bit SIMD.
+    pub wasm_simd: bool,
+    /// The Origin Private File System is available.
+    pub opfs: bool,
+    /// The decode strategy that will be used.
+    pub strategy: DecodeStrategy,
+}
+
+// Smallest module using a SIMD instruction (i8x16.splat + i8x16.popcnt)
+const SIMD_PROBE: [u8; 31] = [
+    0, 97, 115, 109, 1, 0, 0, 0, 1, 5, 1, 96, 0, 1, 123, 3, 2, 1, 0, 10, 10, 1, 8, 0, 65, 0, 253,
+    15, 253, 98, 11,
+];
+
+fn get_global(key: &str) -> JsValue {
+    js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str(key)).unwrap_or(JsValue::UNDEFINED)
+}
+
+fn has_global(key: &str) -> bool {
+    !get_global(key).is_undefined()
+}
+
+fn supports_module_workers() -> bool {
+    // The `type` option is only read by browsers that support module workers.
+    let probe = r#"
+        (function() {
+            let supported = false;
+            try {
+                new Worker("data:,", { get type() { supported = true; return "module"; } }).terminate();
+            } catch (e) {}
+            return supported;
+        })()
+    "#;
+    js_sys::eval(probe).is_ok_and(|value| value.is_truthy())
+}
+
+/// Starts and terminates a Worker from `url`, as the embedded module does.
+fn can_start_worker(url: &str) -> bool {
+    web_sys::Worker::new(url).is_ok_and(|worker| {
+        worker.terminate();
+        true
+    })
+}
+
+/// Returns the strategy of the embedded module: a Worker from a Blob URL,
+/// falling back to a `data:` URL when the Blob URL Worker cannot be created,
+/// for example because the Content Security Policy forbids `blob:` workers.
+fn decode_strategy(workers: bool) -> DecodeStrategy {
+    // The embedded module itself is imported from a Blob URL
+    let Ok(blob) = web_sys::Blob::new_with_str_sequence(&Array::of1(&JsValue::from_str(""))) else {
+        return DecodeStrategy::Unsupported;
+    };
+    let Ok(url) = web_sys::Url::create_object_url_with_blob(&blob) else {
+        return DecodeStrategy::Unsupported;
+    };
+    let blob_worker = workers && can_start_worker(&url);
+    let _ = web_sys::Url::revoke_object_url(&url);
+
+    if blob_worker {
+        DecodeStrategy::BlobWorker
+    } else if workers && can_start_worker("data:text/javascript;charset=utf-8,") {
+        DecodeStrategy::DataUrlWorker
+    } else {
+        DecodeStrategy::Unsupported
+    }
+}
+
+fn supports_opfs() -> bool {
+    js_sys::Reflect::get(&get_global("navigator"), &JsValue::from_str("storage"))
+        .and_then(|storage| js_sys::Reflect::get(&storage, &JsValue::from_str("getDirectory")))
+        .is_ok_and(|get_directory| get_directory.is_function())
+}
+
+/// Detects which browser features are available and how decoding will be
+/// performed.
+///
+/// Behavior differs between browsers: for example Safari may lack module
+/// workers and `SharedArrayBuffer` requires cross-origin isolation
+/// everywhere. The report can be logged to explain these differences.
+pub fn environment_report() -> EnvironmentReport {
+    let workers = has_global("Worker");
+    let strategy = decode_strategy(workers);
+
+    EnvironmentReport {
+        workers,
+        module_workers: workers && supports_module_workers(),
+        shared_array_buffer: has_global("SharedArrayBuffer")
+            && get_global("crossOriginIsolated").is_truthy(),
+        wasm_simd: js_sys::WebAssembly::validate(&Uint8Array::from(&SIMD_PROBE[..]).into())
+            .unwrap_or(false),
+        opfs: supports_opfs(),
+        strategy,
     }
 }
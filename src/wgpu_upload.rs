@@ -0,0 +1,67 @@
+use std::num::NonZeroU64;
+
+use crate::{DecodeError, DecodeOptions, DracoDecodeConfig, MeshHandle};
+
+impl MeshHandle {
+    /// Writes the indices and attributes straight into the staging memory of
+    /// a `wgpu` buffer write, skipping the intermediate `Vec` of
+    /// [`crate::MeshDecodeResult`].
+    ///
+    /// The decoded buffer is written to `buffer` at `offset` with
+    /// `Queue::write_buffer_with`, so `buffer` needs `COPY_DST` usage and room
+    /// for [`DracoDecodeConfig::buffer_size`] bytes rounded up to a multiple
+    /// of 4. The transfer starts with the next `Queue::submit`. Returns the
+    /// layout of the written data. Fails like
+    /// [`MeshHandle::decode_into_with_options`], and with `DecodeFailed` if
+    /// the mesh is empty, does not fit, or `offset` is not a multiple of 4.
+    /// The returned layout holds the extrema recorded with
+    /// [`DecodeOptions::compute_min_max`]. Indices rejected by
+    /// [`DecodeOptions::validate_indices`] have already been written to the
+    /// staging memory, so the buffer must not be used after such an error.
+    ///
+    /// A buffer created with `mapped_at_creation` can be filled without a
+    /// queue by passing its mapped range to [`MeshHandle::decode_into`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use draco_decoder::{DecodeOptions, MeshHandle};
+    ///
+    /// let options = DecodeOptions::new().with_offset_alignment(4);
+    /// let handle = MeshHandle::decode_with_options(&data, &options).unwrap();
+    /// let size = handle.config_with_options(&options).unwrap().buffer_size();
+    /// let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+    ///     label: Some("draco mesh"),
+    ///     size: (size as u64).next_multiple_of(4),
+    ///     usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
+    ///     mapped_at_creation: false,
+    /// });
+    /// let config = handle.decode_into_mapped_buffer(&options, &queue, &buffer, 0).unwrap();
+    /// queue.submit([]);
+    /// ```
+    pub fn decode_into_mapped_buffer(
+        &self,
+        options: &DecodeOptions,
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        offset: u64,
+    ) -> Result<DracoDecodeConfig, DecodeError> {
+        let config = self.config_with_options(options)?;
+        let size = (config.buffer_size() as u64).next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT);
+        if !offset.is_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT)
+            || offset
+                .checked_add(size)
+                .is_none_or(|end| end > buffer.size())
+            || !buffer.usage().contains(wgpu::BufferUsages::COPY_DST)
+        {
+            return Err(DecodeError::DecodeFailed);
+        }
+
+        let mut view = NonZeroU64::new(size)
+            .and_then(|size| queue.write_buffer_with(buffer, offset, size))
+            .ok_or(DecodeError::DecodeFailed)?;
+        // The written layout carries the extrema recorded while writing
+        let (_, config) = self.write_with_options(options, &mut view)?;
+        Ok(config)
+    }
+}
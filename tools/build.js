@@ -5,23 +5,76 @@ const path = require('path');
 const rootDir = path.join(__dirname, '..');
 const jsDir = path.join(rootDir, 'third_party', 'draco_decoder_js');
 
+// With --bundle-only, the bundle is regenerated from an existing
+// draco_decoder_js build and javascript/src without building anything
+const bundleOnly = process.argv.includes('--bundle-only');
+
 // Source files
 const srcJsFile = path.join(jsDir, 'dist', 'index.es.js');
 const srcWasmFile = path.join(jsDir, 'dist', 'draco3d', 'draco_decoder.wasm');
+const srcDir = path.join(rootDir, 'javascript', 'src');
 
 // Destination files
 const destJsFile = path.join(rootDir, 'javascript', 'index.es.js');
 const destWasmDir = path.join(rootDir, 'javascript', 'draco3d');
 const destWasmFile = path.join(destWasmDir, 'draco_decoder.wasm');
 
+// Returns the source of the worker embedded in the draco_decoder_js bundle as
+// a template literal
+function upstreamWorkerSource(bundle) {
+    const start = bundle.match(/^const \w+=`/);
+    if (!start) throw new Error('The draco_decoder_js bundle does not start with its worker');
+    let end = start[0].length;
+    while (bundle[end] !== '`') end += bundle[end] === '\\' ? 2 : 1;
+    return new Function('return ' + bundle.slice(start[0].length - 1, end + 1))();
+}
+
+// Returns the Draco module loader of the draco_decoder_js worker followed by
+// javascript/src/decode.js, without the upstream message handler. The worker
+// and the time-sliced main thread decoder both run this source.
+function dracoSource(bundle) {
+    const upstream = upstreamWorkerSource(bundle);
+    const prefix = '!function(){';
+    const handler = upstream.indexOf('self.onmessage=');
+    const loader = upstream.slice(0, handler).match(/let (\w+)=null;async function (\w+)\(\)\{return \1\|\|/);
+    if (!upstream.startsWith(prefix) || handler < 0 || !loader) {
+        throw new Error('Unexpected draco_decoder_js worker');
+    }
+
+    const decode = fs.readFileSync(path.join(srcDir, 'decode.js'), 'utf8');
+    return `${upstream.slice(prefix.length, handler)}const loadDraco=${loader[2]};\n${decode}`;
+}
+
+function templateLiteral(source) {
+    return '`' + source.replace(/\\/g, '\\\\').replace(/`/g, '\\`').replace(/\$\{/g, '\\${') + '`';
+}
+
+function bundle() {
+    const draco = dracoSource(fs.readFileSync(srcJsFile, 'utf8'));
+    const worker = fs.readFileSync(path.join(srcDir, 'worker.js'), 'utf8');
+    const main = fs.readFileSync(path.join(srcDir, 'main.js'), 'utf8');
+    const header = '// Generated by tools/build.js from draco_decoder_js and javascript/src, do not edit.\n';
+
+    fs.writeFileSync(
+        destJsFile,
+        `${header}const dracoSource=${templateLiteral(draco)};\n` +
+            `const workerSource="!function(){"+dracoSource+${templateLiteral(worker)}+"}();\\n";\n${main}`,
+    );
+    console.log('Generated javascript/index.es.js');
+}
+
+if (bundleOnly) {
+    bundle();
+    return;
+}
+
 // Build draco_decoder_js
 console.log('Building draco_decoder_js...');
 execSync('npm run build', { cwd: jsDir, stdio: 'inherit' });
 
-// Copy index.es.js
-console.log('Copying index.es.js...');
-fs.copyFileSync(srcJsFile, destJsFile);
-console.log('Copied index.es.js to javascript/');
+// Generate index.es.js
+console.log('Generating bundle...');
+bundle();
 
 // Copy draco_decoder.wasm
 console.log('Copying draco_decoder.wasm...');